@@ -226,6 +226,83 @@ fn encode_decode_complex_type(c: &mut Criterion) {
 	}
 }
 
+/// An item of 93 bytes, whose `encoded_fixed_size` is given if `FIXED` is `true`.
+#[derive(Encode, Clone)]
+struct MediumItem<const FIXED: bool> {
+	id: u64,
+	hashes: [[u8; 32]; 2],
+	amount: u128,
+	weight: u32,
+	flag: bool,
+}
+
+impl<const FIXED: bool> Decode for MediumItem<FIXED> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Ok(MediumItem {
+			id: Decode::decode(input)?,
+			hashes: Decode::decode(input)?,
+			amount: Decode::decode(input)?,
+			weight: Decode::decode(input)?,
+			flag: Decode::decode(input)?,
+		})
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		FIXED.then_some(93)
+	}
+}
+
+fn decode_vec_fixed_size<const FIXED: bool>(c: &mut Criterion) {
+	let item =
+		MediumItem::<FIXED> { id: 1, hashes: [[2; 32]; 2], amount: 3, weight: 4, flag: true };
+	let name = if FIXED { "fixed_size" } else { "unknown_size" };
+
+	let mut g = c.benchmark_group("vec_decode_medium_item");
+	for vec_size in [32, 1024, 16384] {
+		let encoded = black_box(vec![item.clone(); vec_size].encode());
+		g.bench_with_input(format!("{name}/slice/{vec_size}"), &vec_size, |b, _| {
+			b.iter(|| {
+				let _: Vec<MediumItem<FIXED>> = Decode::decode(&mut &encoded[..]).unwrap();
+			})
+		});
+		g.bench_with_input(format!("{name}/io_reader/{vec_size}"), &vec_size, |b, _| {
+			b.iter(|| {
+				let mut input = IoReader(std::io::Cursor::new(&encoded[..]));
+				let _: Vec<MediumItem<FIXED>> = Decode::decode(&mut input).unwrap();
+			})
+		});
+		let path = std::env::temp_dir().join(format!("scale_codec_bench_{vec_size}"));
+		std::fs::write(&path, &encoded).unwrap();
+		g.bench_with_input(format!("{name}/file/{vec_size}"), &vec_size, |b, _| {
+			b.iter(|| {
+				let mut input = IoReader(std::fs::File::open(&path).unwrap());
+				let _: Vec<MediumItem<FIXED>> = Decode::decode(&mut input).unwrap();
+			})
+		});
+		let buffered = CodecConfig::DEFAULT.with_buffered_reads(true);
+		g.bench_with_input(format!("{name}/file_buffered_reads/{vec_size}"), &vec_size, |b, _| {
+			b.iter(|| {
+				let mut input = IoReader(std::fs::File::open(&path).unwrap());
+				let _: Vec<MediumItem<FIXED>> =
+					DecodeWithConfig::decode_with_config(&mut input, &buffered).unwrap();
+			})
+		});
+	}
+}
+
+fn encode_small_compacts(c: &mut Criterion) {
+	let mut g = c.benchmark_group("encode_small_compacts");
+
+	let blobs: Vec<Vec<u8>> = black_box((0..1024).map(|i| vec![7; i % 64]).collect());
+	g.bench_function("vec_of_small_vec_u8", |b| b.iter(|| blobs.encode()));
+
+	let compacts: Vec<Compact<u32>> = black_box((0..1024).map(|i| Compact(i % 64)).collect());
+	g.bench_function("vec_of_small_compact_u32", |b| b.iter(|| compacts.encode()));
+	g.bench_function("small_compact_u32_size_hint", |b| {
+		b.iter(|| compacts.iter().map(Encode::size_hint).sum::<usize>())
+	});
+}
+
 fn bench_fn(c: &mut Criterion) {
 	c.bench_function("vec_write_as_output", vec_write_as_output);
 	c.bench_function("vec_extend", vec_extend);
@@ -276,6 +353,7 @@ criterion_group! {
 	config = Criterion::default().warm_up_time(Duration::from_millis(500)).without_plots();
 	targets = encode_decode_vec::<u8>, encode_decode_vec::<u16>, encode_decode_vec::<u32>, encode_decode_vec::<u64>,
 			encode_decode_vec::<i8>, encode_decode_vec::<i16>, encode_decode_vec::<i32>, encode_decode_vec::<i64>,
-			bench_fn, encode_decode_bitvec_u8, encode_decode_complex_type
+			bench_fn, encode_decode_bitvec_u8, encode_decode_complex_type,
+			decode_vec_fixed_size::<false>, decode_vec_fixed_size::<true>, encode_small_compacts
 }
 criterion_main!(benches);
@@ -0,0 +1,83 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compare the compact encoding with other varints, see `parity_scale_codec::varint_compare`.
+
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use parity_scale_codec::{
+	varint_compare::{CompactU64, PrefixVarint, Scheme},
+	Leb128,
+};
+
+/// `len` pseudo-random values, with the number of significant bits uniform in `bits`.
+fn distribution(len: usize, bits: std::ops::RangeInclusive<u32>) -> Vec<u64> {
+	let mut state = 0x2545_f491_4f6c_dd1d_u64;
+	let span = bits.end() - bits.start() + 1;
+	(0..len)
+		.map(|_| {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			let bits = bits.start() + (state % span as u64) as u32;
+			state >> (64 - bits)
+		})
+		.collect()
+}
+
+fn bench_scheme<S: Scheme>(c: &mut Criterion, name: &str, values: &[u64]) {
+	let mut group = c.benchmark_group(format!("varint/{name}"));
+	group.throughput(Throughput::Elements(values.len() as u64));
+
+	let mut encoded = Vec::new();
+	group.bench_with_input(BenchmarkId::new("encode", S::NAME), values, |b, values| {
+		b.iter(|| {
+			encoded.clear();
+			for &value in values {
+				S::encode(black_box(value), &mut encoded);
+			}
+		})
+	});
+
+	group.bench_with_input(BenchmarkId::new("decode", S::NAME), &encoded, |b, encoded| {
+		b.iter(|| {
+			let mut input = &encoded[..];
+			while !input.is_empty() {
+				black_box(S::decode(&mut input).unwrap());
+			}
+		})
+	});
+	group.finish();
+}
+
+fn varint(c: &mut Criterion) {
+	let distributions = [
+		("small", distribution(10_000, 1..=6)),
+		("medium", distribution(10_000, 1..=32)),
+		("large", distribution(10_000, 33..=64)),
+	];
+	for (name, values) in &distributions {
+		bench_scheme::<CompactU64>(c, name, values);
+		bench_scheme::<Leb128>(c, name, values);
+		bench_scheme::<PrefixVarint>(c, name, values);
+	}
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default().warm_up_time(Duration::from_millis(500)).without_plots();
+	targets = varint
+}
+criterion_main!(benches);
@@ -3,4 +3,13 @@ fn main() {
 	if rustversion::cfg!(nightly) {
 		println!("cargo:rustc-cfg=nightly");
 	}
+
+	// `Rc::new_uninit` and `Arc::new_uninit` are stable since 1.82.
+	println!("cargo:rustc-check-cfg=cfg(new_uninit)");
+	if rustversion::cfg!(since(1.82)) {
+		println!("cargo:rustc-cfg=new_uninit");
+	}
+
+	// Set by `cargo fuzz` and `cargo hfuzz`, enables the fuzz targets of `CodecFuzz` in the tests.
+	println!("cargo:rustc-check-cfg=cfg(fuzzing)");
 }
@@ -0,0 +1,127 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of the fuzz target and exhaustive test of `#[derive(CodecFuzz)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, Type};
+
+use crate::utils;
+
+/// The maximum encoded length for which the exhaustive test is generated, as it decodes
+/// `256^len` inputs.
+const MAX_EXHAUSTIVE_LEN: usize = 2;
+
+/// The encoded length of a field of type `ty`, if it is a small integer, `bool` or `()`.
+fn small_type_len(ty: &Type) -> Option<usize> {
+	match ty {
+		Type::Tuple(tuple) if tuple.elems.is_empty() => Some(0),
+		Type::Path(path) if path.qself.is_none() => {
+			let ident = path.path.get_ident()?;
+			match ident.to_string().as_str() {
+				"u8" | "i8" | "bool" => Some(1),
+				"u16" | "i16" => Some(2),
+				_ => None,
+			}
+		},
+		_ => None,
+	}
+}
+
+/// The encoded length of `fields`, if all encoded fields are small types without attributes.
+fn fields_len(fields: &Fields) -> Option<usize> {
+	fields
+		.iter()
+		.filter(|field| !utils::should_skip(&field.attrs))
+		.try_fold(0, |len, field| {
+			let plain = !utils::is_compact(field) &&
+				utils::get_alternative_type(field).is_none() &&
+				utils::get_max_len(field).is_none() &&
+				utils::get_mel_cap(field).is_none() &&
+				!utils::is_lock(field);
+			Some(len + small_type_len(&field.ty).filter(|_| plain)?)
+		})
+}
+
+/// The maximum encoded length of the type, if it is small enough to check all encodings.
+fn exhaustive_len(input: &DeriveInput) -> Option<usize> {
+	if !input.generics.params.is_empty() {
+		return None;
+	}
+
+	let len = match input.data {
+		Data::Struct(ref data) => fields_len(&data.fields)?,
+		Data::Enum(ref data) => {
+			let variants = utils::try_get_variants(data).ok()?;
+			1 + variants
+				.iter()
+				.try_fold(0, |len, variant| Some(usize::max(len, fields_len(&variant.fields)?)))?
+		},
+		Data::Union(_) => return None,
+	};
+	(len <= MAX_EXHAUSTIVE_LEN).then_some(len)
+}
+
+/// Generate the inherent `codec_fuzz_roundtrip` function, which is only compiled when fuzzing.
+pub fn fuzz_target(input: &DeriveInput, crate_path: &syn::Path) -> TokenStream {
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	// `fuzzing` is not a known cfg of the crate using the derive.
+	quote! {
+		#[allow(unexpected_cfgs)]
+		const _: () = {
+		#[cfg(fuzzing)]
+		#[automatically_derived]
+		impl #impl_generics #name #ty_generics #where_clause {
+			/// Decode `data` and check that the decoded value survives an encoding roundtrip.
+			///
+			/// Call this from a fuzz target, see `parity_scale_codec::codec_fuzz::roundtrip`.
+			pub fn codec_fuzz_roundtrip(data: &[u8])
+			where
+				Self: #crate_path::Encode
+					+ #crate_path::Decode
+					+ ::core::cmp::PartialEq
+					+ ::core::fmt::Debug,
+			{
+				#crate_path::codec_fuzz::roundtrip::<Self>(data)
+			}
+		}
+		};
+	}
+}
+
+/// Generate the test checking all encodings of the type, if it is small enough.
+///
+/// The test is generated next to the type, as tests in the dummy const of the implementations
+/// would not be run.
+pub fn exhaustive_test(input: &DeriveInput, crate_path: &syn::Path) -> TokenStream {
+	let name = &input.ident;
+	let Some(len) = exhaustive_len(input) else { return quote!() };
+	// The name of the type is kept as is, so the names of the tests of different types can't
+	// collide.
+	let test_name = Ident::new(
+		&format!("codec_fuzz_exhaustive_{}", name.to_string().trim_start_matches("r#")),
+		Span::call_site(),
+	);
+	quote! {
+		#[cfg(test)]
+		#[test]
+		#[allow(non_snake_case)]
+		fn #test_name() {
+			#crate_path::codec_fuzz::check_exhaustive::<#name>(#len);
+		}
+	}
+}
@@ -16,7 +16,7 @@ use crate::utils;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
 use std::iter;
-use syn::{spanned::Spanned, Data, Error, Field, Fields};
+use syn::{spanned::Spanned, Data, Field, Fields};
 
 /// Generate function block for function `Decode::decode`.
 ///
@@ -45,9 +45,8 @@ pub fn quote(
 				Err(e) => return e.to_compile_error(),
 			};
 
-			let recurse = variants.iter().enumerate().map(|(i, v)| {
+			let arm = |v: &syn::Variant, index: TokenStream| {
 				let name = &v.ident;
-				let index = utils::variant_index(v, i);
 
 				let create = create_instance(
 					quote! { #type_name #type_generics :: #name },
@@ -57,6 +56,19 @@ pub fn quote(
 					crate_path,
 				);
 
+				// Only successfully decoded variants are recorded.
+				let create = match utils::record_variant(crate_path, "Decode", type_name, v, &index)
+				{
+					Some(record) => quote! {
+						let __codec_res_edqy = { #create };
+						if __codec_res_edqy.is_ok() {
+							#record
+						}
+						__codec_res_edqy
+					},
+					None => create,
+				};
+
 				quote_spanned! { v.span() =>
 					#[allow(clippy::unnecessary_cast)]
 					__codec_x_edqy if __codec_x_edqy == #index as ::core::primitive::u8 => {
@@ -69,7 +81,32 @@ pub fn quote(
 						})();
 					},
 				}
-			});
+			};
+			let recurse =
+				variants.iter().enumerate().map(|(i, v)| arm(v, utils::variant_index(v, i)));
+
+			// Skipped variants which are still decoded, e.g. to keep decoding old encodings.
+			let skipped =
+				utils::skipped_variant_decodes(data).map(|(v, index, decode)| match decode {
+					utils::SkippedVariantDecode::As(target) => {
+						let target = variants
+							.iter()
+							.find(|variant| variant.ident == target)
+							.expect("Checked by `check_attributes`");
+						arm(target, index)
+					},
+					utils::SkippedVariantDecode::Error(msg) => quote_spanned! { v.span() =>
+						#[allow(clippy::unnecessary_cast)]
+						__codec_x_edqy if __codec_x_edqy == #index as ::core::primitive::u8 => {
+							#[allow(clippy::redundant_closure_call)]
+							return (move || {
+								::core::result::Result::Err(
+									<_ as ::core::convert::Into<_>>::into(#msg)
+								)
+							})();
+						},
+					},
+				});
 
 			let read_byte_err_msg =
 				format!("Could not decode `{type_name}`, failed to read variant byte");
@@ -80,6 +117,7 @@ pub fn quote(
 					.map_err(|e| e.chain(#read_byte_err_msg))?
 				{
 					#( #recurse )*
+					#( #skipped )*
 					_ => {
 						#[allow(clippy::redundant_closure_call)]
 						return (move || {
@@ -91,8 +129,7 @@ pub fn quote(
 				}
 			}
 		},
-		Data::Union(_) =>
-			Error::new(Span::call_site(), "Union types are not supported.").to_compile_error(),
+		Data::Union(ref data) => utils::union_error(data.union_token.span()).to_compile_error(),
 	}
 }
 
@@ -108,6 +145,14 @@ pub fn quote_decode_into(
 		return None;
 	}
 
+	// The decoded value must go through the normalization function, or be followed by the appended
+	// value.
+	if crate::utils::get_normalize(attrs).is_some() ||
+		crate::utils::get_append_with(attrs).is_some()
+	{
+		return None;
+	}
+
 	let fields = match data {
 		Data::Struct(syn::DataStruct {
 			fields:
@@ -124,8 +169,11 @@ pub fn quote_decode_into(
 
 	// Bail if there are any extra attributes which could influence how the type is decoded.
 	if fields.iter().any(|field| {
-		utils::get_encoded_as_type(field).is_some() ||
+		utils::get_alternative_type(field).is_some() ||
 			utils::is_compact(field) ||
+			utils::is_lock(field) ||
+			utils::get_max_len(field).is_some() ||
+			utils::get_mel_cap(field).is_some_and(|mel| mel.check) ||
 			utils::should_skip(&field.attrs)
 	}) {
 		return None;
@@ -186,17 +234,25 @@ fn create_decode_expr(
 	crate_path: &syn::Path,
 ) -> TokenStream {
 	let encoded_as = utils::get_encoded_as_type(field);
+	let decoded_via = utils::get_decoded_via_type(field);
 	let compact = utils::get_compact_type(field, crate_path);
+	let lock = utils::is_lock(field);
+	let max_len = utils::get_max_len(field);
+	let mel_cap = utils::get_mel_cap(field);
 	let skip = utils::should_skip(&field.attrs);
 
 	let res = quote!(__codec_res_edqy);
 
-	if encoded_as.is_some() as u8 + compact.is_some() as u8 + skip as u8 > 1 {
-		return Error::new(
-			field.span(),
-			"`encoded_as`, `compact` and `skip` can only be used one at a time!",
-		)
-		.to_compile_error();
+	if encoded_as.is_some() as u8 +
+		decoded_via.is_some() as u8 +
+		compact.is_some() as u8 +
+		lock as u8 +
+		max_len.is_some() as u8 +
+		mel_cap.is_some() as u8 +
+		skip as u8 >
+		1
+	{
+		return utils::conflicting_field_attributes_error(field.span()).to_compile_error();
 	}
 
 	let err_msg = format!("Could not decode `{}`", name);
@@ -221,6 +277,55 @@ fn create_decode_expr(
 				}
 			}
 		}
+	} else if let Some(decoded_via) = decoded_via {
+		let field_type = &field.ty;
+		quote_spanned! { field.span() =>
+			{
+				let #res = <#decoded_via as #crate_path::Decode>::decode(#input).and_then(|#res| {
+					<#decoded_via as #crate_path::DecodeAndConvert<#field_type>>::convert(#res)
+				});
+				match #res {
+					::core::result::Result::Err(e) => return ::core::result::Result::Err(e.chain(#err_msg)),
+					::core::result::Result::Ok(#res) => #res,
+				}
+			}
+		}
+	} else if lock {
+		let field_type = &field.ty;
+		quote_spanned! { field.span() =>
+			{
+				let #res = <
+					<#field_type as #crate_path::Lockable>::Inner as #crate_path::Decode
+				>::decode(#input);
+				match #res {
+					::core::result::Result::Err(e) => return ::core::result::Result::Err(e.chain(#err_msg)),
+					::core::result::Result::Ok(#res) =>
+						<#field_type as #crate_path::Lockable>::from_inner(#res),
+				}
+			}
+		}
+	} else if let Some(max_len) = max_len {
+		let field_type = &field.ty;
+		quote_spanned! { field.span() =>
+			{
+				let #res = #crate_path::decode_with_max_len::<#field_type, _>(#input, #max_len);
+				match #res {
+					::core::result::Result::Err(e) => return ::core::result::Result::Err(e.chain(#err_msg)),
+					::core::result::Result::Ok(#res) => #res,
+				}
+			}
+		}
+	} else if let Some(utils::MelCap { cap, check: true }) = mel_cap {
+		let field_type = &field.ty;
+		quote_spanned! { field.span() =>
+			{
+				let #res = #crate_path::decode_with_max_encoded_len::<#field_type, _>(#input, #cap);
+				match #res {
+					::core::result::Result::Err(e) => return ::core::result::Result::Err(e.chain(#err_msg)),
+					::core::result::Result::Ok(#res) => #res,
+				}
+			}
+		}
 	} else if skip {
 		quote_spanned! { field.span() => ::core::default::Default::default() }
 	} else {
@@ -244,6 +349,10 @@ fn create_instance(
 	fields: &Fields,
 	crate_path: &syn::Path,
 ) -> TokenStream {
+	if fields.iter().any(|f| utils::get_skip_default(&f.attrs).is_some()) {
+		return create_instance_with_skip_defaults(name, name_str, input, fields, crate_path);
+	}
+
 	match *fields {
 		Fields::Named(ref fields) => {
 			let recurse = fields.named.iter().map(|f| {
@@ -286,6 +395,54 @@ fn create_instance(
 	}
 }
 
+/// Like [`create_instance`], but first decodes every field into a local variable, so that fields
+/// with `#[codec(skip, default = "$path")]` can be reconstructed by calling `$path` with references
+/// to all the non-skipped fields that precede them.
+fn create_instance_with_skip_defaults(
+	name: TokenStream,
+	name_str: &str,
+	input: &TokenStream,
+	fields: &Fields,
+	crate_path: &syn::Path,
+) -> TokenStream {
+	let mut decoded = Vec::new();
+	let mut locals = Vec::new();
+	let mut decode_locals = Vec::new();
+	for (i, f) in fields.iter().enumerate() {
+		let local = Ident::new(&format!("__codec_field_{}_edqy", i), Span::call_site());
+		let decode = if let Some(default) = utils::get_skip_default(&f.attrs) {
+			quote_spanned! { f.span() => #default( #( &#decoded ),* ) }
+		} else {
+			let field_name = match f.ident {
+				Some(ref ident) => format!("{}::{}", name_str, ident),
+				None => format!("{}.{}", name_str, i),
+			};
+			let decode = create_decode_expr(f, &field_name, input, crate_path);
+			if !utils::should_skip(&f.attrs) {
+				decoded.push(local.clone());
+			}
+			decode
+		};
+
+		decode_locals.push(quote_spanned! { f.span() => let #local = #decode; });
+		locals.push(local);
+	}
+
+	let construct = match *fields {
+		Fields::Named(ref fields) => {
+			let names = fields.named.iter().map(|f| &f.ident);
+			quote! { #name { #( #names: #locals, )* } }
+		},
+		Fields::Unnamed(_) => quote! { #name ( #( #locals, )* ) },
+		Fields::Unit => quote! { #name },
+	};
+
+	quote! {
+		#( #decode_locals )*
+		::core::result::Result::Ok(#construct)
+	}
+}
+
 pub fn quote_decode_with_mem_tracking_checks(data: &Data, crate_path: &syn::Path) -> TokenStream {
 	let fields: Box<dyn Iterator<Item = &Field>> = match data {
 		Data::Struct(data) => Box::new(data.fields.iter()),
@@ -301,9 +458,8 @@ pub fn quote_decode_with_mem_tracking_checks(data: &Data, crate_path: &syn::Path
 			}
 			fields
 		},
-		Data::Union(_) => {
-			return Error::new(Span::call_site(), "Union types are not supported.")
-				.to_compile_error();
+		Data::Union(ref data) => {
+			return utils::union_error(data.union_token.span()).to_compile_error();
 		},
 	};
 
@@ -314,8 +470,11 @@ pub fn quote_decode_with_mem_tracking_checks(data: &Data, crate_path: &syn::Path
 
 		let field_type = if let Some(compact) = utils::get_compact_type(field, crate_path) {
 			compact
-		} else if let Some(encoded_as) = utils::get_encoded_as_type(field) {
+		} else if let Some(encoded_as) = utils::get_alternative_type(field) {
 			encoded_as
+		} else if utils::is_lock(field) {
+			let field_type = &field.ty;
+			quote! { <#field_type as #crate_path::Lockable>::Inner }
 		} else {
 			field.ty.to_token_stream()
 		};
@@ -0,0 +1,93 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of `DecodeLength` for enums whose variants each wrap a collection.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Field, Variant};
+
+use crate::utils;
+
+/// The only encoded field of `variant`.
+fn single_field(variant: &Variant) -> syn::Result<&Field> {
+	let mut fields = variant.fields.iter().filter(|field| !utils::should_skip(&field.attrs));
+	match (fields.next(), fields.next()) {
+		(Some(field), None) => Ok(field),
+		_ => Err(Error::new_spanned(
+			variant,
+			"`DecodeLength` can only be derived for enums whose variants have exactly one field \
+			that is not skipped",
+		)),
+	}
+}
+
+/// The type `field` is encoded as, which must implement `DecodeLength`.
+fn encoded_type(field: &Field, crate_path: &syn::Path) -> syn::Result<TokenStream> {
+	let ty = &field.ty;
+	if utils::is_compact(field) {
+		Err(Error::new_spanned(field, "`DecodeLength` can't be derived for `compact` fields"))
+	} else if let Some(alternative) = utils::get_alternative_type(field) {
+		Ok(alternative)
+	} else if utils::is_lock(field) {
+		Ok(quote!(<#ty as #crate_path::Lockable>::Inner))
+	} else {
+		Ok(quote!(#ty))
+	}
+}
+
+/// Generate the implementation of `DecodeLength`.
+pub fn quote(input: &DeriveInput, crate_path: &syn::Path) -> syn::Result<TokenStream> {
+	let Data::Enum(ref data) = input.data else {
+		return Err(Error::new(Span::call_site(), "`DecodeLength` can only be derived for enums"));
+	};
+
+	let name = &input.ident;
+	let mut generics = input.generics.clone();
+	let mut arms = Vec::new();
+	for (i, variant) in utils::try_get_variants(data)?.into_iter().enumerate() {
+		let ty = encoded_type(single_field(variant)?, crate_path)?;
+		let index = utils::variant_index(variant, i);
+		generics
+			.make_where_clause()
+			.predicates
+			.push(parse_quote!(#ty: #crate_path::DecodeLength));
+		arms.push(quote_spanned! { variant.span() =>
+			#[allow(clippy::unnecessary_cast)]
+			__codec_x_edqy if __codec_x_edqy == #index as ::core::primitive::u8 =>
+				<#ty as #crate_path::DecodeLength>::len(__codec_rest_edqy),
+		});
+	}
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let read_byte_err_msg = format!("Could not decode `{name}`, failed to read variant byte");
+	let invalid_variant_err_msg = format!("Could not decode `{name}`, variant doesn't exist");
+
+	Ok(quote! {
+		#[automatically_derived]
+		impl #impl_generics #crate_path::DecodeLength for #name #ty_generics #where_clause {
+			fn len(
+				self_encoded: &[::core::primitive::u8],
+			) -> ::core::result::Result<::core::primitive::usize, #crate_path::Error> {
+				let (__codec_index_edqy, __codec_rest_edqy) = self_encoded
+					.split_first()
+					.ok_or_else(|| #crate_path::Error::from(#read_byte_err_msg))?;
+				match *__codec_index_edqy {
+					#( #arms )*
+					_ => ::core::result::Result::Err(#invalid_variant_err_msg.into()),
+				}
+			}
+		}
+	})
+}
@@ -26,8 +26,9 @@ fn encode_single_field(
 	field: &Field,
 	field_name: TokenStream,
 	crate_path: &syn::Path,
+	inline: &TokenStream,
 ) -> TokenStream {
-	let encoded_as = utils::get_encoded_as_type(field);
+	let encoded_as = utils::get_alternative_type(field);
 	let compact = utils::get_compact_type(field, crate_path);
 
 	if utils::should_skip(&field.attrs) {
@@ -41,7 +42,7 @@ fn encode_single_field(
 	if encoded_as.is_some() && compact.is_some() {
 		return Error::new(
 			Span::call_site(),
-			"`encoded_as` and `compact` can not be used at the same time!",
+			"`encoded_as`/`decoded_via` and `compact` can not be used at the same time!",
 		)
 		.to_compile_error();
 	}
@@ -71,10 +72,12 @@ fn encode_single_field(
 	let i_self = quote! { self };
 
 	quote_spanned! { field.span() =>
+			#inline
 			fn size_hint(&#i_self) -> usize {
 				#crate_path::Encode::size_hint(&#final_field_variable)
 			}
 
+			#inline
 			fn encode_to<__CodecOutputEdqy: #crate_path::Output + ?::core::marker::Sized>(
 				&#i_self,
 				__codec_dest_edqy: &mut __CodecOutputEdqy
@@ -82,10 +85,12 @@ fn encode_single_field(
 				#crate_path::Encode::encode_to(&#final_field_variable, __codec_dest_edqy)
 			}
 
+			#inline
 			fn encode(&#i_self) -> #crate_path::alloc::vec::Vec<::core::primitive::u8> {
 				#crate_path::Encode::encode(&#final_field_variable)
 			}
 
+			#inline
 			fn using_encoded<
 				__CodecOutputReturn,
 				__CodecUsingEncodedCallback: ::core::ops::FnOnce(
@@ -102,6 +107,7 @@ enum FieldAttribute<'a> {
 	None(&'a Field),
 	Compact(&'a Field),
 	EncodedAs { field: &'a Field, encoded_as: &'a TokenStream },
+	Lock(&'a Field),
 	Skip,
 }
 
@@ -116,32 +122,42 @@ where
 	H: Fn(TokenStream, FieldAttribute) -> TokenStream,
 	J: Fn(&mut dyn Iterator<Item = TokenStream>) -> TokenStream,
 {
-	let mut recurse = fields.iter().enumerate().map(|(i, f)| {
-		let field = field_name(i, &f.ident);
-		let encoded_as = utils::get_encoded_as_type(f);
-		let compact = utils::is_compact(f);
-		let skip = utils::should_skip(&f.attrs);
-
-		if encoded_as.is_some() as u8 + compact as u8 + skip as u8 > 1 {
-			return Error::new(
-				f.span(),
-				"`encoded_as`, `compact` and `skip` can only be used one at a time!",
-			)
-			.to_compile_error();
-		}
+	let mut recurse =
+		fields.iter().enumerate().map(|(i, f)| {
+			let field = field_name(i, &f.ident);
+			let encoded_as = utils::get_encoded_as_type(f);
+			let decoded_via = utils::get_decoded_via_type(f);
+			let compact = utils::is_compact(f);
+			let lock = utils::is_lock(f);
+			let max_len = utils::get_max_len(f).is_some();
+			let skip = utils::should_skip(&f.attrs);
+
+			if encoded_as.is_some() as u8 +
+				decoded_via.is_some() as u8 +
+				compact as u8 +
+				lock as u8 + max_len as u8 +
+				skip as u8 > 1
+			{
+				return utils::conflicting_field_attributes_error(f.span()).to_compile_error();
+			}
 
-		// Based on the seen attribute, we call a handler that generates code for a specific
-		// attribute type.
-		if compact {
-			field_handler(field, FieldAttribute::Compact(f))
-		} else if let Some(ref encoded_as) = encoded_as {
-			field_handler(field, FieldAttribute::EncodedAs { field: f, encoded_as })
-		} else if skip {
-			field_handler(field, FieldAttribute::Skip)
-		} else {
-			field_handler(field, FieldAttribute::None(f))
-		}
-	});
+			// `decoded_via` fields are encoded like `encoded_as` fields.
+			let encoded_as = encoded_as.or(decoded_via);
+
+			// Based on the seen attribute, we call a handler that generates code for a specific
+			// attribute type.
+			if compact {
+				field_handler(field, FieldAttribute::Compact(f))
+			} else if let Some(ref encoded_as) = encoded_as {
+				field_handler(field, FieldAttribute::EncodedAs { field: f, encoded_as })
+			} else if lock {
+				field_handler(field, FieldAttribute::Lock(f))
+			} else if skip {
+				field_handler(field, FieldAttribute::Skip)
+			} else {
+				field_handler(field, FieldAttribute::None(f))
+			}
+		});
 
 	field_joiner(&mut recurse)
 }
@@ -190,6 +206,11 @@ where
 					}
 				}
 			},
+			FieldAttribute::Lock(f) => quote_spanned! { f.span() =>
+				#crate_path::Lockable::with_inner(#field, |__codec_inner_edqy| {
+					#crate_path::Encode::encode_to(__codec_inner_edqy, #dest);
+				});
+			},
 			FieldAttribute::Skip => quote! {
 				let _ = #field;
 			},
@@ -235,7 +256,8 @@ where
 					))
 				}
 			},
-			FieldAttribute::Skip => quote!(),
+			// Taking the lock only for a hint would double the locking of every encoding.
+			FieldAttribute::Lock(_) | FieldAttribute::Skip => quote!(),
 		},
 		|recurse| {
 			quote! {
@@ -248,19 +270,26 @@ where
 fn try_impl_encode_single_field_optimisation(
 	data: &Data,
 	crate_path: &syn::Path,
+	inline: &TokenStream,
 ) -> Option<TokenStream> {
 	match *data {
 		Data::Struct(ref data) => match data.fields {
 			Fields::Named(ref fields) if utils::filter_skip_named(fields).count() == 1 => {
 				let field = utils::filter_skip_named(fields).next().unwrap();
+				if utils::is_lock(field) {
+					return None;
+				}
 				let name = &field.ident;
-				Some(encode_single_field(field, quote!(&self.#name), crate_path))
+				Some(encode_single_field(field, quote!(&self.#name), crate_path, inline))
 			},
 			Fields::Unnamed(ref fields) if utils::filter_skip_unnamed(fields).count() == 1 => {
 				let (id, field) = utils::filter_skip_unnamed(fields).next().unwrap();
+				if utils::is_lock(field) {
+					return None;
+				}
 				let id = syn::Index::from(id);
 
-				Some(encode_single_field(field, quote!(&self.#id), crate_path))
+				Some(encode_single_field(field, quote!(&self.#id), crate_path, inline))
 			},
 			_ => None,
 		},
@@ -268,7 +297,13 @@ fn try_impl_encode_single_field_optimisation(
 	}
 }
 
-fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenStream {
+fn impl_encode(
+	data: &Data,
+	type_name: &Ident,
+	crate_path: &syn::Path,
+	inline: &TokenStream,
+	append_with: Option<&syn::Path>,
+) -> TokenStream {
 	let self_ = quote!(self);
 	let dest = &quote!(__codec_dest_edqy);
 	let [hinting, encoding] = match *data {
@@ -310,6 +345,7 @@ fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenS
 			let recurse = variants.iter().enumerate().map(|(i, f)| {
 				let name = &f.ident;
 				let index = utils::variant_index(f, i);
+				let record = utils::record_variant(crate_path, "Encode", type_name, f, &index);
 
 				match f.fields {
 					Fields::Named(ref fields) => {
@@ -334,6 +370,7 @@ fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenS
 						let encoding = quote_spanned! { f.span() =>
 							#type_name :: #name { #( ref #encoding_names, )* } => {
 								#dest.push_byte(#index as ::core::primitive::u8);
+								#record
 								#encode_fields
 							}
 						};
@@ -367,6 +404,7 @@ fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenS
 						let encoding = quote_spanned! { f.span() =>
 							#type_name :: #name ( #( ref #encoding_names, )* ) => {
 								#dest.push_byte(#index as ::core::primitive::u8);
+								#record
 								#encode_fields
 							}
 						};
@@ -384,6 +422,7 @@ fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenS
 							#type_name :: #name => {
 								#[allow(clippy::unnecessary_cast)]
 								#dest.push_byte(#index as ::core::primitive::u8);
+								#record
 							}
 						};
 
@@ -413,14 +452,27 @@ fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenS
 			[hinting, encoding]
 		},
 		Data::Union(ref data) =>
-			return Error::new(data.union_token.span(), "Union types are not supported.")
-				.to_compile_error(),
+			return utils::union_error(data.union_token.span()).to_compile_error(),
+	};
+	let [hinting, encoding] = match append_with {
+		Some(append_with) => [
+			quote! {
+				#hinting + #crate_path::Encode::size_hint(&#append_with(#self_))
+			},
+			quote! {
+				#encoding
+				#crate_path::Encode::encode_to(&#append_with(#self_), #dest);
+			},
+		],
+		None => [hinting, encoding],
 	};
 	quote! {
+		#inline
 		fn size_hint(&#self_) -> usize {
 			#hinting
 		}
 
+		#inline
 		fn encode_to<__CodecOutputEdqy: #crate_path::Output + ?::core::marker::Sized>(
 			&#self_,
 			#dest: &mut __CodecOutputEdqy
@@ -430,11 +482,21 @@ fn impl_encode(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenS
 	}
 }
 
-pub fn quote(data: &Data, type_name: &Ident, crate_path: &syn::Path) -> TokenStream {
-	if let Some(implementation) = try_impl_encode_single_field_optimisation(data, crate_path) {
+pub fn quote(
+	data: &Data,
+	type_name: &Ident,
+	crate_path: &syn::Path,
+	inline: &TokenStream,
+	append_with: Option<&syn::Path>,
+) -> TokenStream {
+	if let Some(implementation) = append_with
+		.is_none()
+		.then(|| try_impl_encode_single_field_optimisation(data, crate_path, inline))
+		.flatten()
+	{
 		implementation
 	} else {
-		impl_encode(data, type_name, crate_path)
+		impl_encode(data, type_name, crate_path, inline, append_with)
 	}
 }
 
@@ -0,0 +1,96 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of `EncodingFingerprint`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, Generics};
+
+use crate::utils;
+
+/// Add the expressions hashing the encoded `fields` to `writes`, and bound their types.
+fn write_fields(
+	fields: &Fields,
+	generics: &mut Generics,
+	writes: &mut Vec<TokenStream>,
+	crate_path: &syn::Path,
+) {
+	let fields = fields
+		.iter()
+		.filter(|field| !utils::should_skip(&field.attrs))
+		.collect::<Vec<_>>();
+	let len = fields.len() as u64;
+	writes.push(quote!(.write_u64(#len)));
+
+	for field in fields {
+		let ty = &field.ty;
+		let encoded_ty = if utils::is_compact(field) {
+			quote!(#crate_path::Compact<#ty>)
+		} else if let Some(encoded_as) = utils::get_alternative_type(field) {
+			encoded_as
+		} else if utils::is_lock(field) {
+			quote!(<#ty as #crate_path::Lockable>::Inner)
+		} else {
+			quote!(#ty)
+		};
+
+		generics
+			.make_where_clause()
+			.predicates
+			.push(parse_quote!(#encoded_ty: #crate_path::EncodingFingerprint));
+		writes.push(quote_spanned! { ty.span() =>
+			.write_u64(<#encoded_ty as #crate_path::EncodingFingerprint>::FINGERPRINT)
+		});
+	}
+}
+
+/// Generate the implementation of `EncodingFingerprint`.
+pub fn quote(input: &DeriveInput, crate_path: &syn::Path) -> syn::Result<TokenStream> {
+	let name = &input.ident;
+	let name_str = name.to_string();
+	let mut generics = input.generics.clone();
+	let mut writes = Vec::new();
+
+	match input.data {
+		Data::Struct(ref data) =>
+			write_fields(&data.fields, &mut generics, &mut writes, crate_path),
+		Data::Enum(ref data) => {
+			let variants = utils::try_get_variants(data)?;
+			let len = variants.len() as u64;
+			writes.push(quote!(.write_u64(#len)));
+			for (i, variant) in variants.into_iter().enumerate() {
+				let index = utils::variant_index(variant, i);
+				writes.push(quote! {
+					.write_u64(#index as ::core::primitive::u8 as ::core::primitive::u64)
+				});
+				write_fields(&variant.fields, &mut generics, &mut writes, crate_path);
+			}
+		},
+		Data::Union(ref data) => return Err(utils::union_error(data.union_token.span())),
+	}
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	Ok(quote! {
+		#[automatically_derived]
+		impl #impl_generics #crate_path::EncodingFingerprint for #name #ty_generics #where_clause {
+			#[allow(clippy::unnecessary_cast)]
+			const FINGERPRINT: ::core::primitive::u64 = #crate_path::FingerprintHasher::new()
+				.write_str(#name_str)
+				#( #writes )*
+				.finish();
+		}
+	})
+}
@@ -0,0 +1,193 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of the `ENCODING_LAYOUT` constant for `#[codec(document_layout)]`.
+
+use std::fmt::Write;
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Data, DeriveInput, Expr, ExprLit, Field, Fields, Lit, Variant};
+
+use crate::utils;
+
+/// Render tokens as compact text, e.g. `Vec<u8>` instead of `Vec < u8 >`.
+fn render(tokens: impl ToTokens) -> String {
+	let mut text = String::new();
+	write_tokens(&mut text, tokens.to_token_stream());
+	text
+}
+
+/// Write `tokens` to `text`, separating them by a space only where Rust code usually does.
+fn write_tokens(text: &mut String, tokens: TokenStream) {
+	// Whether the previous token is an identifier or a literal, which a following one must be
+	// separated from.
+	let mut after_word = false;
+	// Whether the previous token is the `-` of a `->`.
+	let mut in_arrow = false;
+	for token in tokens {
+		let is_word = matches!(token, TokenTree::Ident(_) | TokenTree::Literal(_));
+		match token {
+			TokenTree::Ident(_) | TokenTree::Literal(_) => {
+				if after_word {
+					text.push(' ');
+				}
+				write!(text, "{token}").expect("Writing to a String");
+			},
+			TokenTree::Group(group) => {
+				let (open, close) = match group.delimiter() {
+					Delimiter::Parenthesis => ("(", ")"),
+					Delimiter::Bracket => ("[", "]"),
+					Delimiter::Brace => ("{", "}"),
+					Delimiter::None => ("", ""),
+				};
+				// E.g. `&'static [u8]`, but `Fn(u8)`.
+				if after_word && group.delimiter() == Delimiter::Bracket {
+					text.push(' ');
+				}
+				text.push_str(open);
+				write_tokens(text, group.stream());
+				text.push_str(close);
+			},
+			TokenTree::Punct(punct) => match punct.as_char() {
+				'-' if punct.spacing() == Spacing::Joint => {
+					text.push_str(" -");
+					in_arrow = true;
+					continue;
+				},
+				'>' if in_arrow => text.push_str("> "),
+				',' | ';' => {
+					text.push(punct.as_char());
+					text.push(' ');
+				},
+				'+' | '=' => write!(text, " {} ", punct.as_char()).expect("Writing to a String"),
+				c => text.push(c),
+			},
+		}
+		after_word = is_word;
+		in_arrow = false;
+	}
+}
+
+/// The index of `variant` as written in the layout, e.g. `5` for `#[codec(index = 5)]`.
+fn index_text(variant: &Variant, i: usize) -> String {
+	let expr = utils::index_attribute(variant)
+		.or_else(|| variant.discriminant.as_ref().map(|(_, expr)| expr.clone()));
+	match expr {
+		// Without the suffix, e.g. `5` for `5u8`.
+		Some(Expr::Lit(ExprLit { lit: Lit::Int(lit), .. })) => lit.base10_digits().to_owned(),
+		Some(expr) => render(expr),
+		None => i.to_string(),
+	}
+}
+
+/// Describe how `field` is encoded.
+fn field_layout(field: &Field) -> String {
+	let ty = render(&field.ty);
+	if utils::is_compact(field) {
+		format!("compact {ty}")
+	} else if let Some(encoded_as) = utils::get_alternative_type(field) {
+		format!("{ty} as {}", render(encoded_as))
+	} else if let Some(max_len) = utils::get_max_len(field) {
+		format!("{ty} max_len {}", render(max_len))
+	} else if utils::is_lock(field) {
+		format!("lock {ty}")
+	} else {
+		ty
+	}
+}
+
+/// Write a line for each encoded field of `fields`, in encoding order.
+fn write_fields(layout: &mut String, fields: &Fields, indent: &str) {
+	for (i, field) in fields.iter().enumerate() {
+		if utils::should_skip(&field.attrs) {
+			continue;
+		}
+
+		let name = field.ident.as_ref().map_or_else(|| i.to_string(), ToString::to_string);
+		writeln!(layout, "{indent}{name}: {}", field_layout(field)).expect("Writing to a String");
+	}
+}
+
+/// The text of the layout of the type, or `None` for unions, which can't be encoded.
+fn layout(input: &DeriveInput) -> syn::Result<Option<String>> {
+	let name = &input.ident;
+	let mut layout = String::new();
+	match input.data {
+		Data::Struct(ref data) => {
+			writeln!(layout, "struct {name}").expect("Writing to a String");
+			write_fields(&mut layout, &data.fields, "  ");
+		},
+		Data::Enum(ref data) => {
+			writeln!(layout, "enum {name}").expect("Writing to a String");
+			for (i, variant) in utils::try_get_variants(data)?.into_iter().enumerate() {
+				let index = index_text(variant, i);
+				writeln!(layout, "  {index}: {}", variant.ident).expect("Writing to a String");
+				write_fields(&mut layout, &variant.fields, "    ");
+			}
+		},
+		Data::Union(_) => return Ok(None),
+	}
+	if let Some(append_with) = utils::get_append_with(&input.attrs) {
+		writeln!(layout, "  appended: {}", render(append_with)).expect("Writing to a String");
+	}
+	Ok(Some(layout))
+}
+
+/// Generate the inherent `ENCODING_LAYOUT` constant if the type has `#[codec(document_layout)]`.
+pub fn quote(input: &DeriveInput) -> TokenStream {
+	if utils::get_document_layout(&input.attrs).is_none() {
+		return quote!();
+	}
+
+	let layout = match layout(input) {
+		Ok(Some(layout)) => layout,
+		// The error is already reported by the derive.
+		Ok(None) | Err(_) => return quote!(),
+	};
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	quote! {
+		impl #impl_generics #name #ty_generics #where_clause {
+			/// The layout of the SCALE encoding of this type.
+			///
+			/// Each encoded field is listed in encoding order as `name: Type`, where the type is
+			/// prefixed by `compact` or `lock`, or followed by `as OtherType` or `max_len N`, if the
+			/// field has the respective attribute. Enum variants are listed as `index: Name`, followed by their
+			/// fields. Skipped fields and variants are not listed.
+			pub const ENCODING_LAYOUT: &'static str = #layout;
+		}
+	}
+}
+
+/// Generate a check that `Encode` is derived as well if the type has `#[codec(document_layout)]`,
+/// for the derives which don't generate the `ENCODING_LAYOUT` constant, as the attribute has no
+/// effect otherwise.
+pub fn quote_check(input: &DeriveInput) -> TokenStream {
+	let Some(attribute) = utils::get_document_layout(&input.attrs) else {
+		return quote!();
+	};
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let layout = quote_spanned!(attribute.span()=> Self::ENCODING_LAYOUT);
+
+	quote! {
+		impl #impl_generics #name #ty_generics #where_clause {
+			#[allow(dead_code)]
+			const __CODEC_DOCUMENT_LAYOUT_EDQY: &'static str = #layout;
+		}
+	}
+}
@@ -24,13 +24,20 @@ extern crate syn;
 extern crate quote;
 
 use crate::utils::{codec_crate_path, is_lint_attribute};
-use syn::{spanned::Spanned, Data, DeriveInput, Error, Field, Fields};
+use syn::DeriveInput;
 
+mod codec_fuzz;
 mod decode;
+mod decode_length;
 mod encode;
+mod fingerprint;
+mod layout;
+mod like_plain;
 mod max_encoded_len;
+mod preflight;
 mod trait_bounds;
 mod utils;
+mod variant_index;
 
 /// Wraps the impl block in a "dummy const"
 fn wrap_with_dummy_const(
@@ -59,6 +66,56 @@ fn wrap_with_dummy_const(
 ///   the `Encode` trait, overriding the default.
 /// * `#[codec(decode_bound(T: Decode))]`: a custom bound added to the `where`-clause when deriving
 ///   the `Decode` trait, overriding the default.
+/// * `#[codec(recursive)]`: the fields only add bounds to the type parameters they use, e.g. `T:
+///   Decode` instead of `Vec<B<T>>: Decode`. This is needed for types which are recursive through
+///   other types, like `A<T>` with a field of type `B<T>` with a field of type `Box<A<T>>`, whose
+///   inferred bounds can't be satisfied. Unlike `#[codec(dumb_trait_bound)]`, type parameters which
+///   aren't used by an encoded field aren't bounded.
+/// * `#[codec(crate = $path)]`: the codec crate is referred to by `$path`, e.g. `$crate::codec`
+///   when deriving in a macro of a crate re-exporting `parity_scale_codec`. It is supported by all
+///   derive macros of this crate. Without it, the crate is looked up in the dependencies of the
+///   `Cargo.toml` of the crate using the derive, also if renamed, and is `::parity_scale_codec`
+///   when building without Cargo. If the `Cargo.toml` doesn't list it, the error names what was
+///   searched and suggests this attribute.
+/// * `#[codec(dyn_input)]`: when deriving `Decode`, the decoding is done by a function taking a
+///   `&mut dyn Input`, which is called by `Decode::decode`. The derived code is then only
+///   instantiated once instead of once per input type, at the cost of dynamic dispatch when reading
+///   from the input.
+/// * `#[codec(document_layout)]`: when deriving `Encode`, an associated `const ENCODING_LAYOUT:
+///   &'static str` describing the order, types and attributes of the encoded fields and variants is
+///   generated. It is a compile error to use it when deriving `Decode` but not `Encode`.
+/// * `#[codec(variant_index)]`: when deriving `Encode` for an enum, a `pub const fn
+///   scale_variant_index(&self) -> u8` returning the index of the variant, which is the first byte
+///   of its encoding, is generated. It can't be used on enums with skipped variants.
+/// * `#[codec(like_plain($OtherType))]`: when deriving `Encode` for a struct, `EncodeLike` is also
+///   implemented from the struct to `$OtherType` and back. `$OtherType` is a struct with the same
+///   fields, which encode like the fields of the struct without their attributes, e.g. a
+///   `Compact<u64>` field for a `#[codec(compact)] u64` field. `$OtherType` must derive `Encode`
+///   too, and skip the same fields. It is a compile error if the field names or their order differ,
+///   if a different field is skipped or if a field doesn't implement `EncodeLike` of the encoded
+///   type of the respective field.
+/// * `#[codec(reserve_index = $n)]`: on enums, the index $n is reserved for a variant gated by
+///   `#[cfg]`, see below. Several indexes are reserved with `#[codec(reserve_index($n, $m))]`.
+/// * `#[codec(inline)]` and `#[codec(never_inline)]`: the methods of the derived `Encode` and
+///   `Decode` implementations are marked `#[inline]` respectively `#[inline(never)]`, e.g. to
+///   inline the methods of small types into their callers or to reduce the code size of large
+///   types. By default, only structs with a single encoded field and `#[repr(transparent)]` types
+///   get `#[inline]`.
+/// * `#[codec(normalize = "$path")]`: when deriving `Decode`, the decoded value is passed to the
+///   function at `$path`, which returns the value to use or an error, e.g. to re-establish the
+///   invariants of the type. See also `parity_scale_codec::DecodeThenNormalize`.
+/// * `#[codec(append_with = "$path")]`: the value returned by the function at `$path`, called with
+///   a reference to the value, is encoded after the value, e.g. a checksum or the length of a field
+///   which the type doesn't store. When decoding, the appended value is decoded and compared to the
+///   value returned by the function for the decoded value, and decoding fails if they differ. The
+///   returned type must implement `Encode`, `Decode` and `PartialEq`. The function is also called
+///   by `Encode::size_hint`. `MaxEncodedLen`, `Preflight`, `EncodingFingerprint`, `CompactAs`, the
+///   wrapper type derives and `like_plain` can't be used with it.
+/// * `#[codec(migrate_from($OldType, "$path"))]`: when deriving `Decode`, also implements
+///   `parity_scale_codec::DecodeMigrate` with `$OldType`, an older layout of the type, and the
+///   function at `$path`, which takes the `$OldType` and returns a `Result<Self, Error>`.
+///   `DecodeMigrate::decode_all_or_migrate` decodes a complete buffer with the current layout, or
+///   else with the old one. The derived `Decode` only decodes the current layout.
 ///
 /// # Struct
 ///
@@ -66,12 +123,36 @@ fn wrap_with_dummy_const(
 ///
 /// Fields can have some attributes:
 /// * `#[codec(skip)]`: the field is not encoded. It must derive `Default` if Decode is derived.
+/// * `#[codec(skip, default = "$path")]`: the field is not encoded. When decoding, it is
+///   reconstructed by calling the function at `$path` with references to all the non-skipped fields
+///   declared before it, e.g. to recompute a cached value. The field does not need to implement
+///   `Default`.
 /// * `#[codec(compact)]`: the field is encoded in its compact representation i.e. the field must
 ///   implement `parity_scale_codec::HasCompact` and will be encoded as `HasCompact::Type`.
 /// * `#[codec(encoded_as = "$EncodeAs")]`: the field is encoded as an alternative type. $EncodedAs
 ///   type must implement `parity_scale_codec::EncodeAsRef<'_, $FieldType>` with $FieldType the type
 ///   of the field with the attribute. This is intended to be used for types implementing
 ///   `HasCompact` as shown in the example.
+/// * `#[codec(decoded_via = "$DecodeVia")]`: like `encoded_as`, the field is encoded as $DecodeVia
+///   through `parity_scale_codec::EncodeAsRef<'_, $FieldType>`. When decoding, the decoded
+///   $DecodeVia is converted with `parity_scale_codec::DecodeAndConvert<$FieldType>`, which can
+///   fail, e.g. to reject values which don't represent a valid $FieldType.
+/// * `#[codec(lock)]`: the field is a lock, e.g. `std::sync::Mutex<T>` or `std::sync::RwLock<T>`,
+///   and is encoded as the value it protects while the lock is held. When decoding, a new lock
+///   around the decoded value is created. The field must implement `parity_scale_codec::Lockable`,
+///   which is only available with the `std` feature. Poisoned locks are encoded as well, see
+///   `Lockable` for details. `Encode::size_hint` doesn't take the lock, and doesn't count the
+///   field.
+/// * `#[codec(max_len = $N)]`: the field is a collection implementing
+///   `parity_scale_codec::LenPrefixed`, e.g. `Vec<T>` or `String`, with at most $N items, a `u32`
+///   literal or a path to a `usize` constant. Decoding fails before allocating anything if the
+///   collection has more items, and the derived `MaxEncodedLen` uses $N as the maximum number of
+///   items. Encoding is unchanged.
+/// * `#[codec(mel(cap = $N))]`: the derived `MaxEncodedLen` uses $N, a `usize` literal or a path to
+///   a `usize` constant, as the maximum encoded length of the field, whose type doesn't need to
+///   implement `MaxEncodedLen`. This is for fields which are bounded by validation elsewhere. With
+///   `#[codec(mel(cap = $N, check))]`, decoding fails if the field is encoded in more than $N
+///   bytes, see `parity_scale_codec::decode_with_max_encoded_len`. Encoding is unchanged.
 ///
 /// ```
 /// # use parity_scale_codec_derive::Encode;
@@ -94,12 +175,29 @@ fn wrap_with_dummy_const(
 /// * if variant has attribute: `#[codec(index = "$n")]` then n
 /// * else if variant has discriminant (like 3 in `enum T { A = 3 }`) then the discriminant.
 /// * else its position in the variant set, excluding skipped variants, but including variant with
-///   discriminant or attribute. Warning this position does collision with discriminant or attribute
-///   index.
+///   discriminant or attribute. Warning this position can collide with a discriminant or attribute
+///   index, which is a compile error.
+///
+/// Variants gated by `#[cfg]` would shift the implicit indexes of the variants after them between
+/// builds with different `#[cfg]` options, so the variants after a gated variant must have
+/// explicit indexes. An enum can reserve the indexes of its gated variants with
+/// `#[codec(reserve_index = $n)]`, so no other variant can use them in builds without the gated
+/// variants. The index of each gated variant of such an enum must then be reserved. As the derive
+/// macros only see the variants which are compiled, this is only checked in builds with the gated
+/// variants.
 ///
 /// variant attributes:
 /// * `#[codec(skip)]`: the variant is not encoded.
+/// * `#[codec(skip, decode_as = "$Variant")]`: the variant is not encoded, but its index is decoded
+///   as the variant `$Variant`, which must not be skipped. The variant must have an explicit index.
+///   This allows retiring a variant while still decoding old encodings of it.
+/// * `#[codec(skip, decode_error = "$msg")]`: like `decode_as`, but decoding the index of the
+///   variant fails with the error `$msg`, instead of the generic error for unknown indexes.
 /// * `#[codec(index = "$n")]`: override variant index.
+/// * `#[codec(index = $expr)]`: override variant index with a constant expression, e.g. a `const`
+///   shared with other code. The expression is evaluated at compile time, outside of the
+///   implementations, so it can't use the generic parameters of the type. It may be of any integer
+///   type, and it is a compile error if it's not in `0..=255`.
 ///
 /// field attributes: same as struct fields attributes.
 ///
@@ -121,6 +219,106 @@ fn wrap_with_dummy_const(
 /// assert_eq!(EnumType::C.encode(), vec![3]);
 /// assert_eq!(EnumType::D.encode(), vec![2]);
 /// ```
+///
+/// ```
+/// # use parity_scale_codec_derive::Encode;
+/// # use parity_scale_codec::Encode as _;
+/// const PING: u8 = 7;
+///
+/// #[derive(Encode)]
+/// enum Message {
+///     #[codec(index = PING)]
+///     Ping,
+///     #[codec(index = PING + 1)]
+///     Pong,
+/// }
+///
+/// assert_eq!(Message::Pong.encode(), vec![PING + 1]);
+/// ```
+///
+/// ```
+/// # use parity_scale_codec_derive::Encode;
+/// # use parity_scale_codec::Encode as _;
+/// #[derive(Encode)]
+/// #[codec(reserve_index = 1)]
+/// enum Event {
+///     Started,
+///     #[cfg(feature = "metrics")]
+///     #[codec(index = 1)]
+///     Measured(u64),
+///     #[codec(index = 2)]
+///     Stopped,
+/// }
+///
+/// assert_eq!(Event::Stopped.encode(), vec![2]);
+/// ```
+///
+/// ```
+/// # use parity_scale_codec_derive::{Decode, Encode};
+/// # use parity_scale_codec::decode_prefix;
+/// #[derive(Encode, Decode, Debug, PartialEq)]
+/// enum Transfer {
+///     #[codec(index = 0)]
+///     #[codec(skip, decode_as = "Transfer")]
+///     LegacyTransfer(u64),
+///     #[codec(index = 1)]
+///     #[codec(skip, decode_error = "Burning is no longer supported")]
+///     Burn,
+///     #[codec(index = 2)]
+///     Transfer(u64),
+/// }
+///
+/// let (decoded, _) = decode_prefix::<Transfer>(&[0, 7, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+/// assert_eq!(decoded, Transfer::Transfer(7));
+/// assert!(decode_prefix::<Transfer>(&[1]).is_err());
+/// ```
+///
+/// # Compile errors
+///
+/// The derive macros reject, with a hint on how to fix it:
+/// * unions, as the encoding can't tell which of their fields is set,
+/// * enums with more than 256 non-skipped variants,
+/// * when deriving `Decode`, enums with several variants with the same index, which couldn't be
+///   told apart. If some indexes are given by discriminant or `#[codec(index = $expr)]`
+///   expressions, this is checked when the expressions are evaluated. `#[derive(Encode)]` still
+///   accepts them.
+/// * enums with variants gated by `#[cfg]` which shift the indexes of the variants after them, or
+///   whose indexes aren't reserved in enums reserving indexes, see above.
+/// * fields with more than one of `skip`, `compact`, `encoded_as`, `decoded_via`, `lock`, `max_len`
+///   and `mel`.
+/// * when deriving `Decode`, types containing themselves without indirection, e.g. in a field of
+///   type `Option<Self>` instead of `Option<Box<Self>>`.
+///
+/// Types which are recursive through a `Box`, `Vec` or other indirection can be decoded, and a
+/// decoded value nests as deep as its encoding tells. Decoding a pointer or a collection calls
+/// `Input::descend_ref`, so untrusted input should be decoded with a depth limit, e.g. with
+/// `parity_scale_codec::DecodeLimit`, to fail instead of overflowing the stack.
+///
+/// ```compile_fail
+/// # use parity_scale_codec_derive::Decode;
+/// #[derive(Decode)]
+/// enum Duplicate {
+///     A,
+///     // `A` has the index 0 as well.
+///     #[codec(index = 0)]
+///     B,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use parity_scale_codec_derive::Decode;
+/// const ONE: isize = 1;
+///
+/// #[derive(Decode)]
+/// enum Duplicate {
+///     A = 5,
+///     // `B` has the index 1, its position.
+///     B,
+///     C = ONE,
+/// }
+/// ```
+///
+/// The cases are tested in `tests/scale_codec_ui` of the `parity-scale-codec` repository.
 #[proc_macro_derive(Encode, attributes(codec))]
 pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 	let mut input: DeriveInput = match syn::parse(input) {
@@ -137,6 +335,12 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 		Err(error) => return error.into_compile_error().into(),
 	};
 
+	let layout = layout::quote(&input);
+	let encoded_field_names = like_plain::quote_encoded_field_names(&input, &crate_path);
+	let variant_index = variant_index::quote(&input);
+	let index_check = utils::quote_index_check(&input, false);
+	let inline = utils::inline_hint(&input);
+
 	if let Err(e) = trait_bounds::add(
 		&input.ident,
 		&mut input.generics,
@@ -144,16 +348,21 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 		utils::custom_encode_trait_bound(&input.attrs),
 		parse_quote!(#crate_path::Encode),
 		None,
+		false,
+		None,
 		utils::has_dumb_trait_bound(&input.attrs),
+		utils::has_recursive(&input.attrs),
 		&crate_path,
 	) {
 		return e.to_compile_error().into();
 	}
 
+	let like_plain = like_plain::quote(&input, &crate_path);
 	let name = &input.ident;
 	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-	let encode_impl = encode::quote(&input.data, name, &crate_path);
+	let append_with = utils::get_append_with(&input.attrs);
+	let encode_impl = encode::quote(&input.data, name, &crate_path, &inline, append_with.as_ref());
 
 	let impl_block = quote! {
 		#[automatically_derived]
@@ -163,6 +372,16 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 		#[automatically_derived]
 		impl #impl_generics #crate_path::EncodeLike for #name #ty_generics #where_clause {}
+
+		#layout
+
+		#variant_index
+
+		#encoded_field_names
+
+		#like_plain
+
+		#index_check
 	};
 
 	wrap_with_dummy_const(input, impl_block)
@@ -182,11 +401,22 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 		return e.to_compile_error().into();
 	}
 
+	if let Err(e) = utils::check_variant_indices(&input) {
+		return e.to_compile_error().into();
+	}
+
+	if let Err(e) = utils::check_no_inline_recursion(&input) {
+		return e.to_compile_error().into();
+	}
+
 	let crate_path = match codec_crate_path(&input.attrs) {
 		Ok(crate_path) => crate_path,
 		Err(error) => return error.into_compile_error().into(),
 	};
 
+	let layout_check = layout::quote_check(&input);
+	let index_check = utils::quote_index_check(&input, true);
+
 	if let Err(e) = trait_bounds::add(
 		&input.ident,
 		&mut input.generics,
@@ -194,7 +424,10 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 		utils::custom_decode_trait_bound(&input.attrs),
 		parse_quote!(#crate_path::Decode),
 		Some(parse_quote!(Default)),
+		true,
+		None,
 		utils::has_dumb_trait_bound(&input.attrs),
+		utils::has_recursive(&input.attrs),
 		&crate_path,
 	) {
 		return e.to_compile_error().into();
@@ -203,16 +436,80 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 	let name = &input.ident;
 	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 	let ty_gen_turbofish = ty_generics.as_turbofish();
+	let inline = utils::inline_hint(&input);
 
 	let input_ = quote!(__codec_input_edqy);
-	let decoding =
+	let mut decoding =
 		decode::quote(&input.data, name, &quote!(#ty_gen_turbofish), &input_, &crate_path);
 
+	if let Some(append_with) = utils::get_append_with(&input.attrs) {
+		let err_msg = format!(
+			"Could not decode `{name}`, the appended value doesn't match `{}`",
+			quote!(#append_with).to_string().replace(' ', "")
+		);
+		decoding = quote! {
+			let __codec_decoded_edqy = {
+				// The decoding may move the input, reborrow it to decode the appended value.
+				let #input_ = &mut *#input_;
+				#[allow(clippy::redundant_closure_call)]
+				(move || -> ::core::result::Result<Self, #crate_path::Error> { #decoding })()?
+			};
+			fn __codec_decode_like_edqy<__CodecValueEdqy, __CodecInputEdqy>(
+				_: &__CodecValueEdqy,
+				input: &mut __CodecInputEdqy,
+			) -> ::core::result::Result<__CodecValueEdqy, #crate_path::Error>
+			where
+				__CodecValueEdqy: #crate_path::Decode,
+				__CodecInputEdqy: #crate_path::Input,
+			{
+				<__CodecValueEdqy as #crate_path::Decode>::decode(input)
+			}
+			let __codec_expected_edqy = #append_with(&__codec_decoded_edqy);
+			if __codec_decode_like_edqy(&__codec_expected_edqy, #input_)? != __codec_expected_edqy {
+				return ::core::result::Result::Err(#err_msg.into());
+			}
+			::core::result::Result::Ok(__codec_decoded_edqy)
+		};
+	}
+
+	if let Some(normalize) = utils::get_normalize(&input.attrs) {
+		let err_msg = format!("Could not decode `{name}`, normalization failed");
+		decoding = quote! {
+			#[allow(clippy::redundant_closure_call)]
+			let __codec_decoded_edqy = (move || -> ::core::result::Result<Self, #crate_path::Error> {
+				#decoding
+			})()?;
+			#normalize(__codec_decoded_edqy).map_err(|e| e.chain(#err_msg))
+		};
+	}
+
+	// Move the decoding into a function that is only generic over the type parameters, and not
+	// over the input.
+	let mut impl_decode_dyn = quote! {};
+	if utils::has_dyn_input(&input.attrs) {
+		impl_decode_dyn = quote! {
+			#[automatically_derived]
+			impl #impl_generics #name #ty_generics #where_clause {
+				#[doc(hidden)]
+				fn __codec_decode_dyn_edqy(
+					mut __codec_dyn_input_edqy: &mut dyn #crate_path::Input,
+				) -> ::core::result::Result<Self, #crate_path::Error> {
+					let #input_ = &mut __codec_dyn_input_edqy;
+					#decoding
+				}
+			}
+		};
+		decoding = quote! {
+			Self::__codec_decode_dyn_edqy(#input_)
+		};
+	}
+
 	let decode_into_body =
 		decode::quote_decode_into(&input.data, &crate_path, &input_, &input.attrs);
 
 	let impl_decode_into = if let Some(body) = decode_into_body {
 		quote! {
+			#inline
 			fn decode_into<__CodecInputEdqy: #crate_path::Input>(
 				#input_: &mut __CodecInputEdqy,
 				dst_: &mut ::core::mem::MaybeUninit<Self>,
@@ -224,9 +521,32 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 		quote! {}
 	};
 
+	let impl_decode_migrate = match utils::get_migrate_from(&input.attrs) {
+		Some(utils::MigrateFrom { old, convert }) => quote! {
+			#[automatically_derived]
+			impl #impl_generics #crate_path::DecodeMigrate for #name #ty_generics #where_clause {
+				type Old = #old;
+
+				fn migrate(old: #old) -> ::core::result::Result<Self, #crate_path::Error> {
+					#convert(old)
+				}
+			}
+		},
+		None => quote! {},
+	};
+
 	let impl_block = quote! {
+		#impl_decode_dyn
+
+		#impl_decode_migrate
+
+		#layout_check
+
+		#index_check
+
 		#[automatically_derived]
 		impl #impl_generics #crate_path::Decode for #name #ty_generics #where_clause {
+			#inline
 			fn decode<__CodecInputEdqy: #crate_path::Input>(
 				#input_: &mut __CodecInputEdqy
 			) -> ::core::result::Result<Self, #crate_path::Error> {
@@ -264,7 +584,10 @@ pub fn decode_with_mem_tracking_derive(input: proc_macro::TokenStream) -> proc_m
 		utils::custom_decode_with_mem_tracking_trait_bound(&input.attrs),
 		parse_quote!(#crate_path::DecodeWithMemTracking),
 		Some(parse_quote!(Default)),
+		true,
+		None,
 		utils::has_dumb_trait_bound(&input.attrs),
+		utils::has_recursive(&input.attrs),
 		&crate_path,
 	) {
 		return e.to_compile_error().into();
@@ -276,6 +599,7 @@ pub fn decode_with_mem_tracking_derive(input: proc_macro::TokenStream) -> proc_m
 	let decode_with_mem_tracking_checks =
 		decode::quote_decode_with_mem_tracking_checks(&input.data, &crate_path);
 	let impl_block = quote! {
+		#[allow(clippy::multiple_bound_locations)]
 		fn check_struct #impl_generics() #where_clause {
 			#decode_with_mem_tracking_checks
 		}
@@ -293,6 +617,10 @@ pub fn decode_with_mem_tracking_derive(input: proc_macro::TokenStream) -> proc_m
 ///
 /// Attribute skip can be used to skip other fields.
 ///
+/// No bounds are added for the type parameters, so a generic wrapper like `Wrapper<T>(T)` can be
+/// used with `#[codec(compact)]` as long as its inner type can be compact encoded, e.g.
+/// `Wrapper<u32>`.
+///
 /// # Example
 ///
 /// ```
@@ -304,7 +632,7 @@ pub fn decode_with_mem_tracking_derive(input: proc_macro::TokenStream) -> proc_m
 /// ```
 #[proc_macro_derive(CompactAs, attributes(codec))]
 pub fn compact_as_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-	let mut input: DeriveInput = match syn::parse(input) {
+	let input: DeriveInput = match syn::parse(input) {
 		Ok(input) => input,
 		Err(e) => return e.to_compile_error().into(),
 	};
@@ -318,67 +646,17 @@ pub fn compact_as_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 		Err(error) => return error.into_compile_error().into(),
 	};
 
-	if let Err(e) = trait_bounds::add::<()>(
-		&input.ident,
-		&mut input.generics,
-		&input.data,
-		None,
-		parse_quote!(#crate_path::CompactAs),
-		None,
-		utils::has_dumb_trait_bound(&input.attrs),
-		&crate_path,
-	) {
-		return e.to_compile_error().into();
-	}
-
+	// No bounds are added for the inner type: `CompactAs` doesn't require anything from it, and
+	// requiring it to implement `CompactAs` itself would rule out e.g. `Wrapper<u32>`. Whether the
+	// inner type can be compact encoded is checked where `Compact<Self>` is used instead.
 	let name = &input.ident;
 	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-	fn val_or_default(field: &Field) -> proc_macro2::TokenStream {
-		let skip = utils::should_skip(&field.attrs);
-		if skip {
-			quote_spanned!(field.span()=> Default::default())
-		} else {
-			quote_spanned!(field.span()=> x)
-		}
-	}
-
-	let (inner_ty, inner_field, constructor) = match input.data {
-		Data::Struct(ref data) => match data.fields {
-			Fields::Named(ref fields) if utils::filter_skip_named(fields).count() == 1 => {
-				let recurse = fields.named.iter().map(|f| {
-					let name_ident = &f.ident;
-					let val_or_default = val_or_default(f);
-					quote_spanned!(f.span()=> #name_ident: #val_or_default)
-				});
-				let field = utils::filter_skip_named(fields).next().expect("Exactly one field");
-				let field_name = &field.ident;
-				let constructor = quote!( #name { #( #recurse, )* });
-				(&field.ty, quote!(&self.#field_name), constructor)
-			},
-			Fields::Unnamed(ref fields) if utils::filter_skip_unnamed(fields).count() == 1 => {
-				let recurse = fields.unnamed.iter().map(|f| {
-					let val_or_default = val_or_default(f);
-					quote_spanned!(f.span()=> #val_or_default)
-				});
-				let (id, field) =
-					utils::filter_skip_unnamed(fields).next().expect("Exactly one field");
-				let id = syn::Index::from(id);
-				let constructor = quote!( #name(#( #recurse, )*));
-				(&field.ty, quote!(&self.#id), constructor)
-			},
-			_ =>
-				return Error::new(
-					data.fields.span(),
-					"Only structs with a single non-skipped field can derive CompactAs",
-				)
-				.to_compile_error()
-				.into(),
-		},
-		Data::Enum(syn::DataEnum { enum_token: syn::token::Enum { span }, .. }) |
-		Data::Union(syn::DataUnion { union_token: syn::token::Union { span }, .. }) =>
-			return Error::new(span, "Only structs can derive CompactAs").to_compile_error().into(),
-	};
+	let utils::SingleField { ty: inner_ty, reference: inner_field, constructor } =
+		match utils::single_field(&input, "CompactAs") {
+			Ok(field) => field,
+			Err(e) => return e.to_compile_error().into(),
+		};
 
 	let impl_block = quote! {
 		#[automatically_derived]
@@ -407,6 +685,361 @@ pub fn compact_as_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 	wrap_with_dummy_const(input, impl_block)
 }
 
+/// Derive `parity_scale_codec::WrapperTypeEncode` for a struct with a single non-skipped field.
+///
+/// A `Deref` implementation with the field as target is generated as well, so the struct is
+/// encoded exactly like its field, and implements `EncodeLike` for itself and its field. Skipped
+/// fields must not carry information that would need to be encoded.
+///
+/// Use it together with `WrapperTypeDecode` to decode the struct like its field.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec_derive::{WrapperTypeDecode, WrapperTypeEncode};
+/// # use parity_scale_codec::{decode_prefix, Encode};
+/// #[derive(Debug, PartialEq, WrapperTypeEncode, WrapperTypeDecode)]
+/// struct AccountName(String);
+///
+/// let encoded = AccountName("alice".into()).encode();
+/// assert_eq!(encoded, "alice".encode());
+/// assert_eq!(decode_prefix::<AccountName>(&encoded), Ok((AccountName("alice".into()), &[][..])));
+/// ```
+#[proc_macro_derive(WrapperTypeEncode, attributes(codec))]
+pub fn wrapper_type_encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input: DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	if let Err(e) = utils::check_attributes(&input) {
+		return e.to_compile_error().into();
+	}
+
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(error) => return error.into_compile_error().into(),
+	};
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let utils::SingleField { ty: inner_ty, reference: inner_field, .. } =
+		match utils::single_field(&input, "WrapperTypeEncode") {
+			Ok(field) => field,
+			Err(e) => return e.to_compile_error().into(),
+		};
+
+	// `EncodeLike` requires `Encode`, which is only implemented if the field implements it.
+	let mut encode_like_generics = input.generics.clone();
+	encode_like_generics
+		.make_where_clause()
+		.predicates
+		.push(parse_quote!(#inner_ty: #crate_path::Encode));
+	let (_, _, encode_like_where_clause) = encode_like_generics.split_for_impl();
+
+	let impl_block = quote! {
+		#[automatically_derived]
+		impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+			type Target = #inner_ty;
+
+			fn deref(&self) -> &#inner_ty {
+				#inner_field
+			}
+		}
+
+		#[automatically_derived]
+		impl #impl_generics #crate_path::WrapperTypeEncode for #name #ty_generics #where_clause {}
+
+		#[automatically_derived]
+		impl #impl_generics #crate_path::EncodeLike for #name #ty_generics
+			#encode_like_where_clause {}
+
+		#[automatically_derived]
+		impl #impl_generics #crate_path::EncodeLike<#inner_ty> for #name #ty_generics
+			#encode_like_where_clause {}
+	};
+
+	wrap_with_dummy_const(input, impl_block)
+}
+
+/// Derive `parity_scale_codec::WrapperTypeDecode` for a struct with a single non-skipped field.
+///
+/// A `From` implementation converting the field to the struct is generated as well, so the struct
+/// is decoded exactly like its field. Skipped fields are set to their `Default` value.
+/// `DecodeWithMemTracking` is implemented if the field implements it.
+///
+/// See `WrapperTypeEncode` for an example.
+#[proc_macro_derive(WrapperTypeDecode, attributes(codec))]
+pub fn wrapper_type_decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input: DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	if let Err(e) = utils::check_attributes(&input) {
+		return e.to_compile_error().into();
+	}
+
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(error) => return error.into_compile_error().into(),
+	};
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let utils::SingleField { ty: inner_ty, constructor, .. } =
+		match utils::single_field(&input, "WrapperTypeDecode") {
+			Ok(field) => field,
+			Err(e) => return e.to_compile_error().into(),
+		};
+
+	let mut mem_tracking_generics = input.generics.clone();
+	mem_tracking_generics
+		.make_where_clause()
+		.predicates
+		.push(parse_quote!(#inner_ty: #crate_path::DecodeWithMemTracking));
+	let (_, _, mem_tracking_where_clause) = mem_tracking_generics.split_for_impl();
+
+	let impl_block = quote! {
+		#[automatically_derived]
+		impl #impl_generics ::core::convert::From<#inner_ty> for #name #ty_generics #where_clause {
+			fn from(x: #inner_ty) -> Self {
+				#constructor
+			}
+		}
+
+		#[automatically_derived]
+		impl #impl_generics #crate_path::WrapperTypeDecode for #name #ty_generics #where_clause {
+			type Wrapped = #inner_ty;
+		}
+
+		#[automatically_derived]
+		impl #impl_generics #crate_path::DecodeWithMemTracking for #name #ty_generics
+			#mem_tracking_where_clause {}
+	};
+
+	wrap_with_dummy_const(input, impl_block)
+}
+
+/// Derive `parity_scale_codec::DecodeLength` for an enum whose variants each wrap a collection.
+///
+/// Each variant must have exactly one field which is not skipped, whose type implements
+/// `DecodeLength`, e.g. `Vec<T>`. The length is read from the encoding by skipping the variant
+/// index and delegating to the field, without decoding any item. Fields with
+/// `#[codec(encoded_as = "...")]` or `#[codec(decoded_via = "...")]` use the alternative type, and
+/// `#[codec(compact)]` fields are not supported.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec::{DecodeLength, Encode};
+/// #[derive(Encode, DecodeLength)]
+/// enum Calls {
+///     V1(Vec<u32>),
+///     #[codec(index = 5)]
+///     V2(Vec<(u8, u64)>),
+/// }
+///
+/// let encoded = Calls::V2(vec![(1, 2); 3]).encode();
+/// assert_eq!(<Calls as DecodeLength>::len(&encoded), Ok(3));
+/// ```
+#[proc_macro_derive(DecodeLength, attributes(codec))]
+pub fn decode_length_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input: DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	if let Err(e) = utils::check_attributes(&input) {
+		return e.to_compile_error().into();
+	}
+
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(error) => return error.into_compile_error().into(),
+	};
+
+	match decode_length::quote(&input, &crate_path) {
+		Ok(impl_block) => wrap_with_dummy_const(input, impl_block),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
+
+/// Derive `parity_scale_codec::EncodingFingerprint` for struct and enum.
+///
+/// The fingerprint is computed from the name of the type, the fingerprints of the types its
+/// encoded fields are encoded as, and the indexes of its variants. So the field attributes
+/// `compact`, `encoded_as`, `lock` and `skip` are taken into account.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec::{AnyScale, Encode, EncodingFingerprint};
+/// #[derive(Encode, EncodingFingerprint)]
+/// enum Job {
+///     Resize { width: u32, height: u32 },
+///     #[codec(index = 3)]
+///     Delete(Vec<u8>),
+/// }
+///
+/// assert!(AnyScale::new(&Job::Delete(vec![1])).is::<Job>());
+/// ```
+#[proc_macro_derive(EncodingFingerprint, attributes(codec))]
+pub fn encoding_fingerprint_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input: DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	if let Err(e) = utils::check_attributes(&input) {
+		return e.to_compile_error().into();
+	}
+
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(error) => return error.into_compile_error().into(),
+	};
+
+	if let Err(e) = utils::reject_append_with(&input.attrs, "EncodingFingerprint") {
+		return e.to_compile_error().into();
+	}
+
+	match fingerprint::quote(&input, &crate_path) {
+		Ok(impl_block) => wrap_with_dummy_const(input, impl_block),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
+
+/// Derive `parity_scale_codec::Preflight` for struct and enum.
+///
+/// The encoding is walked like the derived `Decode` reads it, so the field attributes `compact`,
+/// `encoded_as`, `decoded_via`, `lock` and `skip` are taken into account, and the types the fields
+/// are encoded as must implement `Preflight`. The limits of `max_len` and `mel(cap = $N, check)`
+/// aren't checked.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec::{preflight_decode, Decode, Encode, Preflight};
+/// #[derive(Encode, Decode, Preflight)]
+/// enum Request {
+///     Get(Vec<u8>),
+///     Put { key: Vec<u8>, value: Vec<u8> },
+/// }
+///
+/// let request = Request::Put { key: vec![1, 2], value: vec![3; 100] };
+/// let estimate = preflight_decode::<Request>(&request.encode(), 16).unwrap();
+/// assert_eq!(estimate.elements, 102);
+/// ```
+#[proc_macro_derive(Preflight, attributes(codec))]
+pub fn preflight_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let mut input: DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	if let Err(e) = utils::check_attributes(&input) {
+		return e.to_compile_error().into();
+	}
+
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(error) => return error.into_compile_error().into(),
+	};
+
+	if let Err(e) = utils::reject_append_with(&input.attrs, "Preflight") {
+		return e.to_compile_error().into();
+	}
+
+	if let Err(e) = trait_bounds::add(
+		&input.ident,
+		&mut input.generics,
+		&input.data,
+		None::<utils::CustomTraitBound<utils::decode_bound>>,
+		parse_quote!(#crate_path::Preflight),
+		Some(parse_quote!(Default)),
+		true,
+		None,
+		utils::has_dumb_trait_bound(&input.attrs),
+		utils::has_recursive(&input.attrs),
+		&crate_path,
+	) {
+		return e.to_compile_error().into();
+	}
+
+	let name = &input.ident;
+	let walk = match preflight::quote(&input.data, name, &crate_path) {
+		Ok(walk) => walk,
+		Err(e) => return e.to_compile_error().into(),
+	};
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let impl_block = quote! {
+		#[automatically_derived]
+		impl #impl_generics #crate_path::Preflight for #name #ty_generics #where_clause {
+			fn preflight<__CodecInputEdqy: #crate_path::Input>(
+				__codec_input_edqy: &mut __CodecInputEdqy,
+				__codec_estimate_edqy: &mut #crate_path::DecodeCostEstimate,
+			) -> ::core::result::Result<(), #crate_path::Error> {
+				#walk
+			}
+		}
+	};
+
+	wrap_with_dummy_const(input, impl_block)
+}
+
+/// Generate a fuzz target and, for small types, an exhaustive test of the encoding of a type.
+///
+/// The type must implement `Encode`, `Decode`, `PartialEq` and `Debug`. The derive generates:
+/// * With `--cfg fuzzing`, as set by `cargo fuzz` and `cargo hfuzz`, an inherent function
+///   `codec_fuzz_roundtrip(data: &[u8])`, which decodes `data` and panics if the decoded value
+///   doesn't survive an encoding roundtrip, see `parity_scale_codec::codec_fuzz::roundtrip`.
+/// * For types without generics whose encoding is at most 2 bytes, a test
+///   `codec_fuzz_exhaustive_<TypeName>` decoding all possible inputs, and checking that each
+///   decoded value is encoded to its input, see `parity_scale_codec::codec_fuzz::check_exhaustive`.
+///   This covers fieldless enums and types made of `u8`, `i8`, `u16`, `i16` and `bool` fields
+///   without attributes.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec_derive::{CodecFuzz, Decode, Encode};
+/// #[derive(Debug, PartialEq, Encode, Decode, CodecFuzz)]
+/// enum Status {
+///     Active,
+///     Suspended { reason: u8 },
+/// }
+///
+/// // In a fuzz target, e.g. `fuzz_target!(|data: &[u8]| Status::codec_fuzz_roundtrip(data));`.
+/// # #[cfg(fuzzing)]
+/// Status::codec_fuzz_roundtrip(&[1, 7]);
+/// ```
+#[proc_macro_derive(CodecFuzz, attributes(codec))]
+pub fn codec_fuzz_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input: DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	if let Err(e) = utils::check_attributes(&input) {
+		return e.to_compile_error().into();
+	}
+
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(error) => return error.into_compile_error().into(),
+	};
+
+	let exhaustive_test = codec_fuzz::exhaustive_test(&input, &crate_path);
+	let fuzz_target = codec_fuzz::fuzz_target(&input, &crate_path);
+	let fuzz_target = proc_macro2::TokenStream::from(wrap_with_dummy_const(input, fuzz_target));
+
+	quote!(#fuzz_target #exhaustive_test).into()
+}
+
 /// Derive `parity_scale_codec::MaxEncodedLen` for struct and enum.
 ///
 /// # Top level attribute
@@ -421,7 +1054,45 @@ pub fn compact_as_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 /// # struct MyWrapper<T>(T);
 /// ```
 #[cfg(feature = "max-encoded-len")]
-#[proc_macro_derive(MaxEncodedLen, attributes(max_encoded_len_mod))]
+#[proc_macro_derive(MaxEncodedLen, attributes(codec, max_encoded_len_mod))]
 pub fn derive_max_encoded_len(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 	max_encoded_len::derive_max_encoded_len(input)
 }
+
+/// Assert that the maximum encoded length of a type is at most a bound, e.g. to lock in a storage
+/// size budget next to the type definition.
+///
+/// `assert_mel_bound!(Type, N)` checks when compiling that `Type` implements `MaxEncodedLen` and
+/// that `N` is a `usize` constant. The bound itself is checked by a generated test, which fails if
+/// `Type::max_encoded_len()` exceeds `N`.
+///
+/// # Limitations
+///
+/// `MaxEncodedLen::max_encoded_len` is not a `const fn`, so the bound can't be checked by a
+/// `const` assertion when compiling. Instead, the macro emits a `#[cfg(test)]` function into the
+/// module it is used in:
+/// * The bound is only checked by `cargo test` of the crate using the macro, not when it is built
+///   or used as a dependency.
+/// * The macro must be used where items are allowed and `#[test]` functions are collected, i.e. at
+///   module level and not in a function body.
+/// * The test is named after the type, e.g. `assert_mel_bound_account_data` for `AccountData`, so
+///   each type can only be asserted once per module, and asserting types whose names only differ in
+///   case or punctuation in the same module fails to compile.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec::{assert_mel_bound, Encode, MaxEncodedLen};
+/// #[derive(Encode, MaxEncodedLen)]
+/// struct AccountData {
+///     free: u128,
+///     nonce: u32,
+/// }
+///
+/// assert_mel_bound!(AccountData, 20);
+/// ```
+#[cfg(feature = "max-encoded-len")]
+#[proc_macro]
+pub fn assert_mel_bound(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	max_encoded_len::assert_mel_bound(input)
+}
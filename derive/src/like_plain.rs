@@ -0,0 +1,174 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of the `EncodeLike` implementations for `#[codec(like_plain(OtherType))]`.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+	parse_quote, spanned::Spanned, Data, DeriveInput, Error, Fields, Member, Path, PathArguments,
+};
+
+use crate::utils;
+
+/// `path` with turbofishes, so it can be used in patterns, e.g. `Plain::<T>` for `Plain<T>`.
+fn with_turbofish(path: &Path) -> Path {
+	let mut path = path.clone();
+	for segment in path.segments.iter_mut() {
+		if let PathArguments::AngleBracketed(ref mut args) = segment.arguments {
+			args.colon2_token = Some(Default::default());
+		}
+	}
+	path
+}
+
+/// The type listing the names of the encoded `fields` in order, see `EncodedFieldNames`.
+fn encoded_field_names(fields: &Fields, crate_path: &Path) -> TokenStream {
+	let names = fields
+		.iter()
+		.enumerate()
+		.filter(|(_, field)| !utils::should_skip(&field.attrs))
+		.map(|(i, field)| {
+			let name = field.ident.as_ref().map_or_else(
+				|| i.to_string(),
+				|ident| ident.to_string().trim_start_matches("r#").to_string(),
+			);
+			let chars = name.chars().map(Literal::character);
+			quote!((#( #crate_path::FieldNameChar<#chars>, )*))
+		});
+	quote!((#( #names, )*))
+}
+
+/// Implement `EncodedFieldNames` for a struct, so it can be the other type of a `like_plain`.
+///
+/// Must be called with the generics of the struct, without the bounds of the derived `Encode`
+/// implementation.
+pub fn quote_encoded_field_names(input: &DeriveInput, crate_path: &Path) -> TokenStream {
+	let Data::Struct(ref data) = input.data else {
+		return quote!();
+	};
+
+	let names = encoded_field_names(&data.fields, crate_path);
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	quote! {
+		#[automatically_derived]
+		impl #impl_generics #crate_path::EncodedFieldNames for #name #ty_generics #where_clause {
+			type Names = #names;
+		}
+	}
+}
+
+/// Generate the `EncodeLike` implementations between the type and the type of its
+/// `#[codec(like_plain(OtherType))]`, if it has one.
+///
+/// Must be called with the generics of the derived `Encode` implementation.
+pub fn quote(input: &DeriveInput, crate_path: &Path) -> TokenStream {
+	let Some(other) = utils::get_like_plain(&input.attrs) else {
+		return quote!();
+	};
+
+	if let Err(e) = utils::reject_append_with(&input.attrs, "`like_plain`") {
+		return e.to_compile_error();
+	}
+
+	let fields = match input.data {
+		Data::Struct(ref data) => &data.fields,
+		_ =>
+			return Error::new(
+				Span::call_site(),
+				"`#[codec(like_plain(OtherType))]` can only be used on structs",
+			)
+			.to_compile_error(),
+	};
+
+	// The pattern doesn't have `..`, so it only compiles if both types have the same fields.
+	let bindings = fields.iter().enumerate().map(|(i, field)| {
+		let member = field.ident.clone().map_or_else(|| Member::from(i), Member::Named);
+		let binding = format_ident!("__codec_field_{}_edqy", i);
+		(member, binding, field)
+	});
+	let pattern = bindings.clone().map(|(member, binding, _)| quote!(#member: #binding));
+	let checked = bindings
+		.filter(|(_, _, field)| !utils::should_skip(&field.attrs))
+		.map(|(_, binding, field)| {
+			let ty = &field.ty;
+			let encoded_ty = if utils::is_compact(field) {
+				quote!(<#ty as #crate_path::HasCompact>::Type)
+			} else if let Some(encoded_as) = utils::get_alternative_type(field) {
+				encoded_as
+			} else if utils::is_lock(field) {
+				quote!(<#ty as #crate_path::Lockable>::Inner)
+			} else {
+				quote!(#ty)
+			};
+			(binding, field, encoded_ty)
+		})
+		.collect::<Vec<_>>();
+	let checks = checked.iter().map(|(binding, field, encoded_ty)| {
+		quote_spanned! { field.ty.span() =>
+			__codec_encode_like_edqy::<_, #encoded_ty>(#binding);
+		}
+	});
+
+	// The encoded types of generic fields only implement `EncodeLike` for themselves if bounded.
+	let mut check_generics = input.generics.clone();
+	let where_clause = check_generics.make_where_clause();
+	for (_, _, encoded_ty) in &checked {
+		where_clause.predicates.push(parse_quote!(#encoded_ty: #crate_path::EncodeLike));
+	}
+	let (check_impl_generics, _, check_where_clause) = check_generics.split_for_impl();
+
+	let other_pattern = with_turbofish(&other);
+	let destructure = quote_spanned! { other.span() =>
+		let #other_pattern { #( #pattern, )* } = __codec_other_edqy;
+	};
+	// The pattern doesn't check the order of the fields, nor that the fields skipped here are
+	// skipped by the other type.
+	let names = encoded_field_names(fields, crate_path);
+	let same_names = quote_spanned! { other.span() =>
+		__codec_same_encoded_fields_edqy::<
+			<#other as #crate_path::EncodedFieldNames>::Names,
+			#names,
+		>();
+	};
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	quote! {
+		#[automatically_derived]
+		impl #impl_generics #crate_path::EncodeLike<#other> for #name #ty_generics #where_clause {}
+
+		#[automatically_derived]
+		impl #impl_generics #crate_path::EncodeLike<#name #ty_generics> for #other #where_clause {}
+
+		// Check that the other type encodes fields with the same names, in the same order, alike.
+		#[allow(dead_code, clippy::all)]
+		fn __codec_check_like_plain_edqy #check_impl_generics (__codec_other_edqy: &#other)
+			#check_where_clause
+		{
+			fn __codec_encode_like_edqy<
+				A: #crate_path::EncodeLike<B>,
+				B: #crate_path::Encode,
+			>(_: &A) {}
+			fn __codec_same_encoded_fields_edqy<A: #crate_path::SameEncodedFields<B>, B>() {}
+
+			#destructure
+			#( #checks )*
+			#same_names
+		}
+	}
+}
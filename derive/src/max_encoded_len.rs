@@ -34,6 +34,10 @@ pub fn derive_max_encoded_len(input: proc_macro::TokenStream) -> proc_macro::Tok
 		Err(error) => return error.into_compile_error().into(),
 	};
 
+	if let Err(e) = utils::reject_append_with(&input.attrs, "MaxEncodedLen") {
+		return e.to_compile_error().into();
+	}
+
 	let name = &input.ident;
 	if let Err(e) = trait_bounds::add(
 		&input.ident,
@@ -42,11 +46,21 @@ pub fn derive_max_encoded_len(input: proc_macro::TokenStream) -> proc_macro::Tok
 		custom_mel_trait_bound(&input.attrs),
 		parse_quote!(#crate_path::MaxEncodedLen),
 		None,
+		true,
+		Some(parse_quote!(#crate_path::MaxEncodedLenWithMaxLen)),
 		has_dumb_trait_bound(&input.attrs),
+		utils::has_recursive(&input.attrs),
 		&crate_path,
 	) {
 		return e.to_compile_error().into();
 	}
+	// The types of capped fields aren't bounded, but must still implement `Encode`, which is a
+	// supertrait of `MaxEncodedLen`.
+	if has_mel_cap(&input.data) {
+		let (_, ty_generics, _) = input.generics.split_for_impl();
+		let self_bound = parse_quote!(#name #ty_generics: #crate_path::Encode);
+		input.generics.make_where_clause().predicates.push(self_bound);
+	}
 	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
 	let data_expr = data_length_expr(&input.data, &crate_path);
@@ -63,6 +77,16 @@ pub fn derive_max_encoded_len(input: proc_macro::TokenStream) -> proc_macro::Tok
 	.into()
 }
 
+/// Whether a field has `#[codec(mel(cap = N))]`.
+fn has_mel_cap(data: &Data) -> bool {
+	let mut fields: Box<dyn Iterator<Item = &Field>> = match *data {
+		Data::Struct(ref data) => Box::new(data.fields.iter()),
+		Data::Enum(ref data) => Box::new(data.variants.iter().flat_map(|v| v.fields.iter())),
+		Data::Union(_) => Box::new(std::iter::empty()),
+	};
+	fields.any(|field| utils::get_mel_cap(field).is_some())
+}
+
 /// generate an expression to sum up the max encoded length from several fields
 fn fields_length_expr(fields: &Fields, crate_path: &syn::Path) -> proc_macro2::TokenStream {
 	let fields_iter: Box<dyn Iterator<Item = &Field>> = match fields {
@@ -84,12 +108,34 @@ fn fields_length_expr(fields: &Fields, crate_path: &syn::Path) -> proc_macro2::T
 	// caused the issue.
 	let expansion = fields_iter.map(|field| {
 		let ty = &field.ty;
-		if utils::is_compact(field) {
+		if let Some(utils::MelCap { cap, .. }) = utils::get_mel_cap(field) {
+			quote_spanned! {
+				ty.span() => .saturating_add(#cap)
+			}
+		} else if utils::is_compact(field) {
 			quote_spanned! {
 				ty.span() => .saturating_add(
 					<<#ty as #crate_path::HasCompact>::Type as #crate_path::MaxEncodedLen>::max_encoded_len()
 				)
 			}
+		} else if let Some(encoded_as) = utils::get_alternative_type(field) {
+			quote_spanned! {
+				ty.span() => .saturating_add(
+					<#encoded_as as #crate_path::MaxEncodedLen>::max_encoded_len()
+				)
+			}
+		} else if let Some(max_len) = utils::get_max_len(field) {
+			quote_spanned! {
+				ty.span() => .saturating_add(
+					<#ty as #crate_path::MaxEncodedLenWithMaxLen>::max_encoded_len_with_max_len(#max_len)
+				)
+			}
+		} else if utils::is_lock(field) {
+			quote_spanned! {
+				ty.span() => .saturating_add(
+					<<#ty as #crate_path::Lockable>::Inner as #crate_path::MaxEncodedLen>::max_encoded_len()
+				)
+			}
 		} else {
 			quote_spanned! {
 				ty.span() => .saturating_add(<#ty as #crate_path::MaxEncodedLen>::max_encoded_len())
@@ -136,8 +182,71 @@ fn data_length_expr(data: &Data, crate_path: &syn::Path) -> proc_macro2::TokenSt
 		Data::Union(ref data) => {
 			// https://github.com/paritytech/parity-scale-codec/
 			//   blob/f0341dabb01aa9ff0548558abb6dcc5c31c669a1/derive/src/encode.rs#L290-L293
-			syn::Error::new(data.union_token.span(), "Union types are not supported.")
-				.to_compile_error()
+			utils::union_error(data.union_token.span()).to_compile_error()
 		},
 	}
 }
+
+/// The input of `assert_mel_bound!`: a type and its maximum encoded length.
+struct MelBound {
+	ty: syn::Type,
+	_comma: Token![,],
+	bound: syn::Expr,
+	_trailing_comma: Option<Token![,]>,
+}
+
+impl syn::parse::Parse for MelBound {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		Ok(MelBound {
+			ty: input.parse()?,
+			_comma: input.parse()?,
+			bound: input.parse()?,
+			_trailing_comma: input.parse()?,
+		})
+	}
+}
+
+/// The name of the test generated for `ty`, e.g. `assert_mel_bound_bounded_vec_u8_32` for
+/// `BoundedVec<u8, 32>`.
+fn mel_bound_test_name(ty: &syn::Type) -> syn::Ident {
+	let mut name = String::from("assert_mel_bound_");
+	let mut previous = '_';
+	for c in quote!(#ty).to_string().chars() {
+		let c = if c.is_ascii_alphanumeric() { c } else { '_' };
+		if c.is_ascii_uppercase() && (previous.is_ascii_lowercase() || previous.is_ascii_digit()) {
+			name.push('_');
+		}
+		if c != '_' || !name.ends_with('_') {
+			name.push(c.to_ascii_lowercase());
+		}
+		previous = c;
+	}
+	syn::Ident::new(name.trim_end_matches('_'), proc_macro2::Span::call_site())
+}
+
+/// impl for `assert_mel_bound!`
+pub fn assert_mel_bound(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let MelBound { ty, bound, .. } = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+	let crate_path = match codec_crate_path(&[]) {
+		Ok(crate_path) => crate_path,
+		Err(e) => return e.into_compile_error().into(),
+	};
+	let test_name = mel_bound_test_name(&ty);
+	let type_name = quote!(#ty).to_string();
+
+	quote! {
+		// Checked when compiling: the type implements `MaxEncodedLen` and the bound is a `usize`.
+		const _: fn() -> usize = <#ty as #crate_path::MaxEncodedLen>::max_encoded_len;
+		const _: usize = #bound;
+
+		#[cfg(test)]
+		#[test]
+		fn #test_name() {
+			#crate_path::assert_max_encoded_len_bound::<#ty>(#type_name, #bound);
+		}
+	}
+	.into()
+}
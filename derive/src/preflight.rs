@@ -0,0 +1,105 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of `Preflight`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, Fields};
+
+use crate::utils;
+
+/// Walk the encoded `fields`.
+fn walk_fields(fields: &Fields, crate_path: &syn::Path) -> TokenStream {
+	let walks = fields.iter().filter(|field| !utils::should_skip(&field.attrs)).map(|field| {
+		let ty = &field.ty;
+		let encoded_ty = if utils::is_compact(field) {
+			quote!(<#ty as #crate_path::HasCompact>::Type)
+		} else if let Some(encoded_as) = utils::get_alternative_type(field) {
+			encoded_as
+		} else if utils::is_lock(field) {
+			quote!(<#ty as #crate_path::Lockable>::Inner)
+		} else {
+			quote!(#ty)
+		};
+
+		quote_spanned! { ty.span() =>
+			<#encoded_ty as #crate_path::Preflight>::preflight(
+				__codec_input_edqy,
+				__codec_estimate_edqy,
+			)?;
+		}
+	});
+
+	quote! {
+		#( #walks )*
+		::core::result::Result::Ok(())
+	}
+}
+
+/// Generate the body of `Preflight::preflight`.
+pub fn quote(
+	data: &Data,
+	type_name: &syn::Ident,
+	crate_path: &syn::Path,
+) -> syn::Result<TokenStream> {
+	match data {
+		Data::Struct(data) => Ok(walk_fields(&data.fields, crate_path)),
+		Data::Enum(data) => {
+			let variants = utils::try_get_variants(data)?;
+			let arm = |variant: &syn::Variant, index: TokenStream, walk: TokenStream| {
+				quote_spanned! { variant.span() =>
+					#[allow(clippy::unnecessary_cast)]
+					__codec_x_edqy if __codec_x_edqy == #index as ::core::primitive::u8 => {
+						#walk
+					},
+				}
+			};
+			let arms = variants.iter().enumerate().map(|(i, variant)| {
+				arm(
+					variant,
+					utils::variant_index(variant, i),
+					walk_fields(&variant.fields, crate_path),
+				)
+			});
+			let skipped = utils::skipped_variant_decodes(data).map(|(variant, index, decode)| {
+				let walk = match decode {
+					utils::SkippedVariantDecode::As(target) => {
+						let target = variants
+							.iter()
+							.find(|variant| variant.ident == target)
+							.expect("Checked by `check_attributes`");
+						walk_fields(&target.fields, crate_path)
+					},
+					utils::SkippedVariantDecode::Error(msg) =>
+						quote!(::core::result::Result::Err(#msg.into())),
+				};
+				arm(variant, index, walk)
+			});
+
+			let read_byte_err_msg =
+				format!("Could not decode `{type_name}`, failed to read variant byte");
+			let invalid_variant_err_msg =
+				format!("Could not decode `{type_name}`, variant doesn't exist");
+			Ok(quote! {
+				match __codec_input_edqy.read_byte().map_err(|e| e.chain(#read_byte_err_msg))? {
+					#( #arms )*
+					#( #skipped )*
+					_ => ::core::result::Result::Err(#invalid_variant_err_msg.into()),
+				}
+			})
+		},
+		Data::Union(data) => Err(utils::union_error(data.union_token.span())),
+	}
+}
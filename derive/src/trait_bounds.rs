@@ -106,6 +106,17 @@ fn find_type_paths_not_start_or_contain_ident(ty: &Type, ident: &Ident) -> Vec<T
 
 #[allow(clippy::too_many_arguments)]
 /// Add required trait bounds to all generic types.
+///
+/// With `bound_alternative_types`, the types of `#[codec(encoded_as)]` and
+/// `#[codec(decoded_via)]` fields which use a generic type must implement `codec_bound` as well.
+/// This isn't required for `Encode`, which encodes them through `EncodeAsRef::RefType`.
+///
+/// With `codec_max_len_bound`, the types of `#[codec(max_len = N)]` fields must implement it
+/// instead of `codec_bound`, e.g. for `MaxEncodedLen`, which isn't implemented by collections.
+/// The types of `#[codec(mel(cap = N))]` fields don't need to implement `codec_bound` either.
+///
+/// With `recursive`, the fields only add bounds to the type parameters they use, see
+/// [`get_types_to_add_trait_bound`].
 pub fn add<N>(
 	input_ident: &Ident,
 	generics: &mut Generics,
@@ -113,7 +124,10 @@ pub fn add<N>(
 	custom_trait_bound: Option<CustomTraitBound<N>>,
 	codec_bound: syn::Path,
 	codec_skip_bound: Option<syn::Path>,
+	bound_alternative_types: bool,
+	codec_max_len_bound: Option<syn::Path>,
 	dumb_trait_bounds: bool,
+	recursive: bool,
 	crate_path: &syn::Path,
 ) -> Result<()> {
 	let skip_type_params = match custom_trait_bound {
@@ -135,8 +149,14 @@ pub fn add<N>(
 		return Ok(());
 	}
 
-	let codec_types =
-		get_types_to_add_trait_bound(input_ident, data, &ty_params, dumb_trait_bounds)?;
+	let codec_types = get_types_to_add_trait_bound(
+		input_ident,
+		data,
+		&ty_params,
+		codec_max_len_bound.is_some(),
+		dumb_trait_bounds,
+		recursive,
+	)?;
 
 	let compact_types = collect_types(data, utils::is_compact)?
 		.into_iter()
@@ -144,8 +164,36 @@ pub fn add<N>(
 		.filter(|ty| type_contain_idents(ty, &ty_params))
 		.collect::<Vec<_>>();
 
+	let alternative_types = if bound_alternative_types && !dumb_trait_bounds {
+		collect_alternative_types(data)?
+			.into_iter()
+			// Only add a bound if the type uses a generic
+			.filter(|ty| type_contain_idents(ty, &ty_params))
+			.collect::<Vec<_>>()
+	} else {
+		Vec::new()
+	};
+
+	let max_len_types = if codec_max_len_bound.is_some() && !dumb_trait_bounds {
+		collect_types(data, |f| utils::get_max_len(f).is_some())?
+			.into_iter()
+			// Only add a bound if the type uses a generic
+			.filter(|ty| type_contain_idents(ty, &ty_params))
+			.collect::<Vec<_>>()
+	} else {
+		Vec::new()
+	};
+
+	let lock_types = collect_types(data, utils::is_lock)?
+		.into_iter()
+		// Only add a bound if the type uses a generic
+		.filter(|ty| type_contain_idents(ty, &ty_params))
+		.collect::<Vec<_>>();
+
 	let skip_types = if codec_skip_bound.is_some() {
-		let needs_default_bound = |f: &syn::Field| utils::should_skip(&f.attrs);
+		let needs_default_bound = |f: &syn::Field| {
+			utils::should_skip(&f.attrs) && utils::get_skip_default(&f.attrs).is_none()
+		};
 		collect_types(data, needs_default_bound)?
 			.into_iter()
 			// Only add a bound if the type uses a generic
@@ -155,7 +203,13 @@ pub fn add<N>(
 		Vec::new()
 	};
 
-	if !codec_types.is_empty() || !compact_types.is_empty() || !skip_types.is_empty() {
+	if !codec_types.is_empty() ||
+		!compact_types.is_empty() ||
+		!alternative_types.is_empty() ||
+		!max_len_types.is_empty() ||
+		!lock_types.is_empty() ||
+		!skip_types.is_empty()
+	{
 		let where_clause = generics.make_where_clause();
 
 		codec_types
@@ -169,6 +223,22 @@ pub fn add<N>(
 				.push(parse_quote!(<#ty as #crate_path::HasCompact>::Type : #codec_bound));
 		});
 
+		alternative_types
+			.into_iter()
+			.for_each(|ty| where_clause.predicates.push(parse_quote!(#ty : #codec_bound)));
+
+		max_len_types.into_iter().for_each(|ty| {
+			let codec_max_len_bound = codec_max_len_bound.as_ref();
+			where_clause.predicates.push(parse_quote!(#ty : #codec_max_len_bound))
+		});
+
+		lock_types.into_iter().for_each(|ty| {
+			where_clause.predicates.push(parse_quote!(#ty : #crate_path::Lockable));
+			where_clause
+				.predicates
+				.push(parse_quote!(<#ty as #crate_path::Lockable>::Inner : #codec_bound));
+		});
+
 		skip_types.into_iter().for_each(|ty| {
 			let codec_skip_bound = codec_skip_bound.as_ref();
 			where_clause.predicates.push(parse_quote!(#ty : #codec_skip_bound))
@@ -179,52 +249,90 @@ pub fn add<N>(
 }
 
 /// Returns all types that must be added to the where clause with the respective trait bound.
+///
+/// With `recursive`, these are the type parameters used by the fields, instead of the types of the
+/// fields. This avoids unsatisfiable bounds for types which are recursive through other types, e.g.
+/// `A<T>` having a field of type `B<T>` having a field of type `Box<A<T>>`, where the bound
+/// `B<T>: Decode` of `A<T>: Decode` would require `A<T>: Decode` again.
 fn get_types_to_add_trait_bound(
 	input_ident: &Ident,
 	data: &syn::Data,
 	ty_params: &[Ident],
+	skip_max_len_types: bool,
 	dumb_trait_bound: bool,
+	recursive: bool,
 ) -> Result<Vec<Type>> {
 	if dumb_trait_bound {
-		Ok(ty_params.iter().map(|t| parse_quote!( #t )).collect())
-	} else {
-		let needs_codec_bound = |f: &syn::Field| {
-			!utils::is_compact(f) &&
-				utils::get_encoded_as_type(f).is_none() &&
-				!utils::should_skip(&f.attrs)
-		};
-		let res = collect_types(data, needs_codec_bound)?
-			.into_iter()
-			// Only add a bound if the type uses a generic
-			.filter(|ty| type_contain_idents(ty, ty_params))
-			// If a struct contains itself as field type, we can not add this type into the where
-			// clause. This is required to work a round the following compiler bug: https://github.com/rust-lang/rust/issues/47032
-			.flat_map(|ty| {
-				find_type_paths_not_start_or_contain_ident(&ty, input_ident)
-					.into_iter()
-					.map(Type::Path)
-					// Remove again types that do not contain any of our generic parameters
-					.filter(|ty| type_contain_idents(ty, ty_params))
-					// Add back the original type, as we don't want to loose it.
-					.chain(iter::once(ty))
-			})
-			// Remove all remaining types that start/contain the input ident to not have them in the
-			// where clause.
-			.filter(|ty| !type_or_sub_type_path_starts_with_ident(ty, input_ident))
-			.collect();
+		return Ok(ty_params.iter().map(|t| parse_quote!( #t )).collect());
+	}
 
-		Ok(res)
+	let needs_codec_bound = |f: &syn::Field| {
+		!utils::is_compact(f) &&
+			!utils::is_lock(f) &&
+			utils::get_alternative_type(f).is_none() &&
+			(!skip_max_len_types ||
+				utils::get_max_len(f).is_none() && utils::get_mel_cap(f).is_none()) &&
+			!utils::should_skip(&f.attrs)
+	};
+	let field_types = collect_field_types(data, |f| needs_codec_bound(f).then(|| f.ty.clone()))?
+		.into_iter()
+		// Only add a bound if the type uses a generic
+		.filter(|ty| type_contain_idents(ty, ty_params));
+
+	if recursive {
+		let field_types = field_types.collect::<Vec<_>>();
+		return Ok(ty_params
+			.iter()
+			.filter(|t| {
+				field_types.iter().any(|ty| type_contain_idents(ty, core::slice::from_ref(t)))
+			})
+			.map(|t| parse_quote!( #t ))
+			.collect());
 	}
+
+	let res = field_types
+		// If a struct contains itself as field type, we can not add this type into the where
+		// clause. This is required to work a round the following compiler bug: https://github.com/rust-lang/rust/issues/47032
+		.flat_map(|ty| {
+			find_type_paths_not_start_or_contain_ident(&ty, input_ident)
+				.into_iter()
+				.map(Type::Path)
+				// Remove again types that do not contain any of our generic parameters
+				.filter(|ty| type_contain_idents(ty, ty_params))
+				// Add back the original type, as we don't want to loose it.
+				.chain(iter::once(ty))
+		})
+		// Remove all remaining types that start/contain the input ident to not have them in the
+		// where clause.
+		.filter(|ty| !type_or_sub_type_path_starts_with_ident(ty, input_ident))
+		.collect();
+
+	Ok(res)
 }
 
 fn collect_types(data: &syn::Data, type_filter: fn(&syn::Field) -> bool) -> Result<Vec<syn::Type>> {
+	collect_field_types(data, |f| type_filter(f).then(|| f.ty.clone()))
+}
+
+/// Collects the types given by `#[codec(encoded_as = "..")]` and `#[codec(decoded_via = "..")]`.
+fn collect_alternative_types(data: &syn::Data) -> Result<Vec<syn::Type>> {
+	collect_field_types(data, |f| {
+		utils::get_alternative_type(f).and_then(|ty| syn::parse2::<syn::Type>(ty).ok())
+	})
+}
+
+/// Collects the type returned by `field_type` for each field of the non-skipped variants.
+fn collect_field_types(
+	data: &syn::Data,
+	field_type: impl Fn(&syn::Field) -> Option<syn::Type>,
+) -> Result<Vec<syn::Type>> {
 	use syn::*;
 
 	let types = match *data {
 		Data::Struct(ref data) => match &data.fields {
 			| Fields::Named(FieldsNamed { named: fields, .. }) |
 			Fields::Unnamed(FieldsUnnamed { unnamed: fields, .. }) =>
-				fields.iter().filter(|f| type_filter(f)).map(|f| f.ty.clone()).collect(),
+				fields.iter().filter_map(&field_type).collect(),
 
 			Fields::Unit => Vec::new(),
 		},
@@ -236,14 +344,13 @@ fn collect_types(data: &syn::Data, type_filter: fn(&syn::Field) -> bool) -> Resu
 			.flat_map(|variant| match &variant.fields {
 				| Fields::Named(FieldsNamed { named: fields, .. }) |
 				Fields::Unnamed(FieldsUnnamed { unnamed: fields, .. }) =>
-					fields.iter().filter(|f| type_filter(f)).map(|f| f.ty.clone()).collect(),
+					fields.iter().filter_map(&field_type).collect(),
 
 				Fields::Unit => Vec::new(),
 			})
 			.collect(),
 
-		Data::Union(ref data) =>
-			return Err(Error::new(data.union_token.span(), "Union types are not supported.")),
+		Data::Union(ref data) => return Err(utils::union_error(data.union_token.span())),
 	};
 
 	Ok(types)
@@ -19,7 +19,7 @@
 
 use std::str::FromStr;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
 	parse::Parse, punctuated::Punctuated, spanned::Spanned, token, Attribute, Data, DataEnum,
@@ -38,27 +38,34 @@ where
 	})
 }
 
-/// Look for a `#[scale(index = $int)]` attribute on a variant. If no attribute
+/// Look for a `#[codec(index = $expr)]` attribute on a variant, and return its expression.
+pub fn index_attribute(v: &Variant) -> Option<Expr> {
+	find_meta_item(v.attrs.iter(), |meta| match meta {
+		Meta::NameValue(nv) if nv.path.is_ident("index") => Some(nv.value),
+		_ => None,
+	})
+}
+
+/// Look for a `#[codec(index = $expr)]` attribute on a variant. If no attribute
 /// is found, fall back to the discriminant or just the variant index.
+///
+/// Expressions which are not literals or paths are parenthesized, so the index can be cast with
+/// `#index as u8`.
 pub fn variant_index(v: &Variant, i: usize) -> TokenStream {
 	// first look for an attribute
-	let index = find_meta_item(v.attrs.iter(), |meta| {
-		if let Meta::NameValue(ref nv) = meta {
-			if nv.path.is_ident("index") {
-				if let Expr::Lit(ExprLit { lit: Lit::Int(ref v), .. }) = nv.value {
-					let byte = v
-						.base10_parse::<u8>()
-						.expect("Internal error, index attribute must have been checked");
-					return Some(byte);
-				}
-			}
-		}
-
-		None
+	let index = index_attribute(v).map(|expr| match expr {
+		Expr::Lit(ExprLit { lit: Lit::Int(ref v), .. }) => {
+			let byte = v
+				.base10_parse::<u8>()
+				.expect("Internal error, index attribute must have been checked");
+			quote! { #byte }
+		},
+		Expr::Path(_) => quote! { #expr },
+		expr => quote! { (#expr) },
 	});
 
 	// then fallback to discriminant or just index
-	index.map(|i| quote! { #i }).unwrap_or_else(|| {
+	index.unwrap_or_else(|| {
 		v.discriminant
 			.as_ref()
 			.map(|(_, expr)| quote! { #expr })
@@ -66,16 +73,15 @@ pub fn variant_index(v: &Variant, i: usize) -> TokenStream {
 	})
 }
 
-/// Look for a `#[codec(encoded_as = "SomeType")]` outer attribute on the given
-/// `Field`.
-pub fn get_encoded_as_type(field: &Field) -> Option<TokenStream> {
+/// Look for a `#[codec($name = "SomeType")]` outer attribute on the given `Field`.
+fn get_type_attribute(field: &Field, name: &str) -> Option<TokenStream> {
 	find_meta_item(field.attrs.iter(), |meta| {
 		if let Meta::NameValue(ref nv) = meta {
-			if nv.path.is_ident("encoded_as") {
+			if nv.path.is_ident(name) {
 				if let Expr::Lit(ExprLit { lit: Lit::Str(ref s), .. }) = nv.value {
 					return Some(
 						TokenStream::from_str(&s.value())
-							.expect("Internal error, encoded_as attribute must have been checked"),
+							.expect("Internal error, type attribute must have been checked"),
 					);
 				}
 			}
@@ -85,6 +91,90 @@ pub fn get_encoded_as_type(field: &Field) -> Option<TokenStream> {
 	})
 }
 
+/// Look for a `#[codec(encoded_as = "SomeType")]` outer attribute on the given
+/// `Field`.
+pub fn get_encoded_as_type(field: &Field) -> Option<TokenStream> {
+	get_type_attribute(field, "encoded_as")
+}
+
+/// Look for a `#[codec(decoded_via = "SomeType")]` outer attribute on the given
+/// `Field`.
+pub fn get_decoded_via_type(field: &Field) -> Option<TokenStream> {
+	get_type_attribute(field, "decoded_via")
+}
+
+/// The alternative type the given `Field` is encoded as, set by either `#[codec(encoded_as)]` or
+/// `#[codec(decoded_via)]`.
+pub fn get_alternative_type(field: &Field) -> Option<TokenStream> {
+	get_encoded_as_type(field).or_else(|| get_decoded_via_type(field))
+}
+
+/// Look for a `#[codec(max_len = N)]` outer attribute on the given `Field` and return `N`.
+pub fn get_max_len(field: &Field) -> Option<Expr> {
+	find_meta_item(field.attrs.iter(), |meta| {
+		if let Meta::NameValue(ref nv) = meta {
+			if nv.path.is_ident("max_len") {
+				return Some(nv.value.clone());
+			}
+		}
+
+		None
+	})
+}
+
+/// The arguments of a `#[codec(mel(cap = N))]` or `#[codec(mel(cap = N, check))]` attribute.
+pub struct MelCap {
+	/// The maximum encoded length of the field.
+	pub cap: Expr,
+	/// Whether decoding checks that the field is encoded in at most `cap` bytes.
+	pub check: bool,
+}
+
+/// Parse the arguments of a `mel(..)` attribute.
+fn parse_mel_cap(list: &syn::MetaList) -> syn::Result<MelCap> {
+	let mel_error = "Invalid `mel` attribute, only `#[codec(mel(cap = $N))]` and \
+		`#[codec(mel(cap = $N, check))]` are accepted, with $N a `usize` literal or a path to a \
+		`usize` constant.";
+
+	let mut cap = None;
+	let mut check = false;
+	for meta in list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+		match meta {
+			Meta::NameValue(MetaNameValue {
+				ref path,
+				value: ref value @ Expr::Lit(ExprLit { lit: Lit::Int(ref lit_int), .. }),
+				..
+			}) if path.is_ident("cap") && cap.is_none() => {
+				lit_int.base10_parse::<usize>().map_err(|_| {
+					syn::Error::new(lit_int.span(), "Maximum encoded length must fit in a usize")
+				})?;
+				cap = Some(value.clone());
+			},
+			Meta::NameValue(MetaNameValue {
+				ref path, value: ref value @ Expr::Path(_), ..
+			}) if path.is_ident("cap") && cap.is_none() => cap = Some(value.clone()),
+			Meta::Path(ref path) if path.is_ident("check") && !check => check = true,
+			meta => return Err(syn::Error::new(meta.span(), mel_error)),
+		}
+	}
+
+	let cap = cap.ok_or_else(|| syn::Error::new(list.span(), mel_error))?;
+	Ok(MelCap { cap, check })
+}
+
+/// Look for a `#[codec(mel(cap = N))]` outer attribute on the given `Field`.
+pub fn get_mel_cap(field: &Field) -> Option<MelCap> {
+	find_meta_item(field.attrs.iter(), |meta| {
+		if let Meta::List(ref list) = meta {
+			if list.path.is_ident("mel") {
+				return parse_mel_cap(list).ok();
+			}
+		}
+
+		None
+	})
+}
+
 /// Look for a `#[codec(compact)]` outer attribute on the given `Field`. If the attribute is found,
 /// return the compact type associated with the field type.
 pub fn get_compact_type(field: &Field, crate_path: &syn::Path) -> Option<TokenStream> {
@@ -105,7 +195,23 @@ pub fn is_compact(field: &Field) -> bool {
 	get_compact_type(field, &parse_quote!(::crate)).is_some()
 }
 
-/// Look for a `#[codec(skip)]` in the given attributes.
+/// Look for a `#[codec(lock)]` outer attribute on the given `Field`.
+pub fn is_lock(field: &Field) -> bool {
+	find_meta_item(field.attrs.iter(), |meta| {
+		if let Meta::Path(ref path) = meta {
+			if path.is_ident("lock") {
+				return Some(());
+			}
+		}
+
+		None
+	})
+	.is_some()
+}
+
+/// Look for a `#[codec(skip)]`, `#[codec(skip, default = "$path")]`,
+/// `#[codec(skip, decode_as = "$Variant")]` or `#[codec(skip, decode_error = "$msg")]` in the
+/// given attributes.
 pub fn should_skip(attrs: &[Attribute]) -> bool {
 	find_meta_item(attrs.iter(), |meta| {
 		if let Meta::Path(ref path) = meta {
@@ -116,7 +222,200 @@ pub fn should_skip(attrs: &[Attribute]) -> bool {
 
 		None
 	})
-	.is_some()
+	.is_some() ||
+		get_skip_default(attrs).is_some() ||
+		get_skipped_variant_decode(attrs).is_some()
+}
+
+/// How a skipped variant is decoded, given by `#[codec(skip, decode_as = "$Variant")]` or
+/// `#[codec(skip, decode_error = "$msg")]`.
+pub enum SkippedVariantDecode {
+	/// The fields of the variant `$Variant` are decoded.
+	As(syn::Ident),
+	/// Decoding fails with the error `$msg`.
+	Error(syn::LitStr),
+}
+
+impl Parse for SkippedVariantDecode {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let error = "Expected `#[codec(skip, decode_as = \"$Variant\")]` or \
+			`#[codec(skip, decode_error = \"$msg\")]`";
+		let span = input.span();
+		let nested = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+
+		let mut skip = false;
+		let mut decode = None;
+		for meta in &nested {
+			match meta {
+				Meta::Path(path) if path.is_ident("skip") && !skip => skip = true,
+				Meta::NameValue(MetaNameValue {
+					path,
+					value: Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }),
+					..
+				}) if decode.is_none() =>
+					if path.is_ident("decode_as") {
+						decode = Some(SkippedVariantDecode::As(lit_str.parse()?));
+					} else if path.is_ident("decode_error") {
+						decode = Some(SkippedVariantDecode::Error(lit_str.clone()));
+					} else {
+						return Err(syn::Error::new(meta.span(), error));
+					},
+				elt => return Err(syn::Error::new(elt.span(), error)),
+			}
+		}
+
+		match decode {
+			Some(decode) if skip => Ok(decode),
+			_ => Err(syn::Error::new(span, error)),
+		}
+	}
+}
+
+/// Look for a `#[codec(skip, decode_as = "$Variant")]` or `#[codec(skip, decode_error = "$msg")]`
+/// in the given attributes of a variant.
+pub fn get_skipped_variant_decode(attrs: &[Attribute]) -> Option<SkippedVariantDecode> {
+	find_meta_item(attrs.iter(), Some)
+}
+
+/// The skipped variants of `data` which are still decoded, with their index and how they are
+/// decoded, see [`SkippedVariantDecode`].
+///
+/// Their attributes must have been checked with `check_attributes`.
+pub fn skipped_variant_decodes(
+	data: &DataEnum,
+) -> impl Iterator<Item = (&Variant, TokenStream, SkippedVariantDecode)> {
+	data.variants.iter().filter_map(|variant| {
+		let decode = get_skipped_variant_decode(&variant.attrs)?;
+		// The index is explicit, so the position is not used.
+		Some((variant, variant_index(variant, 0), decode))
+	})
+}
+
+/// This struct matches `skip, default = "..."` where the ellipsis is a `Path`.
+struct SkipWithDefault {
+	default: Path,
+}
+
+impl Parse for SkipWithDefault {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let error = "Expected `#[codec(skip, default = \"$path\")]`";
+		let span = input.span();
+		let nested = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+
+		let mut skip = false;
+		let mut default = None;
+		for meta in &nested {
+			match meta {
+				Meta::Path(path) if path.is_ident("skip") && !skip => skip = true,
+				Meta::NameValue(MetaNameValue {
+					path,
+					value: Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }),
+					..
+				}) if path.is_ident("default") && default.is_none() => default = Some(lit_str.parse::<Path>()?),
+				elt => return Err(syn::Error::new(elt.span(), error)),
+			}
+		}
+
+		match default {
+			Some(default) if skip => Ok(SkipWithDefault { default }),
+			_ => Err(syn::Error::new(span, error)),
+		}
+	}
+}
+
+/// Look for a `#[codec(skip, default = "$path")]` in the given attributes and return the path of
+/// the function that reconstructs the skipped field on decode.
+pub fn get_skip_default(attrs: &[Attribute]) -> Option<Path> {
+	find_meta_item(attrs.iter(), |meta: SkipWithDefault| Some(meta.default))
+}
+
+/// Look for a `#[codec(append_with = "$path")]` in the given attributes and return the path of the
+/// function computing the value appended to the encoding.
+pub fn get_append_with(attrs: &[Attribute]) -> Option<Path> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::NameValue(ref nv) = meta {
+			if nv.path.is_ident("append_with") {
+				if let Expr::Lit(ExprLit { lit: Lit::Str(ref s), .. }) = nv.value {
+					return Some(
+						s.parse::<Path>()
+							.expect("Internal error, append_with attribute must have been checked"),
+					);
+				}
+			}
+		}
+
+		None
+	})
+}
+
+/// Reject `#[codec(append_with = "$path")]` when deriving `derive_name`, which can't know the type
+/// of the appended value.
+pub fn reject_append_with(attrs: &[Attribute], derive_name: &str) -> syn::Result<()> {
+	match get_append_with(attrs) {
+		Some(path) => Err(syn::Error::new(
+			path.span(),
+			format!(
+				"`#[codec(append_with = \"$path\")]` is not supported when deriving {derive_name}"
+			),
+		)),
+		None => Ok(()),
+	}
+}
+
+/// Look for a `#[codec(normalize = "$path")]` in the given attributes and return the path of the
+/// function that is called with the decoded value.
+pub fn get_normalize(attrs: &[Attribute]) -> Option<Path> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::NameValue(ref nv) = meta {
+			if nv.path.is_ident("normalize") {
+				if let Expr::Lit(ExprLit { lit: Lit::Str(ref s), .. }) = nv.value {
+					return Some(
+						s.parse::<Path>()
+							.expect("Internal error, normalize attribute must have been checked"),
+					);
+				}
+			}
+		}
+
+		None
+	})
+}
+
+/// The old type and the conversion function of a `#[codec(migrate_from(OldType, "$path"))]`.
+pub struct MigrateFrom {
+	pub old: syn::Type,
+	pub convert: Path,
+}
+
+impl Parse for MigrateFrom {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let old = input.parse()?;
+		input.parse::<Token![,]>().map_err(|e| {
+			error_with_help(
+				e.span(),
+				"`migrate_from` takes the old type and the path of the function converting it.",
+				"Add the path as a string, e.g. `#[codec(migrate_from(OldType, \"Self::from_old\"))]`.",
+			)
+		})?;
+		let convert = input.parse::<syn::LitStr>()?.parse()?;
+		Ok(MigrateFrom { old, convert })
+	}
+}
+
+/// Look for a `#[codec(migrate_from(OldType, "$path"))]` in the given attributes.
+pub fn get_migrate_from(attrs: &[Attribute]) -> Option<MigrateFrom> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::List(ref list) = meta {
+			if list.path.is_ident("migrate_from") {
+				return Some(
+					list.parse_args::<MigrateFrom>()
+						.expect("Internal error, migrate_from attribute must have been checked"),
+				);
+			}
+		}
+
+		None
+	})
 }
 
 /// Look for a `#[codec(dumb_trait_bound)]`in the given attributes.
@@ -133,18 +432,147 @@ pub fn has_dumb_trait_bound(attrs: &[Attribute]) -> bool {
 	.is_some()
 }
 
-/// Generate the crate access for the crate using 2018 syntax.
+/// Look for a `#[codec(recursive)]` in the given attributes.
+pub fn has_recursive(attrs: &[Attribute]) -> bool {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::Path(ref path) = meta {
+			if path.is_ident("recursive") {
+				return Some(());
+			}
+		}
+
+		None
+	})
+	.is_some()
+}
+
+/// Look for a `#[codec(dyn_input)]` in the given attributes.
+pub fn has_dyn_input(attrs: &[Attribute]) -> bool {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::Path(ref path) = meta {
+			if path.is_ident("dyn_input") {
+				return Some(());
+			}
+		}
+
+		None
+	})
+	.is_some()
+}
+
+/// The inline hint for the methods of the derived `Encode` and `Decode` implementations.
+///
+/// This is `#[inline]` with `#[codec(inline)]` and `#[inline(never)]` with
+/// `#[codec(never_inline)]`. Without these attributes, structs with a single encoded field and
+/// `#[repr(transparent)]` types get `#[inline]`, as their methods only forward to the field.
+pub fn inline_hint(input: &DeriveInput) -> TokenStream {
+	let hint = find_meta_item(input.attrs.iter(), |meta| {
+		if let Meta::Path(ref path) = meta {
+			if path.is_ident("inline") {
+				return Some(quote!(#[inline]));
+			} else if path.is_ident("never_inline") {
+				return Some(quote!(#[inline(never)]));
+			}
+		}
+
+		None
+	});
+
+	hint.unwrap_or_else(|| {
+		let single_field = match input.data {
+			Data::Struct(ref data) => match data.fields {
+				Fields::Named(ref fields) => filter_skip_named(fields).count() == 1,
+				Fields::Unnamed(ref fields) => filter_skip_unnamed(fields).count() == 1,
+				Fields::Unit => false,
+			},
+			_ => false,
+		};
+
+		if single_field || is_transparent(&input.attrs) {
+			quote!(#[inline])
+		} else {
+			quote!()
+		}
+	})
+}
+
+/// Look for a `#[codec(variant_index)]` in the given attributes.
+pub fn has_variant_index(attrs: &[Attribute]) -> bool {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::Path(ref path) = meta {
+			if path.is_ident("variant_index") {
+				return Some(());
+			}
+		}
+
+		None
+	})
+	.is_some()
+}
+
+/// Look for a `#[codec(document_layout)]` in the given attributes, and return its path.
+pub fn get_document_layout(attrs: &[Attribute]) -> Option<Path> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::Path(path) = meta {
+			if path.is_ident("document_layout") {
+				return Some(path);
+			}
+		}
+
+		None
+	})
+}
+
+/// Look for a `#[codec(like_plain(OtherType))]` in the given attributes and return `OtherType`.
+pub fn get_like_plain(attrs: &[Attribute]) -> Option<Path> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let Meta::List(ref list) = meta {
+			if list.path.is_ident("like_plain") {
+				return Some(
+					list.parse_args::<Path>()
+						.expect("Internal error, like_plain attribute must have been checked"),
+				);
+			}
+		}
+
+		None
+	})
+}
+
+/// The name `parity-scale-codec` is imported as by the crate using the derive.
+///
+/// The dependencies in the `Cargo.toml` of the crate are searched, so a renamed dependency is
+/// found. Without a `Cargo.toml`, e.g. when building with `rustc` directly, the crate is expected
+/// as `::parity_scale_codec`. If the `Cargo.toml` doesn't list the crate, the error names what was
+/// searched and suggests `#[codec(crate = ...)]`.
 fn crate_access() -> syn::Result<proc_macro2::Ident> {
 	use proc_macro2::{Ident, Span};
-	use proc_macro_crate::{crate_name, FoundCrate};
+	use proc_macro_crate::{crate_name, Error, FoundCrate};
 	const DEF_CRATE: &str = "parity-scale-codec";
+	let default_name = || Ident::new(&DEF_CRATE.replace('-', "_"), Span::call_site());
+	if std::env::var_os("CARGO_MANIFEST_DIR").is_none() {
+		return Ok(default_name());
+	}
 	match crate_name(DEF_CRATE) {
-		Ok(FoundCrate::Itself) => {
-			let name = DEF_CRATE.to_string().replace('-', "_");
-			Ok(syn::Ident::new(&name, Span::call_site()))
-		},
+		Ok(FoundCrate::Itself) => Ok(default_name()),
 		Ok(FoundCrate::Name(name)) => Ok(Ident::new(&name, Span::call_site())),
-		Err(e) => Err(syn::Error::new(Span::call_site(), e)),
+		Err(Error::CrateNotFound { path, .. }) => Err(error_with_help(
+			Span::call_site(),
+			&format!(
+				"Could not find `{DEF_CRATE}`: the type has no `#[codec(crate = ...)]` attribute \
+				and `{DEF_CRATE}` is not a dependency in `{}`, also not renamed.",
+				path.display(),
+			),
+			&format!(
+				"Add `{DEF_CRATE}` to the dependencies, or give the path of the crate or of a crate \
+				re-exporting it with `#[codec(crate = path::to::parity_scale_codec)]`."
+			),
+		)),
+		Err(e) => Err(error_with_help(
+			Span::call_site(),
+			&format!("Could not find `{DEF_CRATE}` in the dependencies: {e}."),
+			"Give the path of the crate with `#[codec(crate = path::to::parity_scale_codec)]`.",
+		)),
 	}
 }
 
@@ -185,13 +613,25 @@ fn codec_crate_path_inner(attr: &Attribute) -> Option<Path> {
 
 /// Match `#[codec(crate = ...)]` and return the ellipsis as a `Path`.
 ///
-/// If not found, returns the default crate access pattern.
+/// The path can have multiple segments and start with `$crate`, e.g. when the derive is used
+/// by a macro of a crate re-exporting `parity_scale_codec`. The legacy
+/// `#[max_encoded_len_mod(...)]` is accepted as well, but not together with `#[codec(crate =
+/// ...)]`.
+///
+/// If not found, returns the path of the crate as found by [`crate_access`].
 ///
 /// If multiple items match the pattern, all but the first are ignored.
 pub fn codec_crate_path(attrs: &[Attribute]) -> syn::Result<Path> {
-	match attrs.iter().find_map(codec_crate_path_inner) {
-		Some(path) => Ok(path),
-		None => crate_access().map(|ident| parse_quote!(::#ident)),
+	let legacy = attrs.iter().find(|attr| attr.path().is_ident("max_encoded_len_mod"));
+	match (attrs.iter().find_map(codec_crate_path_inner), legacy) {
+		(Some(_), Some(legacy)) => Err(syn::Error::new_spanned(
+			legacy,
+			"`#[max_encoded_len_mod(...)]` can't be used together with `#[codec(crate = ...)]`, \
+			remove it and only use `#[codec(crate = ...)]`",
+		)),
+		(Some(path), None) => Ok(path),
+		(None, Some(legacy)) => legacy.parse_args::<Path>(),
+		(None, None) => crate_access().map(|ident| parse_quote!(::#ident)),
 	}
 }
 
@@ -271,6 +711,37 @@ pub fn custom_mel_trait_bound(attrs: &[Attribute]) -> Option<CustomTraitBound<me
 	find_meta_item(attrs.iter(), Some)
 }
 
+/// Returns the statement reporting that the variant `variant` of the enum `type_name` was encoded
+/// or decoded, depending on `operation`, to the `enum_stats` recorder.
+///
+/// Returns `None` if the `enum-stats` feature is disabled.
+#[cfg_attr(not(feature = "enum-stats"), allow(unused_variables))]
+pub fn record_variant(
+	crate_path: &syn::Path,
+	operation: &str,
+	type_name: &syn::Ident,
+	variant: &Variant,
+	index: &TokenStream,
+) -> Option<TokenStream> {
+	#[cfg(feature = "enum-stats")]
+	{
+		let operation = syn::Ident::new(operation, proc_macro2::Span::call_site());
+		let type_name = type_name.to_string();
+		let variant = variant.ident.to_string();
+		Some(quote! {
+			#[allow(clippy::unnecessary_cast)]
+			#crate_path::enum_stats::record(
+				#crate_path::enum_stats::Operation::#operation,
+				::core::concat!(::core::module_path!(), "::", #type_name),
+				#variant,
+				#index as ::core::primitive::u8,
+			);
+		})
+	}
+	#[cfg(not(feature = "enum-stats"))]
+	None
+}
+
 /// Given a set of named fields, return an iterator of `Field` where all fields
 /// marked `#[codec(skip)]` are filtered out.
 pub fn filter_skip_named(fields: &syn::FieldsNamed) -> impl Iterator<Item = &Field> {
@@ -283,6 +754,128 @@ pub fn filter_skip_unnamed(fields: &syn::FieldsUnnamed) -> impl Iterator<Item =
 	fields.unnamed.iter().enumerate().filter(|(_, f)| !should_skip(&f.attrs))
 }
 
+/// The single non-skipped field of a struct, for derives wrapping it like `CompactAs`.
+pub struct SingleField<'a> {
+	/// The type of the field.
+	pub ty: &'a syn::Type,
+	/// A reference to the field of `self`.
+	pub reference: TokenStream,
+	/// An expression constructing the struct from the field value `x`, with the skipped fields set
+	/// to their default.
+	pub constructor: TokenStream,
+}
+
+/// Get the single non-skipped field of the struct `input`, or an error mentioning `derive_name`.
+pub fn single_field<'a>(input: &'a DeriveInput, derive_name: &str) -> syn::Result<SingleField<'a>> {
+	let data = match input.data {
+		Data::Struct(ref data) => data,
+		Data::Enum(DataEnum { enum_token: token::Enum { span }, .. }) |
+		Data::Union(syn::DataUnion { union_token: token::Union { span }, .. }) =>
+			return Err(syn::Error::new(span, format!("Only structs can derive {derive_name}"))),
+	};
+
+	reject_append_with(&input.attrs, derive_name)?;
+
+	if let Some(field) = data.fields.iter().find(|f| get_skip_default(&f.attrs).is_some()) {
+		return Err(syn::Error::new(
+			field.span(),
+			format!(
+				"`#[codec(skip, default = \"$path\")]` is not supported when deriving {derive_name}"
+			),
+		));
+	}
+
+	fn val_or_default(field: &Field) -> TokenStream {
+		if should_skip(&field.attrs) {
+			quote_spanned!(field.span()=> Default::default())
+		} else {
+			quote_spanned!(field.span()=> x)
+		}
+	}
+
+	let name = &input.ident;
+	match data.fields {
+		Fields::Named(ref fields) if filter_skip_named(fields).count() == 1 => {
+			let recurse = fields.named.iter().map(|f| {
+				let name_ident = &f.ident;
+				let val_or_default = val_or_default(f);
+				quote_spanned!(f.span()=> #name_ident: #val_or_default)
+			});
+			let field = filter_skip_named(fields).next().expect("Exactly one field");
+			let field_name = &field.ident;
+			Ok(SingleField {
+				ty: &field.ty,
+				reference: quote!(&self.#field_name),
+				constructor: quote!( #name { #( #recurse, )* }),
+			})
+		},
+		Fields::Unnamed(ref fields) if filter_skip_unnamed(fields).count() == 1 => {
+			let recurse = fields.unnamed.iter().map(val_or_default);
+			let (id, field) = filter_skip_unnamed(fields).next().expect("Exactly one field");
+			let id = syn::Index::from(id);
+			Ok(SingleField {
+				ty: &field.ty,
+				reference: quote!(&self.#id),
+				constructor: quote!( #name(#( #recurse, )*)),
+			})
+		},
+		_ => Err(syn::Error::new(
+			data.fields.span(),
+			format!("Only structs with a single non-skipped field can derive {derive_name}"),
+		)),
+	}
+}
+
+/// The types which contain their type arguments inline, so a type containing itself through them
+/// has an infinite size.
+const INLINE_WRAPPERS: &[&str] = &["Option", "Result", "Compact", "Cell", "RefCell"];
+
+/// Whether `ty` contains the type `name` inline, i.e. not behind a pointer or in a collection.
+fn contains_inline(ty: &syn::Type, name: &syn::Ident) -> bool {
+	match ty {
+		syn::Type::Paren(ty) => contains_inline(&ty.elem, name),
+		syn::Type::Group(ty) => contains_inline(&ty.elem, name),
+		syn::Type::Array(ty) => contains_inline(&ty.elem, name),
+		syn::Type::Tuple(ty) => ty.elems.iter().any(|ty| contains_inline(ty, name)),
+		syn::Type::Path(syn::TypePath { qself: None, path }) => {
+			let Some(last) = path.segments.last() else { return false };
+			if path.segments.len() == 1 && (last.ident == "Self" || last.ident == *name) {
+				return true;
+			}
+			let syn::PathArguments::AngleBracketed(ref args) = last.arguments else { return false };
+			INLINE_WRAPPERS.iter().any(|wrapper| last.ident == wrapper) &&
+				args.args.iter().any(
+					|arg| matches!(arg, syn::GenericArgument::Type(ty) if contains_inline(ty, name)),
+				)
+		},
+		_ => false,
+	}
+}
+
+/// Ensure the type doesn't contain itself without indirection, which would make decoding it
+/// recurse without end.
+///
+/// Such a type is rejected by the compiler anyway, but the derived code would add confusing errors
+/// about the trait bounds of the type.
+pub fn check_no_inline_recursion(input: &DeriveInput) -> syn::Result<()> {
+	let fields: Vec<&Field> = match input.data {
+		Data::Struct(ref data) => data.fields.iter().collect(),
+		Data::Enum(ref data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+		Data::Union(_) => return Ok(()),
+	};
+	let name = &input.ident;
+	match fields.into_iter().find(|field| contains_inline(&field.ty, name)) {
+		Some(field) => Err(error_with_help(
+			field.ty.span(),
+			&format!("`{name}` contains itself without indirection, so it can't be decoded."),
+			&format!(
+				"Put it behind a pointer or in a collection, e.g. `Box<{name}>` or `Vec<{name}>`."
+			),
+		)),
+		None => Ok(()),
+	}
+}
+
 /// Ensure attributes are correctly applied. This *must* be called before using
 /// any of the attribute finder methods or the macro may panic if it encounters
 /// misapplied attributes.
@@ -298,8 +891,11 @@ pub fn filter_skip_unnamed(fields: &syn::FieldsUnnamed) -> impl Iterator<Item =
 /// Fields can have the following attributes:
 ///
 /// * `#[codec(skip)]`
+/// * `#[codec(skip, default = "$path")]` with $path a valid path to a function
 /// * `#[codec(compact)]`
 /// * `#[codec(encoded_as = "$EncodeAs")]` with $EncodedAs a valid TokenStream
+/// * `#[codec(decoded_via = "$DecodeVia")]` with $DecodeVia a valid TokenStream
+/// * `#[codec(lock)]`
 ///
 /// Variants can have the following attributes:
 ///
@@ -309,15 +905,18 @@ pub fn check_attributes(input: &DeriveInput) -> syn::Result<()> {
 	for attr in &input.attrs {
 		check_top_attribute(attr)?;
 	}
+	check_inline_attributes(&input.attrs)?;
+	check_cfg_gated_variants(input)?;
+	if let Data::Enum(ref data) = input.data {
+		check_skipped_variant_decodes(data)?;
+	}
 
 	match input.data {
 		Data::Struct(ref data) => match &data.fields {
 			| Fields::Named(FieldsNamed { named: fields, .. }) |
 			Fields::Unnamed(FieldsUnnamed { unnamed: fields, .. }) =>
 				for field in fields {
-					for attr in &field.attrs {
-						check_field_attribute(attr)?;
-					}
+					check_field(field)?;
 				},
 			Fields::Unit => (),
 		},
@@ -327,9 +926,7 @@ pub fn check_attributes(input: &DeriveInput) -> syn::Result<()> {
 					check_variant_attribute(attr)?;
 				}
 				for field in &variant.fields {
-					for attr in &field.attrs {
-						check_field_attribute(attr)?;
-					}
+					check_field(field)?;
 				}
 			},
 		Data::Union(_) => (),
@@ -337,6 +934,27 @@ pub fn check_attributes(input: &DeriveInput) -> syn::Result<()> {
 	Ok(())
 }
 
+// Ensure the attributes of a field are valid, and at most one of them changes its encoding.
+fn check_field(field: &Field) -> syn::Result<()> {
+	for attr in &field.attrs {
+		check_field_attribute(attr)?;
+	}
+
+	let encoding_attributes = [
+		get_encoded_as_type(field).is_some(),
+		get_decoded_via_type(field).is_some(),
+		is_compact(field),
+		is_lock(field),
+		get_max_len(field).is_some(),
+		get_mel_cap(field).is_some(),
+		should_skip(&field.attrs),
+	];
+	if encoding_attributes.into_iter().filter(|set| *set).count() > 1 {
+		return Err(conflicting_field_attributes_error(field.span()));
+	}
+	Ok(())
+}
+
 // Check if the attribute is `#[allow(..)]`, `#[deny(..)]`, `#[forbid(..)]` or `#[warn(..)]`.
 pub fn is_lint_attribute(attr: &Attribute) -> bool {
 	attr.path().is_ident("allow") ||
@@ -347,14 +965,28 @@ pub fn is_lint_attribute(attr: &Attribute) -> bool {
 
 // Ensure a field is decorated only with the following attributes:
 // * `#[codec(skip)]`
+// * `#[codec(skip, default = "$path")]` with $path a valid path to a function
 // * `#[codec(compact)]`
 // * `#[codec(encoded_as = "$EncodeAs")]` with $EncodedAs a valid TokenStream
+// * `#[codec(decoded_via = "$DecodeVia")]` with $DecodeVia a valid TokenStream
+// * `#[codec(lock)]`
+// * `#[codec(max_len = $N)]` with $N a `u32` literal or a path to a `usize` constant
+// * `#[codec(mel(cap = $N))]` and `#[codec(mel(cap = $N, check))]` with $N a `usize` literal or a
+//   path to a `usize` constant
 fn check_field_attribute(attr: &Attribute) -> syn::Result<()> {
-	let field_error = "Invalid attribute on field, only `#[codec(skip)]`, `#[codec(compact)]` and \
-		`#[codec(encoded_as = \"$EncodeAs\")]` are accepted.";
+	let field_error = "Invalid attribute on field, only `#[codec(skip)]`, \
+		`#[codec(skip, default = \"$path\")]`, `#[codec(compact)]`, \
+		`#[codec(encoded_as = \"$EncodeAs\")]`, `#[codec(decoded_via = \"$DecodeVia\")]`, \
+		`#[codec(lock)]`, `#[codec(max_len = $N)]` and `#[codec(mel(cap = $N))]` are accepted.";
 
 	if attr.path().is_ident("codec") {
 		let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+		// Only a pair of `skip` and `default` is accepted, other pairs are reported below.
+		let is_skip_or_default =
+			|meta: &Meta| meta.path().is_ident("skip") || meta.path().is_ident("default");
+		if nested.len() == 2 && nested.iter().any(is_skip_or_default) {
+			return attr.parse_args::<SkipWithDefault>().map(|_| ());
+		}
 		if nested.len() != 1 {
 			return Err(syn::Error::new(attr.meta.span(), field_error));
 		}
@@ -363,15 +995,32 @@ fn check_field_attribute(attr: &Attribute) -> syn::Result<()> {
 
 			Meta::Path(path) if path.get_ident().map_or(false, |i| i == "compact") => Ok(()),
 
+			Meta::Path(path) if path.is_ident("lock") => Ok(()),
+
 			Meta::NameValue(MetaNameValue {
 				path,
 				value: Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }),
 				..
-			}) if path.get_ident().map_or(false, |i| i == "encoded_as") =>
+			}) if path.get_ident().map_or(false, |i| i == "encoded_as" || i == "decoded_via") =>
 				TokenStream::from_str(&lit_str.value())
 					.map(|_| ())
 					.map_err(|_e| syn::Error::new(lit_str.span(), "Invalid token stream")),
 
+			Meta::NameValue(MetaNameValue {
+				path,
+				value: Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. }),
+				..
+			}) if path.is_ident("max_len") => lit_int
+				.base10_parse::<u32>()
+				.map(|_| ())
+				.map_err(|_| syn::Error::new(lit_int.span(), "Maximum length must fit in a u32")),
+
+			Meta::NameValue(MetaNameValue { path, value: Expr::Path(_), .. })
+				if path.is_ident("max_len") =>
+				Ok(()),
+
+			Meta::List(list) if list.path.is_ident("mel") => parse_mel_cap(list).map(|_| ()),
+
 			elt => Err(syn::Error::new(elt.span(), field_error)),
 		}
 	} else {
@@ -379,15 +1028,22 @@ fn check_field_attribute(attr: &Attribute) -> syn::Result<()> {
 	}
 }
 
-// Ensure a field is decorated only with the following attributes:
+// Ensure a variant is decorated only with the following attributes:
 // * `#[codec(skip)]`
+// * `#[codec(skip, decode_as = "$Variant")]`
+// * `#[codec(skip, decode_error = "$msg")]`
 // * `#[codec(index = $int)]`
+// * `#[codec(index = $expr)]`
 fn check_variant_attribute(attr: &Attribute) -> syn::Result<()> {
-	let variant_error = "Invalid attribute on variant, only `#[codec(skip)]` and \
+	let variant_error = "Invalid attribute on variant, only `#[codec(skip)]`, \
+		`#[codec(skip, decode_as = \"$Variant\")]`, `#[codec(skip, decode_error = \"$msg\")]` and \
 		`#[codec(index = $u8)]` are accepted.";
 
 	if attr.path().is_ident("codec") {
 		let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+		if nested.len() == 2 {
+			return attr.parse_args::<SkippedVariantDecode>().map(|_| ());
+		}
 		if nested.len() != 1 {
 			return Err(syn::Error::new(attr.meta.span(), variant_error));
 		}
@@ -403,6 +1059,13 @@ fn check_variant_attribute(attr: &Attribute) -> syn::Result<()> {
 				.map(|_| ())
 				.map_err(|_| syn::Error::new(lit_int.span(), "Index must be in 0..255")),
 
+			// Other expressions, like constants, are checked at compile time by
+			// `quote_index_check`.
+			Meta::NameValue(MetaNameValue { path, value, .. })
+				if path.get_ident().map_or(false, |i| i == "index") &&
+					!matches!(value, Expr::Lit(_)) =>
+				Ok(()),
+
 			elt => Err(syn::Error::new(elt.span(), variant_error)),
 		}
 	} else {
@@ -410,13 +1073,184 @@ fn check_variant_attribute(attr: &Attribute) -> syn::Result<()> {
 	}
 }
 
-// Only `#[codec(dumb_trait_bound)]` is accepted as top attribute
+/// The indexes reserved by the `#[codec(reserve_index = $u8)]` and
+/// `#[codec(reserve_index($u8, ...))]` attributes in `attrs`.
+fn reserved_indices(attrs: &[Attribute]) -> Vec<(u8, Span)> {
+	let literals = attrs
+		.iter()
+		.filter(|attr| attr.path().is_ident("codec"))
+		.filter_map(|attr| attr.parse_args::<Meta>().ok())
+		.flat_map(|meta| match meta {
+			Meta::NameValue(MetaNameValue {
+				path,
+				value: Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. }),
+				..
+			}) if path.is_ident("reserve_index") => vec![lit_int],
+			Meta::List(list) if list.path.is_ident("reserve_index") => list
+				.parse_args_with(Punctuated::<syn::LitInt, Token![,]>::parse_terminated)
+				.map_or_else(|_| Vec::new(), |lits| lits.into_iter().collect()),
+			_ => Vec::new(),
+		});
+	literals
+		.filter_map(|lit_int| Some((lit_int.base10_parse::<u8>().ok()?, lit_int.span())))
+		.collect()
+}
+
+/// Whether `variant` is only compiled with some `#[cfg]` options.
+///
+/// The derive macros only see the variants which are compiled, so it is only known for them.
+fn is_cfg_gated(variant: &Variant) -> bool {
+	variant.attrs.iter().any(|attr| attr.path().is_ident("cfg"))
+}
+
+// Ensure the indexes of the variants don't change between builds with different `#[cfg]` options:
+// the variants after variants gated by `#[cfg]` have explicit indexes, and in enums reserving
+// indexes with `#[codec(reserve_index = $u8)]`, the indexes of gated variants are reserved, so that
+// other variants can't take them when they are not compiled.
+fn check_cfg_gated_variants(input: &DeriveInput) -> syn::Result<()> {
+	let reserved = reserved_indices(&input.attrs);
+	let Data::Enum(ref data) = input.data else {
+		return match reserved.first() {
+			Some((_, span)) => Err(syn::Error::new(
+				*span,
+				"`#[codec(reserve_index = $u8)]` can only be used on enums",
+			)),
+			None => Ok(()),
+		};
+	};
+
+	let mut first_gated: Option<&Variant> = None;
+	let variants = data.variants.iter().filter(|variant| !should_skip(&variant.attrs));
+	for (i, variant) in variants.enumerate() {
+		let gated = is_cfg_gated(variant);
+		let explicit = index_attribute(variant).is_some() || variant.discriminant.is_some();
+		if let Some(gated) = first_gated.filter(|_| !explicit) {
+			return Err(error_with_help(
+				variant.ident.span(),
+				&format!(
+					"Variant `{}` follows `{}`, which is gated by `#[cfg]`, so it needs an explicit \
+					index, otherwise its index changes between builds with and without `{}`.",
+					variant.ident, gated.ident, gated.ident,
+				),
+				"Add `#[codec(index = $u8)]` to the variant.",
+			));
+		}
+		if gated {
+			first_gated.get_or_insert(variant);
+		}
+
+		// Indexes given by expressions are checked at compile time by `quote_index_check`.
+		let Some(index) = literal_variant_index(variant, i) else { continue };
+		let is_reserved = reserved.iter().any(|(reserved, _)| *reserved == index);
+		if gated && !is_reserved && !reserved.is_empty() {
+			return Err(error_with_help(
+				variant.ident.span(),
+				&format!(
+					"Variant `{}` is gated by `#[cfg]`, but its index `{index}` is not reserved, so \
+					another variant could take it in builds without it.",
+					variant.ident,
+				),
+				&format!("Add `#[codec(reserve_index = {index})]` to the enum."),
+			));
+		}
+		if !gated && is_reserved {
+			return Err(error_with_help(
+				variant.ident.span(),
+				&format!(
+					"Variant `{}` has the index `{index}`, which is reserved by \
+					`#[codec(reserve_index = {index})]` for a variant gated by `#[cfg]`.",
+					variant.ident,
+				),
+				"Use another index, or gate the variant with `#[cfg]`.",
+			));
+		}
+	}
+	Ok(())
+}
+
+// Ensure the skipped variants which are still decoded have an explicit index, which isn't used by
+// another variant, and are decoded as a variant which isn't skipped.
+fn check_skipped_variant_decodes(data: &DataEnum) -> syn::Result<()> {
+	let variants = data.variants.iter().filter(|variant| !should_skip(&variant.attrs));
+	let indices = variants
+		.clone()
+		.enumerate()
+		.filter_map(|(i, v)| Some((literal_variant_index(v, i)?, v)));
+	let mut indices = indices.collect::<Vec<_>>();
+
+	for (variant, _, decode) in skipped_variant_decodes(data) {
+		if index_attribute(variant).is_none() && variant.discriminant.is_none() {
+			return Err(error_with_help(
+				variant.ident.span(),
+				&format!(
+					"Skipped variant `{}` is decoded, so it needs an explicit index.",
+					variant.ident,
+				),
+				"Add `#[codec(index = $u8)]` with the index the variant had when it was encoded.",
+			));
+		}
+
+		if let SkippedVariantDecode::As(ref target) = decode {
+			if !variants.clone().any(|v| v.ident == *target) {
+				return Err(error_with_help(
+					target.span(),
+					&format!(
+						"Skipped variant `{}` is decoded as `{target}`, which isn't a variant \
+						that is decoded.",
+						variant.ident
+					),
+					"Use the name of a variant of the enum which isn't skipped.",
+				));
+			}
+		}
+
+		// Indices given by expressions are checked at compile time by `quote_index_check`.
+		let Some(index) = literal_variant_index(variant, 0) else { continue };
+		if let Some((_, other)) = indices.iter().find(|(other, _)| *other == index) {
+			return Err(error_with_help(
+				variant.ident.span(),
+				&format!(
+					"Found variants that have duplicate indexes. Both `{}` and `{}` have the index \
+					`{index}`.",
+					other.ident, variant.ident,
+				),
+				"Use different indexes for each variant, e.g. with `#[codec(index = $u8)]`.",
+			));
+		}
+		indices.push((index, variant));
+	}
+	Ok(())
+}
+
+// Ensure `#[codec(inline)]` and `#[codec(never_inline)]` are not used together.
+fn check_inline_attributes(attrs: &[Attribute]) -> syn::Result<()> {
+	let inline = |name| {
+		find_meta_item(attrs.iter(), |meta| match meta {
+			Meta::Path(ref path) if path.is_ident(name) => Some(path.span()),
+			_ => None,
+		})
+	};
+
+	match (inline("inline"), inline("never_inline")) {
+		(Some(_), Some(span)) => Err(syn::Error::new(
+			span,
+			"`#[codec(inline)]` and `#[codec(never_inline)]` can't be used together",
+		)),
+		_ => Ok(()),
+	}
+}
+
 fn check_top_attribute(attr: &Attribute) -> syn::Result<()> {
 	let top_error = "Invalid attribute: only `#[codec(dumb_trait_bound)]`, \
+		`#[codec(recursive)]`, `#[codec(dyn_input)]`, `#[codec(document_layout)]`, \
+		`#[codec(variant_index)]`, `#[codec(like_plain(OtherType))]`, \
+		`#[codec(reserve_index = $u8)]`, `#[codec(inline)]`, `#[codec(never_inline)]`, \
 		`#[codec(crate = path::to::crate)]`, `#[codec(encode_bound(T: Encode))]`, \
 		`#[codec(decode_bound(T: Decode))]`, \
-		`#[codec(decode_bound_with_mem_tracking_bound(T: DecodeWithMemTracking))]` or \
-		`#[codec(mel_bound(T: MaxEncodedLen))]` are accepted as top attribute";
+		`#[codec(decode_bound_with_mem_tracking_bound(T: DecodeWithMemTracking))]`, \
+		`#[codec(mel_bound(T: MaxEncodedLen))]`, `#[codec(normalize = \"$path\")]`, \
+		`#[codec(append_with = \"$path\")]` or `#[codec(migrate_from(OldType, \"$path\"))]` are \
+		accepted as top attribute";
 	if attr.path().is_ident("codec") &&
 		attr.parse_args::<CustomTraitBound<encode_bound>>().is_err() &&
 		attr.parse_args::<CustomTraitBound<decode_bound>>().is_err() &&
@@ -429,9 +1263,49 @@ fn check_top_attribute(attr: &Attribute) -> syn::Result<()> {
 			return Err(syn::Error::new(attr.meta.span(), top_error));
 		}
 		match nested.first().expect("Just checked that there is one item; qed") {
-			Meta::Path(path) if path.get_ident().map_or(false, |i| i == "dumb_trait_bound") =>
+			Meta::Path(path)
+				if path.get_ident().map_or(false, |i| {
+					i == "dumb_trait_bound" ||
+						i == "recursive" || i == "dyn_input" ||
+						i == "document_layout" ||
+						i == "variant_index" ||
+						i == "inline" || i == "never_inline"
+				}) =>
 				Ok(()),
 
+			Meta::NameValue(MetaNameValue {
+				path,
+				value: Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }),
+				..
+			}) if path.get_ident().map_or(false, |i| i == "normalize" || i == "append_with") =>
+				lit_str.parse::<Path>().map(|_| ()),
+
+			Meta::List(list) if list.path.is_ident("like_plain") =>
+				list.parse_args::<Path>().map(|_| ()),
+
+			Meta::List(list) if list.path.is_ident("migrate_from") =>
+				list.parse_args::<MigrateFrom>().map(|_| ()),
+
+			Meta::NameValue(MetaNameValue {
+				path,
+				value: Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. }),
+				..
+			}) if path.get_ident().map_or(false, |i| i == "reserve_index") => lit_int
+				.base10_parse::<u8>()
+				.map(|_| ())
+				.map_err(|_| syn::Error::new(lit_int.span(), "Index must be in 0..255")),
+
+			Meta::List(list) if list.path.is_ident("reserve_index") => {
+				for lit_int in
+					list.parse_args_with(Punctuated::<syn::LitInt, Token![,]>::parse_terminated)?
+				{
+					lit_int
+						.base10_parse::<u8>()
+						.map_err(|_| syn::Error::new(lit_int.span(), "Index must be in 0..255"))?;
+				}
+				Ok(())
+			},
+
 			elt => Err(syn::Error::new(elt.span(), top_error)),
 		}
 	} else {
@@ -458,11 +1332,174 @@ pub fn try_get_variants(data: &DataEnum) -> Result<Vec<&Variant>, syn::Error> {
 		data.variants.iter().filter(|variant| !should_skip(&variant.attrs)).collect();
 
 	if data_variants.len() > 256 {
-		return Err(syn::Error::new(
+		return Err(error_with_help(
 			data.variants.span(),
 			"Currently only enums with at most 256 variants are encodable/decodable.",
+			"Skip some variants with `#[codec(skip)]`, or group them into a nested enum.",
 		));
 	}
 
 	Ok(data_variants)
 }
+
+/// Ensure the variants of an enum have different literal indexes, so that they can be told apart
+/// when decoding.
+///
+/// Indexes given by expressions are checked at compile time by `quote_index_check`. Encoding
+/// variants with the same index is still accepted, as it used to be.
+pub fn check_variant_indices(input: &DeriveInput) -> syn::Result<()> {
+	let Data::Enum(ref data) = input.data else { return Ok(()) };
+	let mut indices: Vec<(u8, &Variant)> = Vec::new();
+	for (i, variant) in try_get_variants(data)?.into_iter().enumerate() {
+		let Some(index) = literal_variant_index(variant, i) else { continue };
+		if let Some((_, first)) = indices.iter().find(|(other, _)| *other == index) {
+			return Err(error_with_help(
+				variant.ident.span(),
+				&format!(
+					"Found variants that have duplicate indexes. Both `{}` and `{}` have the index \
+					`{index}`.",
+					first.ident, variant.ident,
+				),
+				"Use different indexes for each variant, e.g. with `#[codec(index = $u8)]`.",
+			));
+		}
+		indices.push((index, variant));
+	}
+	Ok(())
+}
+
+/// The index of `v` as returned by [`variant_index`], if it is a literal.
+fn literal_variant_index(v: &Variant, i: usize) -> Option<u8> {
+	let index = variant_index(v, i);
+	let lit = syn::parse2::<syn::LitInt>(index).ok()?;
+	// Like the `as u8` cast of the derived code.
+	lit.base10_parse::<u128>().ok().map(|index| index as u8)
+}
+
+/// Check at compile time that indexes given by `#[codec(index = $expr)]` are in `0..=255`, and
+/// reserved by `#[codec(reserve_index = $u8)]` if and only if the variant is gated by `#[cfg]`, as
+/// the derive macros can't evaluate the expressions.
+///
+/// With `check_duplicates`, which only `#[derive(Decode)]` sets, the variants are also checked to
+/// have different indexes.
+pub fn quote_index_check(input: &DeriveInput, check_duplicates: bool) -> TokenStream {
+	let Data::Enum(ref data) = input.data else { return quote!() };
+	let Ok(variants) = try_get_variants(data) else { return quote!() };
+	let skipped_indices = skipped_variant_decodes(data).map(|(_, index, _)| index);
+	let skipped_indices = skipped_indices.collect::<Vec<_>>();
+	if variants.iter().enumerate().all(|(i, v)| literal_variant_index(v, i).is_some()) &&
+		skipped_indices
+			.iter()
+			.all(|index| syn::parse2::<syn::LitInt>(index.clone()).is_ok())
+	{
+		return quote!();
+	}
+
+	let ranges = variants.iter().filter_map(|v| {
+		let index = index_attribute(v)?;
+		if matches!(index, Expr::Lit(_)) {
+			return None;
+		}
+		let message = format!("The index of variant `{}` must be in 0..=255.", v.ident);
+		Some(quote! {
+			if !(0 <= (#index) as ::core::primitive::i128 &&
+				(#index) as ::core::primitive::i128 <= 255)
+			{
+				::core::panic!(#message);
+			}
+		})
+	});
+	let reserved = reserved_indices(&input.attrs).into_iter().map(|(index, _)| index);
+	let reserved = reserved.collect::<Vec<_>>();
+	let reservations = variants.iter().filter(|_| !reserved.is_empty()).filter_map(|v| {
+		let index = index_attribute(v)?;
+		if matches!(index, Expr::Lit(_)) {
+			return None;
+		}
+		let gated = is_cfg_gated(v);
+		let message = if gated {
+			format!(
+				"The index of variant `{}`, which is gated by `#[cfg]`, must be reserved.",
+				v.ident
+			)
+		} else {
+			format!(
+				"The index of variant `{}` is reserved for a variant gated by `#[cfg]`.",
+				v.ident
+			)
+		};
+		Some(quote! {
+			let mut reserved = false;
+			let mut i = 0;
+			while i < reserved_indices.len() {
+				reserved |= reserved_indices[i] == (#index) as ::core::primitive::u8;
+				i += 1;
+			}
+			if reserved != #gated {
+				::core::panic!(#message);
+			}
+		})
+	});
+	let reservations = reservations.collect::<Vec<_>>();
+	let reserved_indices = (!reservations.is_empty())
+		.then(|| quote!(let reserved_indices: &[::core::primitive::u8] = &[#( #reserved, )*];));
+	let indices = variants
+		.iter()
+		.enumerate()
+		.map(|(i, v)| variant_index(v, i))
+		.chain(skipped_indices);
+	let duplicates = check_duplicates.then(|| {
+		quote! {
+			let indices = [#( (#indices) as ::core::primitive::u8, )*];
+			let mut i = 0;
+			while i < indices.len() {
+				let mut j = i + 1;
+				while j < indices.len() {
+					if indices[i] == indices[j] {
+						::core::panic!(
+							"Found variants that have duplicate indexes. Use different indexes \
+							for each variant."
+						);
+					}
+					j += 1;
+				}
+				i += 1;
+			}
+		}
+	});
+	quote! {
+		#[allow(clippy::unnecessary_cast)]
+		const _: () = {
+			#( #ranges )*
+			#reserved_indices
+			#( #reservations )*
+			#duplicates
+		};
+	}
+}
+
+/// An error with `message` at `span`, followed by the sentence `help` on how to fix it.
+pub fn error_with_help(span: Span, message: &str, help: &str) -> syn::Error {
+	syn::Error::new(span, format!("{message} {help}"))
+}
+
+/// The error for deriving a trait for a union.
+pub fn union_error(span: Span) -> syn::Error {
+	error_with_help(
+		span,
+		"Union types are not supported.",
+		"The encoding can't tell which field of a union is set, use an enum instead or implement \
+		the trait manually.",
+	)
+}
+
+/// The error for a field with more than one attribute changing its encoding.
+pub fn conflicting_field_attributes_error(span: Span) -> syn::Error {
+	error_with_help(
+		span,
+		"`encoded_as`, `decoded_via`, `compact`, `lock`, `max_len`, `mel` and `skip` can only be \
+		used one at a time!",
+		"Remove all but one of these attributes, they all change how the field is encoded or its \
+		maximum encoded length.",
+	)
+}
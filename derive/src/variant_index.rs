@@ -0,0 +1,76 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of the `scale_variant_index` method for `#[codec(variant_index)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Error};
+
+use crate::utils;
+
+/// Generate the inherent `scale_variant_index` method if the type has `#[codec(variant_index)]`.
+pub fn quote(input: &DeriveInput) -> TokenStream {
+	if !utils::has_variant_index(&input.attrs) {
+		return quote!();
+	}
+
+	let data = match input.data {
+		Data::Enum(ref data) => data,
+		_ =>
+			return Error::new(
+				Span::call_site(),
+				"`#[codec(variant_index)]` can only be used on enums",
+			)
+			.to_compile_error(),
+	};
+
+	if let Some(skipped) = data.variants.iter().find(|v| utils::should_skip(&v.attrs)) {
+		return Error::new(
+			skipped.span(),
+			"`#[codec(variant_index)]` can't be used on enums with skipped variants, as they have no \
+			index",
+		)
+		.to_compile_error();
+	}
+
+	let variants = match utils::try_get_variants(data) {
+		Ok(variants) => variants,
+		// The error is already reported by the derive.
+		Err(_) => return quote!(),
+	};
+
+	let name = &input.ident;
+	let arms = variants.iter().enumerate().map(|(i, variant)| {
+		let variant_name = &variant.ident;
+		// The same index as pushed by the derived `Encode::encode_to`.
+		let index = utils::variant_index(variant, i);
+		quote! {
+			#name::#variant_name { .. } => #index as ::core::primitive::u8,
+		}
+	});
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	quote! {
+		impl #impl_generics #name #ty_generics #where_clause {
+			/// The index of the variant of `self`, which is the first byte of its SCALE encoding.
+			#[allow(clippy::unnecessary_cast)]
+			pub const fn scale_variant_index(&self) -> ::core::primitive::u8 {
+				match *self {
+					#( #arms )*
+				}
+			}
+		}
+	}
+}
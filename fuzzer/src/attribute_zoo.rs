@@ -0,0 +1,105 @@
+//! A "zoo" of types exercising the combinations of `#[codec(..)]` attributes supported by the
+//! derive macros, so that regressions in the generated code are caught by the fuzzer.
+
+use std::marker::PhantomData;
+
+use arbitrary::Arbitrary;
+use parity_scale_codec::{Be, CompactAs, Decode, Encode, HasCompact};
+
+/// A type implementing `CompactAs`, to be used in compact fields.
+#[derive(CompactAs, Encode, Decode, Clone, Copy, PartialEq, Debug, Arbitrary)]
+pub struct CompactWrapper(u64, #[codec(skip)] PhantomData<u8>);
+
+/// For every given field, generate a struct with named fields and a tuple struct that contain the
+/// field between two other fields, and a tuple struct that only contains the field, so that the
+/// single field optimisations of the derive macros are exercised as well.
+macro_rules! zoo_structs {
+	( $( $named:ident, $unnamed:ident, $single:ident: $( #[$($attr:tt)*] )* $ty:ty; )* ) => { $(
+		#[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
+		pub struct $named {
+			head: u8,
+			$( #[$($attr)*] )*
+			field: $ty,
+			tail: Vec<u16>,
+		}
+
+		#[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
+		pub struct $unnamed(u8, $( #[$($attr)*] )* $ty, Vec<u16>);
+
+		#[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
+		pub struct $single($( #[$($attr)*] )* $ty);
+	)* }
+}
+
+zoo_structs! {
+	PlainNamed, PlainUnnamed, PlainSingle: u32;
+	SkipNamed, SkipUnnamed, SkipSingle: #[codec(skip)] PhantomData<u64>;
+	CompactNamed, CompactUnnamed, CompactSingle: #[codec(compact)] u64;
+	CompactAsNamed, CompactAsUnnamed, CompactAsSingle: #[codec(compact)] CompactWrapper;
+	EncodedAsCompactNamed, EncodedAsCompactUnnamed, EncodedAsCompactSingle:
+		#[codec(encoded_as = "<u128 as HasCompact>::Type")] u128;
+	EncodedAsBeNamed, EncodedAsBeUnnamed, EncodedAsBeSingle: #[codec(encoded_as = "Be<u32>")] u32;
+}
+
+/// An enum mixing explicit indices, implicit indices and field attributes.
+#[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
+pub enum IndexedEnum {
+	#[codec(index = 3)]
+	A,
+	B(u8),
+	#[codec(index = 0)]
+	C {
+		#[codec(compact)]
+		x: u32,
+		#[codec(skip)]
+		_skipped: PhantomData<u32>,
+	},
+	#[codec(index = 255)]
+	D(#[codec(encoded_as = "Be<u16>")] u16, Vec<u8>),
+}
+
+/// An enum with a skipped variant.
+///
+/// The skipped variant is never produced by decoding, so this is only used for decoding round
+/// trips.
+#[derive(Encode, Decode, Clone, PartialEq, Debug)]
+pub enum SkippedVariantEnum {
+	A(u8),
+	#[codec(skip)]
+	#[allow(dead_code)]
+	Skipped(u16),
+	B {
+		#[codec(compact)]
+		x: u128,
+	},
+}
+
+/// A field-less enum with discriminants, indices and positions.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Debug, Arbitrary)]
+pub enum DiscriminantEnum {
+	A = 5,
+	B = 1,
+	#[codec(index = 7)]
+	C,
+	D,
+}
+
+/// A generic type with attributes on the generic fields.
+#[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
+pub struct Generic<T, U> {
+	#[codec(compact)]
+	t: T,
+	u: Vec<U>,
+	maybe: Option<(T, U)>,
+	#[codec(skip)]
+	_phantom: PhantomData<(T, U)>,
+}
+
+/// A recursive type.
+#[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
+pub enum Recursive {
+	Leaf(#[codec(compact)] u32),
+	Neg(Box<Recursive>),
+	Add(Box<Recursive>, Box<Recursive>),
+	List(Vec<Recursive>),
+}
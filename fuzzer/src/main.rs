@@ -12,7 +12,13 @@ use honggfuzz::{
 	arbitrary::{Arbitrary, Result as ArbResult, Unstructured},
 	fuzz,
 };
-use parity_scale_codec::{Compact, Decode, Encode};
+use parity_scale_codec::{Compact, Decode, DecodeLimit, Encode};
+
+mod attribute_zoo;
+use attribute_zoo::*;
+
+/// The maximum recursion depth of the decoded values.
+const MAX_DEPTH: u32 = 64;
 
 #[derive(Encode, Decode, Clone, PartialEq, Debug, Arbitrary)]
 pub struct MockStruct {
@@ -107,7 +113,8 @@ macro_rules! fuzz_decoder {
 		if $data[0] % num == $index {
 			let mut d = &$data[1..];
 			let raw1 = d.clone();
-			let maybe_obj = <$parsed>::decode(&mut d);
+			// Recursive types would overflow the stack without a depth limit.
+			let maybe_obj = <$parsed>::decode_with_depth_limit(MAX_DEPTH, &mut d);
 
 			match maybe_obj {
 				Ok(obj) => {
@@ -240,6 +247,30 @@ fn fuzz_decode(data: &[u8]) {
 		BitVec<u8, Msb0>,
 		BitVec<u32, Msb0>,
 		Duration,
+		PlainNamed,
+		PlainUnnamed,
+		PlainSingle,
+		SkipNamed,
+		SkipUnnamed,
+		SkipSingle,
+		CompactNamed,
+		CompactUnnamed,
+		CompactSingle,
+		CompactAsNamed,
+		CompactAsUnnamed,
+		CompactAsSingle,
+		EncodedAsCompactNamed,
+		EncodedAsCompactUnnamed,
+		EncodedAsCompactSingle,
+		EncodedAsBeNamed,
+		EncodedAsBeUnnamed,
+		EncodedAsBeSingle,
+		IndexedEnum,
+		SkippedVariantEnum,
+		DiscriminantEnum,
+		Generic<u64, MockStruct>,
+		Generic<CompactWrapper, IndexedEnum>,
+		Recursive,
 	};
 	// Types for which we wish to apply the "sorted" method.
 	fuzz_decoder! {
@@ -312,6 +343,29 @@ macro_rules! fuzz_encoding {
 			BitVecWrapper<u8, Msb0>,
 			BitVecWrapper<u32, Msb0>,
 			Duration,
+			PlainNamed,
+			PlainUnnamed,
+			PlainSingle,
+			SkipNamed,
+			SkipUnnamed,
+			SkipSingle,
+			CompactNamed,
+			CompactUnnamed,
+			CompactSingle,
+			CompactAsNamed,
+			CompactAsUnnamed,
+			CompactAsSingle,
+			EncodedAsCompactNamed,
+			EncodedAsCompactUnnamed,
+			EncodedAsCompactSingle,
+			EncodedAsBeNamed,
+			EncodedAsBeUnnamed,
+			EncodedAsBeSingle,
+			IndexedEnum,
+			DiscriminantEnum,
+			Generic<u64, MockStruct>,
+			Generic<CompactWrapper, IndexedEnum>,
+			Recursive,
 		}
 	};
 }
@@ -0,0 +1,148 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A type-erased encoded value with the fingerprint of its type.
+
+use crate::{
+	alloc::vec::Vec, Decode, DecodeAll, DecodeWithMemTracking, Encode, EncodeLike,
+	EncodingFingerprint, Error, Input, Output,
+};
+
+/// An encoded value of any type, together with the [`EncodingFingerprint`] of its type.
+///
+/// This allows storing values of different types in the same place, e.g. a persisted queue, and
+/// checking at runtime that a value is decoded as the type it was encoded as.
+///
+/// It is encoded as the fingerprint as `u64`, followed by the encoded value as `Vec<u8>`.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, AnyScale, Encode};
+/// let queue = vec![AnyScale::new(&7u32), AnyScale::new(&String::from("job"))];
+/// let (queue, _) = decode_prefix::<Vec<AnyScale>>(&queue.encode()).unwrap();
+///
+/// assert_eq!(queue[0].try_downcast_decode::<u32>(), Ok(7));
+/// assert!(queue[1].try_downcast_decode::<u32>().is_err());
+/// assert_eq!(queue[1].try_downcast_decode::<String>().as_deref(), Ok("job"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnyScale {
+	fingerprint: u64,
+	bytes: Vec<u8>,
+}
+
+impl AnyScale {
+	/// Encode `value`.
+	pub fn new<T: Encode + EncodingFingerprint + ?Sized>(value: &T) -> Self {
+		AnyScale { fingerprint: T::FINGERPRINT, bytes: value.encode() }
+	}
+
+	/// Create an envelope from an encoded value and the fingerprint of its type.
+	pub fn from_raw_parts(fingerprint: u64, bytes: Vec<u8>) -> Self {
+		AnyScale { fingerprint, bytes }
+	}
+
+	/// The fingerprint of the type of the value.
+	pub fn fingerprint(&self) -> u64 {
+		self.fingerprint
+	}
+
+	/// The encoded value.
+	pub fn bytes(&self) -> &[u8] {
+		&self.bytes
+	}
+
+	/// Whether the value was encoded as `T`, according to its fingerprint.
+	pub fn is<T: EncodingFingerprint + ?Sized>(&self) -> bool {
+		self.fingerprint == T::FINGERPRINT
+	}
+
+	/// Decode the value as `T`, failing if it wasn't encoded as `T`.
+	///
+	/// All the encoded bytes must be consumed, like with [`DecodeAll`].
+	pub fn try_downcast_decode<T: Decode + EncodingFingerprint>(&self) -> Result<T, Error> {
+		if !self.is::<T>() {
+			return Err("`AnyScale` was encoded as a different type".into());
+		}
+		T::decode_all(&mut &self.bytes[..])
+	}
+
+	/// The fingerprint and the encoded value.
+	pub fn into_raw_parts(self) -> (u64, Vec<u8>) {
+		(self.fingerprint, self.bytes)
+	}
+}
+
+impl Encode for AnyScale {
+	fn size_hint(&self) -> usize {
+		self.fingerprint.size_hint() + self.bytes.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.fingerprint.encode_to(dest);
+		self.bytes.encode_to(dest);
+	}
+}
+
+impl EncodeLike for AnyScale {}
+
+impl Decode for AnyScale {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Ok(AnyScale { fingerprint: u64::decode(input)?, bytes: Vec::decode(input)? })
+	}
+}
+
+impl DecodeWithMemTracking for AnyScale {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::string::String, CodecConfig, Compact, LengthPrefix};
+
+	#[test]
+	fn any_scale_checks_fingerprint() {
+		let value = AnyScale::new(&Compact(300u32));
+		assert!(value.is::<Compact<u32>>());
+		assert!(!value.is::<u32>());
+		assert_eq!(value.try_downcast_decode::<Compact<u32>>(), Ok(Compact(300)));
+		assert_eq!(
+			value.try_downcast_decode::<u32>(),
+			Err("`AnyScale` was encoded as a different type".into())
+		);
+
+		let (fingerprint, mut bytes) = value.into_raw_parts();
+		bytes.push(0);
+		let value = AnyScale::from_raw_parts(fingerprint, bytes);
+		assert!(value.try_downcast_decode::<Compact<u32>>().is_err());
+	}
+
+	#[test]
+	fn any_scale_encoding() {
+		let value = AnyScale::new("text");
+		let encoded = value.encode();
+		assert_eq!(encoded, (String::FINGERPRINT, "text".encode()).encode());
+		assert!(value.size_hint() >= encoded.len());
+		assert_eq!(AnyScale::decode(&mut &encoded[..]), Ok(value));
+	}
+
+	#[test]
+	fn any_scale_size_hint_covers_every_length_prefix() {
+		let value = AnyScale::new(&vec![7u8; 100]);
+		for length_prefix in
+			[LengthPrefix::ScaleCompact, LengthPrefix::FixedU32LE, LengthPrefix::Leb128]
+		{
+			let config = CodecConfig::DEFAULT.with_length_prefix(length_prefix);
+			assert!(value.size_hint() >= value.encode_with_config(&config).len());
+		}
+	}
+}
@@ -0,0 +1,211 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of collections into a [`Bump`] arena.
+
+use core::mem;
+
+use bumpalo::{
+	boxed::Box as BumpBox,
+	collections::{String as BumpString, Vec as BumpVec},
+	Bump,
+};
+
+use crate::{codec::decode_len, Decode, Error, Input};
+
+/// Decode a value whose collections are allocated in a [`Bump`] arena.
+///
+/// Short-lived values can be decoded into an arena, which is freed at once when it is reset or
+/// dropped, instead of freeing each `Vec`, `String` or `Box` separately. This is implemented for
+/// the collections of `bumpalo` and `&'bump [u8]` and `&'bump str`, which are encoded like
+/// `Vec`, `String` and `Box`, and for all types implementing `Decode`, which don't allocate in the
+/// arena.
+///
+/// Other types, e.g. structs with arena allocated fields, can implement it by decoding their
+/// fields in order:
+///
+/// ```
+/// use bumpalo::{collections::Vec, Bump};
+/// use parity_scale_codec::{ArenaDecode, Encode, Error, Input};
+///
+/// struct Message<'bump> {
+///     id: u32,
+///     payloads: Vec<'bump, &'bump [u8]>,
+/// }
+///
+/// impl<'bump> ArenaDecode<'bump> for Message<'bump> {
+///     fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error> {
+///         Ok(Message { id: u32::decode_in(input, bump)?, payloads: Vec::decode_in(input, bump)? })
+///     }
+/// }
+///
+/// let encoded = (7u32, vec![vec![1u8, 2], vec![3]]).encode();
+/// let mut bump = Bump::new();
+/// for _ in 0..3 {
+///     let message = Message::decode_in(&mut &encoded[..], &bump)?;
+///     assert_eq!((message.id, &message.payloads[..]), (7, &[&[1, 2][..], &[3]][..]));
+///     // Free all allocations of the message at once.
+///     drop(message);
+///     bump.reset();
+/// }
+/// # Ok::<(), Error>(())
+/// ```
+pub trait ArenaDecode<'bump>: Sized {
+	/// Decode the value from `input`, allocating its collections in `bump`.
+	fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error>;
+}
+
+impl<'bump, T: Decode> ArenaDecode<'bump> for T {
+	fn decode_in<I: Input>(input: &mut I, _bump: &'bump Bump) -> Result<Self, Error> {
+		T::decode(input)
+	}
+}
+
+/// The number of items of `T` allocated at once, like for `Vec`, so a wrong length can't make
+/// decoding allocate much more than the input.
+fn chunk_len<T, I: Input>(input: &I) -> usize {
+	input
+		.config()
+		.max_preallocation
+		.checked_div(mem::size_of::<T>())
+		.unwrap_or(usize::MAX)
+		.max(1)
+}
+
+impl<'bump, T: ArenaDecode<'bump>> ArenaDecode<'bump> for BumpVec<'bump, T> {
+	fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error> {
+		let len = decode_len(input)?;
+		let chunk_len = chunk_len::<T, I>(input);
+
+		input.descend_ref()?;
+		let mut vec = BumpVec::new_in(bump);
+		while vec.len() < len {
+			let chunk_len = chunk_len.min(len - vec.len());
+			input.on_before_alloc_mem(chunk_len.saturating_mul(mem::size_of::<T>()))?;
+			vec.reserve_exact(chunk_len);
+			for _ in 0..chunk_len {
+				vec.push(T::decode_in(input, bump)?);
+			}
+		}
+		input.ascend_ref();
+
+		Ok(vec)
+	}
+}
+
+/// Decode the bytes of a `Vec<u8>` into `bump`.
+fn decode_bytes<'bump, I: Input>(
+	input: &mut I,
+	bump: &'bump Bump,
+) -> Result<BumpVec<'bump, u8>, Error> {
+	let len = decode_len(input)?;
+	if input.remaining_len()?.is_some_and(|remaining| remaining < len) {
+		return Err("Not enough data to decode vector".into());
+	}
+
+	let chunk_len = chunk_len::<u8, I>(input);
+	let mut bytes = BumpVec::new_in(bump);
+	while bytes.len() < len {
+		let start = bytes.len();
+		let chunk_len = chunk_len.min(len - start);
+		input.on_before_alloc_mem(chunk_len)?;
+		bytes.reserve_exact(chunk_len);
+		bytes.resize(start + chunk_len, 0);
+		input.read(&mut bytes[start..])?;
+	}
+	Ok(bytes)
+}
+
+impl<'bump> ArenaDecode<'bump> for &'bump [u8] {
+	fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error> {
+		decode_bytes(input, bump).map(BumpVec::into_bump_slice)
+	}
+}
+
+impl<'bump> ArenaDecode<'bump> for BumpString<'bump> {
+	fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error> {
+		BumpString::from_utf8(decode_bytes(input, bump)?)
+			.map_err(|_| "Invalid utf8 sequence".into())
+	}
+}
+
+impl<'bump> ArenaDecode<'bump> for &'bump str {
+	fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error> {
+		BumpString::decode_in(input, bump).map(BumpString::into_bump_str)
+	}
+}
+
+impl<'bump, T: ArenaDecode<'bump>> ArenaDecode<'bump> for BumpBox<'bump, T> {
+	fn decode_in<I: Input>(input: &mut I, bump: &'bump Bump) -> Result<Self, Error> {
+		input.descend_ref()?;
+		input.on_before_alloc_mem(mem::size_of::<T>())?;
+		let value = T::decode_in(input, bump)?;
+		input.ascend_ref();
+		Ok(BumpBox::new_in(value, bump))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		alloc::{string::String, vec::Vec},
+		Compact, DecodeWithMemLimit, Encode, MemTrackingInput,
+	};
+
+	#[test]
+	fn arena_collections_decode_like_std_collections() {
+		let value = (vec![vec![1u8, 2, 3], vec![]], String::from("arena"), 5u64, vec![1u32, 2]);
+		let encoded = value.encode();
+
+		let bump = Bump::new();
+		let input = &mut &encoded[..];
+		let bytes = BumpVec::<&[u8]>::decode_in(input, &bump).unwrap();
+		assert_eq!(&bytes[..], &[&[1, 2, 3][..], &[]]);
+		assert_eq!(<&str>::decode_in(input, &bump), Ok("arena"));
+		assert_eq!(*BumpBox::<u64>::decode_in(input, &bump).unwrap(), 5);
+		assert_eq!(&BumpVec::<u32>::decode_in(input, &bump).unwrap()[..], &[1, 2]);
+		assert!(input.is_empty());
+	}
+
+	#[test]
+	fn arena_decode_rejects_invalid_input() {
+		let bump = Bump::new();
+		let encoded = (Compact(4u32), [0xffu8, 0xfe, 0, 0]).encode();
+		assert_eq!(
+			<&str>::decode_in(&mut &encoded[..], &bump),
+			Err("Invalid utf8 sequence".into())
+		);
+
+		// A length of `u32::MAX` with a few bytes of input.
+		let encoded = Compact(u32::MAX).encode();
+		assert!(<&[u8]>::decode_in(&mut &encoded[..], &bump).is_err());
+		assert!(BumpVec::<u8>::decode_in(&mut &encoded[..], &bump).is_err());
+		assert!(bump.allocated_bytes() < 1 << 20);
+	}
+
+	#[test]
+	fn arena_decode_tracks_memory() {
+		let encoded = vec![vec![0u8; 100]; 10].encode();
+		assert!(Vec::<Vec<u8>>::decode_with_mem_limit(&mut &encoded[..], 1000).is_err());
+
+		let bump = Bump::new();
+		let mut bytes = &encoded[..];
+		let mut input = MemTrackingInput::new(&mut bytes, 1000);
+		assert!(BumpVec::<&[u8]>::decode_in(&mut input, &bump).is_err());
+		let mut bytes = &encoded[..];
+		let mut input = MemTrackingInput::new(&mut bytes, 2000);
+		assert_eq!(BumpVec::<&[u8]>::decode_in(&mut input, &bump).unwrap().len(), 10);
+	}
+}
@@ -14,9 +14,11 @@
 
 //! `BitVec` specific serialization.
 
+use core::{mem, slice};
+
 use crate::{
-	codec::decode_vec_with_len, Compact, Decode, DecodeWithMemTracking, Encode, EncodeLike, Error,
-	Input, Output,
+	codec::{decode_len, encode_len_to, TypeInfo},
+	Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
 };
 use bitvec::{
 	boxed::BitBox, order::BitOrder, slice::BitSlice, store::BitStore, vec::BitVec, view::BitView,
@@ -25,11 +27,11 @@ use bitvec::{
 impl<O: BitOrder, T: BitStore + Encode> Encode for BitSlice<T, O> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		let bits = self.len();
-		assert!(
-			bits <= ARCH32BIT_BITSLICE_MAX_BITS,
-			"Attempted to encode a BitSlice with too many bits.",
-		);
-		Compact(bits as u32).encode_to(dest);
+		if bits > ARCH32BIT_BITSLICE_MAX_BITS {
+			return dest
+				.on_encode_error("Attempted to encode a BitSlice with too many bits.".into());
+		}
+		encode_len_to(dest, bits);
 
 		// Iterate over chunks
 		for chunk in self.chunks(core::mem::size_of::<T>() * 8) {
@@ -49,30 +51,72 @@ impl<O: BitOrder, T: BitStore + Encode> Encode for BitVec<T, O> {
 impl<O: BitOrder, T: BitStore + Encode> EncodeLike for BitVec<T, O> {}
 
 /// Equivalent of `BitStore::MAX_BITS` on 32bit machine.
-const ARCH32BIT_BITSLICE_MAX_BITS: usize = 0x1fff_ffff;
+pub(crate) const ARCH32BIT_BITSLICE_MAX_BITS: usize = 0x1fff_ffff;
 
 impl<O: BitOrder, T: BitStore + Decode> Decode for BitVec<T, O> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		<Compact<u32>>::decode(input).and_then(move |Compact(bits)| {
-			// Otherwise it is impossible to store it on 32bit machine.
-			if bits as usize > ARCH32BIT_BITSLICE_MAX_BITS {
-				return Err("Attempt to decode a BitVec with too many bits".into());
+		let bits = decode_len(input)?;
+		// Otherwise it is impossible to store it on 32bit machine.
+		if bits > ARCH32BIT_BITSLICE_MAX_BITS {
+			return Err("Attempt to decode a BitVec with too many bits".into());
+		}
+		decode_bit_vec_with_len(input, bits)
+	}
+}
+
+/// Decode a `BitVec` of `bits` bits, without its length prefix.
+///
+/// The elements are read directly into the storage of the `BitVec`, which is allocated in chunks
+/// of at most `max_preallocation` bytes, each reported to [`Input::on_before_alloc_mem`].
+fn decode_bit_vec_with_len<T: BitStore + Decode, O: BitOrder, I: Input>(
+	input: &mut I,
+	bits: usize,
+) -> Result<BitVec<T, O>, Error> {
+	let element_size = mem::size_of::<T>();
+	// Elements of unsigned integers are encoded as their bytes in `CodecConfig::byte_order`.
+	let read_raw =
+		matches!(T::TYPE_INFO, TypeInfo::U8 | TypeInfo::U16 | TypeInfo::U32 | TypeInfo::U64) &&
+			(input.config().byte_order.is_native() || element_size == 1);
+
+	// Check if there is enough data in the input buffer.
+	if read_raw {
+		if let Some(input_len) = input.remaining_len()? {
+			if input_len < bitvec::mem::elts::<T>(bits) * element_size {
+				return Err("Not enough data to decode BitVec".into());
 			}
-			let vec = decode_vec_with_len(input, bitvec::mem::elts::<T>(bits as usize))?;
+		}
+	}
 
-			let mut result = Self::try_from_vec(vec).map_err(|_| {
-				Error::from(
-					"UNEXPECTED ERROR: `bits` is less or equal to
-					`ARCH32BIT_BITSLICE_MAX_BITS`; So BitVec must be able to handle the number of
-					segment needed for `bits` to be represented; qed",
-				)
-			})?;
+	let chunk_bits = (input.config().max_preallocation / element_size)
+		.max(1)
+		.saturating_mul(bitvec::mem::bits_of::<T>());
+	let mut result = BitVec::<T, O>::new();
+	while result.len() < bits {
+		// All chunks but the last one are made of whole elements.
+		let start = result.as_raw_slice().len();
+		let chunk_bits = chunk_bits.min(bits - result.len());
+		input.on_before_alloc_mem(bitvec::mem::elts::<T>(chunk_bits) * element_size)?;
+		result.reserve_exact(chunk_bits);
+		result.resize(result.len() + chunk_bits, false);
 
-			assert!(bits as usize <= result.len());
-			result.truncate(bits as usize);
-			Ok(result)
-		})
+		let elements = &mut result.as_raw_mut_slice()[start..];
+		if read_raw {
+			// SAFETY: `T` is an unsigned integer, so any bytes are a valid value.
+			let bytes = unsafe {
+				slice::from_raw_parts_mut(
+					elements.as_mut_ptr().cast::<u8>(),
+					mem::size_of_val(elements),
+				)
+			};
+			input.read(bytes)?;
+		} else {
+			for element in elements {
+				*element = T::decode(input)?;
+			}
+		}
 	}
+
+	Ok(result)
 }
 
 impl<O: BitOrder, T: BitStore + Decode> DecodeWithMemTracking for BitVec<T, O> {}
@@ -96,7 +140,7 @@ impl<O: BitOrder, T: BitStore + Decode> DecodeWithMemTracking for BitBox<T, O> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{codec::MAX_PREALLOCATION, CompactLen};
+	use crate::{codec::MAX_PREALLOCATION, Compact, CompactLen};
 	use bitvec::{
 		bitvec,
 		order::{Lsb0, Msb0},
@@ -184,6 +228,33 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn bitvec_decode_respects_mem_limit() {
+		use crate::DecodeWithMemLimit;
+
+		let encoded = bitvec![u32, Msb0; 1; MAX_PREALLOCATION * 8 + 3].encode();
+		let size = bitvec::mem::elts::<u32>(MAX_PREALLOCATION * 8 + 3) * 4;
+		// The limit is exclusive.
+		assert!(BitVec::<u32, Msb0>::decode_with_mem_limit(&mut &encoded[..], size).is_err());
+		let decoded =
+			BitVec::<u32, Msb0>::decode_with_mem_limit(&mut &encoded[..], size + 1).unwrap();
+		assert_eq!(decoded.len(), MAX_PREALLOCATION * 8 + 3);
+		assert!(decoded.capacity() <= size * 8);
+	}
+
+	#[test]
+	fn bitvec_decode_checks_remaining_len() {
+		let encoded = Compact(ARCH32BIT_BITSLICE_MAX_BITS as u32).encode();
+		assert_eq!(
+			BitVec::<u64, Lsb0>::decode(&mut &encoded[..]),
+			Err("Not enough data to decode BitVec".into())
+		);
+
+		let mut encoded = bitvec![u16, Lsb0; 1; 17].encode();
+		encoded.pop();
+		assert!(BitVec::<u16, Lsb0>::decode(&mut &encoded[..]).is_err());
+	}
+
 	#[test]
 	fn bitslice() {
 		let data: &[u8] = &[0x69];
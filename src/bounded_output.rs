@@ -0,0 +1,167 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An [`Output`] with a byte budget.
+
+use crate::{CodecConfig, Error, Output};
+
+/// A wrapper for `Output` which stops writing once a number of bytes is exceeded.
+///
+/// A write which doesn't fit in the remaining budget is dropped, as are all later writes, and
+/// [`Self::exceeded`] returns `true`. The inner output then contains a truncated encoding, which
+/// should be discarded.
+///
+/// As `Output` is infallible, the value may still be traversed until its end, but nothing is
+/// written to the inner output after the limit was exceeded. Collections stop encoding their
+/// elements once it is, see [`Output::is_exhausted`].
+///
+/// ```
+/// # use parity_scale_codec::{BoundedOutput, Encode};
+/// let mut block = Vec::new();
+/// for extrinsic in [vec![1u8; 10], vec![2; 100], vec![3; 5]] {
+///     let start = block.len();
+///     let mut output = BoundedOutput::new(&mut block, 32 - start);
+///     extrinsic.encode_to(&mut output);
+///     if output.exceeded() {
+///         block.truncate(start);
+///     }
+/// }
+/// assert_eq!(block, [vec![1u8; 10].encode(), vec![3u8; 5].encode()].concat());
+/// ```
+pub struct BoundedOutput<'a, O: Output + ?Sized> {
+	output: &'a mut O,
+	remaining: usize,
+	exceeded: bool,
+}
+
+impl<'a, O: Output + ?Sized> BoundedOutput<'a, O> {
+	/// Create a new `BoundedOutput` writing at most `limit` bytes to `output`.
+	pub fn new(output: &'a mut O, limit: usize) -> Self {
+		Self { output, remaining: limit, exceeded: false }
+	}
+
+	/// Whether a write didn't fit in the limit and was dropped.
+	pub fn exceeded(&self) -> bool {
+		self.exceeded
+	}
+
+	/// The number of bytes which can still be written.
+	///
+	/// This is `0` once the limit was exceeded.
+	pub fn remaining(&self) -> usize {
+		self.remaining
+	}
+}
+
+impl<O: Output + ?Sized> Output for BoundedOutput<'_, O> {
+	fn write(&mut self, bytes: &[u8]) {
+		if self.exceeded {
+			return;
+		}
+
+		if bytes.len() > self.remaining {
+			self.exceeded = true;
+			self.remaining = 0;
+		} else {
+			self.remaining -= bytes.len();
+			self.output.write(bytes);
+		}
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		if self.exceeded {
+			return;
+		}
+
+		if self.remaining == 0 {
+			self.exceeded = true;
+		} else {
+			self.remaining -= 1;
+			self.output.push_byte(byte);
+		}
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.output.config()
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		self.output.on_encode_error(error)
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.exceeded || self.output.is_exhausted()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		alloc::{string::String, vec::Vec},
+		Compact, Encode,
+	};
+
+	#[test]
+	fn bounded_output_writes_within_limit() {
+		let mut dest = Vec::new();
+		let mut output = BoundedOutput::new(&mut dest, 5);
+		(1u32, 2u8).encode_to(&mut output);
+		assert!(!output.exceeded());
+		assert_eq!(output.remaining(), 0);
+		assert_eq!(dest, [1, 0, 0, 0, 2]);
+	}
+
+	#[test]
+	fn bounded_output_drops_writes_after_limit() {
+		let mut dest = Vec::new();
+		let mut output = BoundedOutput::new(&mut dest, 5);
+		(1u32, 2u16, 3u8).encode_to(&mut output);
+		assert!(output.exceeded());
+		assert_eq!(output.remaining(), 0);
+		// The `u8` would fit, but is dropped as well.
+		assert_eq!(dest, [1, 0, 0, 0]);
+	}
+
+	#[test]
+	fn fits_within_works() {
+		let value = (vec![1u64; 10], Compact(1u32 << 20), String::from("value"));
+		let size = value.encoded_size();
+		assert!(value.fits_within(size));
+		assert!(value.fits_within(usize::MAX));
+		assert!(!value.fits_within(size - 1));
+		assert!(!value.fits_within(0));
+		assert!(().fits_within(0));
+	}
+
+	#[test]
+	fn fits_within_stops_at_the_limit() {
+		use core::cell::Cell;
+
+		struct Counted<'a>(&'a Cell<usize>);
+
+		impl Encode for Counted<'_> {
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				self.0.set(self.0.get() + 1);
+				dest.write(&[0; 4]);
+			}
+		}
+
+		let encoded = Cell::new(0);
+		let value = (0..1000).map(|_| Counted(&encoded)).collect::<Vec<_>>();
+		assert!(!value.fits_within(10));
+		// The length prefix and two elements fit, the third one exceeds the limit.
+		assert_eq!(encoded.get(), 3);
+	}
+}
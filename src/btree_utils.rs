@@ -21,7 +21,7 @@ const CAPACITY: usize = 2 * B - 1;
 const MIN_LEN_AFTER_SPLIT: usize = B - 1;
 
 /// Estimate the mem size of a btree.
-pub fn mem_size_of_btree<T>(len: u32) -> usize {
+pub fn mem_size_of_btree<T>(len: usize) -> usize {
 	if len == 0 {
 		return 0;
 	}
@@ -33,7 +33,7 @@ pub fn mem_size_of_btree<T>(len: u32) -> usize {
 	// An `InternalNode` additionally has 2*B `usize` overhead.
 	let internal_node_size = leaf_node_size + size_of::<[usize; 2 * B]>();
 	// A node can contain between B - 1 and 2*B - 1 elements. We assume 2/3 occupancy.
-	let num_nodes = (len as usize).saturating_div((CAPACITY + MIN_LEN_AFTER_SPLIT) * 2 / 3);
+	let num_nodes = len.saturating_div((CAPACITY + MIN_LEN_AFTER_SPLIT) * 2 / 3);
 
 	// If the tree has only one node, it's a leaf node.
 	if num_nodes == 0 {
@@ -99,10 +99,10 @@ mod test {
 			// For small number of elements, the differences between the expected size and
 			// the actual size can be higher.
 			if i > 100 {
-				let map_expected_size = mem_size_of_btree::<(u32, u32)>(map.len() as u32);
+				let map_expected_size = mem_size_of_btree::<(u32, u32)>(map.len());
 				check_btree_size(map_expected_size, map_actual_size.clone());
 
-				let set_expected_size = mem_size_of_btree::<u128>(set.len() as u32);
+				let set_expected_size = mem_size_of_btree::<u128>(set.len());
 				check_btree_size(set_expected_size, set_actual_size.clone());
 			}
 		}
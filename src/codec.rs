@@ -15,10 +15,11 @@
 //! Serialization.
 
 use core::{
+	cmp::{Ordering, Reverse},
 	convert::TryFrom,
 	fmt,
 	iter::FromIterator,
-	marker::PhantomData,
+	marker::{PhantomData, PhantomPinned},
 	mem,
 	mem::MaybeUninit,
 	num::{
@@ -26,6 +27,7 @@ use core::{
 		NonZeroU32, NonZeroU64, NonZeroU8,
 	},
 	ops::{Deref, Range, RangeInclusive},
+	pin::Pin,
 	time::Duration,
 };
 
@@ -33,6 +35,8 @@ use byte_slice_cast::{AsByteSlice, AsMutByteSlice, ToMutByteSlice};
 
 #[cfg(target_has_atomic = "ptr")]
 use crate::alloc::sync::Arc;
+#[cfg(target_has_atomic = "ptr")]
+use crate::Interner;
 use crate::{
 	alloc::{
 		borrow::{Cow, ToOwned},
@@ -42,23 +46,35 @@ use crate::{
 		string::String,
 		vec::Vec,
 	},
-	compact::Compact,
+	compact::{Compact, SMALL_COMPACTS},
 	encode_like::EncodeLike,
+	error::decode_bug,
 	mem_tracking::DecodeWithMemTracking,
-	DecodeFinished, Error,
+	normalize::DecodeThenNormalize,
+	BoundedOutput, ByteOrder, CodecConfig, CountedInput, DecodeFinished, DepthTrackingInput, Error,
+	MemTrackingInput, ProgressInput,
 };
 
 pub(crate) const MAX_PREALLOCATION: usize = 16 * 1024;
 const A_BILLION: u32 = 1_000_000_000;
 
+/// The interner of [`Input::interner`].
+///
+/// Interning needs atomic pointers, so on targets without them, this type can't be created and
+/// inputs never have an interner.
+#[cfg(not(target_has_atomic = "ptr"))]
+pub enum Interner {}
+
 /// Trait that allows reading of data into a slice.
 pub trait Input {
 	/// Should return the remaining length of the input data. If no information about the input
 	/// length is available, `None` should be returned.
 	///
-	/// The length is used to constrain the preallocation while decoding. Returning a garbage
-	/// length can open the doors for a denial of service attack to your application.
-	/// Otherwise, returning `None` can decrease the performance of your application.
+	/// The length is only a hint, used to reject inputs which are too short early. It is never
+	/// trusted for preallocation: collections are allocated in chunks of at most
+	/// [`CodecConfig::max_preallocation`] bytes as their data is read, so over-reporting the length
+	/// can't cause large allocations. Under-reporting it makes decoding fail, and returning `None`
+	/// can decrease the performance of your application.
 	fn remaining_len(&mut self) -> Result<Option<usize>, Error>;
 
 	/// Read the exact number of bytes required to fill the given buffer.
@@ -97,6 +113,124 @@ pub trait Input {
 		Ok(())
 	}
 
+	/// The configuration to decode with, see [`ConfiguredInput`](crate::ConfiguredInput).
+	///
+	/// Inputs wrapping another input should forward it.
+	fn config(&self) -> &CodecConfig {
+		&CodecConfig::DEFAULT
+	}
+
+	/// The interner sharing the decoded [`Interned`](crate::Interned) values, see
+	/// [`InterningInput`](crate::InterningInput).
+	///
+	/// Inputs wrapping another input should forward it.
+	fn interner(&mut self) -> Option<&mut Interner> {
+		None
+	}
+
+	/// Read all remaining bytes of the input, appending them to `into`.
+	///
+	/// The default implementation reads the bytes in chunks, like collections are allocated, while
+	/// [`Input::remaining_len`] reports more than a chunk, and the last chunk byte by byte. It
+	/// stops at a failed read after which the input reports no remaining bytes, so a remaining
+	/// length over-reporting the end of the input by less than a chunk is fine. It fails if the
+	/// length is unknown, inputs which don't know their length but can detect their end, like
+	/// readers, should override it.
+	///
+	/// On error, `into` is left with the bytes read before the failing read.
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		let max_chunk_len = self.config().max_preallocation.max(1);
+
+		loop {
+			let remaining = self
+				.remaining_len()?
+				.ok_or("Cannot read the rest of an input of unknown length")?;
+			if remaining == 0 {
+				return Ok(());
+			}
+
+			if remaining > max_chunk_len {
+				self.on_before_alloc_mem(max_chunk_len)?;
+				let start = into.len();
+				into.resize(start + max_chunk_len, 0);
+				if let Err(e) = self.read(&mut into[start..]) {
+					into.truncate(start);
+					return Err(e);
+				}
+				continue;
+			}
+
+			self.on_before_alloc_mem(remaining)?;
+			into.reserve(remaining);
+			for _ in 0..remaining {
+				match self.read_byte() {
+					Ok(byte) => into.push(byte),
+					Err(_) if self.remaining_len()? == Some(0) => return Ok(()),
+					Err(e) => return Err(e),
+				}
+			}
+		}
+	}
+
+	/// Skip the next `n` bytes of the input.
+	///
+	/// The default implementation reads them into a scratch buffer. Inputs which can move forward
+	/// without reading, like slices or files, should override it.
+	fn seek_forward(&mut self, mut n: usize) -> Result<(), Error> {
+		let mut buf = [0u8; 1024];
+		while n > 0 {
+			let len = n.min(buf.len());
+			self.read(&mut buf[..len])?;
+			n -= len;
+		}
+		Ok(())
+	}
+
+	/// Wrap this input in a [`MemTrackingInput`] limiting the heap memory used
+	/// while decoding to less than `mem_limit` bytes, see
+	/// [`DecodeWithMemLimit`](crate::DecodeWithMemLimit).
+	///
+	/// It can be chained with [`Input::with_depth_limit`] to limit both:
+	///
+	/// ```
+	/// # use parity_scale_codec::{Decode, Encode, Input};
+	/// let encoded = vec![vec![1u8, 2], vec![3]].encode();
+	///
+	/// let mut input = encoded.as_slice().with_mem_limit(1024).with_depth_limit(8);
+	/// let decoded = Vec::<Vec<u8>>::decode(&mut input).unwrap();
+	/// assert_eq!(decoded, [vec![1, 2], vec![3]]);
+	/// assert!(input.into_inner().used_mem() > 0);
+	///
+	/// let mut input = encoded.as_slice().with_mem_limit(8);
+	/// assert!(Vec::<Vec<u8>>::decode(&mut input).is_err());
+	/// ```
+	fn with_mem_limit<'a>(self, mem_limit: usize) -> MemTrackingInput<'a, Self, Self>
+	where
+		Self: Sized + 'a,
+	{
+		MemTrackingInput::new_owned(self, mem_limit)
+	}
+
+	/// Wrap this input in a [`DepthTrackingInput`] limiting the recursion depth
+	/// reached while decoding to `depth_limit`, see [`DecodeLimit`](crate::DecodeLimit).
+	///
+	/// It can be chained with [`Input::with_mem_limit`].
+	fn with_depth_limit<'a>(self, depth_limit: u32) -> DepthTrackingInput<'a, Self, Self>
+	where
+		Self: Sized + 'a,
+	{
+		DepthTrackingInput::new_owned(self, depth_limit)
+	}
+
+	/// Wrap this input in a [`ProgressInput`] calling `callback` with the number of bytes read
+	/// so far, every time another `every` bytes have been read.
+	fn with_progress<F: FnMut(u64)>(self, every: u64, callback: F) -> ProgressInput<Self, F>
+	where
+		Self: Sized,
+	{
+		ProgressInput::new(self, every, callback)
+	}
+
 	/// !INTERNAL USE ONLY!
 	///
 	/// Decodes a `bytes::Bytes`.
@@ -110,6 +244,61 @@ pub trait Input {
 	}
 }
 
+macro_rules! impl_input_for_dyn {
+	( $( $( #[$attr:meta] )* $type:ty ),* $(,)? ) => { $(
+		$( #[$attr] )*
+		impl Input for $type {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				(**self).remaining_len()
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				(**self).read(into)
+			}
+
+			fn read_byte(&mut self) -> Result<u8, Error> {
+				(**self).read_byte()
+			}
+
+			fn descend_ref(&mut self) -> Result<(), Error> {
+				(**self).descend_ref()
+			}
+
+			fn ascend_ref(&mut self) {
+				(**self).ascend_ref()
+			}
+
+			fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+				(**self).on_before_alloc_mem(size)
+			}
+
+			fn config(&self) -> &CodecConfig {
+				(**self).config()
+			}
+
+			fn interner(&mut self) -> Option<&mut Interner> {
+				(**self).interner()
+			}
+
+			fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+				(**self).read_remaining_into(into)
+			}
+
+			fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+				(**self).seek_forward(n)
+			}
+		}
+	)* }
+}
+
+impl_input_for_dyn!(
+	/// Allows decoding from a `&mut dyn Input`, e.g. in the non-generic functions generated by
+	/// `#[codec(dyn_input)]`.
+	&mut (dyn Input + '_),
+	/// Allows decoding from a boxed input, e.g. for a backend chosen at runtime.
+	Box<dyn Input + '_>,
+);
+
 impl<'a> Input for &'a [u8] {
 	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
 		Ok(Some(self.len()))
@@ -124,8 +313,26 @@ impl<'a> Input for &'a [u8] {
 		*self = &self[len..];
 		Ok(())
 	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		into.extend_from_slice(self);
+		*self = &[];
+		Ok(())
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		if n > self.len() {
+			return Err("Not enough data to fill buffer".into());
+		}
+		*self = &self[n..];
+		Ok(())
+	}
 }
 
+/// The error of reading past the end of an `std::io::Read`.
+#[cfg(feature = "std")]
+const IO_UNEXPECTED_EOF: &str = "io error: UnexpectedEof";
+
 #[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
 	fn from(err: std::io::Error) -> Self {
@@ -148,7 +355,7 @@ impl From<std::io::Error> for Error {
 			WriteZero => "io error: WriteZero".into(),
 			Interrupted => "io error: Interrupted".into(),
 			Other => "io error: Other".into(),
-			UnexpectedEof => "io error: UnexpectedEof".into(),
+			UnexpectedEof => IO_UNEXPECTED_EOF.into(),
 			_ => "io error: Unknown".into(),
 		}
 	}
@@ -167,6 +374,60 @@ impl<R: std::io::Read> Input for IoReader<R> {
 	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
 		self.0.read_exact(into).map_err(Into::into)
 	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		self.0.read_to_end(into).map(drop).map_err(Into::into)
+	}
+}
+
+/// Wrapper that implements `Read` for any `Input`, the counterpart of [`IoReader`].
+///
+/// Code written against `std::io::Read` can read from an `Input` through it. As `Input` only reads
+/// exact amounts, each read is limited to the bytes known to be left. If the input doesn't know
+/// its length, the buffer is filled byte by byte, until the input fails with the
+/// `UnexpectedEof` error of readers like [`IoReader`], which ends the input.
+#[cfg(feature = "std")]
+pub struct InputReader<I: Input>(pub I);
+
+#[cfg(feature = "std")]
+impl<I: Input> std::io::Read for InputReader<I> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if let Some(remaining) = self.0.remaining_len()? {
+			let len = remaining.min(buf.len());
+			self.0.read(&mut buf[..len])?;
+			return Ok(len);
+		}
+
+		for (read, byte) in buf.iter_mut().enumerate() {
+			match self.0.read_byte() {
+				Ok(b) => *byte = b,
+				// The error is returned by the next call, unless it was the end of the input.
+				Err(_) if read > 0 => return Ok(read),
+				Err(e) if e.root_cause() == IO_UNEXPECTED_EOF => return Ok(0),
+				Err(e) => return Err(e.into()),
+			}
+		}
+		Ok(buf.len())
+	}
+}
+
+/// Wrapper that implements `Write` for any `Output`, the counterpart of the implementation of
+/// `Output` for `std::io::Write`.
+///
+/// Code written against `std::io::Write` can write to an `Output` through it. Writes never fail.
+#[cfg(feature = "std")]
+pub struct OutputWriter<O: Output>(pub O);
+
+#[cfg(feature = "std")]
+impl<O: Output> std::io::Write for OutputWriter<O> {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.0.write(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
 }
 
 /// Trait that allows writing of data.
@@ -178,6 +439,31 @@ pub trait Output {
 	fn push_byte(&mut self, byte: u8) {
 		self.write(&[byte]);
 	}
+
+	/// The configuration to encode with, see [`ConfiguredOutput`](crate::ConfiguredOutput).
+	///
+	/// Outputs wrapping another output should forward it.
+	fn config(&self) -> &CodecConfig {
+		&CodecConfig::DEFAULT
+	}
+
+	/// Called when a value can't be encoded, e.g. a collection with more than `u32::MAX`
+	/// elements, or when writing fails.
+	///
+	/// Panics by default. Outputs which keep the error, e.g.
+	/// [`FallibleOutput`](crate::FallibleOutput), override it, and outputs wrapping another
+	/// output should forward it.
+	fn on_encode_error(&mut self, error: Error) {
+		panic!("{}", error);
+	}
+
+	/// Whether the output won't take any more bytes, e.g. because a limit was exceeded.
+	///
+	/// Encoders of collections check it between their elements to stop early, as whatever they
+	/// write is dropped anyway. Outputs wrapping another output should forward it.
+	fn is_exhausted(&self) -> bool {
+		false
+	}
 }
 
 #[cfg(not(feature = "std"))]
@@ -187,18 +473,123 @@ impl Output for Vec<u8> {
 	}
 }
 
+/// Wrapper that implements [`Output`] for a `bytes::BytesMut`.
+///
+/// `BytesMut` can't implement `Output` directly, as this would conflict with the implementation
+/// for `std::io::Write` types when the `std` feature is enabled.
+///
+/// ```
+/// # use parity_scale_codec::{BytesMutOutput, Encode};
+/// let mut output = BytesMutOutput(bytes::BytesMut::new());
+/// (1u8, 2u16).encode_to(&mut output);
+/// assert_eq!(output.0.freeze(), [1u8, 2, 0][..]);
+/// ```
+#[cfg(feature = "bytes")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BytesMutOutput(pub bytes::BytesMut);
+
+#[cfg(feature = "bytes")]
+impl Output for BytesMutOutput {
+	fn write(&mut self, bytes: &[u8]) {
+		self.0.extend_from_slice(bytes)
+	}
+}
+
 #[cfg(feature = "std")]
 impl<W: std::io::Write> Output for W {
 	fn write(&mut self, bytes: &[u8]) {
-		(self as &mut dyn std::io::Write)
-			.write_all(bytes)
-			.expect("Codec outputs are infallible");
+		if let Err(e) = (self as &mut dyn std::io::Write).write_all(bytes) {
+			self.on_encode_error(e.into());
+		}
+	}
+}
+
+/// An [`Output`] writing to a `std::io::Write`, which keeps the first error instead of panicking.
+///
+/// Like `EmbeddedIoWriter`, all writes after an error are skipped and the error is returned from
+/// `finish`. Values which can't be encoded are reported the same way, see
+/// [`Output::on_encode_error`].
+///
+/// ```
+/// # use parity_scale_codec::{Encode, IoWriter};
+/// let mut output = IoWriter::new(Vec::new());
+/// 42u32.encode_to(&mut output);
+/// assert_eq!(output.finish().unwrap(), [42, 0, 0, 0]);
+/// ```
+#[cfg(feature = "std")]
+pub struct IoWriter<W: std::io::Write> {
+	writer: W,
+	error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoWriter<W> {
+	/// Write to `writer`.
+	pub fn new(writer: W) -> Self {
+		IoWriter { writer, error: None }
+	}
+
+	/// Flush the writer and return it, or the first error that occurred while writing.
+	pub fn finish(mut self) -> std::io::Result<W> {
+		match self.error {
+			Some(e) => Err(e),
+			None => self.writer.flush().map(|()| self.writer),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Output for IoWriter<W> {
+	fn write(&mut self, bytes: &[u8]) {
+		if self.error.is_none() {
+			self.error = self.writer.write_all(bytes).err();
+		}
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		if self.error.is_none() {
+			self.error = Some(error.into());
+		}
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.error.is_some()
+	}
+}
+
+/// Allows encoding to a boxed output, e.g. for a backend chosen at runtime.
+///
+/// Only implemented for trait objects, as with `std` a boxed writer is an output through
+/// `std::io::Write`.
+impl Output for Box<dyn Output + '_> {
+	fn write(&mut self, bytes: &[u8]) {
+		(**self).write(bytes)
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		(**self).push_byte(byte)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		(**self).config()
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		(**self).on_encode_error(error)
+	}
+
+	fn is_exhausted(&self) -> bool {
+		(**self).is_exhausted()
 	}
 }
 
 /// !INTERNAL USE ONLY!
 ///
 /// This enum provides type information to optimize encoding/decoding by doing fake specialization.
+///
+/// It is not part of the public API. Types whose encoding is their memory representation can
+/// implement [`TriviallyCodable`](crate::TriviallyCodable) instead, to be encoded and decoded in
+/// bulk by collections using [`trivially_codable`](crate::trivially_codable).
 #[doc(hidden)]
 #[non_exhaustive]
 pub enum TypeInfo {
@@ -249,7 +640,21 @@ pub trait Encode {
 		r
 	}
 
+	/// Convert self to `bytes::Bytes`.
+	///
+	/// The encoding is written to a `bytes::BytesMut` with the capacity given by `size_hint`, which
+	/// is then frozen without copying.
+	#[cfg(feature = "bytes")]
+	fn encode_to_bytes(&self) -> bytes::Bytes {
+		let mut dest = BytesMutOutput(bytes::BytesMut::with_capacity(self.size_hint()));
+		self.encode_to(&mut dest);
+		dest.0.freeze()
+	}
+
 	/// Convert self to a slice and then invoke the given closure with it.
+	///
+	/// Like [`Encode::encode`], this encodes with the default [`CodecConfig`], e.g. integers are
+	/// little endian. Encode to an [`Output`] with another config with [`Encode::encode_to`].
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
 		f(&self.encode())
 	}
@@ -264,15 +669,48 @@ pub trait Encode {
 	/// allocations inside the output. However, this can not prevent allocations that some types are
 	/// doing inside their own encoding.
 	fn encoded_size(&self) -> usize {
-		let mut size_tracker = SizeTracker { written: 0 };
-		self.encode_to(&mut size_tracker);
+		self.encoded_size_with_config(&CodecConfig::DEFAULT)
+	}
+
+	/// Calculates the size of the encoding with `config`, like [`Encode::encoded_size`].
+	fn encoded_size_with_config(&self, config: &CodecConfig) -> usize {
+		let mut size_tracker = SizeTracker { written: 0, config: *config };
+		measure(|| self.encode_to(&mut size_tracker));
 		size_tracker.written
 	}
+
+	/// Whether the encoding of self is at most `limit` bytes long.
+	///
+	/// This encodes to a [`BoundedOutput`], so nothing is written or allocated by the output, and
+	/// collections stop encoding their elements once the limit is exceeded. Types knowing their
+	/// encoded size cheaply can override this.
+	fn fits_within(&self, limit: usize) -> bool {
+		let mut size_tracker = SizeTracker { written: 0, config: CodecConfig::DEFAULT };
+		let mut output = BoundedOutput::new(&mut size_tracker, limit);
+		measure(|| self.encode_to(&mut output));
+		!output.exceeded()
+	}
+
+	/// Convert self to an owned vector, encoding with `config` instead of SCALE.
+	fn encode_with_config(&self, config: &CodecConfig) -> Vec<u8> {
+		crate::config::encode_with_config(self, config)
+	}
+}
+
+/// Run `f`, which only encodes to measure the encoded size, so it isn't recorded by
+/// `enum_stats`.
+#[inline]
+fn measure<R>(f: impl FnOnce() -> R) -> R {
+	#[cfg(feature = "enum-stats")]
+	return crate::enum_stats::without_recording(f);
+	#[cfg(not(feature = "enum-stats"))]
+	f()
 }
 
 // Implements `Output` and only keeps track of the number of written bytes
 struct SizeTracker {
 	written: usize,
+	config: CodecConfig,
 }
 
 impl Output for SizeTracker {
@@ -283,10 +721,16 @@ impl Output for SizeTracker {
 	fn push_byte(&mut self, _byte: u8) {
 		self.written += 1;
 	}
+
+	fn config(&self) -> &CodecConfig {
+		&self.config
+	}
 }
 
 /// Trait that allows the length of a collection to be read, without having
 /// to read and decode the entire elements.
+///
+/// The length prefix is read as encoded with the default [`CodecConfig`].
 pub trait DecodeLength {
 	/// Return the number of elements in `self_encoded`.
 	fn len(self_encoded: &[u8]) -> Result<usize, Error>;
@@ -382,6 +826,22 @@ impl<T: ?Sized> WrapperTypeEncode for &mut T {}
 impl<T: ?Sized + Encode> EncodeLike for &mut T {}
 impl<T: Encode> EncodeLike<T> for &mut T {}
 impl<T: Encode> EncodeLike<&mut T> for T {}
+impl<T: Encode> EncodeLike<&T> for &mut T {}
+impl<T: Encode> EncodeLike<&mut T> for &T {}
+impl<T: Encode> EncodeLike<T> for &&mut T {}
+impl<T: Encode> EncodeLike<&&mut T> for T {}
+
+// A pinned pointer is encoded like the value it points to.
+impl<P: Deref> WrapperTypeEncode for Pin<P> {}
+impl<P: Deref> EncodeLike for Pin<P> where P::Target: Encode {}
+impl<T: Encode> EncodeLike<T> for Pin<&T> {}
+impl<T: Encode> EncodeLike<Pin<&T>> for T {}
+impl<T: Encode> EncodeLike<T> for Pin<&mut T> {}
+impl<T: Encode> EncodeLike<Pin<&mut T>> for T {}
+impl<T: Encode> EncodeLike<T> for Pin<Box<T>> {}
+impl<T: Encode> EncodeLike<Pin<Box<T>>> for T {}
+impl<T: Encode> EncodeLike<Box<T>> for Pin<Box<T>> {}
+impl<T: Encode> EncodeLike<Pin<Box<T>>> for Box<T> {}
 
 impl<'a, T: ToOwned + ?Sized> WrapperTypeEncode for Cow<'a, T> {}
 impl<'a, T: ToOwned + Encode + ?Sized> EncodeLike for Cow<'a, T> {}
@@ -421,9 +881,9 @@ mod feature_wrapper_bytes {
 }
 
 #[cfg(feature = "bytes")]
-struct BytesCursor {
-	bytes: bytes::Bytes,
-	position: usize,
+pub(crate) struct BytesCursor {
+	pub(crate) bytes: bytes::Bytes,
+	pub(crate) position: usize,
 }
 
 #[cfg(feature = "bytes")]
@@ -442,8 +902,17 @@ impl Input for BytesCursor {
 		Ok(())
 	}
 
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		if n > self.bytes.len() - self.position {
+			return Err("Not enough data to fill buffer".into());
+		}
+
+		self.position += n;
+		Ok(())
+	}
+
 	fn scale_internal_decode_bytes(&mut self) -> Result<bytes::Bytes, Error> {
-		let length = <Compact<u32>>::decode(self)?.0 as usize;
+		let length = decode_len(self)?;
 
 		bytes::Buf::advance(&mut self.bytes, self.position);
 		self.position = 0;
@@ -595,6 +1064,31 @@ impl<T> WrapperTypeDecode for Box<T> {
 
 impl<T: DecodeWithMemTracking> DecodeWithMemTracking for Box<T> {}
 
+/// Decode the `T` of an `Rc<T>` or `Arc<T>` directly into its allocation, so that a big `T`, e.g.
+/// a large array, neither ends up on the stack nor is copied.
+#[cfg(new_uninit)]
+macro_rules! decode_shared_in_place {
+	($shared:ident, $input:expr) => {{
+		let input = $input;
+		input.descend_ref()?;
+		input.on_before_alloc_mem(mem::size_of::<T>())?;
+
+		// Only used with Rust 1.82 or later.
+		#[allow(clippy::incompatible_msrv)]
+		let mut shared = $shared::<T>::new_uninit();
+		let Some(uninit) = $shared::get_mut(&mut shared) else {
+			decode_bug!("Not shared yet; qed")
+		};
+		T::decode_into(input, uninit)?;
+
+		input.ascend_ref();
+		#[allow(clippy::incompatible_msrv)]
+		// SAFETY: `decode_into` succeeded, so the value is initialized.
+		let shared = unsafe { shared.assume_init() };
+		Ok(shared)
+	}};
+}
+
 impl<T> WrapperTypeDecode for Rc<T> {
 	type Wrapped = T;
 
@@ -602,14 +1096,32 @@ impl<T> WrapperTypeDecode for Rc<T> {
 	where
 		Self::Wrapped: Decode,
 	{
-		// TODO: This is inefficient; use `Rc::new_uninit` once that's stable.
+		#[cfg(new_uninit)]
+		{
+			decode_shared_in_place!(Rc, input)
+		}
+		#[cfg(not(new_uninit))]
 		Box::<T>::decode(input).map(|output| output.into())
 	}
 }
 
-// `Rc<T>` uses `Box::<T>::decode()` internally, so it supports `DecodeWithMemTracking`.
+// `Rc<T>` tracks the memory of `T` like `Box<T>` does, so it supports `DecodeWithMemTracking`.
 impl<T: DecodeWithMemTracking> DecodeWithMemTracking for Rc<T> {}
 
+// `Pin<Box<T>>` is decoded as `Box<T>`, which is then pinned.
+impl<T> WrapperTypeDecode for Pin<Box<T>> {
+	type Wrapped = Box<T>;
+
+	fn decode_wrapped<I: Input>(input: &mut I) -> Result<Self, Error>
+	where
+		Self::Wrapped: Decode,
+	{
+		Box::<T>::decode(input).map(Box::into_pin)
+	}
+}
+
+impl<T: DecodeWithMemTracking> DecodeWithMemTracking for Pin<Box<T>> {}
+
 #[cfg(target_has_atomic = "ptr")]
 impl<T> WrapperTypeDecode for Arc<T> {
 	type Wrapped = T;
@@ -618,12 +1130,17 @@ impl<T> WrapperTypeDecode for Arc<T> {
 	where
 		Self::Wrapped: Decode,
 	{
-		// TODO: This is inefficient; use `Arc::new_uninit` once that's stable.
+		#[cfg(new_uninit)]
+		{
+			decode_shared_in_place!(Arc, input)
+		}
+		#[cfg(not(new_uninit))]
 		Box::<T>::decode(input).map(|output| output.into())
 	}
 }
 
-// `Arc<T>` uses `Box::<T>::decode()` internally, so it supports `DecodeWithMemTracking`.
+// `Arc<T>` tracks the memory of `T` like `Box<T>` does, so it supports `DecodeWithMemTracking`.
+#[cfg(target_has_atomic = "ptr")]
 impl<T: DecodeWithMemTracking> DecodeWithMemTracking for Arc<T> {}
 
 impl<T, X> Decode for X
@@ -669,6 +1186,59 @@ pub trait EncodeAsRef<'a, T: 'a> {
 	type RefType: Encode + From<&'a T>;
 }
 
+/// Something that can be decoded and then converted into `T`, where the conversion may fail.
+///
+/// This is the decoding counterpart of [`EncodeAsRef`] for fields with
+/// `#[codec(decoded_via = "Type")]`: the field is encoded as `Type` through
+/// `EncodeAsRef<'_, FieldType>`, and decoded by decoding a `Type` and converting it. Unlike the
+/// `Into` conversion of `#[codec(encoded_as)]`, the conversion can reject values which don't
+/// represent a valid `T`.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// # use parity_scale_codec::{decode_prefix, Decode, DecodeAndConvert, Encode, EncodeAsRef, Error};
+/// /// A percentage in `0..=100`.
+/// struct Percent(u8);
+///
+/// /// The encoding of a `Percent`.
+/// #[derive(Encode, Decode)]
+/// struct RawPercent(u8);
+///
+/// impl From<&Percent> for RawPercent {
+///     fn from(p: &Percent) -> Self {
+///         RawPercent(p.0)
+///     }
+/// }
+///
+/// impl EncodeAsRef<'_, Percent> for RawPercent {
+///     type RefType = RawPercent;
+/// }
+///
+/// impl DecodeAndConvert<Percent> for RawPercent {
+///     fn convert(self) -> Result<Percent, Error> {
+///         if self.0 <= 100 {
+///             Ok(Percent(self.0))
+///         } else {
+///             Err("Percentage is above 100".into())
+///         }
+///     }
+/// }
+///
+/// #[derive(Encode, Decode)]
+/// struct Fee {
+///     #[codec(decoded_via = "RawPercent")]
+///     percent: Percent,
+/// }
+///
+/// assert_eq!(Fee { percent: Percent(5) }.encode(), [5]);
+/// assert!(decode_prefix::<Fee>(&[101]).is_err());
+/// # }
+/// ```
+pub trait DecodeAndConvert<T>: Decode {
+	/// Convert the decoded value into `T`, or return an error if it doesn't represent a valid `T`.
+	fn convert(self) -> Result<T, Error>;
+}
+
 impl<T: Encode, E: Encode> Encode for Result<T, E> {
 	fn size_hint(&self) -> usize {
 		1 + match *self {
@@ -845,12 +1415,15 @@ pub(crate) fn encode_slice_no_len<T: Encode, W: Output + ?Sized>(slice: &[T], de
 			$dest.write(&typed)
 		}};
 		( $ty:ty, $slice:ident, $dest:ident ) => {{
-			if cfg!(target_endian = "little") {
+			if $dest.config().byte_order.is_native() {
 				let typed = unsafe { mem::transmute::<&[T], &[$ty]>(&$slice[..]) };
 				$dest.write(<[$ty] as AsByteSlice<$ty>>::as_byte_slice(typed))
 			} else {
 				for item in $slice.iter() {
-					item.encode_to(dest);
+					if $dest.is_exhausted() {
+						return;
+					}
+					item.encode_to($dest);
 				}
 			}
 		}};
@@ -861,6 +1434,9 @@ pub(crate) fn encode_slice_no_len<T: Encode, W: Output + ?Sized>(slice: &[T], de
 		encode_to(slice, dest),
 		{
 			for item in slice.iter() {
+				if dest.is_exhausted() {
+					return;
+				}
 				item.encode_to(dest);
 			}
 		},
@@ -902,9 +1478,71 @@ const fn calculate_array_bytesize<T, const N: usize>() -> usize {
 	mem::size_of::<T>() * N
 }
 
+/// The number of bytes each element of an array of `T` must consume when decoding from `input`,
+/// if it decodes arrays strictly, see [`CodecConfig::strict_arrays`].
+pub(crate) fn strict_element_size<T: Decode, I: Input>(input: &I) -> Option<usize> {
+	if input.config().strict_arrays {
+		T::encoded_fixed_size()
+	} else {
+		None
+	}
+}
+
+/// Check that the array element `index` consumed its fixed encoded size.
+pub(crate) fn check_element_size(index: usize, size: usize, consumed: u64) -> Result<(), Error> {
+	if consumed == size as u64 {
+		return Ok(());
+	}
+
+	let err = Error::from("Array element did not consume its fixed encoded size");
+	#[cfg(feature = "chain-error")]
+	let err = {
+		use core::fmt::Write;
+
+		let mut desc = String::new();
+		let _ = write!(
+			desc,
+			"Element {index} consumed {consumed} bytes, but its fixed encoded size is {size}",
+		);
+		err.chain(desc)
+	};
+	#[cfg(not(feature = "chain-error"))]
+	let _ = index;
+	Err(err)
+}
+
+/// Arrays bigger than this (in bytes) are decoded on the heap by `Decode::decode`, to not need
+/// stack space for both the array being decoded and the returned one.
+const ARRAY_HEAP_DECODE_THRESHOLD: usize = 16 * 1024;
+
+/// Decode an array into a heap allocation, and move it out of it once it's fully decoded.
+fn decode_array_on_heap<T: Decode, I: Input, const N: usize>(
+	input: &mut I,
+) -> Result<[T; N], Error> {
+	input.on_before_alloc_mem(mem::size_of::<[T; N]>())?;
+	let mut vec = Vec::<T>::with_capacity(N);
+
+	// SAFETY: The vector has a capacity of at least `N` elements, so its buffer is valid for
+	//         writes of `[T; N]`, and `MaybeUninit` doesn't need to be initialized.
+	let dst: &mut MaybeUninit<[T; N]> = unsafe { &mut *vec.as_mut_ptr().cast() };
+	<[T; N]>::decode_into(input, dst)?;
+
+	// SAFETY: `decode_into` succeeded, so the first `N` elements are initialized.
+	unsafe { vec.set_len(N) };
+
+	let Ok(boxed) = Box::<[T; N]>::try_from(vec.into_boxed_slice()) else {
+		decode_bug!("The vector has exactly `N` elements; qed")
+	};
+	Ok(*boxed)
+}
+
 impl<T: Decode, const N: usize> Decode for [T; N] {
 	#[inline(always)]
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		if calculate_array_bytesize::<T, N>() > ARRAY_HEAP_DECODE_THRESHOLD {
+			return decode_array_on_heap(input);
+		}
+
 		let mut array = MaybeUninit::uninit();
 		Self::decode_into(input, &mut array)?;
 
@@ -927,7 +1565,7 @@ impl<T: Decode, const N: usize> Decode for [T; N] {
 			TypeInfo::U128 |
 			TypeInfo::I128 |
 			TypeInfo::F32 |
-			TypeInfo::F64 => cfg!(target_endian = "little"),
+			TypeInfo::F64 => input.config().byte_order.is_native(),
 			TypeInfo::Unknown => false,
 		};
 
@@ -999,9 +1637,19 @@ impl<T: Decode, const N: usize> Decode for [T; N] {
 
 		let mut state = State { count: 0, slice };
 
-		while state.count < state.slice.len() {
-			T::decode_into(input, &mut state.slice[state.count])?;
-			state.count += 1;
+		if let Some(size) = strict_element_size::<T, I>(input) {
+			while state.count < state.slice.len() {
+				let mut counted = CountedInput::new(&mut *input);
+				T::decode_into(&mut counted, &mut state.slice[state.count])?;
+				let consumed = counted.count();
+				state.count += 1;
+				check_element_size(state.count - 1, size, consumed)?;
+			}
+		} else {
+			while state.count < state.slice.len() {
+				T::decode_into(input, &mut state.slice[state.count])?;
+				state.count += 1;
+			}
 		}
 
 		// We've successfully read everything, so disarm the `Drop` impl.
@@ -1012,19 +1660,19 @@ impl<T: Decode, const N: usize> Decode for [T; N] {
 	}
 
 	fn skip<I: Input>(input: &mut I) -> Result<(), Error> {
-		if Self::encoded_fixed_size().is_some() {
-			// Should skip the bytes, but Input does not support skip.
-			for _ in 0..N {
-				T::skip(input)?;
-			}
-		} else {
-			Self::decode(input)?;
+		if matches!(<T as Decode>::TYPE_INFO, TypeInfo::U8 | TypeInfo::I8) {
+			return input.seek_forward(N);
+		}
+
+		// Skipping the elements one by one avoids materializing the whole array.
+		for _ in 0..N {
+			T::skip(input)?;
 		}
 		Ok(())
 	}
 
 	fn encoded_fixed_size() -> Option<usize> {
-		Some(<T as Decode>::encoded_fixed_size()? * N)
+		<T as Decode>::encoded_fixed_size()?.checked_mul(N)
 	}
 }
 
@@ -1082,6 +1730,11 @@ impl Decode for String {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
 		Self::from_utf8(Vec::decode(input)?).map_err(|_| "Invalid utf8 sequence".into())
 	}
+
+	/// Skips the bytes of the string without checking that they are valid UTF-8.
+	fn skip<I: Input>(input: &mut I) -> Result<(), Error> {
+		<Vec<u8>>::skip(input)
+	}
 }
 
 impl DecodeWithMemTracking for String {}
@@ -1091,6 +1744,10 @@ pub(crate) fn compact_encode_len_to<W: Output + ?Sized>(
 	dest: &mut W,
 	len: usize,
 ) -> Result<(), Error> {
+	if let Some(&byte) = SMALL_COMPACTS.get(len) {
+		dest.push_byte(byte);
+		return Ok(());
+	}
 	if len > u32::MAX as usize {
 		return Err("Attempted to serialize a collection with too many elements.".into());
 	}
@@ -1099,44 +1756,84 @@ pub(crate) fn compact_encode_len_to<W: Output + ?Sized>(
 	Ok(())
 }
 
+/// Writes `len` to `dest` as the length prefix of a collection, see
+/// [`CodecConfig::length_prefix`].
+pub(crate) fn encode_len_to<W: Output + ?Sized>(dest: &mut W, len: usize) {
+	let length_prefix = dest.config().length_prefix;
+	length_prefix.encode_len(len, dest)
+}
+
+/// Reads the length prefix of a collection, see [`CodecConfig::length_prefix`].
+pub(crate) fn decode_len<I: Input>(input: &mut I) -> Result<usize, Error> {
+	let length_prefix = input.config().length_prefix;
+	length_prefix.decode_len(input)
+}
+
 impl<T: Encode> Encode for [T] {
 	fn size_hint(&self) -> usize {
 		mem::size_of::<u32>() + mem::size_of_val(self)
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+		encode_len_to(dest, self.len());
 
 		encode_slice_no_len(self, dest)
 	}
 }
 
-fn decode_vec_chunked<T, I: Input, F>(
+pub(crate) fn decode_vec_chunked<T, I: Input, F>(
 	input: &mut I,
 	len: usize,
-	mut decode_chunk: F,
+	decode_chunk: F,
 ) -> Result<Vec<T>, Error>
 where
 	F: FnMut(&mut I, &mut Vec<T>, usize) -> Result<(), Error>,
 {
-	const { assert!(MAX_PREALLOCATION >= mem::size_of::<T>()) }
+	let mut decoded_vec = vec![];
+	decode_vec_chunked_into(input, len, &mut decoded_vec, decode_chunk)?;
+	Ok(decoded_vec)
+}
+
+/// The number of items of type `T` allocated at once while decoding a collection, so that at most
+/// [`CodecConfig::max_preallocation`] bytes are allocated before the items are read.
+fn preallocation_chunk_len<T>(config: &CodecConfig) -> usize {
+	const { assert!(MAX_PREALLOCATION >= mem::size_of::<T>()) }
 	// we have to account for the fact that `mem::size_of::<T>` can be 0 for types like `()`
 	// for example.
-	let chunk_len = MAX_PREALLOCATION.checked_div(mem::size_of::<T>()).unwrap_or(usize::MAX);
+	config
+		.max_preallocation
+		.checked_div(mem::size_of::<T>())
+		.unwrap_or(usize::MAX)
+		.max(1)
+}
+
+/// Like [`decode_vec_chunked`], but appends the items to `decoded_vec`, only allocating when its
+/// spare capacity is too small for the next chunk.
+pub(crate) fn decode_vec_chunked_into<T, I: Input, F>(
+	input: &mut I,
+	len: usize,
+	decoded_vec: &mut Vec<T>,
+	mut decode_chunk: F,
+) -> Result<(), Error>
+where
+	F: FnMut(&mut I, &mut Vec<T>, usize) -> Result<(), Error>,
+{
+	let chunk_len = preallocation_chunk_len::<T>(input.config());
 
-	let mut decoded_vec = vec![];
 	let mut num_undecoded_items = len;
 	while num_undecoded_items > 0 {
 		let chunk_len = chunk_len.min(num_undecoded_items);
-		input.on_before_alloc_mem(chunk_len.saturating_mul(mem::size_of::<T>()))?;
-		decoded_vec.reserve_exact(chunk_len);
+		if decoded_vec.capacity() - decoded_vec.len() < chunk_len {
+			input.on_before_alloc_mem(chunk_len.saturating_mul(mem::size_of::<T>()))?;
+			decoded_vec.reserve_exact(chunk_len);
+		}
 
-		decode_chunk(input, &mut decoded_vec, chunk_len)?;
+		decode_chunk(input, decoded_vec, chunk_len)?;
 
 		num_undecoded_items -= chunk_len;
 	}
 
-	Ok(decoded_vec)
+	Ok(())
 }
 
 /// Create a `Vec<T>` by casting directly from a buffer of read `u8`s
@@ -1171,19 +1868,141 @@ where
 	})
 }
 
+/// The size of the buffer items of a fixed encoded size are read into, see
+/// [`decode_fixed_size_items`].
+const FIXED_SIZE_BUFFER: usize = 1024;
+
+/// The methods of [`Input`] a [`BufferInput`] forwards to the input the buffer was read from.
+///
+/// The input is kept as a trait object, so that decoding nested vectors from a `BufferInput`
+/// doesn't instantiate infinitely nested `BufferInput` types for recursive types.
+trait InputHooks {
+	fn hook_descend_ref(&mut self) -> Result<(), Error>;
+	fn hook_ascend_ref(&mut self);
+	fn hook_on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error>;
+	fn hook_config(&self) -> &CodecConfig;
+	fn hook_interner(&mut self) -> Option<&mut Interner>;
+}
+
+impl<I: Input> InputHooks for I {
+	fn hook_descend_ref(&mut self) -> Result<(), Error> {
+		Input::descend_ref(self)
+	}
+
+	fn hook_ascend_ref(&mut self) {
+		Input::ascend_ref(self)
+	}
+
+	fn hook_on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		Input::on_before_alloc_mem(self, size)
+	}
+
+	fn hook_config(&self) -> &CodecConfig {
+		Input::config(self)
+	}
+
+	fn hook_interner(&mut self) -> Option<&mut Interner> {
+		Input::interner(self)
+	}
+}
+
+/// An input reading from the bytes of a buffer, which forwards everything else to the input the
+/// buffer was read from.
+struct BufferInput<'a> {
+	bytes: &'a [u8],
+	input: &'a mut dyn InputHooks,
+}
+
+impl Input for BufferInput<'_> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(Some(self.bytes.len()))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.bytes.read(into)
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.hook_descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.hook_ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.hook_on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.hook_config()
+	}
+
+	fn interner(&mut self) -> Option<&mut Interner> {
+		self.input.hook_interner()
+	}
+}
+
+/// Decode `len` items of the fixed encoded size `size` to `decoded_vec`.
+///
+/// The encodings of as many items as fit are read into a buffer at once, and the items are decoded
+/// from the buffer, instead of making many small reads from the input, which is much faster for
+/// inputs with costly reads, e.g. an unbuffered file.
+fn decode_fixed_size_items<T, I>(
+	input: &mut I,
+	decoded_vec: &mut Vec<T>,
+	len: usize,
+	size: usize,
+) -> Result<(), Error>
+where
+	T: Decode,
+	I: Input,
+{
+	let mut buffer = [0; FIXED_SIZE_BUFFER];
+	let items_per_read = FIXED_SIZE_BUFFER / size;
+	let mut num_undecoded_items = len;
+	while num_undecoded_items > 0 {
+		let items = items_per_read.min(num_undecoded_items);
+		let bytes = &mut buffer[..items * size];
+		input.read(bytes)?;
+
+		let mut buffer_input = BufferInput { bytes, input };
+		for _ in 0..items {
+			decoded_vec.push(T::decode(&mut buffer_input)?);
+		}
+		if !buffer_input.bytes.is_empty() {
+			return Err("Decoded size of an item differs from its `encoded_fixed_size`".into());
+		}
+
+		num_undecoded_items -= items;
+	}
+	Ok(())
+}
+
 fn decode_vec_from_items<T, I>(input: &mut I, len: usize) -> Result<Vec<T>, Error>
 where
 	T: Decode,
 	I: Input,
 {
 	input.descend_ref()?;
-	let vec = decode_vec_chunked(input, len, |input, decoded_vec, chunk_len| {
-		for _ in 0..chunk_len {
-			decoded_vec.push(T::decode(input)?);
-		}
+	let vec = match T::encoded_fixed_size() {
+		// Inputs of a known length are usually in memory, where reading is cheap and copying the
+		// bytes to the buffer costs more than it saves.
+		Some(size)
+			if (1..=FIXED_SIZE_BUFFER / 4).contains(&size) &&
+				input.config().buffered_reads &&
+				input.remaining_len()?.is_none() =>
+			decode_vec_chunked(input, len, |input, decoded_vec, chunk_len| {
+				decode_fixed_size_items(input, decoded_vec, chunk_len, size)
+			})?,
+		_ => decode_vec_chunked(input, len, |input, decoded_vec, chunk_len| {
+			for _ in 0..chunk_len {
+				decoded_vec.push(T::decode(input)?);
+			}
 
-		Ok(())
-	})?;
+			Ok(())
+		})?,
+	};
 	input.ascend_ref();
 
 	Ok(vec)
@@ -1199,7 +2018,7 @@ pub fn decode_vec_with_len<T: Decode, I: Input>(
 ) -> Result<Vec<T>, Error> {
 	macro_rules! decode {
 		( $ty:ty, $input:ident, $len:ident ) => {{
-			if cfg!(target_endian = "little") || mem::size_of::<T>() == 1 {
+			if $input.config().byte_order.is_native() || mem::size_of::<T>() == 1 {
 				let vec = read_vec_from_u8s::<$ty, _>($input, $len)?;
 				Ok(unsafe { mem::transmute::<Vec<$ty>, Vec<T>>(vec) })
 			} else {
@@ -1224,8 +2043,16 @@ impl<T: EncodeLike<U>, U: Encode> EncodeLike<Vec<U>> for &[T] {}
 
 impl<T: Decode> Decode for Vec<T> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		<Compact<u32>>::decode(input)
-			.and_then(move |Compact(len)| decode_vec_with_len(input, len as usize))
+		decode_len(input).and_then(move |len| decode_vec_with_len(input, len))
+	}
+
+	fn skip<I: Input>(input: &mut I) -> Result<(), Error> {
+		let len = decode_len(input)?;
+		if matches!(T::TYPE_INFO, TypeInfo::U8 | TypeInfo::I8) {
+			input.seek_forward(len)
+		} else {
+			decode_vec_with_len::<T, I>(input, len).map(drop)
+		}
 	}
 }
 
@@ -1244,9 +2071,12 @@ macro_rules! impl_encode_for_collection {
 			}
 
 			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-				compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+				encode_len_to(dest, self.len());
 
 				for i in self.iter() {
+					if dest.is_exhausted() {
+						return;
+					}
 					i.encode_to(dest);
 				}
 			}
@@ -1268,7 +2098,7 @@ impl_encode_for_collection! {
 
 impl<K: Decode + Ord, V: Decode> Decode for BTreeMap<K, V> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		<Compact<u32>>::decode(input).and_then(move |Compact(len)| {
+		decode_len(input).and_then(move |len| {
 			input.descend_ref()?;
 			input.on_before_alloc_mem(super::btree_utils::mem_size_of_btree::<(K, V)>(len))?;
 			let result = Result::from_iter((0..len).map(|_| Decode::decode(input)));
@@ -1283,6 +2113,80 @@ impl<K: DecodeWithMemTracking, V: DecodeWithMemTracking> DecodeWithMemTracking f
 {
 }
 
+/// Encode the entries of `iter` like the `BTreeMap` collecting them, without building the map.
+///
+/// The entries are encoded in the order of their keys, with one entry per key: like
+/// `BTreeMap::from_iter`, the last value of a key is kept. This gives the canonical encoding of
+/// maps which aren't sorted, like a `HashMap`, which decodes as a `BTreeMap`:
+///
+/// ```
+/// # use std::collections::{BTreeMap, HashMap};
+/// # use parity_scale_codec::{encode_map_from_iter, Encode};
+/// let map = HashMap::from([(3u32, "c"), (1, "a"), (2, "b")]);
+///
+/// let mut encoded = Vec::new();
+/// encode_map_from_iter(map.iter(), &mut encoded);
+/// assert_eq!(encoded, map.into_iter().collect::<BTreeMap<_, _>>().encode());
+/// ```
+///
+/// The entries are encoded into one buffer and only their keys are kept to be sorted, so the keys
+/// should be references when they are expensive to move, like the items of `HashMap::iter`.
+pub fn encode_map_from_iter<K, V, I, W>(iter: I, dest: &mut W)
+where
+	K: Encode + Ord,
+	V: Encode,
+	I: IntoIterator<Item = (K, V)>,
+	W: Output + ?Sized,
+{
+	let iter = iter.into_iter();
+	let mut buffer = EntryBuffer { encoded: Vec::new(), dest };
+	let mut entries: Vec<(K, Range<usize>)> = Vec::with_capacity(iter.size_hint().0);
+	for (key, value) in iter {
+		let start = buffer.encoded.len();
+		key.encode_to(&mut buffer);
+		value.encode_to(&mut buffer);
+		entries.push((key, start..buffer.encoded.len()));
+	}
+	let EntryBuffer { encoded, dest } = buffer;
+	// The later entries of a key come first, as the last value of a key is kept.
+	entries.sort_unstable_by(|(a, a_range), (b, b_range)| {
+		a.cmp(b).then(b_range.start.cmp(&a_range.start))
+	});
+
+	let is_kept = |i: usize| i == 0 || entries[i - 1].0 != entries[i].0;
+	encode_len_to(dest, (0..entries.len()).filter(|i| is_kept(*i)).count());
+	for (i, (_, range)) in entries.iter().enumerate() {
+		if is_kept(i) {
+			dest.write(&encoded[range.clone()]);
+		}
+	}
+}
+
+/// The buffer of [`encode_map_from_iter`], encoding with the configuration of `dest` and reporting
+/// the encoding errors to it.
+struct EntryBuffer<'a, W: Output + ?Sized> {
+	encoded: Vec<u8>,
+	dest: &'a mut W,
+}
+
+impl<W: Output + ?Sized> Output for EntryBuffer<'_, W> {
+	fn write(&mut self, bytes: &[u8]) {
+		self.encoded.extend_from_slice(bytes)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.dest.config()
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		self.dest.on_encode_error(error)
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.dest.is_exhausted()
+	}
+}
+
 impl_encode_for_collection! {
 	BTreeSet { T: Ord } { LikeT }
 		{ T: EncodeLike<LikeT>, LikeT: Encode }
@@ -1290,7 +2194,7 @@ impl_encode_for_collection! {
 
 impl<T: Decode + Ord> Decode for BTreeSet<T> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		<Compact<u32>>::decode(input).and_then(move |Compact(len)| {
+		decode_len(input).and_then(move |len| {
 			input.descend_ref()?;
 			input.on_before_alloc_mem(super::btree_utils::mem_size_of_btree::<T>(len))?;
 			let result = Result::from_iter((0..len).map(|_| Decode::decode(input)));
@@ -1308,15 +2212,11 @@ impl_encode_for_collection! {
 
 impl<T: Decode> Decode for LinkedList<T> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		<Compact<u32>>::decode(input).and_then(move |Compact(len)| {
+		decode_len(input).and_then(move |len| {
 			input.descend_ref()?;
 			// We account for the size of the `prev` and `next` pointers of each list node,
 			// plus the decoded element.
-			input.on_before_alloc_mem((len as usize).saturating_mul(mem::size_of::<(
-				usize,
-				usize,
-				T,
-			)>()))?;
+			input.on_before_alloc_mem(len.saturating_mul(mem::size_of::<(usize, usize, T)>()))?;
 			let result = Result::from_iter((0..len).map(|_| Decode::decode(input)));
 			input.ascend_ref();
 			result
@@ -1350,7 +2250,7 @@ impl<T: Encode> Encode for VecDeque<T> {
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+		encode_len_to(dest, self.len());
 
 		let slices = self.as_slices();
 		encode_slice_no_len(slices.0, dest);
@@ -1358,9 +2258,108 @@ impl<T: Encode> Encode for VecDeque<T> {
 	}
 }
 
+/// Decode `len` items into a `VecDeque<T>`, allocating its buffer in chunks like
+/// [`decode_vec_chunked`].
+///
+/// `decode_chunk` appends the given number of items to the deque.
+fn decode_vec_deque_chunked<T, I: Input, F>(
+	input: &mut I,
+	len: usize,
+	mut decode_chunk: F,
+) -> Result<VecDeque<T>, Error>
+where
+	F: FnMut(&mut I, &mut VecDeque<T>, usize) -> Result<(), Error>,
+{
+	let chunk_len = preallocation_chunk_len::<T>(input.config());
+
+	let mut decoded = VecDeque::new();
+	let mut num_undecoded_items = len;
+	while num_undecoded_items > 0 {
+		let chunk_len = chunk_len.min(num_undecoded_items);
+		input.on_before_alloc_mem(chunk_len.saturating_mul(mem::size_of::<T>()))?;
+		decoded.reserve_exact(chunk_len);
+
+		decode_chunk(input, &mut decoded, chunk_len)?;
+
+		num_undecoded_items -= chunk_len;
+	}
+
+	Ok(decoded)
+}
+
+/// Decode a `VecDeque<T>` of `len` items (without a prepended len).
+///
+/// Like [`decode_vec_with_len`], primitive types are read as bytes when their encoding is their
+/// binary representation.
+fn decode_vec_deque_with_len<T: Decode, I: Input>(
+	input: &mut I,
+	len: usize,
+) -> Result<VecDeque<T>, Error> {
+	macro_rules! decode {
+		( $ty:ty, $input:ident, $len:ident ) => {{
+			if $input.config().byte_order.is_native() || mem::size_of::<T>() == 1 {
+				let deque = read_vec_deque_from_u8s::<$ty, _>($input, $len)?;
+				Ok(unsafe { mem::transmute::<VecDeque<$ty>, VecDeque<T>>(deque) })
+			} else {
+				decode_vec_deque_from_items::<T, _>($input, $len)
+			}
+		}};
+	}
+
+	with_type_info! {
+		<T as Decode>::TYPE_INFO,
+		decode(input, len),
+		{
+			decode_vec_deque_from_items::<T, _>(input, len)
+		},
+	}
+}
+
+/// Create a `VecDeque<T>` from the read bytes of its items, like [`read_vec_from_u8s`].
+fn read_vec_deque_from_u8s<T, I>(input: &mut I, len: usize) -> Result<VecDeque<T>, Error>
+where
+	T: ToMutByteSlice + Default + Clone,
+	I: Input,
+{
+	let byte_len = len
+		.checked_mul(mem::size_of::<T>())
+		.ok_or("Item is too big and cannot be allocated")?;
+
+	if let Some(input_len) = input.remaining_len()? {
+		if input_len < byte_len {
+			return Err("Not enough data to decode vector".into());
+		}
+	}
+
+	decode_vec_deque_chunked(input, len, |input, decoded, chunk_len| {
+		let decoded_len = decoded.len();
+		decoded.resize(decoded_len + chunk_len, T::default());
+		// A deque only grown at the back from empty is already contiguous.
+		let items = &mut decoded.make_contiguous()[decoded_len..];
+		input.read(items.as_mut_byte_slice())
+	})
+}
+
+fn decode_vec_deque_from_items<T: Decode, I: Input>(
+	input: &mut I,
+	len: usize,
+) -> Result<VecDeque<T>, Error> {
+	input.descend_ref()?;
+	let deque = decode_vec_deque_chunked(input, len, |input, decoded, chunk_len| {
+		for _ in 0..chunk_len {
+			decoded.push_back(T::decode(input)?);
+		}
+
+		Ok(())
+	})?;
+	input.ascend_ref();
+
+	Ok(deque)
+}
+
 impl<T: Decode> Decode for VecDeque<T> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		Ok(<Vec<T>>::decode(input)?.into())
+		decode_len(input).and_then(move |len| decode_vec_deque_with_len(input, len))
 	}
 }
 
@@ -1527,6 +2526,15 @@ macro_rules! impl_endians {
 				mem::size_of::<$t>()
 			}
 
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				match dest.config().byte_order {
+					ByteOrder::LittleEndian => dest.write(&self.to_le_bytes()),
+					ByteOrder::BigEndian => dest.write(&self.to_be_bytes()),
+				}
+			}
+
+			// Without an output, this is the encoding of the default config, which is little
+			// endian.
 			fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
 				let buf = self.to_le_bytes();
 				f(&buf[..])
@@ -1539,7 +2547,10 @@ macro_rules! impl_endians {
 			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
 				let mut buf = [0u8; mem::size_of::<$t>()];
 				input.read(&mut buf)?;
-				Ok(<$t>::from_le_bytes(buf))
+				match input.config().byte_order {
+					ByteOrder::LittleEndian => Ok(<$t>::from_le_bytes(buf)),
+					ByteOrder::BigEndian => Ok(<$t>::from_be_bytes(buf)),
+				}
 			}
 
 			fn encoded_fixed_size() -> Option<usize> {
@@ -1624,10 +2635,10 @@ impl Encode for Duration {
 	}
 }
 
-impl Decode for Duration {
-	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		let (secs, nanos) = <(u64, u32)>::decode(input)
-			.map_err(|e| e.chain("Could not decode `Duration(u64, u32)`"))?;
+impl DecodeThenNormalize for Duration {
+	type Raw = (u64, u32);
+
+	fn normalize((secs, nanos): (u64, u32)) -> Result<Self, Error> {
 		if nanos >= A_BILLION {
 			Err("Could not decode `Duration`: Number of nanoseconds should not be higher than 10^9.".into())
 		} else {
@@ -1636,10 +2647,106 @@ impl Decode for Duration {
 	}
 }
 
+impl Decode for Duration {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let raw = <(u64, u32)>::decode(input)
+			.map_err(|e| e.chain("Could not decode `Duration(u64, u32)`"))?;
+		Self::normalize(raw)
+	}
+}
+
 impl DecodeWithMemTracking for Duration {}
 
 impl EncodeLike for Duration {}
 
+impl<T: EncodeLike<U>, U: Encode> EncodeLike<Reverse<U>> for Reverse<T> {}
+
+impl<T: Encode> Encode for Reverse<T> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn encode(&self) -> Vec<u8> {
+		self.0.encode()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+
+	fn encoded_size(&self) -> usize {
+		self.0.encoded_size()
+	}
+}
+
+impl<T: Decode> Decode for Reverse<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		T::decode(input).map(Reverse)
+	}
+
+	fn skip<I: Input>(input: &mut I) -> Result<(), Error> {
+		T::skip(input)
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		T::encoded_fixed_size()
+	}
+}
+
+impl<T: DecodeWithMemTracking> DecodeWithMemTracking for Reverse<T> {}
+
+impl EncodeLike for Ordering {}
+
+/// `Ordering` is encoded like its `i8` representation, so `Less` is `0xff`.
+impl Encode for Ordering {
+	fn size_hint(&self) -> usize {
+		1
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		(*self as i8).using_encoded(f)
+	}
+}
+
+impl Decode for Ordering {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		match i8::decode(input)? {
+			-1 => Ok(Ordering::Less),
+			0 => Ok(Ordering::Equal),
+			1 => Ok(Ordering::Greater),
+			_ => Err("Invalid `Ordering` representation".into()),
+		}
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(1)
+	}
+}
+
+impl DecodeWithMemTracking for Ordering {}
+
+impl EncodeLike for PhantomPinned {}
+
+impl Encode for PhantomPinned {
+	fn encode_to<W: Output + ?Sized>(&self, _dest: &mut W) {}
+}
+
+impl Decode for PhantomPinned {
+	fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+		Ok(PhantomPinned)
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(0)
+	}
+}
+
+impl DecodeWithMemTracking for PhantomPinned {}
+
 impl<T> Encode for Range<T>
 where
 	T: Encode,
@@ -1692,6 +2799,42 @@ where
 
 impl<T: DecodeWithMemTracking> DecodeWithMemTracking for RangeInclusive<T> {}
 
+macro_rules! impl_range_len {
+	( $( $t:ty => $wide:ty ),* ) => { $(
+		/// The number of integers in the range, without decoding more than its bounds.
+		impl DecodeLength for Range<$t> {
+			fn len(mut self_encoded: &[u8]) -> Result<usize, Error> {
+				let (start, end) = <($t, $t)>::decode(&mut self_encoded)?;
+				if start >= end {
+					return Ok(0);
+				}
+				usize::try_from((end as $wide).wrapping_sub(start as $wide))
+					.map_err(|_| "Range length doesn't fit in usize".into())
+			}
+		}
+
+		/// The number of integers in the range, without decoding more than its bounds.
+		impl DecodeLength for RangeInclusive<$t> {
+			fn len(mut self_encoded: &[u8]) -> Result<usize, Error> {
+				let (start, end) = <($t, $t)>::decode(&mut self_encoded)?;
+				if start > end {
+					return Ok(0);
+				}
+				(end as $wide)
+					.wrapping_sub(start as $wide)
+					.checked_add(1)
+					.and_then(|len| usize::try_from(len).ok())
+					.ok_or_else(|| "Range length doesn't fit in usize".into())
+			}
+		}
+	)* }
+}
+
+impl_range_len!(
+	u8 => u128, u16 => u128, u32 => u128, u64 => u128, u128 => u128,
+	i8 => u128, i16 => u128, i32 => u128, i64 => u128, i128 => u128
+);
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1823,6 +2966,38 @@ mod tests {
 		assert_eq!(encoded.slice_ref(decoded), &b"hello"[..]);
 	}
 
+	#[cfg(feature = "bytes")]
+	#[test]
+	fn encode_to_bytes_works() {
+		let value = (1u32, vec![2u8; 100], String::from("hello"));
+		let encoded = value.encode_to_bytes();
+		assert_eq!(encoded, value.encode());
+
+		let value = bytes::Bytes::from_static(b"hello");
+		assert_eq!(value.encode_to_bytes(), value.encode());
+		assert!(().encode_to_bytes().is_empty());
+	}
+
+	#[test]
+	fn encode_map_from_iter_encodes_like_btree_map() {
+		let entries = [(5u16, vec![1u8]), (1, vec![]), (5, vec![2, 3]), (3, vec![4]), (1, vec![5])];
+
+		let mut encoded = Vec::new();
+		encode_map_from_iter(entries.iter().map(|(k, v)| (k, v)), &mut encoded);
+		let map = BTreeMap::from_iter(entries.clone());
+		assert_eq!(encoded, map.encode());
+		assert_eq!(BTreeMap::decode(&mut &encoded[..]), Ok(map));
+
+		let mut encoded = Vec::new();
+		encode_map_from_iter(core::iter::empty::<(u8, u8)>(), &mut encoded);
+		assert_eq!(encoded, BTreeMap::<u8, u8>::new().encode());
+
+		// The keys are sorted by their order, not by their encoding.
+		let mut encoded = Vec::new();
+		encode_map_from_iter([(256u16, 1u8), (1, 2)], &mut encoded);
+		assert_eq!(encoded, BTreeMap::from([(1u16, 2u8), (256, 1)]).encode());
+	}
+
 	fn test_encode_length<T: Encode + Decode + DecodeLength>(thing: &T, len: usize) {
 		assert_eq!(<T as DecodeLength>::len(&thing.encode()[..]).unwrap(), len);
 	}
@@ -1904,6 +3079,14 @@ mod tests {
 		assert_eq!(MyWrapper::decode(&mut &*result).unwrap(), MyWrapper(3_u32.into()));
 	}
 
+	#[test]
+	fn pin_box_works() {
+		let value = Box::pin((1u32, String::from("pinned")));
+		let encoded = value.encode();
+		assert_eq!(encoded, (1u32, "pinned").encode());
+		assert_eq!(Pin::<Box<(u32, String)>>::decode(&mut &encoded[..]).unwrap(), value);
+	}
+
 	#[test]
 	fn codec_vec_deque_u8_and_u16() {
 		let mut v_u8 = VecDeque::new();
@@ -1954,6 +3137,51 @@ mod tests {
 		assert_eq!(Decode::decode(&mut &t8.encode()[..]), Ok(t8));
 	}
 
+	#[test]
+	fn vec_of_fixed_size_items_works() {
+		use crate::DecodeWithConfig;
+
+		// More items than are read into the buffer at once.
+		let vec = (0..100u32).map(|i| [i; 20]).collect::<Vec<_>>();
+		let encoded = vec.encode();
+		assert_eq!(<Vec<[u32; 20]>>::decode(&mut &encoded[..]).unwrap(), vec);
+
+		let buffered = CodecConfig::DEFAULT.with_buffered_reads(true);
+		let mut reader = IoReader(std::io::Cursor::new(&encoded[..]));
+		assert_eq!(<Vec<[u32; 20]>>::decode_with_config(&mut reader, &buffered).unwrap(), vec);
+		let mut reader = IoReader(std::io::Cursor::new(&encoded[..encoded.len() - 1]));
+		assert!(<Vec<[u32; 20]>>::decode_with_config(&mut reader, &buffered).is_err());
+
+		let vec = vec![[true, false]; 1000];
+		let mut reader = IoReader(std::io::Cursor::new(vec.encode()));
+		assert_eq!(<Vec<[bool; 2]>>::decode_with_config(&mut reader, &buffered).unwrap(), vec);
+	}
+
+	#[test]
+	fn vec_of_fixed_size_items_checks_the_size() {
+		use crate::DecodeWithConfig;
+
+		#[derive(Debug)]
+		struct WrongSize;
+
+		impl Decode for WrongSize {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				input.read_byte().map(|_| WrongSize)
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(2)
+			}
+		}
+
+		let buffered = CodecConfig::DEFAULT.with_buffered_reads(true);
+		let mut reader = IoReader(std::io::Cursor::new(&[4u8, 0, 0][..]));
+		assert_eq!(
+			<Vec<WrongSize>>::decode_with_config(&mut reader, &buffered).unwrap_err(),
+			"Decoded size of an item differs from its `encoded_fixed_size`".into()
+		);
+	}
+
 	#[test]
 	fn io_reader() {
 		let mut io_reader = IoReader(std::io::Cursor::new(&[1u8, 2, 3][..]));
@@ -1967,6 +3195,36 @@ mod tests {
 		assert_eq!(io_reader.read_byte(), Err("io error: UnexpectedEof".into()));
 	}
 
+	#[test]
+	fn input_reader_and_output_writer() {
+		use std::io::{Read as _, Write as _};
+
+		let mut reader = InputReader(&[1u8, 2, 3][..]);
+		let mut v = [0; 2];
+		assert_eq!(reader.read(&mut v).unwrap(), 2);
+		assert_eq!(v, [1, 2]);
+		let mut rest = Vec::new();
+		reader.read_to_end(&mut rest).unwrap();
+		assert_eq!(rest, [3]);
+
+		let mut reader = InputReader(IoReader(std::io::Cursor::new(&[4u8, 5, 6][..])));
+		assert_eq!(reader.read(&mut v).unwrap(), 2);
+		assert_eq!(v, [4, 5]);
+		assert_eq!(reader.read(&mut v).unwrap(), 1);
+		assert_eq!(v[0], 6);
+		assert_eq!(reader.read(&mut v).unwrap(), 0);
+
+		let mut rest = Vec::new();
+		let mut reader = InputReader(IoReader(std::io::Cursor::new(vec![7u8; 1000])));
+		assert_eq!(std::io::copy(&mut reader, &mut rest).unwrap(), 1000);
+		assert_eq!(rest, [7; 1000]);
+
+		let mut writer = OutputWriter(Vec::new());
+		writer.write_all(&[1, 2]).unwrap();
+		write!(writer, "{}", 3).unwrap();
+		assert_eq!(writer.0, [1, 2, b'3']);
+	}
+
 	#[test]
 	fn shared_references_implement_encode() {
 		Arc::new(10u32).encode();
@@ -2007,6 +3265,128 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn over_reported_remaining_len_does_not_preallocate() {
+		use crate::Input;
+
+		/// Claims to have far more data than it has, and records the requested allocations.
+		struct Liar<'a> {
+			data: &'a [u8],
+			allocated: Vec<usize>,
+		}
+
+		impl<'a> Input for Liar<'a> {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				Ok(Some(usize::MAX))
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				self.data.read(into)
+			}
+
+			fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+				self.allocated.push(size);
+				Ok(())
+			}
+		}
+
+		fn check<T: Decode + core::fmt::Debug>(data: &[u8]) {
+			let mut input = Liar { data, allocated: Vec::new() };
+			assert_eq!(
+				T::decode(&mut input).unwrap_err().to_string(),
+				"Not enough data to fill buffer"
+			);
+			// Only the first chunk is allocated before reading runs out of data.
+			assert!(input.allocated.iter().all(|size| *size <= MAX_PREALLOCATION));
+			assert!(input.allocated.iter().sum::<usize>() <= MAX_PREALLOCATION);
+		}
+
+		let mut claims_u32_max = Compact(u32::MAX).encode();
+		claims_u32_max.extend_from_slice(&[0; 10]);
+		check::<Vec<u8>>(&claims_u32_max);
+		check::<Vec<u64>>(&claims_u32_max);
+		check::<Vec<u128>>(&claims_u32_max);
+		check::<String>(&claims_u32_max);
+		check::<VecDeque<u32>>(&claims_u32_max);
+		check::<Vec<Vec<u8>>>(&claims_u32_max);
+
+		// Honest data still decodes through the lying input.
+		let value = vec![7u32; MAX_PREALLOCATION];
+		let encoded = value.encode();
+		let mut input = Liar { data: &encoded, allocated: Vec::new() };
+		assert_eq!(<Vec<u32>>::decode(&mut input).unwrap(), value);
+		assert!(input.allocated.iter().all(|size| *size <= MAX_PREALLOCATION));
+	}
+
+	#[test]
+	fn under_reported_remaining_len_fails_early() {
+		use crate::Input;
+
+		struct Empty<'a>(&'a [u8]);
+
+		impl<'a> Input for Empty<'a> {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				Ok(Some(0))
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				self.0.read(into)
+			}
+		}
+
+		let encoded = vec![1u16, 2, 3].encode();
+		assert_eq!(
+			<Vec<u16>>::decode(&mut Empty(&encoded)).unwrap_err().to_string(),
+			"Not enough data to decode vector"
+		);
+	}
+
+	#[test]
+	fn vec_deque_decodes_contiguously_without_extra_allocations() {
+		struct Tracking<'a> {
+			data: &'a [u8],
+			allocated: usize,
+		}
+
+		impl<'a> Input for Tracking<'a> {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				self.data.remaining_len()
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				self.data.read(into)
+			}
+
+			fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+				self.allocated += size;
+				Ok(())
+			}
+		}
+
+		let len = MAX_PREALLOCATION + 3;
+		let value: VecDeque<u16> = (0..len as u16).collect();
+		let encoded = value.encode();
+
+		let mut input = Tracking { data: &encoded, allocated: 0 };
+		let decoded = VecDeque::<u16>::decode(&mut input).unwrap();
+		assert_eq!(decoded, value);
+		assert_eq!(decoded.as_slices().1, &[] as &[u16]);
+		assert_eq!(input.allocated, len * mem::size_of::<u16>());
+
+		let value: VecDeque<Option<u16>> = (0..len as u16).map(Some).collect();
+		let encoded = value.encode();
+
+		let mut input = Tracking { data: &encoded, allocated: 0 };
+		let decoded = VecDeque::<Option<u16>>::decode(&mut input).unwrap();
+		assert_eq!(decoded, value);
+		assert_eq!(input.allocated, len * mem::size_of::<Option<u16>>());
+
+		// A length prefix claiming more items than the input has fails after the first chunk.
+		let mut input = Tracking { data: &encoded[..100], allocated: 0 };
+		assert!(VecDeque::<Option<u16>>::decode(&mut input).is_err());
+		assert_eq!(input.allocated, MAX_PREALLOCATION);
+	}
+
 	#[test]
 	fn boolean() {
 		assert_eq!(true.encode(), vec![1]);
@@ -2054,6 +3434,32 @@ mod tests {
 		assert_eq!(decoded.capacity(), decoded.len());
 	}
 
+	#[test]
+	fn reverse_ordering_and_phantom_pinned() {
+		let value = (Reverse(5u32), Reverse(vec![1u8]), PhantomPinned, Ordering::Less);
+		let encoded = value.encode();
+		assert_eq!(encoded, (5u32, vec![1u8], 0xffu8).encode());
+		assert_eq!(value.encoded_size(), encoded.len());
+		assert_eq!(
+			<(Reverse<u32>, Reverse<Vec<u8>>, PhantomPinned, Ordering)>::decode(&mut &encoded[..]),
+			Ok(value)
+		);
+
+		for ordering in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+			assert_eq!(Ordering::decode(&mut &ordering.encode()[..]), Ok(ordering));
+		}
+		assert_eq!(Ordering::Greater.encode(), [1]);
+		assert_eq!(
+			Ordering::decode(&mut &[2][..]),
+			Err("Invalid `Ordering` representation".into())
+		);
+
+		// `Reverse` changes the order, not the encoding.
+		let mut keys = vec![Reverse(1u16), Reverse(3)];
+		keys.sort();
+		assert_eq!(keys.encode(), vec![3u16, 1].encode());
+	}
+
 	#[test]
 	fn duration() {
 		let num_secs = 13;
@@ -2147,6 +3553,120 @@ mod tests {
 
 	test_array_encode_and_decode!(f32, f64);
 
+	#[test]
+	fn big_array_encode_and_decode() {
+		// Decoding into a `Box` and skipping must not put the array on the stack, which is checked
+		// with a thread that has a smaller stack than the array.
+		std::thread::Builder::new()
+			.stack_size(512 * 1024)
+			.spawn(|| {
+				let data: Box<[u8; 1_000_000]> = vec![7u8; 1_000_000].try_into().unwrap();
+				let encoded = data.encode();
+				assert_eq!(encoded.len(), 1_000_000);
+				assert_eq!(Box::<[u8; 1_000_000]>::decode(&mut &encoded[..]).unwrap(), data);
+
+				<[u8; 1_000_000]>::skip(&mut &encoded[..]).unwrap();
+			})
+			.unwrap()
+			.join()
+			.unwrap();
+
+		let data: [u64; 4096] = core::array::from_fn(|i| i as u64);
+		let encoded = data.encode();
+		assert_eq!(<[u64; 4096]>::decode(&mut &encoded[..]).unwrap(), data);
+
+		let data: [Option<u16>; 8192] = core::array::from_fn(|i| (i % 3 != 0).then_some(i as u16));
+		let encoded = data.encode();
+		assert_eq!(<[Option<u16>; 8192]>::decode(&mut &encoded[..]).unwrap(), data);
+		<[Option<u16>; 8192]>::skip(&mut &encoded[..]).unwrap();
+	}
+
+	#[test]
+	fn big_array_decode_failure_drops_decoded_elements() {
+		let data: [String; 2048] = core::array::from_fn(|i| i.to_string());
+		let encoded = data.encode();
+
+		assert!(<[String; 2048]>::decode(&mut &encoded[..encoded.len() - 1]).is_err());
+		assert_eq!(<[String; 2048]>::decode(&mut &encoded[..]).unwrap(), data);
+	}
+
+	#[test]
+	fn big_array_on_the_heap_is_mem_tracked() {
+		use crate::DecodeWithMemLimit;
+
+		let data: [u64; 4096] = core::array::from_fn(|i| i as u64);
+		let encoded = data.encode();
+
+		let mut input = (&encoded[..]).with_mem_limit(64 * 1024);
+		assert_eq!(<[u64; 4096]>::decode(&mut input), Ok(data));
+		assert_eq!(input.used_mem(), 32 * 1024);
+		assert!(<[u64; 4096]>::decode_with_mem_limit(&mut &encoded[..], 32 * 1024).is_err());
+	}
+
+	#[test]
+	fn array_encoded_fixed_size_does_not_overflow() {
+		struct Huge;
+
+		impl Decode for Huge {
+			fn decode<I: Input>(_: &mut I) -> Result<Self, Error> {
+				unimplemented!()
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(usize::MAX / 2)
+			}
+		}
+
+		assert_eq!(<[Huge; 2]>::encoded_fixed_size(), Some(usize::MAX - 1));
+		assert_eq!(<[Huge; 3]>::encoded_fixed_size(), None);
+	}
+
+	#[test]
+	fn strict_arrays_check_consumed_bytes() {
+		use crate::DecodeWithConfig;
+
+		// Claims to be encoded in 2 bytes, but only reads 1.
+		#[derive(Debug, PartialEq)]
+		struct Short(u8);
+
+		impl Decode for Short {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				input.read_byte().map(Short)
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(2)
+			}
+		}
+
+		let encoded = [1, 2, 3, 4];
+		assert_eq!(<[Short; 2]>::decode(&mut &encoded[..]), Ok([Short(1), Short(2)]));
+
+		let config = CodecConfig::DEFAULT.with_strict_arrays(true);
+		let err = <[Short; 2]>::decode_with_config(&mut &encoded[..], &config).unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			if cfg!(feature = "chain-error") {
+				"Element 0 consumed 1 bytes, but its fixed encoded size is 2:\n\
+				\tArray element did not consume its fixed encoded size\n"
+			} else {
+				"Array element did not consume its fixed encoded size"
+			}
+		);
+
+		// Elements which consume their fixed size, and elements without one, are accepted.
+		let value = [(1u8, 2u32), (3, 4)];
+		assert_eq!(
+			<[(u8, u32); 2]>::decode_with_config(&mut &value.encode()[..], &config),
+			Ok(value)
+		);
+		let value = [vec![1u8], vec![]];
+		assert_eq!(
+			<[Vec<u8>; 2]>::decode_with_config(&mut &value.encode()[..], &config),
+			Ok(value)
+		);
+	}
+
 	fn test_encoded_size(val: impl Encode) {
 		let length = val.using_encoded(|v| v.len());
 
@@ -2184,6 +3704,18 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn encoded_size_with_config_follows_the_config() {
+		let config = CodecConfig::DEFAULT.with_length_prefix(crate::LengthPrefix::FixedU32LE);
+		let value = (vec![1u16, 2, 3], String::from("abc"));
+		assert_eq!(
+			value.encoded_size_with_config(&config),
+			value.encode_with_config(&config).len()
+		);
+		assert_eq!(value.encoded_size_with_config(&config), 17);
+		assert_eq!(value.encoded_size(), 11);
+	}
+
 	#[test]
 	fn ranges() {
 		let range = Range { start: 1, end: 100 };
@@ -2196,4 +3728,68 @@ mod tests {
 		assert_eq!(range_inclusive.encode(), range_inclusive_bytes);
 		assert_eq!(RangeInclusive::decode(&mut &range_inclusive_bytes[..]), Ok(range_inclusive));
 	}
+
+	#[test]
+	fn range_decode_length() {
+		assert_eq!(<Range<u32> as DecodeLength>::len(&(1u32..100).encode()), Ok(99));
+		assert_eq!(<Range<u32> as DecodeLength>::len(&(100u32, 1u32).encode()), Ok(0));
+		assert_eq!(<RangeInclusive<u8> as DecodeLength>::len(&(0u8..=255).encode()), Ok(256));
+		assert_eq!(<RangeInclusive<u8> as DecodeLength>::len(&(1u8, 0u8).encode()), Ok(0));
+		assert_eq!(<Range<i8> as DecodeLength>::len(&(-128i8..127).encode()), Ok(255));
+		assert_eq!(<Range<i64> as DecodeLength>::len(&(-5i64..5).encode()), Ok(10));
+		assert_eq!(<RangeInclusive<i128> as DecodeLength>::len(&(-1i128..=1).encode()), Ok(3));
+		assert_eq!(
+			<RangeInclusive<u128> as DecodeLength>::len(&(0u128..=u128::MAX).encode()),
+			Err("Range length doesn't fit in usize".into())
+		);
+		assert!(<Range<u64> as DecodeLength>::len(&[0; 15]).is_err());
+	}
+
+	#[test]
+	fn skip_byte_containers_seeks_forward() {
+		/// Counts the bytes read, as opposed to the bytes skipped.
+		struct CountReads<'a>(&'a [u8], usize);
+
+		impl Input for CountReads<'_> {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				self.0.remaining_len()
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				self.1 += into.len();
+				self.0.read(into)
+			}
+
+			fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+				self.0.seek_forward(n)
+			}
+		}
+
+		fn check<T: Decode>(encoded: &[u8]) {
+			let mut input = CountReads(encoded, 0);
+			T::skip(&mut input).unwrap();
+			assert!(input.0.is_empty());
+			assert!(input.1 <= 5, "read {} bytes", input.1);
+
+			// Through the default implementation.
+			let mut reader = IoReader(encoded);
+			T::skip(&mut reader).unwrap();
+			assert!(reader.0.is_empty());
+
+			assert_eq!(
+				T::skip(&mut &encoded[..encoded.len() - 1]),
+				Err("Not enough data to fill buffer".into())
+			);
+		}
+
+		check::<Vec<u8>>(&vec![1u8; 100_000].encode());
+		check::<Vec<i8>>(&vec![-1i8; 3000].encode());
+		check::<[u8; 5000]>(&[2u8; 5000].encode());
+		// Invalid UTF-8 is not detected when skipping.
+		check::<String>(&vec![0xffu8; 2000].encode());
+
+		let mut input = CountReads(&[], 0);
+		input.seek_forward(0).unwrap();
+		assert_eq!(input.seek_forward(1), Err("Not enough data to fill buffer".into()));
+	}
 }
@@ -0,0 +1,118 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks of the encoding of a type, used by the code generated by `#[derive(CodecFuzz)]`.
+//!
+//! [`roundtrip`] is a fuzz target body, [`check_exhaustive`] decodes all small inputs:
+//!
+//! ```
+//! use parity_scale_codec::codec_fuzz;
+//!
+//! codec_fuzz::roundtrip::<(u8, Option<bool>)>(&[1, 1, 0]);
+//! codec_fuzz::check_exhaustive::<(u8, Option<bool>)>(2);
+//! ```
+
+use core::fmt::Debug;
+
+use crate::{alloc::vec::Vec, Decode, Encode};
+
+/// Check that a value decoded from `data` is encoded to a value which decodes to the same value.
+///
+/// Data that doesn't decode is ignored, so this can be called with arbitrary fuzzer input.
+///
+/// # Panics
+///
+/// If the value is encoded to bytes which don't decode, or decode to another value.
+pub fn roundtrip<T: Encode + Decode + PartialEq + Debug>(mut data: &[u8]) {
+	let Ok(value) = T::decode(&mut data) else { return };
+
+	let encoded = value.encode();
+	assert_eq!(value.encoded_size(), encoded.len(), "`encoded_size` doesn't match the encoding");
+	let decoded = T::decode(&mut &encoded[..]).expect("An encoded value must decode");
+	assert_eq!(decoded, value, "The value changed in the roundtrip");
+	assert_eq!(decoded.encode(), encoded, "The encoding changed in the roundtrip");
+}
+
+/// Decode all inputs of at most `max_len` bytes, and check that each input which decodes without
+/// remaining bytes is the encoding of the decoded value.
+///
+/// This checks that no two inputs decode to the same value. There are `256^max_len` inputs of
+/// `max_len` bytes, so `max_len` should be at most 2 or 3.
+///
+/// # Panics
+///
+/// If a decoded value is encoded to other bytes than its input.
+pub fn check_exhaustive<T: Encode + Decode + Debug>(max_len: usize) {
+	let mut input = Vec::with_capacity(max_len);
+	for len in 0..=max_len {
+		input.clear();
+		input.resize(len, 0);
+		loop {
+			let mut remaining = &input[..];
+			if let Ok(value) = T::decode(&mut remaining) {
+				if remaining.is_empty() {
+					assert_eq!(value.encode(), input, "{value:?} is not encoded to its input");
+				}
+			}
+
+			// Advance to the next input of `len` bytes, or to the next length.
+			let Some(i) = input.iter().rposition(|byte| *byte != u8::MAX) else { break };
+			input[i] += 1;
+			input[i + 1..].fill(0);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Compact;
+
+	#[test]
+	fn roundtrip_accepts_any_data() {
+		for data in [&[][..], &[0], &[1, 2, 3], &[0xff; 9]] {
+			roundtrip::<Compact<u64>>(data);
+			roundtrip::<Vec<u16>>(data);
+			roundtrip::<Option<(bool, i8)>>(data);
+		}
+	}
+
+	#[test]
+	fn check_exhaustive_works() {
+		check_exhaustive::<()>(1);
+		check_exhaustive::<Option<u8>>(2);
+		check_exhaustive::<Compact<u32>>(2);
+	}
+
+	#[derive(Debug)]
+	struct Lossy(u8);
+
+	impl Encode for Lossy {
+		fn encode_to<W: crate::Output + ?Sized>(&self, dest: &mut W) {
+			dest.push_byte(self.0 / 2 * 2)
+		}
+	}
+
+	impl Decode for Lossy {
+		fn decode<I: crate::Input>(input: &mut I) -> Result<Self, crate::Error> {
+			input.read_byte().map(Lossy)
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "Lossy(1) is not encoded to its input")]
+	fn check_exhaustive_detects_non_canonical_encodings() {
+		check_exhaustive::<Lossy>(1);
+	}
+}
@@ -22,12 +22,13 @@ use crate::{
 	alloc::vec::Vec,
 	codec::{Decode, Encode, EncodeAsRef, Input, Output},
 	encode_like::EncodeLike,
-	DecodeWithMemTracking, Error,
+	error::decode_bug,
+	DecodeWithMemTracking, Error, Le,
 };
 #[cfg(feature = "fuzz")]
 use arbitrary::Arbitrary;
 
-struct ArrayVecWrapper<const N: usize>(ArrayVec<u8, N>);
+pub(crate) struct ArrayVecWrapper<const N: usize>(pub(crate) ArrayVec<u8, N>);
 
 impl<const N: usize> Output for ArrayVecWrapper<N> {
 	fn write(&mut self, bytes: &[u8]) {
@@ -77,6 +78,19 @@ impl<'a, T: 'a + Input> Input for PrefixInput<'a, T> {
 	}
 }
 
+/// The encodings of the compact integers `0..=63`, which are a single byte.
+///
+/// Most length prefixes are this small, so encoding them is a lookup.
+pub(crate) const SMALL_COMPACTS: [u8; 64] = {
+	let mut table = [0; 64];
+	let mut i = 0;
+	while i < 64 {
+		table[i] = (i as u8) << 2;
+		i += 1;
+	}
+	table
+};
+
 /// Something that can return the compact encoded length for a given value.
 pub trait CompactLen<T> {
 	/// Returns the compact encoded length for the given value.
@@ -135,32 +149,82 @@ where
 	}
 }
 
-impl<'a, T> EncodeLike for CompactRef<'a, T>
+impl<'a, T> EncodeLike for CompactRef<'a, T> where CompactRef<'a, T>: Encode {}
+
+impl<'a, T> EncodeLike<Compact<T>> for CompactRef<'a, T> where for<'b> CompactRef<'b, T>: Encode {}
+
+impl<'a, T> EncodeLike<Compact<T>> for &CompactRef<'a, T> where for<'b> CompactRef<'b, T>: Encode {}
+
+impl<'a, T> EncodeLike<CompactRef<'a, T>> for Compact<T> where for<'b> CompactRef<'b, T>: Encode {}
+
+impl<'a, T> EncodeLike<CompactRef<'a, T>> for &Compact<T> where for<'b> CompactRef<'b, T>: Encode {}
+
+/// A type which can be compact encoded, i.e. `CompactRef` of it implements `Encode`.
+///
+/// The implementations for `CompactAs` types require it of `CompactAs::As`, instead of requiring
+/// `CompactRef<T::As>: Encode` directly: compact encoding a type which isn't `CompactAs` then
+/// fails with an error about the type, instead of overflowing while evaluating the requirement.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+	message = "`{Self}` cannot be compact encoded",
+	label = "`{Self}` cannot be compact encoded",
+	note = "compact encoding is implemented for unsigned integers, `()` and `CompactAs` types \
+		wrapping them"
+)]
+pub trait CompactEncodeAs {
+	/// `Encode::size_hint` of the compact encoding.
+	fn compact_size_hint(&self) -> usize;
+
+	/// `Encode::encode_to` of the compact encoding.
+	fn compact_encode_to<W: Output + ?Sized>(&self, dest: &mut W);
+
+	/// `Encode::encode` of the compact encoding.
+	fn compact_encode(&self) -> Vec<u8>;
+
+	/// `Encode::using_encoded` of the compact encoding.
+	fn compact_using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R;
+}
+
+impl<T> CompactEncodeAs for T
 where
-	T: CompactAs,
-	for<'b> CompactRef<'b, T::As>: Encode,
+	for<'a> CompactRef<'a, T>: Encode,
 {
+	fn compact_size_hint(&self) -> usize {
+		CompactRef(self).size_hint()
+	}
+
+	fn compact_encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		CompactRef(self).encode_to(dest)
+	}
+
+	fn compact_encode(&self) -> Vec<u8> {
+		CompactRef(self).encode()
+	}
+
+	fn compact_using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		CompactRef(self).using_encoded(f)
+	}
 }
 
 impl<'a, T> Encode for CompactRef<'a, T>
 where
 	T: CompactAs,
-	for<'b> CompactRef<'b, T::As>: Encode,
+	T::As: CompactEncodeAs,
 {
 	fn size_hint(&self) -> usize {
-		CompactRef(self.0.encode_as()).size_hint()
+		self.0.encode_as().compact_size_hint()
 	}
 
 	fn encode_to<Out: Output + ?Sized>(&self, dest: &mut Out) {
-		CompactRef(self.0.encode_as()).encode_to(dest)
+		self.0.encode_as().compact_encode_to(dest)
 	}
 
 	fn encode(&self) -> Vec<u8> {
-		CompactRef(self.0.encode_as()).encode()
+		self.0.encode_as().compact_encode()
 	}
 
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
-		CompactRef(self.0.encode_as()).using_encoded(f)
+		self.0.encode_as().compact_using_encoded(f)
 	}
 }
 
@@ -194,6 +258,42 @@ macro_rules! impl_from_compact {
 
 impl_from_compact! { (), u8, u16, u32, u64, u128 }
 
+const CONVERSION_OUT_OF_RANGE: &str = "out of range converting Compact into a narrower integer";
+
+impl<T> Compact<T> {
+	/// Convert the inner value into `U`, failing if it does not fit into `U`.
+	///
+	/// This is useful when a value was decoded as a wide compact (e.g. `Compact<u64>`) but is
+	/// required to fit into a narrower integer.
+	pub fn try_into_narrow<U: TryFrom<T>>(self) -> Result<U, Error> {
+		U::try_from(self.0).map_err(|_| CONVERSION_OUT_OF_RANGE.into())
+	}
+}
+
+macro_rules! impl_try_from_compact {
+	( $( $from:ty => [ $( $to:ty ),* ]; )* ) => {
+		$(
+			$(
+				impl TryFrom<Compact<$from>> for $to {
+					type Error = Error;
+
+					fn try_from(x: Compact<$from>) -> Result<$to, Error> {
+						x.try_into_narrow()
+					}
+				}
+			)*
+		)*
+	}
+}
+
+impl_try_from_compact! {
+	u8 => [u16, u32, u64, u128];
+	u16 => [u8, u32, u64, u128];
+	u32 => [u8, u16, u64, u128];
+	u64 => [u8, u16, u32, u128];
+	u128 => [u8, u16, u32, u64];
+}
+
 /// Compact-encoded variant of &'a T. This is more space-efficient but less compute-efficient.
 #[derive(Eq, PartialEq, Clone, Copy)]
 pub struct CompactRef<'a, T>(pub &'a T);
@@ -213,32 +313,6 @@ where
 	}
 }
 
-#[cfg(feature = "serde")]
-impl<T> serde::Serialize for Compact<T>
-where
-	T: serde::Serialize,
-{
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		T::serialize(&self.0, serializer)
-	}
-}
-
-#[cfg(feature = "serde")]
-impl<'de, T> serde::Deserialize<'de> for Compact<T>
-where
-	T: serde::Deserialize<'de>,
-{
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		T::deserialize(deserializer).map(Compact)
-	}
-}
-
 /// Requires the presence of `MaxEncodedLen` when the `max-encoded-len` feature is active.
 // Remove this trait when the feature is removed.
 #[cfg(feature = "max-encoded-len")]
@@ -254,6 +328,12 @@ pub trait MaybeMaxEncodedLen {}
 impl<T> MaybeMaxEncodedLen for T {}
 
 /// Trait that tells you if a given type can be encoded/decoded in a compact way.
+#[diagnostic::on_unimplemented(
+	message = "`{Self}` cannot be compact encoded",
+	label = "`{Self}` cannot be compact encoded",
+	note = "compact encoding is implemented for unsigned integers, `()` and `CompactAs` types \
+		wrapping them"
+)]
 pub trait HasCompact: Sized {
 	/// The compact type; this can be
 	type Type: for<'a> EncodeAsRef<'a, Self> + Decode + From<Self> + Into<Self> + MaybeMaxEncodedLen;
@@ -266,18 +346,43 @@ where
 	type RefType = CompactRef<'a, T>;
 }
 
+/// A type whose compact encoding implements `MaxEncodedLen`, see [`CompactEncodeAs`].
+#[cfg(feature = "max-encoded-len")]
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+	message = "`{Self}` cannot be compact encoded",
+	label = "`{Self}` cannot be compact encoded",
+	note = "compact encoding is implemented for unsigned integers, `()` and `CompactAs` types \
+		wrapping them"
+)]
+pub trait CompactMaxEncodedLen {
+	/// `MaxEncodedLen::max_encoded_len` of the compact encoding.
+	fn compact_max_encoded_len() -> usize;
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T> CompactMaxEncodedLen for T
+where
+	Compact<T>: MaxEncodedLen,
+{
+	fn compact_max_encoded_len() -> usize {
+		Compact::<T>::max_encoded_len()
+	}
+}
+
 #[cfg(feature = "max-encoded-len")]
 impl<T> MaxEncodedLen for Compact<T>
 where
 	T: CompactAs,
-	Compact<T::As>: MaxEncodedLen,
+	T::As: CompactMaxEncodedLen,
 	Compact<T>: Encode,
 {
 	fn max_encoded_len() -> usize {
-		Compact::<T::As>::max_encoded_len()
+		T::As::compact_max_encoded_len()
 	}
 }
 
+#[diagnostic::do_not_recommend]
 impl<T: 'static> HasCompact for T
 where
 	Compact<T>: for<'a> EncodeAsRef<'a, T> + Decode + From<Self> + Into<Self> + MaybeMaxEncodedLen,
@@ -285,6 +390,40 @@ where
 	type Type = Compact<T>;
 }
 
+/// Extension trait to encode and decode any [`HasCompact`] type in its compact representation,
+/// without going through [`Compact`] explicitly.
+///
+/// ```
+/// use parity_scale_codec::{Compact, CompactExt, Encode};
+///
+/// assert_eq!(1_000u32.encode_compact(), Compact(1_000u32).encode());
+/// assert_eq!(1_000u32.compact_len(), 2);
+/// assert_eq!(u32::decode_compact(&mut &[0xa1, 0x0f][..]).unwrap(), 1_000);
+/// ```
+pub trait CompactExt: HasCompact {
+	/// Encode `self` in its compact representation to the given output.
+	fn encode_compact_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		<Self::Type as EncodeAsRef<'_, Self>>::RefType::from(self).encode_to(dest)
+	}
+
+	/// Encode `self` in its compact representation.
+	fn encode_compact(&self) -> Vec<u8> {
+		<Self::Type as EncodeAsRef<'_, Self>>::RefType::from(self).encode()
+	}
+
+	/// Return the length of the compact representation of `self`.
+	fn compact_len(&self) -> usize {
+		<Self::Type as EncodeAsRef<'_, Self>>::RefType::from(self).encoded_size()
+	}
+
+	/// Decode a value of `Self` from its compact representation.
+	fn decode_compact<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Self::Type::decode(input).map(Into::into)
+	}
+}
+
+impl<T: HasCompact> CompactExt for T {}
+
 impl<'a> Encode for CompactRef<'a, ()> {
 	fn encode_to<W: Output + ?Sized>(&self, _dest: &mut W) {}
 
@@ -297,6 +436,8 @@ impl<'a> Encode for CompactRef<'a, ()> {
 	}
 }
 
+// The integers in compact encodings are little endian, whatever `CodecConfig::byte_order`.
+
 impl<'a> Encode for CompactRef<'a, u8> {
 	fn size_hint(&self) -> usize {
 		Compact::compact_len(self.0)
@@ -305,7 +446,7 @@ impl<'a> Encode for CompactRef<'a, u8> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte(self.0 << 2),
-			_ => ((u16::from(*self.0) << 2) | 0b01).encode_to(dest),
+			_ => Le((u16::from(*self.0) << 2) | 0b01).encode_to(dest),
 		}
 	}
 
@@ -333,8 +474,8 @@ impl<'a> Encode for CompactRef<'a, u16> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
-			0..=0b0011_1111_1111_1111 => ((*self.0 << 2) | 0b01).encode_to(dest),
-			_ => ((u32::from(*self.0) << 2) | 0b10).encode_to(dest),
+			0..=0b0011_1111_1111_1111 => Le((*self.0 << 2) | 0b01).encode_to(dest),
+			_ => Le((u32::from(*self.0) << 2) | 0b10).encode_to(dest),
 		}
 	}
 
@@ -363,12 +504,12 @@ impl<'a> Encode for CompactRef<'a, u32> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
-			0..=0b0011_1111_1111_1111 => (((*self.0 as u16) << 2) | 0b01).encode_to(dest),
+			0..=0b0011_1111_1111_1111 => Le(((*self.0 as u16) << 2) | 0b01).encode_to(dest),
 			0..=0b0011_1111_1111_1111_1111_1111_1111_1111 =>
-				((*self.0 << 2) | 0b10).encode_to(dest),
+				Le((*self.0 << 2) | 0b10).encode_to(dest),
 			_ => {
 				dest.push_byte(0b11);
-				self.0.encode_to(dest);
+				Le(*self.0).encode_to(dest);
 			},
 		}
 	}
@@ -399,9 +540,9 @@ impl<'a> Encode for CompactRef<'a, u64> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
-			0..=0b0011_1111_1111_1111 => (((*self.0 as u16) << 2) | 0b01).encode_to(dest),
+			0..=0b0011_1111_1111_1111 => Le(((*self.0 as u16) << 2) | 0b01).encode_to(dest),
 			0..=0b0011_1111_1111_1111_1111_1111_1111_1111 =>
-				(((*self.0 as u32) << 2) | 0b10).encode_to(dest),
+				Le(((*self.0 as u32) << 2) | 0b10).encode_to(dest),
 			_ => {
 				let bytes_needed = 8 - self.0.leading_zeros() / 8;
 				assert!(
@@ -445,9 +586,9 @@ impl<'a> Encode for CompactRef<'a, u128> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
-			0..=0b0011_1111_1111_1111 => (((*self.0 as u16) << 2) | 0b01).encode_to(dest),
+			0..=0b0011_1111_1111_1111 => Le(((*self.0 as u16) << 2) | 0b01).encode_to(dest),
 			0..=0b0011_1111_1111_1111_1111_1111_1111_1111 =>
-				(((*self.0 as u32) << 2) | 0b10).encode_to(dest),
+				Le(((*self.0 as u32) << 2) | 0b10).encode_to(dest),
 			_ => {
 				let bytes_needed = 16 - self.0.leading_zeros() / 8;
 				assert!(
@@ -503,7 +644,7 @@ impl Decode for Compact<u8> {
 		Ok(Compact(match prefix % 4 {
 			0 => prefix >> 2,
 			1 => {
-				let x = u16::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u16>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111 && x <= 255 {
 					x as u8
 				} else {
@@ -523,7 +664,7 @@ impl Decode for Compact<u16> {
 		Ok(Compact(match prefix % 4 {
 			0 => u16::from(prefix) >> 2,
 			1 => {
-				let x = u16::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u16>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111 && x <= 0b0011_1111_1111_1111 {
 					x
 				} else {
@@ -531,7 +672,7 @@ impl Decode for Compact<u16> {
 				}
 			},
 			2 => {
-				let x = u32::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u32>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111_1111_1111 && x < 65536 {
 					x as u16
 				} else {
@@ -551,7 +692,7 @@ impl Decode for Compact<u32> {
 		Ok(Compact(match prefix % 4 {
 			0 => u32::from(prefix) >> 2,
 			1 => {
-				let x = u16::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u16>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111 && x <= 0b0011_1111_1111_1111 {
 					u32::from(x)
 				} else {
@@ -559,7 +700,7 @@ impl Decode for Compact<u32> {
 				}
 			},
 			2 => {
-				let x = u32::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u32>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111_1111_1111 && x <= u32::MAX >> 2 {
 					x
 				} else {
@@ -569,7 +710,7 @@ impl Decode for Compact<u32> {
 			3 => {
 				if prefix >> 2 == 0 {
 					// just 4 bytes. ok.
-					let x = u32::decode(input)?;
+					let x = Le::<u32>::decode(input)?.0;
 					if x > u32::MAX >> 2 {
 						x
 					} else {
@@ -580,7 +721,7 @@ impl Decode for Compact<u32> {
 					return Err(U32_OUT_OF_RANGE.into());
 				}
 			},
-			_ => unreachable!(),
+			_ => decode_bug!("The prefix has two bits; qed"),
 		}))
 	}
 }
@@ -593,7 +734,7 @@ impl Decode for Compact<u64> {
 		Ok(Compact(match prefix % 4 {
 			0 => u64::from(prefix) >> 2,
 			1 => {
-				let x = u16::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u16>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111 && x <= 0b0011_1111_1111_1111 {
 					u64::from(x)
 				} else {
@@ -601,7 +742,7 @@ impl Decode for Compact<u64> {
 				}
 			},
 			2 => {
-				let x = u32::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u32>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111_1111_1111 && x <= u32::MAX >> 2 {
 					u64::from(x)
 				} else {
@@ -610,7 +751,7 @@ impl Decode for Compact<u64> {
 			},
 			3 => match (prefix >> 2) + 4 {
 				4 => {
-					let x = u32::decode(input)?;
+					let x = Le::<u32>::decode(input)?.0;
 					if x > u32::MAX >> 2 {
 						u64::from(x)
 					} else {
@@ -618,7 +759,7 @@ impl Decode for Compact<u64> {
 					}
 				},
 				8 => {
-					let x = u64::decode(input)?;
+					let x = Le::<u64>::decode(input)?.0;
 					if x > u64::MAX >> 8 {
 						x
 					} else {
@@ -638,7 +779,7 @@ impl Decode for Compact<u64> {
 					}
 				},
 			},
-			_ => unreachable!(),
+			_ => decode_bug!("The prefix has two bits; qed"),
 		}))
 	}
 }
@@ -651,7 +792,7 @@ impl Decode for Compact<u128> {
 		Ok(Compact(match prefix % 4 {
 			0 => u128::from(prefix) >> 2,
 			1 => {
-				let x = u16::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u16>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111 && x <= 0b0011_1111_1111_1111 {
 					u128::from(x)
 				} else {
@@ -659,7 +800,7 @@ impl Decode for Compact<u128> {
 				}
 			},
 			2 => {
-				let x = u32::decode(&mut PrefixInput { prefix: Some(prefix), input })? >> 2;
+				let x = Le::<u32>::decode(&mut PrefixInput { prefix: Some(prefix), input })?.0 >> 2;
 				if x > 0b0011_1111_1111_1111 && x <= u32::MAX >> 2 {
 					u128::from(x)
 				} else {
@@ -668,7 +809,7 @@ impl Decode for Compact<u128> {
 			},
 			3 => match (prefix >> 2) + 4 {
 				4 => {
-					let x = u32::decode(input)?;
+					let x = Le::<u32>::decode(input)?.0;
 					if x > u32::MAX >> 2 {
 						u128::from(x)
 					} else {
@@ -676,7 +817,7 @@ impl Decode for Compact<u128> {
 					}
 				},
 				8 => {
-					let x = u64::decode(input)?;
+					let x = Le::<u64>::decode(input)?.0;
 					if x > u64::MAX >> 8 {
 						u128::from(x)
 					} else {
@@ -684,7 +825,7 @@ impl Decode for Compact<u128> {
 					}
 				},
 				16 => {
-					let x = u128::decode(input)?;
+					let x = Le::<u128>::decode(input)?.0;
 					if x > u128::MAX >> 8 {
 						x
 					} else {
@@ -704,7 +845,7 @@ impl Decode for Compact<u128> {
 					}
 				},
 			},
-			_ => unreachable!(),
+			_ => decode_bug!("The prefix has two bits; qed"),
 		}))
 	}
 }
@@ -839,6 +980,18 @@ mod tests {
 			.join(" ")
 	}
 
+	#[test]
+	fn small_compacts_are_single_bytes() {
+		for n in 0..64u8 {
+			let expected = [SMALL_COMPACTS[n as usize]];
+			assert_eq!(Compact(n).encode(), expected);
+			assert_eq!(Compact(n as u128).encode(), expected);
+			assert_eq!(vec![0u8; n as usize].encode()[..1], expected);
+			assert_eq!(Compact::<u32>::decode(&mut &expected[..]), Ok(Compact(n as u32)));
+		}
+		assert_eq!(vec![0u8; 64].encode()[..2], [0x01, 0x01]);
+	}
+
 	#[test]
 	fn compact_integers_encoded_as_expected() {
 		let tests = [
@@ -928,6 +1081,80 @@ mod tests {
 		let _data = WithCompact { _data: Wrapper(1) };
 	}
 
+	#[test]
+	fn compact_and_compact_ref_are_encode_like_each_other() {
+		fn insert<T: Encode, V: EncodeLike<T>>(value: V) -> Vec<u8> {
+			value.encode()
+		}
+
+		let expected = Compact(1_000u32).encode();
+		assert_eq!(insert::<Compact<u32>, _>(Compact(1_000u32)), expected);
+		assert_eq!(insert::<Compact<u32>, _>(&Compact(1_000u32)), expected);
+		assert_eq!(insert::<Compact<u32>, _>(CompactRef(&1_000u32)), expected);
+		assert_eq!(insert::<Compact<u32>, _>(&CompactRef(&1_000u32)), expected);
+		assert_eq!(insert::<CompactRef<u32>, _>(CompactRef(&1_000u32)), expected);
+		assert_eq!(insert::<CompactRef<u32>, _>(Compact(1_000u32)), expected);
+		assert_eq!(insert::<CompactRef<u32>, _>(&Compact(1_000u32)), expected);
+
+		let expected = Compact(Wrapper(100)).encode();
+		assert_eq!(insert::<Compact<Wrapper>, _>(CompactRef(&Wrapper(100))), expected);
+		assert_eq!(insert::<Compact<Wrapper>, _>(&CompactRef(&Wrapper(100))), expected);
+		assert_eq!(insert::<CompactRef<Wrapper>, _>(Compact(Wrapper(100))), expected);
+	}
+
+	#[test]
+	fn compact_try_from_works() {
+		assert_eq!(u32::try_from(Compact(u32::MAX as u64)), Ok(u32::MAX));
+		assert_eq!(u8::try_from(Compact(255u128)), Ok(255u8));
+		assert_eq!(u128::try_from(Compact(u8::MAX)), Ok(255u128));
+		assert_eq!(
+			u32::try_from(Compact(u32::MAX as u64 + 1)),
+			Err(CONVERSION_OUT_OF_RANGE.into())
+		);
+		assert_eq!(u8::try_from(Compact(256u16)), Err(CONVERSION_OUT_OF_RANGE.into()));
+
+		assert_eq!(Compact(1000u64).try_into_narrow::<u16>(), Ok(1000u16));
+		assert_eq!(Compact(1000u64).try_into_narrow::<u8>(), Err(CONVERSION_OUT_OF_RANGE.into()));
+	}
+
+	#[test]
+	fn compact_ext_works() {
+		#[derive(Debug, PartialEq)]
+		struct Wrapper(u64);
+
+		impl CompactAs for Wrapper {
+			type As = u64;
+			fn encode_as(&self) -> &u64 {
+				&self.0
+			}
+			fn decode_from(x: u64) -> Result<Wrapper, Error> {
+				Ok(Wrapper(x))
+			}
+		}
+
+		impl From<Compact<Wrapper>> for Wrapper {
+			fn from(x: Compact<Wrapper>) -> Wrapper {
+				x.0
+			}
+		}
+
+		for n in [0u64, 63, 64, 16383, 16384, 1 << 30, u64::MAX] {
+			let encoded = n.encode_compact();
+			assert_eq!(encoded, Compact(n).encode());
+			assert_eq!(n.compact_len(), encoded.len());
+			assert_eq!(u64::decode_compact(&mut &encoded[..]).unwrap(), n);
+
+			let mut dest = Vec::new();
+			Wrapper(n).encode_compact_to(&mut dest);
+			assert_eq!(dest, encoded);
+			assert_eq!(Wrapper(n).compact_len(), encoded.len());
+			assert_eq!(Wrapper::decode_compact(&mut &encoded[..]).unwrap(), Wrapper(n));
+		}
+
+		assert_eq!(().encode_compact(), Vec::<u8>::new());
+		assert!(u8::decode_compact(&mut &Compact(256u16).encode()[..]).is_err());
+	}
+
 	#[test]
 	fn compact_using_encoded_arrayvec_size() {
 		Compact(u8::MAX).using_encoded(|_| {});
@@ -0,0 +1,317 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `serde` support of `Compact`.
+//!
+//! Human-readable formats like JSON are often consumed by JavaScript, whose numbers can't represent
+//! integers above 2^53 - 1 exactly. So with human-readable formats, a `Compact` of a larger value
+//! is serialized as a decimal string, and a `Compact` is deserialized from a number or a decimal
+//! string. Other formats are not affected.
+
+use core::fmt;
+
+use serde::{
+	de::{self, Deserializer, Visitor},
+	ser::Serializer,
+	Deserialize, Serialize,
+};
+
+use crate::Compact;
+
+/// The largest integer exactly representable by a JavaScript number, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+impl<T: Serialize> Serialize for Compact<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if serializer.is_human_readable() {
+			T::serialize(&self.0, SafeIntegers(serializer))
+		} else {
+			T::serialize(&self.0, serializer)
+		}
+	}
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Compact<T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if deserializer.is_human_readable() {
+			T::deserialize(SafeIntegers(deserializer)).map(Compact)
+		} else {
+			T::deserialize(deserializer).map(Compact)
+		}
+	}
+}
+
+/// A serializer or deserializer of a human-readable format, which represents unsigned integers
+/// above [`MAX_SAFE_INTEGER`] as decimal strings.
+///
+/// Only the value itself is affected, not the values nested in it.
+struct SafeIntegers<S>(S);
+
+macro_rules! forward_serialize {
+	($( fn $method:ident(self $(, $arg:ident: $ty:ty)* ) -> $ret:ty; )*) => {
+		$(
+			fn $method(self $(, $arg: $ty)*) -> $ret {
+				self.0.$method($($arg),*)
+			}
+		)*
+	};
+}
+
+impl<S: Serializer> Serializer for SafeIntegers<S> {
+	type Ok = S::Ok;
+	type Error = S::Error;
+	type SerializeSeq = S::SerializeSeq;
+	type SerializeTuple = S::SerializeTuple;
+	type SerializeTupleStruct = S::SerializeTupleStruct;
+	type SerializeTupleVariant = S::SerializeTupleVariant;
+	type SerializeMap = S::SerializeMap;
+	type SerializeStruct = S::SerializeStruct;
+	type SerializeStructVariant = S::SerializeStructVariant;
+
+	fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+		if v > MAX_SAFE_INTEGER {
+			self.0.collect_str(&v)
+		} else {
+			self.0.serialize_u64(v)
+		}
+	}
+
+	fn serialize_u128(self, v: u128) -> Result<S::Ok, S::Error> {
+		if v > MAX_SAFE_INTEGER as u128 {
+			self.0.collect_str(&v)
+		} else {
+			self.0.serialize_u64(v as u64)
+		}
+	}
+
+	fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<S::Ok, S::Error> {
+		self.0.serialize_some(value)
+	}
+
+	fn serialize_newtype_struct<T: ?Sized + Serialize>(
+		self,
+		name: &'static str,
+		value: &T,
+	) -> Result<S::Ok, S::Error> {
+		self.0.serialize_newtype_struct(name, value)
+	}
+
+	fn serialize_newtype_variant<T: ?Sized + Serialize>(
+		self,
+		name: &'static str,
+		variant_index: u32,
+		variant: &'static str,
+		value: &T,
+	) -> Result<S::Ok, S::Error> {
+		self.0.serialize_newtype_variant(name, variant_index, variant, value)
+	}
+
+	fn collect_str<T: ?Sized + fmt::Display>(self, value: &T) -> Result<S::Ok, S::Error> {
+		self.0.collect_str(value)
+	}
+
+	fn is_human_readable(&self) -> bool {
+		self.0.is_human_readable()
+	}
+
+	forward_serialize! {
+		fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error>;
+		fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error>;
+		fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error>;
+		fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error>;
+		fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error>;
+		fn serialize_i128(self, v: i128) -> Result<S::Ok, S::Error>;
+		fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error>;
+		fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error>;
+		fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error>;
+		fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error>;
+		fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error>;
+		fn serialize_char(self, v: char) -> Result<S::Ok, S::Error>;
+		fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error>;
+		fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error>;
+		fn serialize_none(self) -> Result<S::Ok, S::Error>;
+		fn serialize_unit(self) -> Result<S::Ok, S::Error>;
+		fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error>;
+		fn serialize_unit_variant(
+			self,
+			name: &'static str,
+			variant_index: u32,
+			variant: &'static str
+		) -> Result<S::Ok, S::Error>;
+		fn serialize_seq(self, len: Option<usize>) -> Result<S::SerializeSeq, S::Error>;
+		fn serialize_tuple(self, len: usize) -> Result<S::SerializeTuple, S::Error>;
+		fn serialize_tuple_struct(
+			self,
+			name: &'static str,
+			len: usize
+		) -> Result<S::SerializeTupleStruct, S::Error>;
+		fn serialize_tuple_variant(
+			self,
+			name: &'static str,
+			variant_index: u32,
+			variant: &'static str,
+			len: usize
+		) -> Result<S::SerializeTupleVariant, S::Error>;
+		fn serialize_map(self, len: Option<usize>) -> Result<S::SerializeMap, S::Error>;
+		fn serialize_struct(
+			self,
+			name: &'static str,
+			len: usize
+		) -> Result<S::SerializeStruct, S::Error>;
+		fn serialize_struct_variant(
+			self,
+			name: &'static str,
+			variant_index: u32,
+			variant: &'static str,
+			len: usize
+		) -> Result<S::SerializeStructVariant, S::Error>;
+	}
+}
+
+macro_rules! forward_deserialize {
+	($( $method:ident($($arg:ident: $ty:ty),*); )*) => {
+		$(
+			fn $method<V: Visitor<'de>>(self $(, $arg: $ty)*, visitor: V) -> Result<V::Value, D::Error> {
+				self.0.$method($($arg,)* visitor)
+			}
+		)*
+	};
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for SafeIntegers<D> {
+	type Error = D::Error;
+
+	fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+		self.0.deserialize_any(SafeIntegers(visitor))
+	}
+
+	fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+		self.0.deserialize_any(SafeIntegers(visitor))
+	}
+
+	fn is_human_readable(&self) -> bool {
+		self.0.is_human_readable()
+	}
+
+	forward_deserialize! {
+		deserialize_any();
+		deserialize_bool();
+		deserialize_i8();
+		deserialize_i16();
+		deserialize_i32();
+		deserialize_i64();
+		deserialize_i128();
+		deserialize_u8();
+		deserialize_u16();
+		deserialize_u32();
+		deserialize_f32();
+		deserialize_f64();
+		deserialize_char();
+		deserialize_str();
+		deserialize_string();
+		deserialize_bytes();
+		deserialize_byte_buf();
+		deserialize_option();
+		deserialize_unit();
+		deserialize_unit_struct(name: &'static str);
+		deserialize_newtype_struct(name: &'static str);
+		deserialize_seq();
+		deserialize_tuple(len: usize);
+		deserialize_tuple_struct(name: &'static str, len: usize);
+		deserialize_map();
+		deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+		deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+		deserialize_identifier();
+		deserialize_ignored_any();
+	}
+}
+
+/// Visits an unsigned integer given as number or as decimal string.
+impl<'de, V: Visitor<'de>> Visitor<'de> for SafeIntegers<V> {
+	type Value = V::Value;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.expecting(f)?;
+		f.write_str(" or a decimal string")
+	}
+
+	fn visit_u64<E: de::Error>(self, v: u64) -> Result<V::Value, E> {
+		self.0.visit_u64(v)
+	}
+
+	fn visit_u128<E: de::Error>(self, v: u128) -> Result<V::Value, E> {
+		self.0.visit_u128(v)
+	}
+
+	fn visit_i64<E: de::Error>(self, v: i64) -> Result<V::Value, E> {
+		self.0.visit_i64(v)
+	}
+
+	fn visit_i128<E: de::Error>(self, v: i128) -> Result<V::Value, E> {
+		self.0.visit_i128(v)
+	}
+
+	fn visit_f64<E: de::Error>(self, v: f64) -> Result<V::Value, E> {
+		self.0.visit_f64(v)
+	}
+
+	fn visit_str<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+		let Ok(int) = v.parse::<u128>() else {
+			return Err(E::invalid_value(de::Unexpected::Str(v), &self));
+		};
+		match u64::try_from(int) {
+			Ok(int) => self.0.visit_u64(int),
+			Err(_) => self.0.visit_u128(int),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check<T>(value: T, json: &str)
+	where
+		T: Serialize + for<'de> Deserialize<'de> + PartialEq + fmt::Debug + Copy,
+	{
+		assert_eq!(serde_json::to_string(&Compact(value)).unwrap(), json);
+		assert_eq!(serde_json::from_str::<Compact<T>>(json).unwrap(), Compact(value));
+	}
+
+	#[test]
+	fn large_values_are_strings_in_human_readable_formats() {
+		check(7u8, "7");
+		check(u32::MAX, "4294967295");
+		check(MAX_SAFE_INTEGER, "9007199254740991");
+		check(MAX_SAFE_INTEGER + 1, "\"9007199254740992\"");
+		check(u64::MAX, "\"18446744073709551615\"");
+		check(MAX_SAFE_INTEGER as u128, "9007199254740991");
+		check(u128::MAX, "\"340282366920938463463374607431768211455\"");
+	}
+
+	#[test]
+	fn numbers_and_strings_are_accepted() {
+		assert_eq!(serde_json::from_str::<Compact<u64>>("\"12\"").unwrap(), Compact(12));
+		assert_eq!(serde_json::from_str::<Compact<u128>>("12").unwrap(), Compact(12));
+		assert_eq!(
+			serde_json::from_str::<Compact<u128>>("\"18446744073709551616\"").unwrap(),
+			Compact(1 << 64)
+		);
+
+		for invalid in ["\"-1\"", "\"1e3\"", "\"\"", "\"18446744073709551616\""] {
+			assert!(serde_json::from_str::<Compact<u64>>(invalid).is_err(), "{invalid}");
+		}
+		assert!(serde_json::from_str::<Compact<u8>>("\"256\"").is_err());
+	}
+}
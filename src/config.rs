@@ -0,0 +1,440 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configuration of the encoding, shared by encoding and decoding.
+
+use crate::{
+	alloc::vec::Vec, codec::MAX_PREALLOCATION, decode_all::DECODE_ALL_ERR_MSG, Decode, Encode,
+	Error, FixedU32LE, Input, Leb128, LengthCodec, Output, ScaleCompact,
+};
+
+/// Configuration of encoding and decoding.
+///
+/// The configuration is passed to implementations of [`Encode`] and [`Decode`] through
+/// [`Output::config`] and [`Input::config`], and set with [`Encode::encode_with_config`] and
+/// [`DecodeWithConfig::decode_with_config`], or with [`ConfiguredOutput`] and
+/// [`ConfiguredInput`]. The default configuration is SCALE, so a configuration only needs to be
+/// passed to use something else.
+///
+/// Encoding and decoding must use the same configuration: it is not part of the encoding.
+///
+/// Only the encoding and decoding of values take the configuration into account. Functions which
+/// don't have an [`Output`] or an [`Input`] assume the default configuration: e.g.
+/// [`Encode::size_hint`], [`Encode::encoded_size`], `MaxEncodedLen`,
+/// [`DecodeLength`](crate::DecodeLength) and [`EncodeAppend`](crate::EncodeAppend).
+///
+/// ```
+/// # use parity_scale_codec::{CodecConfig, DecodeWithConfig, Encode, LengthPrefix};
+/// let config = CodecConfig::DEFAULT.with_length_prefix(LengthPrefix::FixedU32LE);
+/// let encoded = vec![7u8, 8].encode_with_config(&config);
+/// assert_eq!(encoded, [2, 0, 0, 0, 7, 8]);
+/// assert_eq!(Vec::<u8>::decode_with_config(&mut &encoded[..], &config), Ok(vec![7, 8]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CodecConfig {
+	/// The encoding of the length prefix of `Vec`, slices, `String`, `str`, `VecDeque`,
+	/// `BTreeMap`, `BTreeSet`, `LinkedList`, `BinaryHeap` and `BitVec`, and of the collections of
+	/// this crate, e.g. [`Rle`](crate::Rle).
+	pub length_prefix: LengthPrefix,
+	/// The maximum number of bytes allocated at once while decoding a collection.
+	///
+	/// Collections are allocated in chunks as their items are read, so a length prefix which is
+	/// larger than the input can't cause large allocations. Larger chunks mean fewer
+	/// reallocations. It is 16KiB by default.
+	pub max_preallocation: usize,
+	/// Whether [`DecodeWithConfig::decode_with_config`] fails if the input is not consumed
+	/// completely, like [`DecodeAll`](crate::DecodeAll).
+	pub require_consumed: bool,
+	/// Whether each element of an array, `[T; N]` or `GenericArray`, must consume exactly the
+	/// fixed encoded size of `T` when decoding, if it has one, see
+	/// [`Decode::encoded_fixed_size`].
+	///
+	/// This catches `Decode` implementations reading fewer or more bytes than they claim, which
+	/// would make the following elements and fields decode from the wrong bytes. The error
+	/// describes the offending element when the `chain-error` feature is enabled.
+	pub strict_arrays: bool,
+	/// The byte order of integers and floating point numbers.
+	///
+	/// This doesn't change encodings which define their own byte order, e.g. the one of
+	/// [`Compact`](crate::Compact), [`FixedU32LE`] or [`Be`](crate::Be).
+	pub byte_order: ByteOrder,
+	/// Whether vectors of items with a fixed encoded size, see [`Decode::encoded_fixed_size`],
+	/// read the encodings of many items at once into a buffer on the stack and decode the items
+	/// from it, if the input doesn't know its length.
+	///
+	/// This is much faster for inputs whose reads are costly, e.g. an
+	/// [`IoReader`](crate::IoReader) over an unbuffered file, but slower for inputs whose reads
+	/// are cheap, e.g. an `IoReader` over a `BufReader` or an in-memory cursor.
+	pub buffered_reads: bool,
+}
+
+impl CodecConfig {
+	/// The configuration of SCALE.
+	pub const DEFAULT: CodecConfig = CodecConfig {
+		length_prefix: LengthPrefix::ScaleCompact,
+		max_preallocation: MAX_PREALLOCATION,
+		require_consumed: false,
+		strict_arrays: false,
+		byte_order: ByteOrder::LittleEndian,
+		buffered_reads: false,
+	};
+
+	/// Set the encoding of length prefixes.
+	pub const fn with_length_prefix(mut self, length_prefix: LengthPrefix) -> Self {
+		self.length_prefix = length_prefix;
+		self
+	}
+
+	/// Set the maximum number of bytes allocated at once while decoding a collection.
+	///
+	/// At least one item is allocated at once, whatever the limit.
+	pub const fn with_max_preallocation(mut self, max_preallocation: usize) -> Self {
+		self.max_preallocation = max_preallocation;
+		self
+	}
+
+	/// Set whether decoding must consume the input completely.
+	pub const fn with_require_consumed(mut self, require_consumed: bool) -> Self {
+		self.require_consumed = require_consumed;
+		self
+	}
+
+	/// Set whether array elements must consume exactly their fixed encoded size.
+	pub const fn with_strict_arrays(mut self, strict_arrays: bool) -> Self {
+		self.strict_arrays = strict_arrays;
+		self
+	}
+
+	/// Set the byte order of integers and floating point numbers.
+	pub const fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+		self.byte_order = byte_order;
+		self
+	}
+
+	/// Set whether vectors of items with a fixed encoded size are read in buffered chunks.
+	pub const fn with_buffered_reads(mut self, buffered_reads: bool) -> Self {
+		self.buffered_reads = buffered_reads;
+		self
+	}
+}
+
+impl Default for CodecConfig {
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
+
+/// The encoding of the length prefix of collections, see [`CodecConfig::length_prefix`].
+///
+/// Each variant corresponds to a [`LengthCodec`], which can be used to change the encoding of the
+/// length of a single field with [`VecWith`](crate::VecWith) instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LengthPrefix {
+	/// [`ScaleCompact`], the SCALE encoding.
+	ScaleCompact,
+	/// [`FixedU32LE`].
+	FixedU32LE,
+	/// [`Leb128`].
+	Leb128,
+}
+
+impl LengthPrefix {
+	/// Encode `len` to `dest`.
+	///
+	/// If `len` can't be represented by this encoding, this is reported through
+	/// [`Output::on_encode_error`].
+	pub fn encode_len<W: Output + ?Sized>(self, len: usize, dest: &mut W) {
+		match self {
+			LengthPrefix::ScaleCompact => ScaleCompact::encode_len(len, dest),
+			LengthPrefix::FixedU32LE => FixedU32LE::encode_len(len, dest),
+			LengthPrefix::Leb128 => Leb128::encode_len(len, dest),
+		}
+	}
+
+	/// Decode a length from `input`.
+	pub fn decode_len<I: Input>(self, input: &mut I) -> Result<usize, Error> {
+		match self {
+			LengthPrefix::ScaleCompact => ScaleCompact::decode_len(input),
+			LengthPrefix::FixedU32LE => FixedU32LE::decode_len(input),
+			LengthPrefix::Leb128 => Leb128::decode_len(input),
+		}
+	}
+}
+
+/// The byte order of integers and floating point numbers, see [`CodecConfig::byte_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+	/// The least significant byte first, as in SCALE.
+	LittleEndian,
+	/// The most significant byte first.
+	BigEndian,
+}
+
+impl ByteOrder {
+	/// Whether this is the byte order of the target, so values can be copied as they are in
+	/// memory.
+	pub(crate) const fn is_native(self) -> bool {
+		match self {
+			ByteOrder::LittleEndian => cfg!(target_endian = "little"),
+			ByteOrder::BigEndian => cfg!(target_endian = "big"),
+		}
+	}
+}
+
+/// An [`Output`] with a [`CodecConfig`].
+pub struct ConfiguredOutput<'a, O: Output + ?Sized> {
+	output: &'a mut O,
+	config: CodecConfig,
+}
+
+impl<'a, O: Output + ?Sized> ConfiguredOutput<'a, O> {
+	/// Write to `output`, encoding with `config`.
+	pub fn new(output: &'a mut O, config: CodecConfig) -> Self {
+		ConfiguredOutput { output, config }
+	}
+}
+
+impl<O: Output + ?Sized> Output for ConfiguredOutput<'_, O> {
+	fn write(&mut self, bytes: &[u8]) {
+		self.output.write(bytes)
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		self.output.push_byte(byte)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		&self.config
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		self.output.on_encode_error(error)
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.output.is_exhausted()
+	}
+}
+
+/// An [`Input`] with a [`CodecConfig`].
+pub struct ConfiguredInput<'a, I: Input> {
+	input: &'a mut I,
+	config: CodecConfig,
+}
+
+impl<'a, I: Input> ConfiguredInput<'a, I> {
+	/// Read from `input`, decoding with `config`.
+	pub fn new(input: &'a mut I, config: CodecConfig) -> Self {
+		ConfiguredInput { input, config }
+	}
+}
+
+impl<I: Input> Input for ConfiguredInput<'_, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.input.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.input.read_byte()
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		&self.config
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		self.input.read_remaining_into(into)
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		self.input.seek_forward(n)
+	}
+}
+
+/// Extension trait to [`Decode`] for decoding with a [`CodecConfig`].
+pub trait DecodeWithConfig: Sized {
+	/// Decode `Self` from `input` with `config`.
+	///
+	/// If [`CodecConfig::require_consumed`] is set, an error is returned if not all of the input
+	/// is consumed.
+	fn decode_with_config<I: Input>(input: &mut I, config: &CodecConfig) -> Result<Self, Error>;
+}
+
+impl<T: Decode> DecodeWithConfig for T {
+	fn decode_with_config<I: Input>(input: &mut I, config: &CodecConfig) -> Result<Self, Error> {
+		let mut input = ConfiguredInput::new(input, *config);
+		let value = T::decode(&mut input)?;
+
+		if config.require_consumed {
+			let consumed = match input.remaining_len()? {
+				Some(remaining) => remaining == 0,
+				None => input.read_byte().is_err(),
+			};
+			if !consumed {
+				return Err(DECODE_ALL_ERR_MSG.into());
+			}
+		}
+
+		Ok(value)
+	}
+}
+
+/// Encode `value` with `config`, see [`Encode::encode_with_config`].
+pub(crate) fn encode_with_config<T: Encode + ?Sized>(value: &T, config: &CodecConfig) -> Vec<u8> {
+	let mut encoded = Vec::with_capacity(value.size_hint());
+	value.encode_to(&mut ConfiguredOutput::new(&mut encoded, *config));
+	encoded
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::alloc::{
+		collections::{BTreeMap, VecDeque},
+		string::String,
+	};
+
+	fn roundtrip<T: Encode + Decode + PartialEq + core::fmt::Debug>(
+		value: T,
+		config: &CodecConfig,
+	) -> Vec<u8> {
+		let encoded = value.encode_with_config(config);
+		assert_eq!(T::decode_with_config(&mut &encoded[..], config), Ok(value));
+		encoded
+	}
+
+	#[test]
+	fn default_config_is_scale() {
+		let value = (vec![1u16, 2], String::from("abc"), BTreeMap::from([(1u8, 2u8)]));
+		assert_eq!(roundtrip(value.clone(), &CodecConfig::DEFAULT), value.encode());
+		assert_eq!(CodecConfig::default(), CodecConfig::DEFAULT);
+		assert_eq!(().encode().len(), 0);
+	}
+
+	#[test]
+	fn length_prefix_applies_to_collections() {
+		let leb128 = CodecConfig::DEFAULT.with_length_prefix(LengthPrefix::Leb128);
+		assert_eq!(roundtrip(vec![0u8; 200], &leb128)[..3], [0xc8, 0x01, 0]);
+		assert_eq!(roundtrip(String::from("hi"), &leb128), [2, b'h', b'i']);
+
+		let fixed = CodecConfig::DEFAULT.with_length_prefix(LengthPrefix::FixedU32LE);
+		assert_eq!(
+			roundtrip((VecDeque::from([vec![1u8]]), BTreeMap::from([(3u8, ())])), &fixed),
+			[1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 0, 0, 0, 3]
+		);
+
+		assert_eq!(roundtrip(crate::Rle(vec![5u8; 3]), &fixed), [1, 0, 0, 0, 3, 0, 0, 0, 5]);
+		#[cfg(feature = "bit-vec")]
+		assert_eq!(roundtrip(bitvec::bitvec![u8, bitvec::order::Lsb0; 1, 0], &leb128), [2, 0b01]);
+
+		let encoded = vec![1u8; 3].encode_with_config(&fixed);
+		let mut bytes = &encoded[..];
+		let mut input = ConfiguredInput::new(&mut bytes, fixed);
+		assert_eq!(crate::decode_with_max_len::<Vec<u8>, _>(&mut input, 3), Ok(vec![1; 3]));
+
+		// The configuration is not part of the encoding.
+		let encoded = vec![1u32; 70].encode_with_config(&fixed);
+		assert!(Vec::<u32>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn byte_order_applies_to_numbers() {
+		let big = CodecConfig::DEFAULT.with_byte_order(ByteOrder::BigEndian);
+		assert_eq!(roundtrip(0x0102u16, &big), [1, 2]);
+		assert_eq!(roundtrip(-2i32, &big), [0xff, 0xff, 0xff, 0xfe]);
+		assert_eq!(roundtrip(1.0f32, &big), 1.0f32.to_be_bytes());
+		assert_eq!(roundtrip([1u16, 2], &big), [0, 1, 0, 2]);
+		assert_eq!(roundtrip(vec![0x0102u16; 70], &big)[2..6], [1, 2, 1, 2]);
+
+		// Encodings with their own byte order don't change.
+		assert_eq!(roundtrip(crate::Compact(0x4000u32), &big), crate::Compact(0x4000u32).encode());
+		assert_eq!(roundtrip(crate::Be(0x0102u16), &big), [1, 2]);
+		let fixed = big.with_length_prefix(LengthPrefix::FixedU32LE);
+		assert_eq!(roundtrip(vec![3u8], &fixed), [1, 0, 0, 0, 3]);
+	}
+
+	/// An input recording the sizes of the allocations.
+	struct AllocRecorder<'a> {
+		input: &'a [u8],
+		allocs: Vec<usize>,
+	}
+
+	impl Input for AllocRecorder<'_> {
+		fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+			self.input.remaining_len()
+		}
+
+		fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+			self.input.read(into)
+		}
+
+		fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+			self.allocs.push(size);
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn max_preallocation_sets_chunk_size() {
+		let encoded = vec![7u64; 5].encode();
+		let mut input = AllocRecorder { input: &encoded, allocs: Vec::new() };
+		let config = CodecConfig::DEFAULT.with_max_preallocation(16);
+		assert_eq!(Vec::<u64>::decode_with_config(&mut input, &config), Ok(vec![7; 5]));
+		assert_eq!(input.allocs, [16, 16, 8]);
+
+		// At least one item is allocated at once.
+		let encoded = vec![vec![1u8, 2]; 3].encode();
+		let mut input = AllocRecorder { input: &encoded, allocs: Vec::new() };
+		let config = config.with_max_preallocation(0);
+		assert_eq!(
+			Vec::<Vec<u8>>::decode_with_config(&mut input, &config),
+			Ok(vec![vec![1, 2]; 3])
+		);
+		assert_eq!(input.allocs.len(), 3 + 3 * 2);
+	}
+
+	#[test]
+	fn require_consumed_works() {
+		let config = CodecConfig::DEFAULT.with_require_consumed(true);
+		let encoded = (1u8, 2u8).encode();
+		assert_eq!(u16::decode_with_config(&mut &encoded[..], &config), Ok(0x0201));
+		assert_eq!(
+			u8::decode_with_config(&mut &encoded[..], &config),
+			Err(DECODE_ALL_ERR_MSG.into())
+		);
+		assert_eq!(u8::decode_with_config(&mut &encoded[..], &CodecConfig::DEFAULT), Ok(1));
+	}
+}
@@ -17,7 +17,8 @@
 
 use crate::{alloc::boxed::Box, MaxEncodedLen};
 use core::{
-	marker::PhantomData,
+	cmp::{Ordering, Reverse},
+	marker::{PhantomData, PhantomPinned},
 	num::*,
 	ops::{Range, RangeInclusive},
 	time::Duration,
@@ -62,10 +63,11 @@ mark_cel!(
 	NonZeroI128
 );
 
-mark_cel!(Duration);
+mark_cel!(Duration, Ordering, PhantomPinned);
 mark_cel!(PhantomData<T>);
 mark_cel!(Box<T>);
 mark_cel!(Range<T>, RangeInclusive<T>);
+mark_cel!(Reverse<T>);
 
 // `Option`, `Result` and `Compact` are sum types, therefore not `CEL`.
 
@@ -131,6 +133,9 @@ mod tests {
 
 	test_cel_compliance!(Duration);
 
+	type SortKey = (Reverse<u64>, Ordering);
+	test_cel_compliance!(SortKey);
+
 	type BoxedArithmetic = Box<(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128)>;
 	test_cel_compliance!(BoxedArithmetic);
 
@@ -32,6 +32,51 @@ impl<'a, I: crate::Input> CountedInput<'a, I> {
 	pub fn count(&self) -> u64 {
 		self.counter
 	}
+
+	/// Run `f` on this input and return its result together with the number of bytes successfully
+	/// read while it was running.
+	///
+	/// This can be used to measure a sub-section of a decode. Calls can be nested, the total
+	/// [`Self::count`] keeps including the bytes read in every section.
+	pub fn scoped<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> (R, u64) {
+		let start = self.counter;
+		let res = f(self);
+		(res, self.counter.saturating_sub(start))
+	}
+}
+
+/// Decode `T` from `input`, augmenting a decoding error with the byte offset at which it happened.
+///
+/// The offset is the number of bytes that were successfully read from `input` by this call before
+/// the error occurred. It is reported together with the name of `T`, as given by
+/// [`core::any::type_name`], and the descriptions chained by the nested decoders (e.g. the names of
+/// the fields and variants for derived implementations) describe where in `T` and why decoding
+/// failed.
+///
+/// The offset is only added to the error when the `chain-error` feature is enabled, otherwise
+/// this is the same as `T::decode`.
+pub fn decode_with_diagnostics<T: crate::Decode, I: crate::Input>(
+	input: &mut I,
+) -> Result<T, crate::Error> {
+	#[cfg(feature = "chain-error")]
+	{
+		use core::fmt::Write;
+
+		let mut input = CountedInput::new(input);
+		T::decode(&mut input).map_err(|e| {
+			let mut desc = crate::alloc::string::String::new();
+			let _ = write!(
+				desc,
+				"Could not decode `{}` at byte offset {}",
+				core::any::type_name::<T>(),
+				input.count(),
+			);
+			e.chain(desc)
+		})
+	}
+
+	#[cfg(not(feature = "chain-error"))]
+	T::decode(input)
 }
 
 impl<I: crate::Input> crate::Input for CountedInput<'_, I> {
@@ -58,12 +103,41 @@ impl<I: crate::Input> crate::Input for CountedInput<'_, I> {
 	fn descend_ref(&mut self) -> Result<(), crate::Error> {
 		self.input.descend_ref()
 	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), crate::Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &crate::CodecConfig {
+		self.input.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), crate::Error> {
+		self.input.seek_forward(n).inspect(|_r| {
+			self.counter = self.counter.saturating_add(n.try_into().unwrap_or(u64::MAX));
+		})
+	}
+
+	fn read_remaining_into(
+		&mut self,
+		into: &mut crate::alloc::vec::Vec<u8>,
+	) -> Result<(), crate::Error> {
+		let start = into.len();
+		let result = self.input.read_remaining_into(into);
+		let read = into.len() - start;
+		self.counter = self.counter.saturating_add(read.try_into().unwrap_or(u64::MAX));
+		result
+	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::Input;
+	use crate::{Decode, Encode, Input, MemTrackingInput};
 
 	#[test]
 	fn test_counted_input_input_impl() {
@@ -105,6 +179,23 @@ mod test {
 		assert_eq!(counted_input.count(), 5);
 	}
 
+	#[test]
+	fn counted_input_forwards_seek_forward_and_interner() {
+		let mut input = &[1u8, 2, 3][..];
+		let mut counted_input = CountedInput::new(&mut input);
+		counted_input.seek_forward(2).unwrap();
+		assert_eq!(counted_input.count(), 2);
+		assert_eq!(counted_input.read_byte(), Ok(3));
+		assert!(counted_input.interner().is_none());
+
+		#[cfg(target_has_atomic = "ptr")]
+		{
+			let mut interner = crate::Interner::new();
+			let mut input = crate::InterningInput::new(&[][..], &mut interner);
+			assert!(CountedInput::new(&mut input).interner().is_some());
+		}
+	}
+
 	#[test]
 	fn test_counted_input_max_count_read_byte() {
 		let max_exact_count = u64::MAX - 1;
@@ -144,4 +235,92 @@ mod test {
 
 		assert_eq!(counted_input.count(), u64::MAX);
 	}
+
+	#[test]
+	fn test_counted_input_scoped() {
+		let encoded = (1u8, 2u32, vec![3u16, 4], 5u64).encode();
+		let mut input = &encoded[..];
+		let mut counted_input = CountedInput::new(&mut input);
+
+		u8::decode(&mut counted_input).unwrap();
+
+		let ((res, inner), outer) = counted_input.scoped(|input| {
+			u32::decode(input).unwrap();
+			input.scoped(Vec::<u16>::decode)
+		});
+		assert_eq!(res.unwrap(), vec![3, 4]);
+		assert_eq!(inner, 5);
+		assert_eq!(outer, 9);
+		assert_eq!(counted_input.count(), 10);
+
+		let (res, count) = counted_input.scoped(u128::decode);
+		assert!(res.is_err());
+		assert_eq!(count, 0);
+
+		let (res, count) = counted_input.scoped(u64::decode);
+		assert_eq!(res.unwrap(), 5);
+		assert_eq!(count, 8);
+		assert_eq!(counted_input.count(), encoded.len() as u64);
+	}
+
+	#[test]
+	fn test_counted_input_forwards_mem_tracking() {
+		let encoded = vec![vec![1u8; 10], vec![2u8; 20]].encode();
+
+		// `CountedInput` wrapping a `MemTrackingInput`.
+		let mut input = &encoded[..];
+		let mut mem_tracking_input = MemTrackingInput::new(&mut input, 1000);
+		let mut counted_input = CountedInput::new(&mut mem_tracking_input);
+		let decoded = Vec::<Vec<u8>>::decode(&mut counted_input).unwrap();
+		assert_eq!(decoded, vec![vec![1u8; 10], vec![2u8; 20]]);
+		assert_eq!(counted_input.count(), encoded.len() as u64);
+		assert!(mem_tracking_input.used_mem() >= 30);
+
+		let mut input = &encoded[..];
+		let mut mem_tracking_input = MemTrackingInput::new(&mut input, 20);
+		let mut counted_input = CountedInput::new(&mut mem_tracking_input);
+		assert_eq!(
+			Vec::<Vec<u8>>::decode(&mut counted_input),
+			Err("Heap memory limit exceeded while decoding".into())
+		);
+
+		// `MemTrackingInput` wrapping a `CountedInput`.
+		let mut input = &encoded[..];
+		let mut counted_input = CountedInput::new(&mut input);
+		let mut mem_tracking_input = MemTrackingInput::new(&mut counted_input, 1000);
+		Vec::<Vec<u8>>::decode(&mut mem_tracking_input).unwrap();
+		assert!(mem_tracking_input.used_mem() >= 30);
+		assert_eq!(counted_input.count(), encoded.len() as u64);
+	}
+
+	/// Check the offset and the cause of a `decode_with_diagnostics` error, but not the name of
+	/// the type, which isn't stable.
+	#[cfg(feature = "chain-error")]
+	fn check_diagnostics(err: crate::Error, offset: u64, cause: &str) {
+		let err = err.to_string();
+		let (context, rest) = err.split_once('\n').unwrap();
+		assert!(context.starts_with("Could not decode `"), "{err}");
+		assert!(context.ends_with(&format!("` at byte offset {offset}:")), "{err}");
+		assert_eq!(rest, format!("\t{cause}\n"));
+	}
+
+	#[cfg(feature = "chain-error")]
+	#[test]
+	fn test_decode_with_diagnostics() {
+		let encoded = (1u8, 2u32, vec![3u16, 4]).encode();
+
+		let decoded = decode_with_diagnostics::<(u8, u32, Vec<u16>), _>(&mut &encoded[..]);
+		assert_eq!(decoded.unwrap(), (1, 2, vec![3, 4]));
+
+		// Truncate the input in the middle of the `u32`.
+		let err = decode_with_diagnostics::<(u8, u32, Vec<u16>), _>(&mut &encoded[..3]);
+		check_diagnostics(err.unwrap_err(), 1, "Not enough data to fill buffer");
+
+		// Truncate the input in the middle of the vector, the length prefix is read.
+		let err = decode_with_diagnostics::<(u8, u32, Vec<u16>), _>(&mut &encoded[..9]);
+		check_diagnostics(err.unwrap_err(), 6, "Not enough data to decode vector");
+
+		let err = decode_with_diagnostics::<Option<u8>, _>(&mut &[2u8][..]);
+		check_diagnostics(err.unwrap_err(), 1, "unexpected first byte decoding Option");
+	}
 }
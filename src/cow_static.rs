@@ -0,0 +1,245 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte blobs which are borrowed from static data when possible.
+
+use core::{fmt, marker::PhantomData, ops::Deref};
+
+use crate::{
+	alloc::{borrow::Cow, vec::Vec},
+	Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
+};
+
+/// A table of static byte blobs that decoded blobs are interned into.
+///
+/// Used by [`CowStatic`] to share the storage of blobs which are decoded many times, e.g. the same
+/// configuration blob in thousands of decoded values.
+pub trait InternTable {
+	/// The static blob equal to `bytes`, if the table has one.
+	fn intern(bytes: &[u8]) -> Option<&'static [u8]>;
+}
+
+/// An [`InternTable`] without any blob, so every decoded blob is owned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoIntern;
+
+impl InternTable for NoIntern {
+	fn intern(_bytes: &[u8]) -> Option<&'static [u8]> {
+		None
+	}
+}
+
+/// A `Cow<'static, [u8]>` which is decoded as a borrow of the static blob of the table `T` equal
+/// to the decoded bytes, if there is one.
+///
+/// It is encoded like a `Vec<u8>`. When decoding, the bytes are read into a temporary buffer,
+/// which is dropped if `T` has an equal blob. So only blobs missing from `T` stay on the heap.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, CowStatic, Encode, InternTable};
+/// # use std::borrow::Cow;
+/// const CONFIG: &[u8] = b"max_peers=25";
+///
+/// struct Configs;
+///
+/// impl InternTable for Configs {
+///     fn intern(bytes: &[u8]) -> Option<&'static [u8]> {
+///         (bytes == CONFIG).then_some(CONFIG)
+///     }
+/// }
+///
+/// let encoded = CONFIG.to_vec().encode();
+/// let (decoded, _) = decode_prefix::<CowStatic<Configs>>(&encoded).unwrap();
+/// assert!(matches!(decoded.as_cow(), Cow::Borrowed(_)));
+/// assert_eq!(decoded.encode(), encoded);
+///
+/// let encoded = b"max_peers=50".to_vec().encode();
+/// let (decoded, _) = decode_prefix::<CowStatic<Configs>>(&encoded).unwrap();
+/// assert!(matches!(decoded.as_cow(), Cow::Owned(_)));
+/// ```
+///
+/// With the `bytes` feature, a borrowed blob is converted into `bytes::Bytes` without copying.
+pub struct CowStatic<T = NoIntern> {
+	value: Cow<'static, [u8]>,
+	_table: PhantomData<fn() -> T>,
+}
+
+impl<T> CowStatic<T> {
+	/// Wrap `value`.
+	pub fn new(value: Cow<'static, [u8]>) -> Self {
+		CowStatic { value, _table: PhantomData }
+	}
+
+	/// The wrapped `Cow`.
+	pub fn as_cow(&self) -> &Cow<'static, [u8]> {
+		&self.value
+	}
+
+	/// Unwrap the `Cow`.
+	pub fn into_inner(self) -> Cow<'static, [u8]> {
+		self.value
+	}
+}
+
+impl<T: InternTable> CowStatic<T> {
+	/// Wrap `bytes`, borrowing the equal blob of `T` if there is one.
+	pub fn interned(bytes: Vec<u8>) -> Self {
+		match T::intern(&bytes) {
+			Some(blob) => Self::new(Cow::Borrowed(blob)),
+			None => Self::new(Cow::Owned(bytes)),
+		}
+	}
+}
+
+impl<T> Deref for CowStatic<T> {
+	type Target = [u8];
+
+	fn deref(&self) -> &[u8] {
+		&self.value
+	}
+}
+
+impl<T> AsRef<[u8]> for CowStatic<T> {
+	fn as_ref(&self) -> &[u8] {
+		&self.value
+	}
+}
+
+impl<T> From<Cow<'static, [u8]>> for CowStatic<T> {
+	fn from(value: Cow<'static, [u8]>) -> Self {
+		Self::new(value)
+	}
+}
+
+impl<T> From<&'static [u8]> for CowStatic<T> {
+	fn from(value: &'static [u8]) -> Self {
+		Self::new(Cow::Borrowed(value))
+	}
+}
+
+impl<T> From<Vec<u8>> for CowStatic<T> {
+	fn from(value: Vec<u8>) -> Self {
+		Self::new(Cow::Owned(value))
+	}
+}
+
+#[cfg(feature = "bytes")]
+impl<T> From<CowStatic<T>> for bytes::Bytes {
+	fn from(value: CowStatic<T>) -> Self {
+		match value.value {
+			Cow::Borrowed(blob) => bytes::Bytes::from_static(blob),
+			Cow::Owned(bytes) => bytes::Bytes::from(bytes),
+		}
+	}
+}
+
+// Implemented by hand to not require `T` to implement the traits.
+
+impl<T> Clone for CowStatic<T> {
+	fn clone(&self) -> Self {
+		Self::new(self.value.clone())
+	}
+}
+
+impl<T> fmt::Debug for CowStatic<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("CowStatic").field(&self.value).finish()
+	}
+}
+
+impl<T> PartialEq for CowStatic<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.value == other.value
+	}
+}
+
+impl<T> Eq for CowStatic<T> {}
+
+impl<T> Encode for CowStatic<T> {
+	fn size_hint(&self) -> usize {
+		self.value.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.value[..].encode_to(dest)
+	}
+
+	fn encoded_size(&self) -> usize {
+		self.value[..].encoded_size()
+	}
+}
+
+impl<T> EncodeLike for CowStatic<T> {}
+impl<T> EncodeLike<Vec<u8>> for CowStatic<T> {}
+impl<T> EncodeLike<CowStatic<T>> for Vec<u8> {}
+impl<T> EncodeLike<CowStatic<T>> for &[u8] {}
+
+impl<T: InternTable> Decode for CowStatic<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Vec::<u8>::decode(input).map(Self::interned)
+	}
+}
+
+impl<T: InternTable> DecodeWithMemTracking for CowStatic<T> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::DecodeWithMemLimit;
+
+	const BLOB: &[u8] = &[1, 2, 3, 4];
+
+	struct Table;
+
+	impl InternTable for Table {
+		fn intern(bytes: &[u8]) -> Option<&'static [u8]> {
+			(bytes == BLOB).then_some(BLOB)
+		}
+	}
+
+	#[test]
+	fn decoded_blobs_are_interned() {
+		let encoded = BLOB.encode();
+		let first = CowStatic::<Table>::decode(&mut &encoded[..]).unwrap();
+		let second = CowStatic::<Table>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(first.as_ptr(), BLOB.as_ptr());
+		assert_eq!(second.as_ptr(), BLOB.as_ptr());
+		assert_eq!(first.encode(), encoded);
+		assert_eq!(first.encoded_size(), encoded.len());
+
+		let other = vec![5u8; 3].encode();
+		let decoded = CowStatic::<Table>::decode(&mut &other[..]).unwrap();
+		assert_eq!(decoded.into_inner(), Cow::<[u8]>::Owned(vec![5; 3]));
+
+		let decoded = CowStatic::<NoIntern>::decode(&mut &encoded[..]).unwrap();
+		assert!(matches!(decoded.as_cow(), Cow::Owned(_)));
+	}
+
+	#[test]
+	fn decode_respects_mem_limit() {
+		let encoded = BLOB.encode();
+		assert!(CowStatic::<Table>::decode_with_mem_limit(&mut &encoded[..], 2).is_err());
+		assert!(CowStatic::<Table>::decode_with_mem_limit(&mut &encoded[..], 5).is_ok());
+	}
+
+	#[cfg(feature = "bytes")]
+	#[test]
+	fn borrowed_blobs_convert_to_bytes_without_copy() {
+		let bytes = bytes::Bytes::from(CowStatic::<Table>::from(BLOB));
+		assert_eq!(bytes.as_ptr(), BLOB.as_ptr());
+
+		let bytes = bytes::Bytes::from(CowStatic::<Table>::from(vec![7u8]));
+		assert_eq!(&bytes[..], &[7]);
+	}
+}
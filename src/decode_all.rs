@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Decode, Error};
+use crate::{alloc::vec::Vec, Decode, Error};
 
 /// The error message returned when `decode_all` fails.
 pub(crate) const DECODE_ALL_ERR_MSG: &str = "Input buffer has still data left after decoding!";
@@ -38,6 +38,62 @@ impl<T: Decode> DecodeAll for T {
 	}
 }
 
+/// Decode a `T` from the start of `bytes`, returning it with the bytes after its encoding.
+///
+/// This is the same as decoding from `&mut bytes`, which shortens the slice to the remaining bytes,
+/// but doesn't modify `bytes`, so the remaining bytes aren't mistaken for the whole input.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode};
+/// let encoded = (1u16, String::from("rest")).encode();
+/// let (value, rest) = decode_prefix::<u16>(&encoded).unwrap();
+/// assert_eq!(value, 1);
+/// assert_eq!(decode_prefix::<String>(rest), Ok((String::from("rest"), &[][..])));
+/// ```
+///
+/// Use [`DecodeAll::decode_all`] if the bytes must be exactly the encoding of a `T`.
+pub fn decode_prefix<T: Decode>(bytes: &[u8]) -> Result<(T, &[u8]), Error> {
+	let mut rest = bytes;
+	let value = T::decode(&mut rest)?;
+	Ok((value, rest))
+}
+
+/// Decode values of type `T` from `input` until it is exhausted.
+///
+/// This is for data consisting of several concatenated encodings of `T` without a length prefix.
+/// An error is returned if the remaining data can't be decoded as a `T`, e.g. because only a part
+/// of a value is left, or if `T` consumes no data at all, as that would never exhaust the input.
+///
+/// ```
+/// # use parity_scale_codec::{decode_all_repeated, Encode};
+/// let mut data = 1u16.encode();
+/// data.extend(2u16.encode());
+/// assert_eq!(decode_all_repeated::<u16>(&data), Ok(vec![1, 2]));
+/// assert!(decode_all_repeated::<u16>(&data[..3]).is_err());
+/// ```
+pub fn decode_all_repeated<T: Decode>(mut input: &[u8]) -> Result<Vec<T>, Error> {
+	let mut values = match T::encoded_fixed_size() {
+		Some(0) => return Err(DECODE_ALL_REPEATED_EMPTY_MSG.into()),
+		Some(size) if input.len() % size != 0 =>
+			return Err("Input length is not a multiple of the encoded size of the values".into()),
+		Some(size) => Vec::with_capacity(input.len() / size),
+		None => Vec::new(),
+	};
+
+	while !input.is_empty() {
+		let remaining = input.len();
+		values.push(T::decode(&mut input)?);
+		if input.len() == remaining {
+			return Err(DECODE_ALL_REPEATED_EMPTY_MSG.into());
+		}
+	}
+
+	Ok(values)
+}
+
+const DECODE_ALL_REPEATED_EMPTY_MSG: &str =
+	"Value consumed no input, so the input is never exhausted";
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -93,6 +149,38 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn decode_prefix_returns_the_rest() {
+		let encoded = (vec![1u32, 2], 3u8, 4u8).encode();
+		let (data, rest) = decode_prefix::<Vec<u32>>(&encoded).unwrap();
+		assert_eq!(data, [1, 2]);
+		assert_eq!(rest, [3, 4]);
+		assert_eq!(decode_prefix::<u16>(rest), Ok((0x0403, &[][..])));
+		assert!(decode_prefix::<u32>(rest).is_err());
+	}
+
+	#[test]
+	fn decode_all_repeated_works() {
+		let values = vec![
+			TestStruct { data: vec![1, 2], other: 3, compact: Compact(4) },
+			TestStruct { data: vec![], other: 5, compact: Compact(u128::MAX) },
+		];
+		let encoded = values.iter().flat_map(Encode::encode).collect::<Vec<_>>();
+		let decoded = decode_all_repeated::<TestStruct>(&encoded).unwrap();
+		assert_eq!(decoded.encode(), values.encode());
+
+		assert!(decode_all_repeated::<TestStruct>(&encoded[..encoded.len() - 1]).is_err());
+		assert_eq!(decode_all_repeated::<TestStruct>(&[]).unwrap().len(), 0);
+
+		assert_eq!(decode_all_repeated::<u32>(&[1, 0, 0, 0, 2, 0, 0, 0]), Ok(vec![1, 2]));
+		assert!(decode_all_repeated::<u32>(&[1, 0, 0, 0, 2]).is_err());
+		assert_eq!(decode_all_repeated::<()>(&[1]), Err(DECODE_ALL_REPEATED_EMPTY_MSG.into()));
+		assert_eq!(
+			decode_all_repeated::<crate::Skipped<u8>>(&[1]),
+			Err(DECODE_ALL_REPEATED_EMPTY_MSG.into())
+		);
+	}
+
 	#[test]
 	fn decode_all_works() {
 		test_decode_all! {
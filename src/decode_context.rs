@@ -0,0 +1,454 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reuse of the allocations of decoded values across decodes.
+//!
+//! Decoding a `Vec` or `String` allocates a new buffer each time. A loop decoding many values of
+//! the same type can instead keep the buffers of the values it is done with in a
+//! [`DecodeContext`], and decode the next values with [`DecodeWithContext`], which takes the
+//! buffers from the context, clears them and decodes into them:
+//!
+//! ```
+//! use parity_scale_codec::{DecodeContext, DecodeWithContext, Encode};
+//!
+//! let messages = [(1u32, vec![1u8, 2, 3], String::from("a")), (2, vec![4], String::from("b"))];
+//! let encoded = messages.iter().map(Encode::encode).collect::<Vec<_>>();
+//!
+//! let mut ctx = DecodeContext::new();
+//! for (message, encoded) in messages.iter().zip(&encoded) {
+//!     let decoded = <(u32, Vec<u8>, String)>::decode_with_context(&mut &encoded[..], &mut ctx)?;
+//!     assert_eq!(&decoded, message);
+//!     // Give the buffers back to be reused by the next decode.
+//!     ctx.recycle(decoded);
+//! }
+//! assert_eq!(ctx.pooled::<u8>(), 2);
+//! # Ok::<(), parity_scale_codec::Error>(())
+//! ```
+//!
+//! Other types can implement the traits by decoding and recycling their fields:
+//!
+//! ```
+//! use parity_scale_codec::{
+//!     Decode, DecodeContext, DecodeWithContext, Encode, Error, Input, Recycle,
+//! };
+//!
+//! # #[cfg(feature = "derive")]
+//! #[derive(Encode, Decode)]
+//! struct Block {
+//!     number: u64,
+//!     extrinsics: Vec<Vec<u8>>,
+//! }
+//! # #[cfg(not(feature = "derive"))]
+//! # struct Block { number: u64, extrinsics: Vec<Vec<u8>> }
+//! # #[cfg(not(feature = "derive"))]
+//! # impl Decode for Block {
+//! #     fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+//! #         Ok(Block { number: Decode::decode(input)?, extrinsics: Decode::decode(input)? })
+//! #     }
+//! # }
+//!
+//! impl DecodeWithContext for Block {
+//!     fn decode_with_context<I: Input>(
+//!         input: &mut I,
+//!         ctx: &mut DecodeContext,
+//!     ) -> Result<Self, Error> {
+//!         Ok(Block {
+//!             number: u64::decode_with_context(input, ctx)?,
+//!             extrinsics: Vec::decode_with_context(input, ctx)?,
+//!         })
+//!     }
+//! }
+//!
+//! impl Recycle for Block {
+//!     fn recycle(self, ctx: &mut DecodeContext) {
+//!         ctx.recycle(self.extrinsics);
+//!     }
+//! }
+//! ```
+//!
+//! The buffers are only reused if they are large enough: like for decoding without a context, a
+//! buffer is grown by [`Input::on_before_alloc_mem`] checked steps if it is too small.
+
+use core::any::{Any, TypeId};
+
+use crate::{
+	alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec},
+	codec::{decode_len, decode_vec_chunked_into},
+	Decode, Error, Input,
+};
+
+/// The default maximum number of buffers pooled per item type, see
+/// [`DecodeContext::with_max_pooled`].
+pub const DEFAULT_MAX_POOLED: usize = 64;
+
+/// Pools of the buffers of decoded values, to be reused by [`DecodeWithContext`].
+///
+/// There is one pool of `Vec<T>` for each item type `T`. `String`s are pooled as `Vec<u8>`.
+pub struct DecodeContext {
+	pools: BTreeMap<TypeId, Box<dyn Any>>,
+	max_pooled: usize,
+}
+
+impl DecodeContext {
+	/// Create a context without pooled buffers, pooling up to [`DEFAULT_MAX_POOLED`] buffers per
+	/// item type.
+	pub fn new() -> Self {
+		Self::with_max_pooled(DEFAULT_MAX_POOLED)
+	}
+
+	/// Create a context without pooled buffers, pooling up to `max_pooled` buffers per item type.
+	///
+	/// Further buffers which are recycled are dropped.
+	pub fn with_max_pooled(max_pooled: usize) -> Self {
+		DecodeContext { pools: BTreeMap::new(), max_pooled }
+	}
+
+	/// Give the buffers of `value` to the context, to be reused by the next decodes.
+	pub fn recycle<T: Recycle>(&mut self, value: T) {
+		value.recycle(self)
+	}
+
+	/// The number of pooled `Vec<T>` buffers.
+	pub fn pooled<T: 'static>(&self) -> usize {
+		self.pools
+			.get(&TypeId::of::<T>())
+			.and_then(|pool| pool.downcast_ref::<Vec<Vec<T>>>())
+			.map_or(0, Vec::len)
+	}
+
+	/// Drop all pooled buffers.
+	pub fn clear(&mut self) {
+		self.pools.clear()
+	}
+
+	/// The pool of buffers of `T`.
+	///
+	/// Never `None`, as pools are keyed by the `TypeId` of their items, but decoding doesn't panic.
+	fn pool<T: 'static>(&mut self) -> Option<&mut Vec<Vec<T>>> {
+		self.pools
+			.entry(TypeId::of::<T>())
+			.or_insert_with(|| Box::new(Vec::<Vec<T>>::new()))
+			.downcast_mut()
+	}
+
+	/// Take an empty buffer from the pool, or a new one if the pool is empty.
+	///
+	/// Empty values don't need a buffer, so they shouldn't take one.
+	fn take<T: 'static>(&mut self) -> Vec<T> {
+		self.pool().and_then(Vec::pop).unwrap_or_default()
+	}
+
+	/// Clear `vec` and put it in the pool, unless it doesn't own an allocation or the pool is full.
+	fn put<T: 'static>(&mut self, mut vec: Vec<T>) {
+		if vec.capacity() == 0 {
+			return;
+		}
+		let max_pooled = self.max_pooled;
+		let Some(pool) = self.pool() else { return };
+		if pool.len() < max_pooled {
+			vec.clear();
+			pool.push(vec);
+		}
+	}
+}
+
+impl Default for DecodeContext {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl core::fmt::Debug for DecodeContext {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("DecodeContext")
+			.field("pools", &self.pools.len())
+			.field("max_pooled", &self.max_pooled)
+			.finish()
+	}
+}
+
+/// Trait for types that can be decoded reusing the buffers pooled in a [`DecodeContext`].
+///
+/// The decoded value is the same as the one of [`Decode::decode`].
+pub trait DecodeWithContext: Decode {
+	/// Attempt to decode the value from input, taking buffers from `ctx`.
+	fn decode_with_context<I: Input>(input: &mut I, ctx: &mut DecodeContext)
+		-> Result<Self, Error>;
+}
+
+/// Trait for types that can give their buffers to a [`DecodeContext`].
+pub trait Recycle: Sized {
+	/// Give the buffers of `self` to `ctx`.
+	///
+	/// By default, `self` is dropped.
+	fn recycle(self, _ctx: &mut DecodeContext) {}
+}
+
+macro_rules! impl_without_buffers {
+	( $( $t:ty ),* $(,)? ) => { $(
+		impl DecodeWithContext for $t {
+			fn decode_with_context<I: Input>(
+				input: &mut I,
+				_ctx: &mut DecodeContext,
+			) -> Result<Self, Error> {
+				Self::decode(input)
+			}
+		}
+
+		impl Recycle for $t {}
+	)* }
+}
+
+impl_without_buffers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, bool, f32, f64);
+
+impl<T: DecodeWithContext> DecodeWithContext for Option<T> {
+	fn decode_with_context<I: Input>(
+		input: &mut I,
+		ctx: &mut DecodeContext,
+	) -> Result<Self, Error> {
+		match input
+			.read_byte()
+			.map_err(|e| e.chain("Could not decode variant byte for `Option`"))?
+		{
+			0 => Ok(None),
+			1 => Ok(Some(
+				T::decode_with_context(input, ctx)
+					.map_err(|e| e.chain("Could not decode `Option::Some(T)`"))?,
+			)),
+			_ => Err("unexpected first byte decoding Option".into()),
+		}
+	}
+}
+
+impl<T: Recycle> Recycle for Option<T> {
+	fn recycle(self, ctx: &mut DecodeContext) {
+		if let Some(value) = self {
+			value.recycle(ctx)
+		}
+	}
+}
+
+impl<T: DecodeWithContext + 'static> DecodeWithContext for Vec<T> {
+	fn decode_with_context<I: Input>(
+		input: &mut I,
+		ctx: &mut DecodeContext,
+	) -> Result<Self, Error> {
+		let len = decode_len(input)?;
+		if len == 0 {
+			return Ok(Vec::new());
+		}
+		let mut vec = ctx.take::<T>();
+
+		if let Some(bytes) = (&mut vec as &mut dyn Any).downcast_mut::<Vec<u8>>() {
+			read_bytes_into(input, len, bytes)?;
+			return Ok(vec);
+		}
+
+		input.descend_ref()?;
+		decode_vec_chunked_into(input, len, &mut vec, |input, vec, chunk_len| {
+			for _ in 0..chunk_len {
+				vec.push(T::decode_with_context(input, ctx)?);
+			}
+			Ok(())
+		})?;
+		input.ascend_ref();
+
+		Ok(vec)
+	}
+}
+
+impl<T: Recycle + 'static> Recycle for Vec<T> {
+	fn recycle(mut self, ctx: &mut DecodeContext) {
+		if core::mem::needs_drop::<T>() {
+			for item in self.drain(..) {
+				item.recycle(ctx);
+			}
+		}
+		ctx.put(self)
+	}
+}
+
+impl DecodeWithContext for String {
+	fn decode_with_context<I: Input>(
+		input: &mut I,
+		ctx: &mut DecodeContext,
+	) -> Result<Self, Error> {
+		let len = decode_len(input)?;
+		if len == 0 {
+			return Ok(String::new());
+		}
+		let mut bytes = ctx.take::<u8>();
+		read_bytes_into(input, len, &mut bytes)?;
+		String::from_utf8(bytes).map_err(|_| "Invalid utf8 sequence".into())
+	}
+}
+
+impl Recycle for String {
+	fn recycle(self, ctx: &mut DecodeContext) {
+		ctx.put(self.into_bytes())
+	}
+}
+
+/// Read `len` bytes into the empty `bytes`.
+fn read_bytes_into<I: Input>(input: &mut I, len: usize, bytes: &mut Vec<u8>) -> Result<(), Error> {
+	if let Some(input_len) = input.remaining_len()? {
+		if input_len < len {
+			return Err("Not enough data to decode vector".into());
+		}
+	}
+
+	decode_vec_chunked_into(input, len, bytes, |input, bytes, chunk_len| {
+		let start = bytes.len();
+		bytes.resize(start + chunk_len, 0);
+		input.read(&mut bytes[start..])
+	})
+}
+
+macro_rules! tuple_impl {
+	() => {
+		impl DecodeWithContext for () {
+			fn decode_with_context<I: Input>(
+				_input: &mut I,
+				_ctx: &mut DecodeContext,
+			) -> Result<Self, Error> {
+				Ok(())
+			}
+		}
+
+		impl Recycle for () {}
+	};
+	( $first:ident, $( $rest:ident, )* ) => {
+		impl<$first: DecodeWithContext, $( $rest: DecodeWithContext ),*> DecodeWithContext
+			for ($first, $( $rest, )*)
+		{
+			fn decode_with_context<INPUT: Input>(
+				input: &mut INPUT,
+				ctx: &mut DecodeContext,
+			) -> Result<Self, Error> {
+				Ok((
+					$first::decode_with_context(input, ctx)?,
+					$( $rest::decode_with_context(input, ctx)?, )*
+				))
+			}
+		}
+
+		impl<$first: Recycle, $( $rest: Recycle ),*> Recycle for ($first, $( $rest, )*) {
+			fn recycle(self, ctx: &mut DecodeContext) {
+				let ($first, $( $rest, )*) = self;
+				$first.recycle(ctx);
+				$( $rest.recycle(ctx); )*
+			}
+		}
+
+		tuple_impl!( $( $rest, )* );
+	};
+}
+
+#[allow(non_snake_case)]
+mod inner_tuple_impl {
+	use super::*;
+
+	tuple_impl!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R,);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{DecodeWithMemLimit, Encode};
+
+	#[test]
+	fn decode_with_context_matches_decode() {
+		let value = (
+			7u32,
+			vec![vec![1u16, 2], vec![], vec![3]],
+			vec![String::from("a"), String::from("bc")],
+			Some(vec![1u8; 20_000]),
+		);
+		let encoded = value.encode();
+		let mut ctx = DecodeContext::new();
+		for _ in 0..3 {
+			let decoded =
+				<(u32, Vec<Vec<u16>>, Vec<String>, Option<Vec<u8>>)>::decode_with_context(
+					&mut &encoded[..],
+					&mut ctx,
+				)
+				.unwrap();
+			assert_eq!(decoded, value);
+			ctx.recycle(decoded);
+		}
+		assert_eq!(ctx.pooled::<u8>(), 3);
+		assert_eq!(ctx.pooled::<u16>(), 2);
+		assert_eq!(ctx.pooled::<String>(), 1);
+		assert_eq!(ctx.pooled::<Vec<u16>>(), 1);
+	}
+
+	#[test]
+	fn decode_with_context_reuses_buffers() {
+		let mut ctx = DecodeContext::new();
+		let first =
+			Vec::<u32>::decode_with_context(&mut &vec![1u32; 100].encode()[..], &mut ctx).unwrap();
+		let ptr = first.as_ptr();
+		ctx.recycle(first);
+
+		let second =
+			Vec::<u32>::decode_with_context(&mut &vec![2u32; 10].encode()[..], &mut ctx).unwrap();
+		assert_eq!(second, [2; 10]);
+		assert_eq!(second.as_ptr(), ptr);
+		assert_eq!(ctx.pooled::<u32>(), 0);
+	}
+
+	#[test]
+	fn decode_context_limits_pooled_buffers() {
+		let mut ctx = DecodeContext::with_max_pooled(1);
+		ctx.recycle(vec![String::from("a"), String::from("b"), String::new()]);
+		assert_eq!(ctx.pooled::<u8>(), 1);
+		assert_eq!(ctx.pooled::<String>(), 1);
+		ctx.clear();
+		assert_eq!(ctx.pooled::<u8>(), 0);
+	}
+
+	#[test]
+	fn decode_with_context_rejects_invalid_input() {
+		let mut ctx = DecodeContext::new();
+		ctx.recycle(vec![0u8; 16]);
+		assert_eq!(
+			String::decode_with_context(&mut &[12, 0xff, 0xfe][..], &mut ctx),
+			Err("Not enough data to decode vector".into())
+		);
+		assert_eq!(
+			String::decode_with_context(&mut &[4, 0xff][..], &mut ctx),
+			Err("Invalid utf8 sequence".into())
+		);
+		assert_eq!(
+			Option::<u8>::decode_with_context(&mut &[2][..], &mut ctx),
+			Err("unexpected first byte decoding Option".into())
+		);
+	}
+
+	#[test]
+	fn decode_with_context_respects_mem_limit() {
+		let encoded = vec![0u32; 10_000].encode();
+		let mut ctx = DecodeContext::new();
+		let mut bytes = &encoded[..];
+		let mut input = crate::MemTrackingInput::new(&mut bytes, 1024);
+		assert!(Vec::<u32>::decode_with_context(&mut input, &mut ctx).is_err());
+
+		// Pooled buffers are reused without counting towards the limit.
+		ctx.recycle(Vec::<u32>::with_capacity(10_000));
+		let mut bytes = &encoded[..];
+		let mut input = crate::MemTrackingInput::new(&mut bytes, 1024);
+		assert_eq!(Vec::<u32>::decode_with_context(&mut input, &mut ctx).unwrap().len(), 10_000);
+		assert!(Vec::<u32>::decode_with_mem_limit(&mut &encoded[..], 1024).is_err());
+	}
+}
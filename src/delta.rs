@@ -0,0 +1,330 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deltas between two values of a collection, for incremental synchronization.
+//!
+//! Instead of sending the full encoding of a collection after each change, a [`Diff::Delta`]
+//! containing only the changed elements is sent, and applied to the previous value by the
+//! receiver:
+//!
+//! ```
+//! use parity_scale_codec::{delta, Encode};
+//!
+//! let old = vec![1u64; 1000];
+//! let mut new = old.clone();
+//! new[500] = 2;
+//! new.push(3);
+//!
+//! let delta = delta::encode_delta::<Vec<u64>>(&old.encode(), &new.encode()).unwrap();
+//! assert!(delta.len() < 25);
+//! assert_eq!(delta::apply_delta::<Vec<u64>>(&old.encode(), &delta).unwrap(), new.encode());
+//! ```
+//!
+//! Deltas are validated when they are applied, so that each delta has a single meaning. A delta
+//! computed against a different old value can still be applied though, as it doesn't identify the
+//! value it was computed against. Protocols should e.g. send a hash of the old value along with the
+//! delta if this must be detected.
+
+use core::cmp::Ordering;
+
+use crate::{
+	alloc::{collections::BTreeMap, vec::Vec},
+	codec::{decode_len, decode_vec_chunked, encode_len_to},
+	Compact, Decode, DecodeAll, DecodeWithMemTracking, Encode, Error, Input, Output,
+};
+
+/// A type for which the difference between two values can be computed and applied.
+pub trait Diff: Sized {
+	/// The difference between two values.
+	type Delta: Encode + Decode;
+
+	/// The delta turning `old` into `new`.
+	fn diff(old: &Self, new: &Self) -> Self::Delta;
+
+	/// Apply `delta` to `self`, returning the new value.
+	///
+	/// Returns an error if `delta` is invalid, or can't be applied to `self`.
+	fn apply(self, delta: Self::Delta) -> Result<Self, Error>;
+}
+
+/// Compute the encoded delta between the encodings `old` and `new` of two `T`.
+///
+/// Both encodings must be consumed completely.
+pub fn encode_delta<T: Diff + Decode>(old: &[u8], new: &[u8]) -> Result<Vec<u8>, Error> {
+	let old = T::decode_all(&mut &old[..])?;
+	let new = T::decode_all(&mut &new[..])?;
+	Ok(T::diff(&old, &new).encode())
+}
+
+/// Apply the encoded `delta` to the encoding `old` of a `T`, returning the new encoding.
+///
+/// Both `old` and `delta` must be consumed completely.
+pub fn apply_delta<T: Diff + Encode + Decode>(old: &[u8], delta: &[u8]) -> Result<Vec<u8>, Error> {
+	let old = T::decode_all(&mut &old[..])?;
+	let delta = T::Delta::decode_all(&mut &delta[..])?;
+	Ok(old.apply(delta)?.encode())
+}
+
+/// The [`Diff::Delta`] of a `Vec<T>`: its new length and the elements which changed.
+///
+/// Encoded as the new length, followed by the changes as a `Vec<(Compact<u32>, T)>`. Both lengths
+/// are encoded like the length of a `Vec`, see [`CodecConfig::length_prefix`](crate::CodecConfig).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VecDelta<T> {
+	/// The length of the new vector.
+	pub len: u32,
+	/// The index and the new value of each changed or appended element, ordered by index.
+	pub changes: Vec<(u32, T)>,
+}
+
+impl<T: Encode> Encode for VecDelta<T> {
+	fn size_hint(&self) -> usize {
+		let changes: usize = self.changes.iter().map(|(_, value)| 5 + value.size_hint()).sum();
+		5 + 5 + changes
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		encode_len_to(dest, self.len as usize);
+		encode_len_to(dest, self.changes.len());
+		for (index, value) in &self.changes {
+			Compact(*index).encode_to(dest);
+			value.encode_to(dest);
+		}
+	}
+}
+
+impl<T: Decode> Decode for VecDelta<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let len = u32::try_from(decode_len(input)?)
+			.map_err(|_| "Invalid `VecDelta`: the length doesn't fit in a `u32`")?;
+		let changes_len = decode_len(input)?;
+		let changes = decode_vec_chunked(input, changes_len, |input, changes, chunk_len| {
+			for _ in 0..chunk_len {
+				let Compact(index) = Compact::<u32>::decode(input)?;
+				changes.push((index, T::decode(input)?));
+			}
+			Ok(())
+		})?;
+		Ok(VecDelta { len, changes })
+	}
+}
+
+impl<T: DecodeWithMemTracking> DecodeWithMemTracking for VecDelta<T> {}
+
+impl<T: Encode + Decode + Clone + PartialEq> Diff for Vec<T> {
+	type Delta = VecDelta<T>;
+
+	fn diff(old: &Self, new: &Self) -> VecDelta<T> {
+		assert!(
+			new.len() <= u32::MAX as usize,
+			"Attempted to serialize a collection with too many elements.",
+		);
+		let changes = new
+			.iter()
+			.enumerate()
+			.filter(|(index, value)| old.get(*index) != Some(*value))
+			.map(|(index, value)| (index as u32, value.clone()))
+			.collect();
+		VecDelta { len: new.len() as u32, changes }
+	}
+
+	fn apply(mut self, delta: VecDelta<T>) -> Result<Self, Error> {
+		let len = delta.len as usize;
+		self.truncate(len);
+
+		let mut min_index = 0;
+		for (index, value) in delta.changes {
+			let index = index as usize;
+			if index < min_index || index >= len {
+				return Err("Invalid `VecDelta`: index out of order or out of bounds".into());
+			}
+			min_index = index + 1;
+
+			match index.cmp(&self.len()) {
+				Ordering::Less => self[index] = value,
+				Ordering::Equal => self.push(value),
+				Ordering::Greater => break,
+			}
+		}
+
+		if self.len() != len {
+			return Err("Invalid `VecDelta`: appended elements are missing".into());
+		}
+		Ok(self)
+	}
+}
+
+/// The [`Diff::Delta`] of a `BTreeMap<K, V>`: the removed keys, and the inserted or changed
+/// entries.
+///
+/// Encoded like `(Vec<K>, Vec<(K, V)>)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapDelta<K, V> {
+	/// The removed keys, in increasing order.
+	pub removed: Vec<K>,
+	/// The inserted or changed entries, in increasing order of their keys.
+	pub inserted: Vec<(K, V)>,
+}
+
+impl<K: Encode, V: Encode> Encode for MapDelta<K, V> {
+	fn size_hint(&self) -> usize {
+		(&self.removed, &self.inserted).size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		(&self.removed, &self.inserted).encode_to(dest)
+	}
+}
+
+impl<K: Decode, V: Decode> Decode for MapDelta<K, V> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let (removed, inserted) = Decode::decode(input)?;
+		Ok(MapDelta { removed, inserted })
+	}
+}
+
+impl<K: DecodeWithMemTracking, V: DecodeWithMemTracking> DecodeWithMemTracking for MapDelta<K, V> {}
+
+/// Whether `keys` are strictly increasing.
+fn is_strictly_increasing<'a, K: Ord + 'a>(keys: impl Iterator<Item = &'a K>) -> bool {
+	let mut previous = None;
+	keys.into_iter()
+		.all(|key| previous.replace(key).map_or(true, |previous| previous < key))
+}
+
+impl<K, V> Diff for BTreeMap<K, V>
+where
+	K: Encode + Decode + Ord + Clone,
+	V: Encode + Decode + Clone + PartialEq,
+{
+	type Delta = MapDelta<K, V>;
+
+	fn diff(old: &Self, new: &Self) -> MapDelta<K, V> {
+		let removed = old.keys().filter(|key| !new.contains_key(key)).cloned().collect();
+		let inserted = new
+			.iter()
+			.filter(|(key, value)| old.get(key) != Some(value))
+			.map(|(key, value)| (key.clone(), value.clone()))
+			.collect();
+		MapDelta { removed, inserted }
+	}
+
+	fn apply(mut self, delta: MapDelta<K, V>) -> Result<Self, Error> {
+		if !is_strictly_increasing(delta.removed.iter()) ||
+			!is_strictly_increasing(delta.inserted.iter().map(|(key, _)| key))
+		{
+			return Err("Invalid `MapDelta`: keys out of order".into());
+		}
+
+		for key in &delta.removed {
+			if self.remove(key).is_none() {
+				return Err("Invalid `MapDelta`: removed key is missing".into());
+			}
+		}
+		self.extend(delta.inserted);
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check_roundtrip<T: Diff + Encode + Decode + PartialEq + core::fmt::Debug>(old: T, new: T) {
+		let delta = encode_delta::<T>(&old.encode(), &new.encode()).unwrap();
+		assert_eq!(apply_delta::<T>(&old.encode(), &delta).unwrap(), new.encode());
+		assert_eq!(old.apply(T::Delta::decode(&mut &delta[..]).unwrap()), Ok(new));
+	}
+
+	#[test]
+	fn vec_delta_works() {
+		check_roundtrip(vec![1u8, 2, 3], vec![1, 5, 3, 4, 5]);
+		check_roundtrip(vec![1u8, 2, 3], vec![1]);
+		check_roundtrip(vec![1u8, 2, 3], vec![]);
+		check_roundtrip(Vec::<String>::new(), vec!["new".into()]);
+
+		let delta = Vec::diff(&vec![1u16, 2, 3], &vec![1, 7]);
+		assert_eq!(delta, VecDelta { len: 2, changes: vec![(1, 7)] });
+		assert_eq!(delta.encode(), [8, 4, 4, 7, 0]);
+		assert_eq!(delta.size_hint(), 5 + 5 + 5 + 2);
+	}
+
+	#[test]
+	fn vec_delta_is_validated() {
+		let old = vec![1u8, 2, 3];
+		let invalid = [
+			(VecDelta { len: 3, changes: vec![(1, 0), (1, 0)] }, "index out of order"),
+			(VecDelta { len: 3, changes: vec![(2, 0), (1, 0)] }, "index out of order"),
+			(VecDelta { len: 2, changes: vec![(2, 0)] }, "out of bounds"),
+			(VecDelta { len: 5, changes: vec![(3, 0)] }, "appended elements are missing"),
+			(VecDelta { len: 5, changes: vec![(4, 0)] }, "appended elements are missing"),
+		];
+		for (delta, error) in invalid {
+			let result = old.clone().apply(delta).map_err(|e| e.to_string());
+			assert!(result.unwrap_err().contains(error));
+		}
+	}
+
+	#[test]
+	fn vec_delta_decoding_tracks_memory() {
+		use crate::DecodeWithMemLimit;
+
+		// A huge number of changes must not be allocated before they are decoded.
+		let encoded = [&Compact(1u32).encode()[..], &Compact(u32::MAX).encode(), &[0, 1]].concat();
+		assert!(VecDelta::<u64>::decode(&mut &encoded[..]).is_err());
+		assert!(VecDelta::<u64>::decode_with_mem_limit(&mut &encoded[..], 1024).is_err());
+
+		let delta = VecDelta { len: 2, changes: vec![(1, 7u64)] };
+		assert_eq!(
+			VecDelta::decode_with_mem_limit(&mut &delta.encode()[..], 17),
+			Ok(delta.clone())
+		);
+		assert!(VecDelta::<u64>::decode_with_mem_limit(&mut &delta.encode()[..], 16).is_err());
+	}
+
+	#[test]
+	fn map_delta_works() {
+		let old: BTreeMap<u32, String> =
+			[(1, "one".into()), (2, "two".into()), (3, "three".into())]
+				.into_iter()
+				.collect();
+		let mut new = old.clone();
+		new.remove(&1);
+		new.insert(2, "TWO".into());
+		new.insert(4, "four".into());
+
+		let delta = BTreeMap::diff(&old, &new);
+		assert_eq!(
+			delta,
+			MapDelta { removed: vec![1], inserted: vec![(2, "TWO".into()), (4, "four".into())] }
+		);
+		assert_eq!(delta.encode(), (vec![1u32], vec![(2u32, "TWO"), (4, "four")]).encode());
+		check_roundtrip(old.clone(), new);
+		check_roundtrip(old, BTreeMap::new());
+	}
+
+	#[test]
+	fn map_delta_is_validated() {
+		let old: BTreeMap<u8, u8> = [(1, 1), (2, 2)].into_iter().collect();
+		let invalid = [
+			(MapDelta { removed: vec![2, 1], inserted: vec![] }, "keys out of order"),
+			(MapDelta { removed: vec![], inserted: vec![(3, 0), (3, 1)] }, "keys out of order"),
+			(MapDelta { removed: vec![3], inserted: vec![] }, "removed key is missing"),
+		];
+		for (delta, error) in invalid {
+			let result = old.clone().apply(delta).map_err(|e| e.to_string());
+			assert!(result.unwrap_err().contains(error));
+		}
+	}
+}
@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Decode, Error, Input};
+use crate::{alloc::vec::Vec, CodecConfig, Decode, Error, Input};
+use core::{borrow::BorrowMut, marker::PhantomData};
 
 /// The error message returned when depth limit is reached.
-const DECODE_MAX_DEPTH_MSG: &str = "Maximum recursion depth reached when decoding";
+pub(crate) const DECODE_MAX_DEPTH_MSG: &str = "Maximum recursion depth reached when decoding";
 
 /// Extension trait to [`Decode`] for decoding with a maximum recursion depth.
 pub trait DecodeLimit: Sized {
@@ -31,27 +32,54 @@ pub trait DecodeLimit: Sized {
 	fn decode_all_with_depth_limit(limit: u32, input: &mut &[u8]) -> Result<Self, Error>;
 }
 
-struct DepthTrackingInput<'a, I> {
-	input: &'a mut I,
+/// An [`Input`] limiting the recursion depth reached while decoding to `max_depth`, like
+/// [`DecodeLimit`].
+///
+/// It wraps a `&mut I`, or owns the input `I` when built with [`Input::with_depth_limit`], in
+/// which case `Inner` is `I`.
+pub struct DepthTrackingInput<'a, I, Inner = &'a mut I> {
+	input: Inner,
 	depth: u32,
 	max_depth: u32,
+	_input: PhantomData<&'a mut I>,
 }
 
-impl<'a, I: Input> Input for DepthTrackingInput<'a, I> {
+impl<'a, I: Input> DepthTrackingInput<'a, I> {
+	/// Wrap `input`, limiting the recursion depth to `max_depth`.
+	pub fn new(input: &'a mut I, max_depth: u32) -> Self {
+		Self { input, depth: 0, max_depth, _input: PhantomData }
+	}
+}
+
+impl<'a, I: Input + 'a> DepthTrackingInput<'a, I, I> {
+	/// Wrap the owned `input`, see [`Input::with_depth_limit`].
+	pub(crate) fn new_owned(input: I, max_depth: u32) -> Self {
+		Self { input, depth: 0, max_depth, _input: PhantomData }
+	}
+}
+
+impl<I, Inner> DepthTrackingInput<'_, I, Inner> {
+	/// The wrapped input.
+	pub fn into_inner(self) -> Inner {
+		self.input
+	}
+}
+
+impl<I: Input, Inner: BorrowMut<I>> Input for DepthTrackingInput<'_, I, Inner> {
 	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
-		self.input.remaining_len()
+		self.input.borrow_mut().remaining_len()
 	}
 
 	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
-		self.input.read(into)
+		self.input.borrow_mut().read(into)
 	}
 
 	fn read_byte(&mut self) -> Result<u8, Error> {
-		self.input.read_byte()
+		self.input.borrow_mut().read_byte()
 	}
 
 	fn descend_ref(&mut self) -> Result<(), Error> {
-		self.input.descend_ref()?;
+		self.input.borrow_mut().descend_ref()?;
 		self.depth += 1;
 		if self.depth > self.max_depth {
 			Err(DECODE_MAX_DEPTH_MSG.into())
@@ -61,12 +89,28 @@ impl<'a, I: Input> Input for DepthTrackingInput<'a, I> {
 	}
 
 	fn ascend_ref(&mut self) {
-		self.input.ascend_ref();
+		self.input.borrow_mut().ascend_ref();
 		self.depth -= 1;
 	}
 
 	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
-		self.input.on_before_alloc_mem(size)
+		self.input.borrow_mut().on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.borrow().config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.borrow_mut().interner()
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		self.input.borrow_mut().seek_forward(n)
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		self.input.borrow_mut().read_remaining_into(into)
 	}
 }
 
@@ -82,7 +126,7 @@ impl<T: Decode> DecodeLimit for T {
 	}
 
 	fn decode_with_depth_limit<I: Input>(limit: u32, input: &mut I) -> Result<Self, Error> {
-		let mut input = DepthTrackingInput { input, depth: 0, max_depth: limit };
+		let mut input = DepthTrackingInput::new(input, limit);
 		T::decode(&mut input)
 	}
 }
@@ -90,7 +134,7 @@ impl<T: Decode> DecodeLimit for T {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::Encode;
+	use crate::{DecodeWithMemLimit, Encode};
 
 	#[test]
 	fn decode_limit_works() {
@@ -132,4 +176,38 @@ mod tests {
 			"Input buffer has still data left after decoding!",
 		);
 	}
+
+	#[test]
+	fn stacked_limits_match_the_separate_inputs() {
+		type NestedVec = Vec<Vec<Vec<Vec<u8>>>>;
+		let encoded = NestedVec::from([vec![vec![vec![1, 2, 3]]]]).encode();
+		for depth_limit in 0..6 {
+			assert_eq!(
+				NestedVec::decode(
+					&mut encoded.as_slice().with_mem_limit(1024).with_depth_limit(depth_limit)
+				),
+				NestedVec::decode_with_depth_limit(depth_limit, &mut encoded.as_slice()),
+			);
+		}
+		for mem_limit in 0..128 {
+			assert_eq!(
+				NestedVec::decode(
+					&mut encoded.as_slice().with_depth_limit(8).with_mem_limit(mem_limit)
+				),
+				NestedVec::decode_with_mem_limit(&mut encoded.as_slice(), mem_limit),
+			);
+		}
+	}
+
+	#[test]
+	fn stacked_limits_wrap_borrowed_inputs() {
+		let encoded = (vec![1u16], 7u8).encode();
+		let mut slice = encoded.as_slice();
+
+		let borrowed: &mut dyn Input = &mut slice;
+		let mut input = borrowed.with_mem_limit(64).with_depth_limit(2);
+		assert_eq!(Vec::<u16>::decode(&mut input), Ok(vec![1]));
+		assert_eq!(input.into_inner().used_mem(), 2);
+		assert_eq!(slice, [7]);
+	}
 }
@@ -0,0 +1,144 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `either::Either` uses the same layout as `Result`: `Left` is encoded like `Ok` (tag `0`) and
+//! `Right` like `Err` (tag `1`).
+
+use crate::{encode_like::EncodeLike, Decode, DecodeWithMemTracking, Encode, Error, Input, Output};
+use either::Either;
+
+impl<L: Encode, R: Encode> Encode for Either<L, R> {
+	fn size_hint(&self) -> usize {
+		1 + match *self {
+			Either::Left(ref l) => l.size_hint(),
+			Either::Right(ref r) => r.size_hint(),
+		}
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		match *self {
+			Either::Left(ref l) => {
+				dest.push_byte(0);
+				l.encode_to(dest);
+			},
+			Either::Right(ref r) => {
+				dest.push_byte(1);
+				r.encode_to(dest);
+			},
+		}
+	}
+}
+
+impl<L, LikeL, R, LikeR> EncodeLike<Either<LikeL, LikeR>> for Either<L, R>
+where
+	L: EncodeLike<LikeL>,
+	LikeL: Encode,
+	R: EncodeLike<LikeR>,
+	LikeR: Encode,
+{
+}
+
+impl<L, LikeL, R, LikeR> EncodeLike<Result<LikeL, LikeR>> for Either<L, R>
+where
+	L: EncodeLike<LikeL>,
+	LikeL: Encode,
+	R: EncodeLike<LikeR>,
+	LikeR: Encode,
+{
+}
+
+impl<L, LikeL, R, LikeR> EncodeLike<Either<LikeL, LikeR>> for Result<L, R>
+where
+	L: EncodeLike<LikeL>,
+	LikeL: Encode,
+	R: EncodeLike<LikeR>,
+	LikeR: Encode,
+{
+}
+
+impl<L: Decode, R: Decode> Decode for Either<L, R> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		match input
+			.read_byte()
+			.map_err(|e| e.chain("Could not read variant byte for `Either`"))?
+		{
+			0 => Ok(Either::Left(
+				L::decode(input).map_err(|e| e.chain("Could not decode `Either::Left(L)`"))?,
+			)),
+			1 => Ok(Either::Right(
+				R::decode(input).map_err(|e| e.chain("Could not decode `Either::Right(R)`"))?,
+			)),
+			_ => Err("unexpected first byte decoding Either".into()),
+		}
+	}
+}
+
+impl<L: DecodeWithMemTracking, R: DecodeWithMemTracking> DecodeWithMemTracking for Either<L, R> {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<L, R> crate::MaxEncodedLen for Either<L, R>
+where
+	L: crate::MaxEncodedLen,
+	R: crate::MaxEncodedLen,
+{
+	fn max_encoded_len() -> usize {
+		L::max_encoded_len().max(R::max_encoded_len()).saturating_add(1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::vec::Vec, Compact};
+
+	#[test]
+	fn either_has_same_layout_as_result() {
+		let values: Vec<(Either<u32, u8>, Result<u32, u8>)> =
+			vec![(Either::Left(0x1234_5678), Ok(0x1234_5678)), (Either::Right(42), Err(42))];
+
+		for (either, result) in values {
+			let encoded = either.encode();
+			assert_eq!(encoded, result.encode());
+			assert_eq!(encoded.len(), either.size_hint());
+			assert_eq!(Either::<u32, u8>::decode(&mut &encoded[..]).unwrap(), either);
+			assert_eq!(Result::<u32, u8>::decode(&mut &encoded[..]).unwrap(), result);
+		}
+	}
+
+	#[test]
+	fn either_decode_rejects_invalid_tag() {
+		assert_eq!(
+			Either::<u8, u8>::decode(&mut &[2u8, 0][..]).unwrap_err().to_string(),
+			"unexpected first byte decoding Either",
+		);
+		assert!(Either::<u8, u8>::decode(&mut &[][..]).is_err());
+	}
+
+	#[test]
+	fn either_is_encode_like_result() {
+		fn is_encode_like<T: EncodeLike<U>, U: Encode>(_: &T) {}
+
+		is_encode_like::<_, Result<Compact<u32>, u8>>(&Either::<Compact<u32>, u8>::Left(1.into()));
+		is_encode_like::<_, Either<Vec<u8>, ()>>(&Result::<&[u8], ()>::Ok(&[1, 2][..]));
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn either_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(Either::<u32, u64>::max_encoded_len(), 9);
+		assert_eq!(Either::<u32, u64>::max_encoded_len(), Result::<u32, u64>::max_encoded_len());
+	}
+}
@@ -0,0 +1,162 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`Input`] and [`Output`] adapters for the `io` traits of `embedded-io`.
+//!
+//! These are the `no_std` counterparts of [`IoReader`](crate::IoReader) and of the implementation
+//! of [`Output`] for `std::io::Write`. As [`Output::write`] can't fail, the writer keeps the first
+//! error of the underlying writer, skips all following writes and returns the error from `finish`.
+//!
+//! The `io` traits of `core2` are not supported.
+
+use crate::{Error, Input, Output};
+
+/// An [`Input`] reading from an [`embedded_io::Read`].
+///
+/// ```
+/// # use parity_scale_codec::{Decode, EmbeddedIoReader};
+/// let mut input = EmbeddedIoReader(&[42, 0, 0, 0][..]);
+/// assert_eq!(u32::decode(&mut input), Ok(42));
+/// ```
+pub struct EmbeddedIoReader<R: embedded_io::Read>(pub R);
+
+impl<R: embedded_io::Read> Input for EmbeddedIoReader<R> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(None)
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.0.read_exact(into).map_err(|e| match e {
+			embedded_io::ReadExactError::UnexpectedEof => "io error: UnexpectedEof".into(),
+			embedded_io::ReadExactError::Other(e) => embedded_io_error(e),
+		})
+	}
+}
+
+/// The error of `err`, described by its kind, like the errors of `std::io`.
+fn embedded_io_error(err: impl embedded_io::Error) -> Error {
+	#[cfg(feature = "chain-error")]
+	{
+		use core::fmt::Write;
+
+		let mut desc = crate::alloc::string::String::new();
+		let _ = write!(desc, "io error: {:?}", err.kind());
+		Error::from_desc(desc)
+	}
+
+	#[cfg(not(feature = "chain-error"))]
+	{
+		let _ = err;
+		"io error".into()
+	}
+}
+
+/// An [`Output`] writing to an [`embedded_io::Write`].
+///
+/// ```
+/// # use parity_scale_codec::{EmbeddedIoWriter, Encode};
+/// let mut buffer = [0u8; 4];
+/// let mut output = EmbeddedIoWriter::new(&mut buffer[..]);
+/// 42u32.encode_to(&mut output);
+/// assert!(output.finish().is_ok());
+/// assert_eq!(buffer, [42, 0, 0, 0]);
+/// ```
+///
+/// Values which can't be encoded are reported like the errors of the writer, see
+/// [`Output::on_encode_error`].
+pub struct EmbeddedIoWriter<W: embedded_io::Write> {
+	writer: W,
+	error: Option<Error>,
+}
+
+impl<W: embedded_io::Write> EmbeddedIoWriter<W> {
+	/// Write to `writer`.
+	pub fn new(writer: W) -> Self {
+		EmbeddedIoWriter { writer, error: None }
+	}
+
+	/// Flush the writer and return it, or the first error that occurred while writing.
+	pub fn finish(mut self) -> Result<W, Error> {
+		match self.error {
+			Some(e) => Err(e),
+			None => self.writer.flush().map(|()| self.writer).map_err(embedded_io_error),
+		}
+	}
+}
+
+impl<W: embedded_io::Write> Output for EmbeddedIoWriter<W> {
+	fn write(&mut self, bytes: &[u8]) {
+		if self.error.is_none() {
+			self.error = self.writer.write_all(bytes).err().map(embedded_io_error);
+		}
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		if self.error.is_none() {
+			self.error = Some(error);
+		}
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.error.is_some()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Decode, Encode};
+
+	#[test]
+	fn embedded_io_adapters_work() {
+		let value = (vec![1u16, 2, 3], String::from("embedded"));
+		let mut buffer = [0u8; 64];
+		let mut output = EmbeddedIoWriter::new(&mut buffer[..]);
+		value.encode_to(&mut output);
+		let remaining = output.finish().unwrap().len();
+
+		let encoded = &buffer[..buffer.len() - remaining];
+		assert_eq!(encoded, value.encode());
+		assert_eq!(<(Vec<u16>, String)>::decode(&mut EmbeddedIoReader(encoded)).unwrap(), value);
+
+		assert_eq!(
+			u32::decode(&mut EmbeddedIoReader(&[1, 2][..])),
+			Err("io error: UnexpectedEof".into())
+		);
+	}
+
+	#[test]
+	fn embedded_io_writer_keeps_first_error() {
+		let mut buffer = [0u8; 3];
+		let mut output = EmbeddedIoWriter::new(&mut buffer[..]);
+		1u16.encode_to(&mut output);
+		2u16.encode_to(&mut output);
+		3u8.encode_to(&mut output);
+		assert_eq!(output.finish().err(), Some("io error: WriteZero".into()));
+		// The write that didn't fit was written as far as it fit, the following writes were
+		// skipped.
+		assert_eq!(buffer, [1, 0, 2]);
+	}
+
+	#[test]
+	fn embedded_io_writer_keeps_encode_errors() {
+		let mut buffer = [0u8; 8];
+		let mut output = EmbeddedIoWriter::new(&mut buffer[..]);
+		output.on_encode_error("Value can't be encoded".into());
+		assert!(output.is_exhausted());
+		1u16.encode_to(&mut output);
+		assert_eq!(output.finish().err(), Some("Value can't be encoded".into()));
+		assert_eq!(buffer, [0; 8]);
+	}
+}
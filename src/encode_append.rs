@@ -23,6 +23,8 @@ use crate::{
 
 /// Trait that allows to append items to an encoded representation without
 /// decoding all previous added items.
+///
+/// The encoded representation must be encoded with the default [`CodecConfig`](crate::CodecConfig).
 pub trait EncodeAppend {
 	/// The item that will be appended.
 	type Item: Encode;
@@ -77,6 +79,99 @@ impl<T: Encode> EncodeAppend for crate::alloc::collections::VecDeque<T> {
 	}
 }
 
+impl<K, V> EncodeAppend for crate::alloc::collections::BTreeMap<K, V>
+where
+	K: Encode + Decode + Ord,
+	V: Encode + Decode,
+{
+	type Item = (K, V);
+
+	/// Append all `(key, value)` pairs in `iter` to the given encoded map, or encode them as a new
+	/// map if `self_encoded` is empty.
+	///
+	/// The encoding of a map is sorted by key, and this is preserved by requiring the keys of the
+	/// appended pairs to be strictly increasing and strictly greater than the last key of the
+	/// encoded map. Only that last key is decoded, not the entire map. The key of each appended
+	/// pair is decoded back from its encoding, so any `EncodeLike<(K, V)>` item can be appended.
+	///
+	/// Returns an error if a key is out of order or if `self_encoded` isn't a valid encoded map.
+	/// Use [`append_to_encoded_map`] to get the encoded map back in that case.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use parity_scale_codec::EncodeAppend;
+	/// # use std::collections::BTreeMap;
+	/// let encoded = <BTreeMap<u32, u8> as EncodeAppend>::append_or_new(Vec::new(), [(1u32, 1u8)])
+	///     .expect("Adds new entry");
+	/// let encoded = <BTreeMap<u32, u8> as EncodeAppend>::append_or_new(encoded, [(&2u32, &2u8)])
+	///     .expect("Adds entry with greater key");
+	/// assert!(<BTreeMap<u32, u8> as EncodeAppend>::append_or_new(encoded, [(2u32, 3u8)]).is_err());
+	/// ```
+	fn append_or_new<EncodeLikeItem, I>(self_encoded: Vec<u8>, iter: I) -> Result<Vec<u8>, Error>
+	where
+		I: IntoIterator<Item = EncodeLikeItem>,
+		EncodeLikeItem: EncodeLike<Self::Item>,
+		I::IntoIter: ExactSizeIterator,
+	{
+		let mut vec = self_encoded;
+		append_to_encoded_map::<K, V, _, _>(&mut vec, iter)?;
+		Ok(vec)
+	}
+}
+
+/// Appends all `(key, value)` pairs in `iter` to the SCALE-encoded `BTreeMap<K, V>` `encoded`, or
+/// encodes them as a new map if `encoded` is empty.
+///
+/// This is [`EncodeAppend::append_or_new`] for `BTreeMap`, except that `encoded` is left
+/// unchanged on error: the new entries and `encoded` are both validated before it is modified.
+/// Besides the order of the keys, this checks that `encoded` has no trailing bytes after its last
+/// entry.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec::append_to_encoded_map;
+/// let mut encoded = Vec::new();
+/// append_to_encoded_map::<u32, u8, _, _>(&mut encoded, [(1u32, 1u8), (3, 3)]).unwrap();
+///
+/// let before = encoded.clone();
+/// assert!(append_to_encoded_map::<u32, u8, _, _>(&mut encoded, [(4u32, 4u8), (2, 2)]).is_err());
+/// assert_eq!(encoded, before);
+/// ```
+pub fn append_to_encoded_map<K, V, EncodeLikeItem, I>(
+	encoded: &mut Vec<u8>,
+	iter: I,
+) -> Result<(), Error>
+where
+	K: Encode + Decode + Ord,
+	V: Encode + Decode,
+	I: IntoIterator<Item = EncodeLikeItem>,
+	EncodeLikeItem: EncodeLike<(K, V)>,
+{
+	let mut last_key = decode_last_map_key::<K, V>(encoded)?;
+
+	let mut entries = Vec::new();
+	let mut entries_count = 0;
+	for item in iter {
+		let start = entries.len();
+		item.encode_to(&mut entries);
+
+		let key = K::decode(&mut &entries[start..])?;
+		if last_key.as_ref().is_some_and(|last_key| *last_key >= key) {
+			return Err(
+				"cannot append new entries into a SCALE-encoded map: keys out of order".into()
+			);
+		}
+		last_key = Some(key);
+		entries_count += 1;
+	}
+
+	update_length_prefix(encoded, entries_count)?;
+	encoded.extend_from_slice(&entries);
+	Ok(())
+}
+
 /// Extends a SCALE-encoded vector with elements from the given `iter`.
 ///
 /// `vec` must either be empty, or contain a valid SCALE-encoded `Vec<Item>` payload.
@@ -87,47 +182,105 @@ where
 	I::IntoIter: ExactSizeIterator,
 {
 	let iter = iter.into_iter();
-	let items_to_append = iter.len();
+	update_length_prefix(&mut vec, iter.len())?;
+
+	// And now we just need to append the new items.
+	iter.for_each(|e| e.encode_to(&mut vec));
+	Ok(vec)
+}
 
+/// Updates the length prefix of a SCALE-encoded vector for `items_to_append` new items.
+///
+/// `vec` must either be empty, or contain a valid SCALE-encoded `Vec<Item>` payload. It is left
+/// unchanged on error.
+fn update_length_prefix(vec: &mut Vec<u8>, items_to_append: usize) -> Result<(), Error> {
 	if vec.is_empty() {
-		crate::codec::compact_encode_len_to(&mut vec, items_to_append)?;
+		crate::codec::compact_encode_len_to(vec, items_to_append)?;
 	} else {
 		let old_item_count = u32::from(Compact::<u32>::decode(&mut &vec[..])?);
-		let new_item_count = old_item_count
-			.checked_add(items_to_append as u32)
+		let new_item_count = u32::try_from(items_to_append)
+			.ok()
+			.and_then(|items_to_append| old_item_count.checked_add(items_to_append))
 			.ok_or("cannot append new items into a SCALE-encoded vector: length overflow due to too many items")?;
 
-		let old_item_count_encoded_bytesize = Compact::<u32>::compact_len(&old_item_count);
-		let new_item_count_encoded_bytesize = Compact::<u32>::compact_len(&new_item_count);
+		patch_compact_len_prefix(vec, new_item_count)?;
+	}
 
-		if old_item_count_encoded_bytesize == new_item_count_encoded_bytesize {
+	Ok(())
+}
+
+/// Replaces the compact length prefix of the SCALE-encoded collection `encoded` by `new_len`.
+///
+/// If the new prefix is wider or narrower than the old one, the rest of `encoded` is moved. Only
+/// the prefix is changed, so after inserting or removing items in the encoding of a collection,
+/// this updates its length without decoding it.
+///
+/// Returns an error if `encoded` doesn't start with a valid `Compact<u32>`, in which case it is
+/// left unchanged.
+///
+/// # Example
+///
+/// ```
+/// # use parity_scale_codec::{patch_compact_len_prefix, Encode};
+/// let mut encoded = vec![7u8; 63].encode();
+/// // Insert an item at index 1.
+/// encoded.insert(2, 8);
+/// patch_compact_len_prefix(&mut encoded, 64).expect("Starts with a length");
+///
+/// let mut expected = vec![7u8; 63];
+/// expected.insert(1, 8);
+/// assert_eq!(encoded, expected.encode());
+/// ```
+pub fn patch_compact_len_prefix(encoded: &mut Vec<u8>, new_len: u32) -> Result<(), Error> {
+	let old_len = u32::from(Compact::<u32>::decode(&mut &encoded[..])?);
+	let old_prefix_len = Compact::<u32>::compact_len(&old_len);
+
+	Compact(new_len).using_encoded(|new_prefix| {
+		if new_prefix.len() == old_prefix_len {
 			// The size of the length as encoded by SCALE didn't change, so we can just
-			// keep the old buffer as-is. We just need to update the length prefix.
-			Compact(new_item_count).using_encoded(|length_encoded| {
-				vec[..old_item_count_encoded_bytesize].copy_from_slice(length_encoded)
-			});
+			// keep the old buffer as-is.
+			encoded[..old_prefix_len].copy_from_slice(new_prefix)
 		} else {
-			// We can't update the length as the new length prefix will take up more
-			// space when encoded, so we need to move our data to make space for it.
-
-			// If this overflows then it means that `vec` is bigger that half of the
-			// total address space, which means that it will be impossible to allocate
-			// enough memory for another vector of at least the same size.
-			//
-			// So let's just immediately bail with an error if this happens.
-			let new_capacity = vec.len().checked_mul(2)
-				.ok_or("cannot append new items into a SCALE-encoded vector: new vector won't fit in memory")?;
-			let mut new_vec = Vec::with_capacity(new_capacity);
-
-			crate::codec::compact_encode_len_to(&mut new_vec, new_item_count as usize)?;
-			new_vec.extend_from_slice(&vec[old_item_count_encoded_bytesize..]);
-			vec = new_vec;
+			encoded.splice(..old_prefix_len, new_prefix.iter().copied());
 		}
+	});
+
+	Ok(())
+}
+
+/// Decodes the key of the last entry of a SCALE-encoded `BTreeMap<K, V>`.
+///
+/// If both `K` and `V` have a fixed encoded size, the last key is found directly. Otherwise all
+/// but the last entry are skipped. In both cases, only the last key is decoded.
+fn decode_last_map_key<K: Decode, V: Decode>(encoded: &[u8]) -> Result<Option<K>, Error> {
+	if encoded.is_empty() {
+		return Ok(None);
 	}
 
-	// And now we just need to append the new items.
-	iter.for_each(|e| e.encode_to(&mut vec));
-	Ok(vec)
+	let input = &mut &encoded[..];
+	let len = u32::from(Compact::<u32>::decode(input)?) as usize;
+	if len == 0 {
+		return Ok(None);
+	}
+
+	if let (Some(key_size), Some(value_size)) = (K::encoded_fixed_size(), V::encoded_fixed_size()) {
+		let entry_size = key_size.saturating_add(value_size);
+		if entry_size.checked_mul(len) != Some(input.len()) {
+			return Err("Invalid length of SCALE-encoded map".into());
+		}
+		*input = &input[input.len() - entry_size..];
+	} else {
+		for _ in 1..len {
+			<(K, V)>::skip(input)?;
+		}
+	}
+
+	let key = K::decode(input)?;
+	V::skip(input)?;
+	if !input.is_empty() {
+		return Err("Invalid length of SCALE-encoded map".into());
+	}
+	Ok(Some(key))
 }
 
 #[cfg(test)]
@@ -235,4 +388,121 @@ mod tests {
 		let decoded = Vec::<u32>::decode(&mut &encoded[..]).unwrap();
 		assert_eq!(decoded, (0..TEST_VALUE).collect::<Vec<_>>());
 	}
+
+	#[test]
+	fn btree_map_encode_append_works() {
+		use std::collections::BTreeMap;
+
+		let encoded = (0..TEST_VALUE).fold(Vec::new(), |encoded, v| {
+			<BTreeMap<u32, u64> as EncodeAppend>::append_or_new(
+				encoded,
+				std::iter::once((v, u64::from(v) * 2)),
+			)
+			.unwrap()
+		});
+
+		let decoded = BTreeMap::<u32, u64>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, (0..TEST_VALUE).map(|v| (v, u64::from(v) * 2)).collect());
+		assert_eq!(decoded.encode(), encoded);
+	}
+
+	#[test]
+	fn btree_map_encode_append_variable_size_works() {
+		use std::collections::BTreeMap;
+
+		let map = BTreeMap::from([(vec![1u8], String::from("a")), (vec![1, 2], String::from("b"))]);
+		let encoded = <BTreeMap<Vec<u8>, String> as EncodeAppend>::append_or_new(
+			map.encode(),
+			[(&[2u8][..], "c"), (&[2, 0][..], "d")],
+		)
+		.unwrap();
+
+		let decoded = BTreeMap::<Vec<u8>, String>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded.len(), 4);
+		assert_eq!(decoded.encode(), encoded);
+	}
+
+	#[test]
+	fn btree_map_encode_append_rejects_out_of_order_keys() {
+		use std::collections::BTreeMap;
+
+		// Keys are compared with `Ord`, not by their little endian encoding.
+		let encoded = BTreeMap::from([(1u16, ()), (256, ())]).encode();
+		let err = "cannot append new entries into a SCALE-encoded map: keys out of order";
+
+		for (appended, expected) in [
+			(vec![257u16], Ok(())),
+			(vec![2], Err(err)),
+			(vec![256], Err(err)),
+			(vec![300, 299], Err(err)),
+			(vec![300, 300], Err(err)),
+		] {
+			let res = <BTreeMap<u16, ()> as EncodeAppend>::append_or_new(
+				encoded.clone(),
+				appended.into_iter().map(|k| (k, ())),
+			);
+			assert_eq!(res.map(drop), expected.map_err(Error::from));
+		}
+
+		assert_eq!(
+			<BTreeMap<u16, u16> as EncodeAppend>::append_or_new(
+				vec![8, 1, 0, 1, 0],
+				[(2u16, 2u16)]
+			),
+			Err("Invalid length of SCALE-encoded map".into()),
+		);
+	}
+
+	#[test]
+	fn append_to_encoded_map_validates_before_appending() {
+		let mut encoded = vec![4, 1, 0, 1, 0];
+		for (appended, err) in [
+			(
+				vec![(2u16, 2u16), (1, 1)],
+				"cannot append new entries into a SCALE-encoded map: keys out of order",
+			),
+			(vec![(1, 1)], "cannot append new entries into a SCALE-encoded map: keys out of order"),
+		] {
+			assert_eq!(
+				append_to_encoded_map::<u16, u16, _, _>(&mut encoded, appended),
+				Err(err.into())
+			);
+			assert_eq!(encoded, vec![4, 1, 0, 1, 0]);
+		}
+
+		// Trailing bytes after the last entry, with fixed and variable size entries.
+		let mut trailing = vec![4, 1, 0, 1, 0, 9];
+		assert_eq!(
+			append_to_encoded_map::<u16, u16, _, _>(&mut trailing, [(2u16, 2u16)]),
+			Err("Invalid length of SCALE-encoded map".into())
+		);
+		let mut trailing = vec![4, 4, 1, 0, 9];
+		assert_eq!(
+			append_to_encoded_map::<Vec<u8>, u8, _, _>(&mut trailing, [(vec![2u8], 2u8)]),
+			Err("Invalid length of SCALE-encoded map".into())
+		);
+		assert_eq!(trailing, vec![4, 4, 1, 0, 9]);
+
+		append_to_encoded_map::<u16, u16, _, _>(&mut encoded, [(2u16, 2u16)]).unwrap();
+		assert_eq!(encoded, vec![8, 1, 0, 1, 0, 2, 0, 2, 0]);
+	}
+
+	#[test]
+	fn patch_compact_len_prefix_works() {
+		// Same width, wider and narrower prefixes.
+		for (old_len, new_len) in
+			[(3u32, 5u32), (63, 64), (16384, 16383), (1 << 30, 0), (0, 1 << 30)]
+		{
+			let items = vec![9u8; 3];
+			let mut encoded = Compact(old_len).encode();
+			encoded.extend_from_slice(&items);
+
+			patch_compact_len_prefix(&mut encoded, new_len).unwrap();
+			assert_eq!(encoded, [Compact(new_len).encode(), items].concat());
+		}
+
+		let mut invalid = vec![0b11];
+		assert!(patch_compact_len_prefix(&mut invalid, 1).is_err());
+		assert_eq!(invalid, [0b11]);
+	}
 }
@@ -72,6 +72,31 @@ use crate::codec::Encode;
 /// ```
 pub trait EncodeLike<T: Encode = Self>: Sized + Encode {}
 
+/// The names of the encoded fields of a struct, in order, implemented by `#[derive(Encode)]`.
+///
+/// `Names` is a tuple with a tuple of [`FieldNameChar`]s for each field which isn't skipped, so
+/// `#[codec(like_plain(OtherType))]` can check that the other type encodes the same fields in the
+/// same order.
+#[doc(hidden)]
+pub trait EncodedFieldNames {
+	type Names;
+}
+
+/// A character of the name of a field, see [`EncodedFieldNames`].
+#[doc(hidden)]
+pub struct FieldNameChar<const C: char>;
+
+/// Implemented if the encoded fields of `Self` and `T` are the same, see [`EncodedFieldNames`].
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+	message = "the `like_plain` type doesn't encode the same fields in the same order",
+	label = "the encoded fields differ",
+	note = "fields skipped with `#[codec(skip)]` must be skipped in both types"
+)]
+pub trait SameEncodedFields<T> {}
+
+impl<T> SameEncodedFields<T> for T {}
+
 /// Reference wrapper that implement encode like any type that is encoded like its inner type.
 ///
 /// # Example
@@ -165,4 +190,20 @@ mod tests {
 			ComplexStuff::<(u32, Vec<u8>)>::complex_method(&(1u32, slice))
 		);
 	}
+
+	#[test]
+	fn mut_references_and_pins_are_working() {
+		let mut value = 10u32;
+		let expected = value.encode();
+		assert_eq!(ComplexStuff::<u32>::complex_method(&&mut value), expected);
+		assert_eq!(ComplexStuff::<&u32>::complex_method(&&mut value), expected);
+		assert_eq!(ComplexStuff::<&mut u32>::complex_method(&&value), expected);
+		assert_eq!(ComplexStuff::<&mut u32>::complex_method(&value), expected);
+
+		assert_eq!(ComplexStuff::<u32>::complex_method(&core::pin::Pin::new(&value)), expected);
+		assert_eq!(ComplexStuff::<u32>::complex_method(&core::pin::Pin::new(&mut value)), expected);
+		assert_eq!(ComplexStuff::<u32>::complex_method(&Box::pin(value)), expected);
+		assert_eq!(ComplexStuff::<Box<u32>>::complex_method(&Box::pin(value)), expected);
+		assert_eq!(ComplexStuff::<core::pin::Pin<Box<u32>>>::complex_method(&value), expected);
+	}
 }
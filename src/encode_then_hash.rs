@@ -0,0 +1,161 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hashing of encoded values, e.g. to derive storage keys.
+
+use core::{hash::Hasher, marker::PhantomData};
+
+use crate::{Encode, EncodeLike, Output};
+
+/// A hash function used by [`EncodeThenHash`].
+///
+/// Hashing crates can implement it for their hashers, e.g. BLAKE2 or xxHash, or a
+/// [`core::hash::Hasher`] can be used with [`CoreHasher`].
+///
+/// The hash must only depend on the concatenation of the bytes given to [`Self::update`], and not
+/// on how they are split into calls.
+pub trait KeyHasher {
+	/// The hash, e.g. `[u8; 32]`.
+	type Output: AsRef<[u8]>;
+
+	/// Create a hasher which didn't hash anything yet.
+	fn new() -> Self;
+
+	/// Hash `bytes`.
+	fn update(&mut self, bytes: &[u8]);
+
+	/// The hash of all bytes given to [`Self::update`].
+	fn finalize(self) -> Self::Output;
+}
+
+/// A [`KeyHasher`] for a [`core::hash::Hasher`], with its 64 bit hash in little endian as output.
+///
+/// The hasher must be stable across versions and platforms if the keys are stored, which isn't the
+/// case of `std::collections::hash_map::DefaultHasher`.
+pub struct CoreHasher<H>(pub H);
+
+impl<H: Hasher + Default> KeyHasher for CoreHasher<H> {
+	type Output = [u8; 8];
+
+	fn new() -> Self {
+		CoreHasher(H::default())
+	}
+
+	fn update(&mut self, bytes: &[u8]) {
+		self.0.write(bytes)
+	}
+
+	fn finalize(self) -> [u8; 8] {
+		self.0.finish().to_le_bytes()
+	}
+}
+
+/// An [`Output`] giving the bytes written to a [`KeyHasher`].
+struct HasherOutput<H>(H);
+
+impl<H: KeyHasher> Output for HasherOutput<H> {
+	fn write(&mut self, bytes: &[u8]) {
+		self.0.update(bytes)
+	}
+}
+
+/// Hashes the encoding of values of type `T` with the [`KeyHasher`] `H`.
+///
+/// The values are encoded into the hasher, without allocating their encoding. Any value encoded
+/// like a `T` can be hashed, so it gives the same key as the `T` it is encoded like:
+///
+/// ```
+/// # use parity_scale_codec::{CoreHasher, EncodeThenHash, KeyHasher};
+/// # #[derive(Default)]
+/// # struct Fnv(u64);
+/// # impl core::hash::Hasher for Fnv {
+/// #     fn write(&mut self, bytes: &[u8]) {
+/// #         for byte in bytes {
+/// #             self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+/// #         }
+/// #     }
+/// #     fn finish(&self) -> u64 { self.0 }
+/// # }
+/// type AccountKey = EncodeThenHash<Vec<u8>, CoreHasher<Fnv>>;
+///
+/// let account = vec![1u8, 2, 3];
+/// assert_eq!(AccountKey::hash(&account[..]), AccountKey::hash(&account));
+///
+/// let mut hasher = CoreHasher::<Fnv>::new();
+/// hasher.update(&[12, 1, 2, 3]);
+/// assert_eq!(AccountKey::hash(account), hasher.finalize());
+/// ```
+pub struct EncodeThenHash<T, H>(PhantomData<(T, H)>);
+
+impl<T: Encode, H: KeyHasher> EncodeThenHash<T, H> {
+	/// The hash of the encoding of `value`.
+	pub fn hash<V: EncodeLike<T>>(value: V) -> H::Output {
+		let mut output = HasherOutput(H::new());
+		value.encode_to(&mut output);
+		output.0.finalize()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		alloc::{string::String, vec, vec::Vec},
+		Compact,
+	};
+
+	/// A [`KeyHasher`] returning the bytes it hashed, to check what is hashed.
+	struct Concat(Vec<u8>);
+
+	impl KeyHasher for Concat {
+		type Output = Vec<u8>;
+
+		fn new() -> Self {
+			Concat(Vec::new())
+		}
+
+		fn update(&mut self, bytes: &[u8]) {
+			self.0.extend_from_slice(bytes)
+		}
+
+		fn finalize(self) -> Vec<u8> {
+			self.0
+		}
+	}
+
+	#[test]
+	fn encode_then_hash_hashes_the_encoding() {
+		let value = (7u32, vec![String::from("a"), String::from("bc")]);
+		assert_eq!(EncodeThenHash::<(u32, Vec<String>), Concat>::hash(&value), value.encode());
+
+		assert_eq!(EncodeThenHash::<Compact<u64>, Concat>::hash(Compact(64u64)), [1, 1]);
+		assert_eq!(
+			EncodeThenHash::<Vec<u32>, Concat>::hash(&[1u32, 2][..]),
+			EncodeThenHash::<Vec<u32>, Concat>::hash(vec![1u32, 2]),
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn core_hasher_hashes_the_encoding() {
+		use std::collections::hash_map::DefaultHasher;
+
+		let mut hasher = DefaultHasher::new();
+		hasher.write(&(1u8, 2u16).encode());
+		assert_eq!(
+			EncodeThenHash::<(u8, u16), CoreHasher<DefaultHasher>>::hash((1u8, 2u16)),
+			hasher.finish().to_le_bytes()
+		);
+	}
+}
@@ -0,0 +1,135 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of encodings, allocating at most one of them.
+
+use core::cmp::Ordering;
+
+use crate::{Encode, Output};
+
+/// Whether `a` and `b` have the same encoding, like `a.encode() == b.encode()`, without allocating
+/// the encoding of `a`.
+///
+/// ```
+/// # use parity_scale_codec::{encoded_eq, Compact};
+/// assert!(encoded_eq(&vec![1u8, 2], &[1u8, 2][..]));
+/// assert!(encoded_eq(&Compact(1u64), &4u8));
+/// assert!(!encoded_eq(&1u16, &1u32));
+/// ```
+///
+/// See [`encoded_cmp`] for when `b` is allocated.
+pub fn encoded_eq<A: Encode + ?Sized, B: Encode + ?Sized>(a: &A, b: &B) -> bool {
+	encoded_cmp(a, b) == Ordering::Equal
+}
+
+/// Compare the encodings of `a` and `b` lexicographically, like `a.encode().cmp(&b.encode())`,
+/// without allocating the encoding of `a`, e.g. to order storage keys by their encoding.
+///
+/// ```
+/// # use core::cmp::Ordering;
+/// # use parity_scale_codec::encoded_cmp;
+/// // `256u16` is encoded as `[0, 1]`.
+/// assert_eq!(encoded_cmp(&256u16, &1u16), Ordering::Less);
+/// assert_eq!(encoded_cmp(&(1u8, 2u8), &[1u8, 2]), Ordering::Equal);
+/// assert_eq!(encoded_cmp(&(1u8, 2u8), &1u8), Ordering::Greater);
+/// ```
+///
+/// The encoding of `a` is compared with the encoding of `b` while it is written, and comparing
+/// stops at the first difference, though `a` is still encoded until its end, as [`Output`] can't
+/// stop an encoding. `b` is encoded once with [`Encode::using_encoded`], which doesn't allocate for
+/// primitives and compact values, but allocates the encoding of most other types. Use
+/// [`encoded_cmp_bytes`] to compare with an encoding which is already at hand.
+pub fn encoded_cmp<A: Encode + ?Sized, B: Encode + ?Sized>(a: &A, b: &B) -> Ordering {
+	b.using_encoded(|b| encoded_cmp_bytes(a, b))
+}
+
+/// Compare the encoding of `value` with `encoded` lexicographically, like
+/// `value.encode().as_slice().cmp(encoded)`, without allocating.
+///
+/// ```
+/// # use core::cmp::Ordering;
+/// # use parity_scale_codec::encoded_cmp_bytes;
+/// assert_eq!(encoded_cmp_bytes(&1u16, &[1, 0]), Ordering::Equal);
+/// assert_eq!(encoded_cmp_bytes(&1u16, &[1]), Ordering::Greater);
+/// ```
+pub fn encoded_cmp_bytes<T: Encode + ?Sized>(value: &T, encoded: &[u8]) -> Ordering {
+	let mut output = CompareOutput { rhs: encoded, ordering: Ordering::Equal };
+	value.encode_to(&mut output);
+	if output.ordering == Ordering::Equal && !output.rhs.is_empty() {
+		// The encoding of `value` is a strict prefix of `encoded`.
+		return Ordering::Less;
+	}
+	output.ordering
+}
+
+/// An [`Output`] comparing the bytes written with `rhs`.
+struct CompareOutput<'a> {
+	rhs: &'a [u8],
+	/// The result of the comparison so far.
+	ordering: Ordering,
+}
+
+impl Output for CompareOutput<'_> {
+	fn write(&mut self, bytes: &[u8]) {
+		if self.ordering != Ordering::Equal {
+			return;
+		}
+		if bytes.len() > self.rhs.len() {
+			// `rhs` ends before the encoding.
+			self.ordering = bytes[..self.rhs.len()].cmp(self.rhs).then(Ordering::Greater);
+			return;
+		}
+		let (compared, rest) = self.rhs.split_at(bytes.len());
+		self.ordering = bytes.cmp(compared);
+		self.rhs = rest;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::alloc::{string::String, vec, vec::Vec};
+
+	fn check<A: Encode + ?Sized, B: Encode + ?Sized>(a: &A, b: &B) {
+		let expected = a.encode().cmp(&b.encode());
+		assert_eq!(encoded_cmp(a, b), expected);
+		assert_eq!(encoded_cmp(b, a), expected.reverse());
+		assert_eq!(encoded_cmp_bytes(a, &b.encode()), expected);
+		assert_eq!(encoded_eq(a, b), expected == Ordering::Equal);
+	}
+
+	#[test]
+	fn encoded_cmp_works() {
+		check(&0u32, &0u32);
+		check(&1u32, &256u32);
+		check(&(), &());
+		check(&(), &0u8);
+		check(&String::from("abc"), "abd");
+		check(&vec![1u8, 2], &vec![1u8, 2, 3]);
+		check(&(1u8, 2u16), &[1u8, 2, 0]);
+	}
+
+	#[test]
+	fn encoded_cmp_works_for_long_encodings() {
+		let long: Vec<u32> = (0..1000).collect();
+		check(&long, &long);
+		for position in [0, 63, 64, 100, 999] {
+			let mut other = long.clone();
+			other[position] += 1;
+			check(&long, &other);
+			check(&long, &other[..position]);
+			check(&long[..position], &long);
+		}
+	}
+}
@@ -0,0 +1,181 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wrappers to choose the byte order of numeric values explicitly.
+
+use core::mem;
+
+use crate::{
+	codec::{Decode, Encode, EncodeAsRef, Input, Output},
+	encode_like::EncodeLike,
+	DecodeWithMemTracking, Error,
+};
+
+/// A numeric value encoded in big-endian byte order.
+///
+/// SCALE encodes numbers in little-endian byte order. This wrapper can be used to encode single
+/// fields in big-endian instead, either by using it as the type of the field or with
+/// `#[codec(encoded_as = "Be<$FieldType>")]`. It stays big-endian whatever
+/// [`CodecConfig::byte_order`](crate::CodecConfig::byte_order).
+///
+/// ```
+/// # use parity_scale_codec::{Be, Encode};
+/// assert_eq!(Be(0x0102u16).encode(), vec![0x01, 0x02]);
+/// assert_eq!(0x0102u16.encode(), vec![0x02, 0x01]);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Be<T>(pub T);
+
+/// A numeric value encoded in little-endian byte order.
+///
+/// This is the same encoding as the one of `T` itself with the default
+/// [`CodecConfig`](crate::CodecConfig), but it stays little-endian whatever
+/// [`CodecConfig::byte_order`](crate::CodecConfig::byte_order). It can be used to make the byte
+/// order of a field explicit, e.g. next to fields using [`Be`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Le<T>(pub T);
+
+macro_rules! impl_endian_wrapper {
+	( $wrapper:ident, $to_bytes:ident, $from_bytes:ident; $( $t:ty ),* ) => { $(
+		impl EncodeLike for $wrapper<$t> {}
+
+		impl Encode for $wrapper<$t> {
+			fn size_hint(&self) -> usize {
+				mem::size_of::<$t>()
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				dest.write(&self.0.$to_bytes())
+			}
+
+			fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+				f(&self.0.$to_bytes())
+			}
+		}
+
+		impl Decode for $wrapper<$t> {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				let mut buf = [0u8; mem::size_of::<$t>()];
+				input.read(&mut buf)?;
+				Ok($wrapper(<$t>::$from_bytes(buf)))
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(mem::size_of::<$t>())
+			}
+		}
+
+		impl DecodeWithMemTracking for $wrapper<$t> {}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for $wrapper<$t> {
+			fn max_encoded_len() -> usize {
+				mem::size_of::<$t>()
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::ConstEncodedLen for $wrapper<$t> {}
+
+		impl<'a> EncodeAsRef<'a, $t> for $wrapper<$t> {
+			type RefType = $wrapper<$t>;
+		}
+
+		impl From<&$t> for $wrapper<$t> {
+			fn from(x: &$t) -> Self {
+				$wrapper(*x)
+			}
+		}
+
+		impl From<$t> for $wrapper<$t> {
+			fn from(x: $t) -> Self {
+				$wrapper(x)
+			}
+		}
+
+		impl From<$wrapper<$t>> for $t {
+			fn from(x: $wrapper<$t>) -> $t {
+				x.0
+			}
+		}
+	)* }
+}
+
+impl_endian_wrapper!(
+	Be, to_be_bytes, from_be_bytes;
+	u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64
+);
+impl_endian_wrapper!(
+	Le, to_le_bytes, from_le_bytes;
+	u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64
+);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::alloc::vec::Vec;
+
+	macro_rules! check_endian {
+		( $( $t:ty = $v:expr ),* ) => { $(
+			let v: $t = $v;
+			let encoded = Be(v).encode();
+			assert_eq!(encoded, v.to_be_bytes());
+			assert_eq!(Be::<$t>::decode(&mut &encoded[..]).unwrap(), Be(v));
+
+			let encoded = Le(v).encode();
+			assert_eq!(encoded, v.encode());
+			assert_eq!(Le::<$t>::decode(&mut &encoded[..]).unwrap(), Le(v));
+		)* }
+	}
+
+	#[test]
+	fn endian_wrappers_encode_decode() {
+		check_endian!(
+			u8 = 0x12,
+			u16 = 0x1234,
+			u32 = 0x1234_5678,
+			u64 = 0x0102_0304_0506_0708,
+			u128 = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10,
+			i8 = -2,
+			i16 = -0x1234,
+			i32 = -0x1234_5678,
+			i64 = -0x0102_0304_0506_0708,
+			i128 = -0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10,
+			f32 = -1.5,
+			f64 = core::f64::consts::PI
+		);
+
+		assert_eq!(
+			Be::<u32>::decode(&mut &[1, 2, 3][..]),
+			Err("Not enough data to fill buffer".into())
+		);
+	}
+
+	#[test]
+	fn vec_of_endian_wrappers() {
+		let v = vec![Be(1u16), Be(0x0203)];
+		let encoded = v.encode();
+		assert_eq!(encoded, vec![8, 0, 1, 2, 3]);
+		assert_eq!(Vec::<Be<u16>>::decode(&mut &encoded[..]).unwrap(), v);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn endian_wrappers_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(Be::<u64>::max_encoded_len(), 8);
+		assert_eq!(Le::<f32>::max_encoded_len(), 4);
+	}
+}
@@ -0,0 +1,170 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statistics of the variants of enums that are encoded and decoded.
+//!
+//! With the `enum-stats` feature, the implementations of `Encode` and `Decode` derived for enums
+//! report every variant they encode or successfully decode to the [`Recorder`] installed with
+//! [`set_recorder`]. [`Counters`] is a recorder counting how often each variant is used:
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use parity_scale_codec::{
+//!     decode_prefix,
+//!     enum_stats::{self, Counters},
+//!     Decode, Encode,
+//! };
+//!
+//! #[derive(Encode, Decode)]
+//! enum Message {
+//!     Ping,
+//!     Data(Vec<u8>),
+//! }
+//!
+//! static COUNTERS: Counters = Counters::new();
+//! enum_stats::set_recorder(&COUNTERS).ok().expect("No recorder is set yet");
+//!
+//! let encoded = Message::Ping.encode();
+//! decode_prefix::<Message>(&encoded).unwrap();
+//!
+//! let stats = COUNTERS.snapshot();
+//! let (ping, counts) = stats.iter().find(|(variant, _)| variant.name == "Ping").unwrap();
+//! assert!(ping.enum_name.ends_with("::Message"));
+//! assert_eq!((counts.encoded, counts.decoded), (1, 1));
+//! # }
+//! ```
+//!
+//! Encoding is recorded by `Encode::encode_to`. `Encode::encoded_size` and `Encode::fits_within`
+//! only encode to measure the size and are not counted, nor is anything encoded or decoded in
+//! [`without_recording`].
+
+use std::{
+	cell::Cell,
+	collections::BTreeMap,
+	sync::{Mutex, OnceLock},
+};
+
+/// Whether a variant was encoded or decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+	/// The variant was encoded.
+	Encode,
+	/// The variant was decoded.
+	Decode,
+}
+
+/// A variant of an enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variant {
+	/// The path of the enum, e.g. `my_crate::module::Enum`.
+	pub enum_name: &'static str,
+	/// The name of the variant.
+	pub name: &'static str,
+	/// The index of the variant in the encoding.
+	pub index: u8,
+}
+
+/// Receives the variants which are encoded and decoded.
+pub trait Recorder: Send + Sync {
+	/// Record that `variant` was encoded or decoded.
+	///
+	/// This is called during encoding and decoding, so it should be cheap.
+	fn record(&self, operation: Operation, variant: Variant);
+}
+
+static RECORDER: OnceLock<&'static dyn Recorder> = OnceLock::new();
+
+/// Install the global recorder.
+///
+/// The recorder can only be set once. If it was set before, `recorder` is returned as error.
+pub fn set_recorder(recorder: &'static dyn Recorder) -> Result<(), &'static dyn Recorder> {
+	RECORDER.set(recorder)
+}
+
+std::thread_local! {
+	static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` without recording the variants it encodes or decodes on this thread.
+pub fn without_recording<R>(f: impl FnOnce() -> R) -> R {
+	/// Restores the previous state, also if `f` panics.
+	struct Resume(bool);
+
+	impl Drop for Resume {
+		fn drop(&mut self) {
+			PAUSED.with(|paused| paused.set(self.0));
+		}
+	}
+
+	let _resume = Resume(PAUSED.with(|paused| paused.replace(true)));
+	f()
+}
+
+/// Report `variant` to the global recorder, if there is one.
+///
+/// Called by the derived implementations.
+#[doc(hidden)]
+#[inline]
+pub fn record(operation: Operation, enum_name: &'static str, name: &'static str, index: u8) {
+	if let Some(recorder) = RECORDER.get() {
+		if !PAUSED.with(Cell::get) {
+			recorder.record(operation, Variant { enum_name, name, index })
+		}
+	}
+}
+
+/// How often a variant was encoded and decoded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+	/// The number of times the variant was encoded.
+	pub encoded: u64,
+	/// The number of times the variant was decoded.
+	pub decoded: u64,
+}
+
+/// A [`Recorder`] counting how often each variant is encoded and decoded.
+#[derive(Debug, Default)]
+pub struct Counters {
+	counts: Mutex<BTreeMap<Variant, Counts>>,
+}
+
+impl Counters {
+	/// Create counters without any recorded variants.
+	pub const fn new() -> Self {
+		Counters { counts: Mutex::new(BTreeMap::new()) }
+	}
+
+	/// The counts of all variants recorded so far.
+	///
+	/// Variants which were never encoded or decoded are not included.
+	pub fn snapshot(&self) -> BTreeMap<Variant, Counts> {
+		self.counts.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// Reset all counts.
+	pub fn clear(&self) {
+		self.counts.lock().unwrap_or_else(|e| e.into_inner()).clear()
+	}
+}
+
+impl Recorder for Counters {
+	fn record(&self, operation: Operation, variant: Variant) {
+		let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+		let counts = counts.entry(variant).or_default();
+		match operation {
+			Operation::Encode => counts.encoded += 1,
+			Operation::Decode => counts.decoded += 1,
+		}
+	}
+}
@@ -0,0 +1,196 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Codec implementations for the flag sets of the `enumflags2` crate.
+//!
+//! `BitFlags<T>` is encoded as its bits, i.e. as the integer the flags of `T` are represented by.
+
+use crate::{Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output};
+use enumflags2::{BitFlag, BitFlags};
+
+impl<T: BitFlag> Encode for BitFlags<T>
+where
+	T::Numeric: Encode,
+{
+	fn size_hint(&self) -> usize {
+		self.bits().size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.bits().encode_to(dest)
+	}
+}
+
+impl<T: BitFlag> EncodeLike for BitFlags<T> where T::Numeric: Encode {}
+
+/// Decoding fails if a bit which isn't a flag of `T` is set, see [`LossyBitFlags`] to ignore such
+/// bits instead.
+impl<T: BitFlag> Decode for BitFlags<T>
+where
+	T::Numeric: Decode,
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		BitFlags::from_bits(T::Numeric::decode(input)?)
+			.map_err(|_| "Unknown bits set in BitFlags".into())
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		T::Numeric::encoded_fixed_size()
+	}
+}
+
+impl<T: BitFlag> DecodeWithMemTracking for BitFlags<T> where T::Numeric: Decode {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: BitFlag> crate::MaxEncodedLen for BitFlags<T>
+where
+	T::Numeric: crate::MaxEncodedLen,
+{
+	fn max_encoded_len() -> usize {
+		T::Numeric::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: BitFlag> crate::ConstEncodedLen for BitFlags<T> where T::Numeric: crate::ConstEncodedLen {}
+
+/// A [`BitFlags<T>`] encoded like it, but which ignores the bits which aren't flags of `T` when
+/// decoding, e.g. to accept the flags added by a newer version of `T`.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, LossyBitFlags};
+/// # use enumflags2::{bitflags, BitFlags};
+/// #[bitflags]
+/// #[repr(u8)]
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Permission {
+///     Read = 0b001,
+///     Write = 0b010,
+/// }
+///
+/// let encoded = 0b111u8.encode();
+/// assert!(decode_prefix::<BitFlags<Permission>>(&encoded).is_err());
+/// let (flags, _) = decode_prefix::<LossyBitFlags<Permission>>(&encoded).unwrap();
+/// assert_eq!(flags.0, Permission::Read | Permission::Write);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LossyBitFlags<T: BitFlag>(pub BitFlags<T>);
+
+impl<T: BitFlag> From<BitFlags<T>> for LossyBitFlags<T> {
+	fn from(flags: BitFlags<T>) -> Self {
+		LossyBitFlags(flags)
+	}
+}
+
+impl<T: BitFlag> From<LossyBitFlags<T>> for BitFlags<T> {
+	fn from(flags: LossyBitFlags<T>) -> Self {
+		flags.0
+	}
+}
+
+impl<T: BitFlag> Encode for LossyBitFlags<T>
+where
+	T::Numeric: Encode,
+{
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+}
+
+impl<T: BitFlag> EncodeLike for LossyBitFlags<T> where T::Numeric: Encode {}
+impl<T: BitFlag> EncodeLike<BitFlags<T>> for LossyBitFlags<T> where T::Numeric: Encode {}
+impl<T: BitFlag> EncodeLike<LossyBitFlags<T>> for BitFlags<T> where T::Numeric: Encode {}
+
+impl<T: BitFlag> Decode for LossyBitFlags<T>
+where
+	T::Numeric: Decode,
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Ok(LossyBitFlags(BitFlags::from_bits_truncate(T::Numeric::decode(input)?)))
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		T::Numeric::encoded_fixed_size()
+	}
+}
+
+impl<T: BitFlag> DecodeWithMemTracking for LossyBitFlags<T> where T::Numeric: Decode {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: BitFlag> crate::MaxEncodedLen for LossyBitFlags<T>
+where
+	T::Numeric: crate::MaxEncodedLen,
+{
+	fn max_encoded_len() -> usize {
+		T::Numeric::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: BitFlag> crate::ConstEncodedLen for LossyBitFlags<T> where T::Numeric: crate::ConstEncodedLen
+{}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use enumflags2::bitflags;
+
+	#[bitflags]
+	#[repr(u32)]
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	enum Permission {
+		Read = 1 << 0,
+		Write = 1 << 1,
+		Admin = 1 << 31,
+	}
+
+	#[test]
+	fn bit_flags_encode_as_their_bits() {
+		let flags = Permission::Read | Permission::Admin;
+		assert_eq!(flags.encode(), (1u32 | 1 << 31).encode());
+		assert_eq!(BitFlags::<Permission>::decode(&mut &flags.encode()[..]), Ok(flags));
+		assert_eq!(BitFlags::<Permission>::encoded_fixed_size(), Some(4));
+		assert_eq!(BitFlags::<Permission>::empty().encode(), [0; 4]);
+
+		let lossy = LossyBitFlags(flags);
+		assert_eq!(lossy.encode(), flags.encode());
+		assert_eq!(LossyBitFlags::<Permission>::decode(&mut &flags.encode()[..]), Ok(lossy));
+	}
+
+	#[test]
+	fn unknown_bits_are_rejected_or_ignored() {
+		let encoded = (1u32 | 1 << 4).encode();
+		assert_eq!(
+			BitFlags::<Permission>::decode(&mut &encoded[..]),
+			Err("Unknown bits set in BitFlags".into())
+		);
+		assert_eq!(
+			LossyBitFlags::<Permission>::decode(&mut &encoded[..]),
+			Ok(LossyBitFlags(Permission::Read.into()))
+		);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn bit_flags_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(BitFlags::<Permission>::max_encoded_len(), 4);
+		assert_eq!(LossyBitFlags::<Permission>::max_encoded_len(), 4);
+	}
+}
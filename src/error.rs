@@ -22,7 +22,11 @@ use crate::alloc::boxed::Box;
 ///
 /// Descriptive on `std` environment, with chaining error on `chain-error` environment,
 /// underscriptive otherwise.
-#[derive(PartialEq, Eq, Clone, Debug)]
+///
+/// Its `Debug` output lists the descriptions of the chain, e.g.
+/// `Error(["final type", "wrap cause", "root cause"])`, so it is stable and doesn't depend on how
+/// the chain is stored.
+#[derive(PartialEq, Eq, Clone)]
 pub struct Error {
 	#[cfg(feature = "chain-error")]
 	cause: Option<Box<Error>>,
@@ -48,6 +52,35 @@ impl Error {
 		}
 	}
 
+	/// An error with a description built at runtime, e.g. from the error of a reader.
+	#[cfg(all(feature = "chain-error", feature = "embedded-io"))]
+	pub(crate) fn from_desc(desc: impl Into<Cow<'static, str>>) -> Self {
+		Error { desc: desc.into(), cause: None }
+	}
+
+	/// The descriptions of the error chain, starting from the outermost description down to the
+	/// root cause.
+	///
+	/// ```
+	/// # use parity_scale_codec::Error;
+	/// let error = Error::from("root cause").chain("wrap cause");
+	/// assert_eq!(error.chain_iter().collect::<Vec<_>>(), ["wrap cause", "root cause"]);
+	/// ```
+	#[cfg(feature = "chain-error")]
+	pub fn chain_iter(&self) -> impl Iterator<Item = &str> {
+		core::iter::successors(Some(self), |error| error.cause.as_deref()).map(|error| &*error.desc)
+	}
+
+	/// The description of the innermost error of the chain, i.e. the one the chain started from.
+	#[cfg(feature = "chain-error")]
+	pub fn root_cause(&self) -> &str {
+		let mut error = self;
+		while let Some(cause) = &error.cause {
+			error = cause;
+		}
+		&error.desc
+	}
+
 	/// Display error with indentation.
 	#[cfg(feature = "chain-error")]
 	fn display_with_indent(&self, indent: u32, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -84,6 +117,31 @@ impl core::fmt::Display for Error {
 	}
 }
 
+impl core::fmt::Debug for Error {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		#[cfg(feature = "chain-error")]
+		{
+			f.debug_tuple("Error").field(&DebugChain(self)).finish()
+		}
+
+		#[cfg(not(feature = "chain-error"))]
+		{
+			f.write_str("Error")
+		}
+	}
+}
+
+/// Debug the descriptions of an error chain as a list.
+#[cfg(feature = "chain-error")]
+struct DebugChain<'a>(&'a Error);
+
+#[cfg(feature = "chain-error")]
+impl core::fmt::Debug for DebugChain<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.debug_list().entries(self.0.chain_iter()).finish()
+	}
+}
+
 impl From<&'static str> for Error {
 	fn from(desc: &'static str) -> Error {
 		#[cfg(feature = "chain-error")]
@@ -99,6 +157,13 @@ impl From<&'static str> for Error {
 	}
 }
 
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+	fn from(err: Error) -> std::io::Error {
+		std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+	}
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -114,6 +179,20 @@ impl std::error::Error for Error {
 	}
 }
 
+/// Handle a broken invariant of decoding, i.e. a bug.
+///
+/// Panics with `$msg`, or with the `no-panic-decode` feature, returns it as an error from the
+/// enclosing function, so decoding never panics.
+macro_rules! decode_bug {
+	($msg:literal) => {{
+		#[cfg(feature = "no-panic-decode")]
+		return Err($crate::Error::from(concat!("Internal error: ", $msg)));
+		#[cfg(not(feature = "no-panic-decode"))]
+		unreachable!($msg)
+	}};
+}
+pub(crate) use decode_bug;
+
 #[cfg(test)]
 mod tests {
 	use crate::Error;
@@ -136,4 +215,49 @@ mod tests {
 
 		assert_eq!(&s.to_string(), "wrap cause:\n\troot cause\n");
 	}
+
+	#[test]
+	fn chain_accessors() {
+		let error = Error::from("root cause").chain("wrap cause").chain("final type");
+		assert_eq!(
+			error.chain_iter().collect::<Vec<_>>(),
+			["final type", "wrap cause", "root cause"]
+		);
+		assert_eq!(error.root_cause(), "root cause");
+
+		let error = Error::from("single");
+		assert_eq!(error.chain_iter().collect::<Vec<_>>(), ["single"]);
+		assert_eq!(error.root_cause(), "single");
+	}
+
+	#[test]
+	fn debug_lists_the_chain() {
+		let error = Error::from("root cause").chain("wrap cause");
+		assert_eq!(format!("{error:?}"), r#"Error(["wrap cause", "root cause"])"#);
+		assert_eq!(
+			format!("{error:#?}"),
+			"Error(\n    [\n        \"wrap cause\",\n        \"root cause\",\n    ],\n)"
+		);
+	}
+
+	fn invariant(holds: bool) -> Result<u8, Error> {
+		if !holds {
+			super::decode_bug!("The invariant holds; qed");
+		}
+		Ok(1)
+	}
+
+	#[cfg(feature = "no-panic-decode")]
+	#[test]
+	fn decode_bug_returns_error() {
+		assert_eq!(invariant(true), Ok(1));
+		assert_eq!(invariant(false), Err("Internal error: The invariant holds; qed".into()));
+	}
+
+	#[cfg(not(feature = "no-panic-decode"))]
+	#[test]
+	#[should_panic(expected = "The invariant holds; qed")]
+	fn decode_bug_panics() {
+		let _ = invariant(false);
+	}
 }
@@ -0,0 +1,111 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An [`Output`] keeping the errors of encoding.
+
+use crate::{CodecConfig, Error, Output};
+
+/// A wrapper for `Output` which keeps the first error of encoding instead of panicking.
+///
+/// Values which can't be encoded, e.g. collections with more than `u32::MAX` elements, are
+/// reported through [`Output::on_encode_error`], which panics for most outputs. This wrapper keeps
+/// the first error, drops all later writes and returns the error from [`Self::finish`]. The inner
+/// output then contains a truncated encoding, which should be discarded.
+///
+/// Errors of writing to the inner output are only kept if the inner output reports them through
+/// its own `on_encode_error`, e.g. an [`IoWriter`](crate::IoWriter).
+///
+/// ```
+/// # use parity_scale_codec::{Encode, FallibleOutput};
+/// let mut dest = Vec::new();
+/// let mut output = FallibleOutput::new(&mut dest);
+/// (1u8, vec![2u16]).encode_to(&mut output);
+/// assert!(output.finish().is_ok());
+/// assert_eq!(dest, [1, 4, 2, 0]);
+/// ```
+pub struct FallibleOutput<'a, O: Output + ?Sized> {
+	output: &'a mut O,
+	error: Option<Error>,
+}
+
+impl<'a, O: Output + ?Sized> FallibleOutput<'a, O> {
+	/// Create a new `FallibleOutput` writing to `output`.
+	pub fn new(output: &'a mut O) -> Self {
+		Self { output, error: None }
+	}
+
+	/// The first error of encoding, if any.
+	pub fn finish(self) -> Result<(), Error> {
+		self.error.map_or(Ok(()), Err)
+	}
+}
+
+impl<O: Output + ?Sized> Output for FallibleOutput<'_, O> {
+	fn write(&mut self, bytes: &[u8]) {
+		if self.error.is_none() {
+			self.output.write(bytes);
+		}
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		if self.error.is_none() {
+			self.output.push_byte(byte);
+		}
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.output.config()
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		if self.error.is_none() {
+			self.error = Some(error);
+		}
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.error.is_some() || self.output.is_exhausted()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::vec::Vec, ConfiguredOutput, Encode, UsizeAs};
+
+	#[cfg(target_pointer_width = "64")]
+	#[test]
+	fn fallible_output_keeps_the_first_error() {
+		let mut dest = Vec::new();
+		let mut output = FallibleOutput::new(&mut dest);
+		(1u8, UsizeAs::<u32>::new(usize::MAX), UsizeAs::<u32>::new(usize::MAX - 1), 2u8)
+			.encode_to(&mut output);
+		assert_eq!(
+			output.finish(),
+			Err("Attempted to encode a usize which doesn't fit in u32".into())
+		);
+		assert_eq!(dest, [1]);
+	}
+
+	#[cfg(target_pointer_width = "64")]
+	#[test]
+	fn wrapping_outputs_forward_errors() {
+		let mut dest = Vec::new();
+		let mut output = FallibleOutput::new(&mut dest);
+		UsizeAs::<u32>::new(usize::MAX)
+			.encode_to(&mut ConfiguredOutput::new(&mut output, Default::default()));
+		assert!(output.finish().is_err());
+		assert!(dest.is_empty());
+	}
+}
@@ -0,0 +1,265 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fingerprints of the encoding of types.
+
+use core::marker::PhantomData;
+
+use crate::{
+	alloc::{
+		boxed::Box,
+		collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+		rc::Rc,
+		string::String,
+		sync::Arc,
+		vec::Vec,
+	},
+	Compact,
+};
+
+/// Trait for types with a fingerprint of their encoding.
+///
+/// Types whose fingerprints differ have different encodings, so the fingerprint can be stored
+/// next to an encoded value to check at runtime that it is decoded as the type it was encoded as,
+/// see [`AnyScale`](crate::AnyScale).
+///
+/// The fingerprint of a type only depends on how it is encoded: e.g. `Vec<T>`, `VecDeque<T>` and
+/// `BTreeSet<T>` have the same fingerprint, and `BTreeMap<K, V>` the one of `Vec<(K, V)>`.
+///
+/// This trait can be derived for structs and enums, in which case the fingerprint is computed
+/// from the name of the type, the fingerprints of its encoded fields and the indexes of its
+/// variants. Renaming a field or changing the path of the type doesn't change the fingerprint.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use parity_scale_codec::{Encode, EncodingFingerprint};
+///
+/// #[derive(Encode, EncodingFingerprint)]
+/// struct Transfer {
+///     #[codec(compact)]
+///     amount: u64,
+///     memo: Vec<u8>,
+/// }
+///
+/// #[derive(Encode, EncodingFingerprint)]
+/// struct Transfer2 {
+///     amount: u64,
+///     memo: Vec<u8>,
+/// }
+///
+/// assert_ne!(Transfer::FINGERPRINT, Transfer2::FINGERPRINT);
+/// # }
+/// ```
+pub trait EncodingFingerprint {
+	/// The fingerprint of the encoding of the type.
+	const FINGERPRINT: u64;
+}
+
+/// A `const` hasher to compute [`EncodingFingerprint::FINGERPRINT`].
+///
+/// This is the 64-bit FNV-1a hash of the written data, which is stable across platforms and
+/// versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FingerprintHasher(u64);
+
+impl FingerprintHasher {
+	/// Create a hasher without written data.
+	pub const fn new() -> Self {
+		FingerprintHasher(0xcbf2_9ce4_8422_2325)
+	}
+
+	const fn write_byte(self, byte: u8) -> Self {
+		FingerprintHasher((self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+	}
+
+	/// Write the bytes of `s`, followed by a `0xff` byte which is not part of any UTF-8 string.
+	pub const fn write_str(self, s: &str) -> Self {
+		let bytes = s.as_bytes();
+		let mut hasher = self;
+		let mut i = 0;
+		while i < bytes.len() {
+			hasher = hasher.write_byte(bytes[i]);
+			i += 1;
+		}
+		hasher.write_byte(0xff)
+	}
+
+	/// Write the little-endian bytes of `x`.
+	pub const fn write_u64(self, x: u64) -> Self {
+		let bytes = x.to_le_bytes();
+		let mut hasher = self;
+		let mut i = 0;
+		while i < bytes.len() {
+			hasher = hasher.write_byte(bytes[i]);
+			i += 1;
+		}
+		hasher
+	}
+
+	/// The hash of the written data.
+	pub const fn finish(self) -> u64 {
+		self.0
+	}
+}
+
+impl Default for FingerprintHasher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// The fingerprint of the encoding `name`, followed by the encodings with fingerprints `inner`.
+const fn fingerprint(name: &str, inner: &[u64]) -> u64 {
+	let mut hasher = FingerprintHasher::new().write_str(name).write_u64(inner.len() as u64);
+	let mut i = 0;
+	while i < inner.len() {
+		hasher = hasher.write_u64(inner[i]);
+		i += 1;
+	}
+	hasher.finish()
+}
+
+macro_rules! impl_primitive {
+	( $( $t:ty ),* ) => { $(
+		impl EncodingFingerprint for $t {
+			const FINGERPRINT: u64 = fingerprint(stringify!($t), &[]);
+		}
+	)* }
+}
+
+impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, bool, f32, f64);
+
+impl EncodingFingerprint for str {
+	const FINGERPRINT: u64 = fingerprint("str", &[]);
+}
+
+impl EncodingFingerprint for String {
+	const FINGERPRINT: u64 = str::FINGERPRINT;
+}
+
+impl<T: EncodingFingerprint> EncodingFingerprint for [T] {
+	const FINGERPRINT: u64 = fingerprint("seq", &[T::FINGERPRINT]);
+}
+
+impl<T: EncodingFingerprint, const N: usize> EncodingFingerprint for [T; N] {
+	const FINGERPRINT: u64 = fingerprint("array", &[N as u64, T::FINGERPRINT]);
+}
+
+macro_rules! impl_like_slice {
+	( $( $t:ident ),* ) => { $(
+		impl<T: EncodingFingerprint> EncodingFingerprint for $t<T> {
+			const FINGERPRINT: u64 = <[T]>::FINGERPRINT;
+		}
+	)* }
+}
+
+impl_like_slice!(Vec, VecDeque, LinkedList, BinaryHeap, BTreeSet);
+
+impl<K: EncodingFingerprint, V: EncodingFingerprint> EncodingFingerprint for BTreeMap<K, V> {
+	const FINGERPRINT: u64 = <[(K, V)]>::FINGERPRINT;
+}
+
+macro_rules! impl_like_inner {
+	( $( $t:ident ),* ) => { $(
+		impl<T: EncodingFingerprint + ?Sized> EncodingFingerprint for $t<T> {
+			const FINGERPRINT: u64 = T::FINGERPRINT;
+		}
+	)* }
+}
+
+impl_like_inner!(Box, Rc, Arc);
+
+impl<T: EncodingFingerprint + ?Sized> EncodingFingerprint for &T {
+	const FINGERPRINT: u64 = T::FINGERPRINT;
+}
+
+impl<T: EncodingFingerprint> EncodingFingerprint for Option<T> {
+	const FINGERPRINT: u64 = fingerprint("Option", &[T::FINGERPRINT]);
+}
+
+impl<T: EncodingFingerprint, E: EncodingFingerprint> EncodingFingerprint for Result<T, E> {
+	const FINGERPRINT: u64 = fingerprint("Result", &[T::FINGERPRINT, E::FINGERPRINT]);
+}
+
+impl<T: EncodingFingerprint> EncodingFingerprint for Compact<T> {
+	const FINGERPRINT: u64 = fingerprint("Compact", &[T::FINGERPRINT]);
+}
+
+impl<T: ?Sized> EncodingFingerprint for PhantomData<T> {
+	const FINGERPRINT: u64 = <()>::FINGERPRINT;
+}
+
+macro_rules! impl_tuple {
+	() => {
+		impl EncodingFingerprint for () {
+			const FINGERPRINT: u64 = fingerprint("tuple", &[]);
+		}
+	};
+	( $first:ident, $( $rest:ident, )* ) => {
+		impl<$first: EncodingFingerprint, $( $rest: EncodingFingerprint ),*> EncodingFingerprint
+			for ($first, $( $rest, )*)
+		{
+			const FINGERPRINT: u64 =
+				fingerprint("tuple", &[$first::FINGERPRINT, $( $rest::FINGERPRINT ),*]);
+		}
+
+		impl_tuple!( $( $rest, )* );
+	};
+}
+
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R,);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fingerprints_follow_the_encoding() {
+		assert_eq!(Vec::<u8>::FINGERPRINT, <[u8]>::FINGERPRINT);
+		assert_eq!(BTreeSet::<u8>::FINGERPRINT, VecDeque::<u8>::FINGERPRINT);
+		assert_eq!(BTreeMap::<u8, u16>::FINGERPRINT, Vec::<(u8, u16)>::FINGERPRINT);
+		assert_eq!(String::FINGERPRINT, <&str>::FINGERPRINT);
+		assert_eq!(Box::<u32>::FINGERPRINT, u32::FINGERPRINT);
+
+		let distinct = [
+			u8::FINGERPRINT,
+			i8::FINGERPRINT,
+			u32::FINGERPRINT,
+			String::FINGERPRINT,
+			Vec::<u8>::FINGERPRINT,
+			Vec::<u16>::FINGERPRINT,
+			<[u8; 4]>::FINGERPRINT,
+			<[u8; 5]>::FINGERPRINT,
+			Compact::<u32>::FINGERPRINT,
+			Option::<u32>::FINGERPRINT,
+			Result::<u32, u8>::FINGERPRINT,
+			Result::<u8, u32>::FINGERPRINT,
+			<()>::FINGERPRINT,
+			<(u8, u16)>::FINGERPRINT,
+			<(u16, u8)>::FINGERPRINT,
+			<((u8,), u16)>::FINGERPRINT,
+		];
+		for (i, a) in distinct.iter().enumerate() {
+			for b in &distinct[i + 1..] {
+				assert_ne!(a, b);
+			}
+		}
+	}
+
+	#[test]
+	fn fingerprint_hasher_is_fnv1a() {
+		assert_eq!(FingerprintHasher::new().finish(), 0xcbf2_9ce4_8422_2325);
+		assert_eq!(FingerprintHasher::new().write_byte(b'a').finish(), 0xaf63_dc4c_8601_ec8c);
+	}
+}
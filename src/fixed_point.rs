@@ -0,0 +1,164 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A binary fixed-point number encoded as its underlying integer.
+
+#[cfg(feature = "max-encoded-len")]
+use crate::{ConstEncodedLen, MaxEncodedLen};
+use crate::{Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output};
+
+/// A binary fixed-point number with `FRAC` fractional bits, stored as the integer `I`.
+///
+/// The value is `bits / 2^FRAC`, where `bits` is the underlying integer. It is encoded exactly
+/// like `bits`, so unlike a float, every value has a single encoding and arithmetic on the bits is
+/// deterministic across platforms.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, FixedPoint};
+/// // A price with 8 fractional bits: 2.5 is stored as `2.5 * 256 = 640`.
+/// type Price = FixedPoint<u32, 8>;
+///
+/// let price = Price::from_bits(640);
+/// assert_eq!(price.to_int(), 2);
+/// assert_eq!(price.to_f64(), 2.5);
+/// assert_eq!(price.encode(), 640u32.encode());
+/// assert_eq!(decode_prefix::<Price>(&price.encode()), Ok((price, &[][..])));
+///
+/// assert_eq!(Price::from_int(3), Some(Price::from_bits(768)));
+/// assert_eq!(Price::from_int(u32::MAX), None);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedPoint<I, const FRAC: u32>(I);
+
+impl<I, const FRAC: u32> FixedPoint<I, FRAC> {
+	/// The number of fractional bits.
+	pub const FRAC_BITS: u32 = FRAC;
+
+	/// Create the number whose underlying integer is `bits`, i.e. `bits / 2^FRAC`.
+	pub const fn from_bits(bits: I) -> Self {
+		FixedPoint(bits)
+	}
+
+	/// The underlying integer.
+	pub fn to_bits(self) -> I {
+		self.0
+	}
+}
+
+macro_rules! impl_int_fixed_point {
+	( $( $t:ty ),* ) => { $(
+		impl<const FRAC: u32> FixedPoint<$t, FRAC> {
+			/// The number equal to the integer `int`, or `None` if it is out of range.
+			pub fn from_int(int: $t) -> Option<Self> {
+				const { assert!(FRAC < <$t>::BITS, "`FRAC` must be less than the bits of `I`") }
+				let bits = int << FRAC;
+				(bits >> FRAC == int).then_some(FixedPoint(bits))
+			}
+
+			/// The largest integer less than or equal to the number.
+			pub fn to_int(self) -> $t {
+				const { assert!(FRAC < <$t>::BITS, "`FRAC` must be less than the bits of `I`") }
+				self.0 >> FRAC
+			}
+
+			/// The nearest `f64` to the number.
+			pub fn to_f64(self) -> f64 {
+				self.0 as f64 / (1u128 << FRAC) as f64
+			}
+		}
+	)* }
+}
+
+impl_int_fixed_point!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl<I: Encode, const FRAC: u32> Encode for FixedPoint<I, FRAC> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+
+	fn encoded_size(&self) -> usize {
+		self.0.encoded_size()
+	}
+}
+
+impl<I: Encode, const FRAC: u32> EncodeLike for FixedPoint<I, FRAC> {}
+
+impl<I: Decode, const FRAC: u32> Decode for FixedPoint<I, FRAC> {
+	fn decode<In: Input>(input: &mut In) -> Result<Self, Error> {
+		I::decode(input).map(FixedPoint)
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		I::encoded_fixed_size()
+	}
+}
+
+impl<I: DecodeWithMemTracking, const FRAC: u32> DecodeWithMemTracking for FixedPoint<I, FRAC> {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<I: MaxEncodedLen, const FRAC: u32> MaxEncodedLen for FixedPoint<I, FRAC> {
+	fn max_encoded_len() -> usize {
+		I::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<I: ConstEncodedLen, const FRAC: u32> ConstEncodedLen for FixedPoint<I, FRAC> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fixed_point_encodes_as_bits() {
+		let value = FixedPoint::<i16, 4>::from_bits(-40);
+		assert_eq!(value.encode(), (-40i16).encode());
+		assert_eq!(value.size_hint(), 2);
+		assert_eq!(FixedPoint::<i16, 4>::decode(&mut &value.encode()[..]), Ok(value));
+		assert!(FixedPoint::<i16, 4>::decode(&mut &[0][..]).is_err());
+	}
+
+	#[test]
+	fn fixed_point_int_conversions() {
+		assert_eq!(FixedPoint::<i16, 4>::from_bits(-40).to_int(), -3);
+		assert_eq!(FixedPoint::<i16, 4>::from_bits(-40).to_f64(), -2.5);
+		assert_eq!(FixedPoint::<i16, 4>::from_int(-3), Some(FixedPoint::from_bits(-48)));
+		assert_eq!(FixedPoint::<i8, 7>::from_int(0), Some(FixedPoint::from_bits(0)));
+		assert_eq!(FixedPoint::<i8, 7>::from_int(1), None);
+		assert_eq!(FixedPoint::<i8, 7>::from_int(-1), Some(FixedPoint::from_bits(i8::MIN)));
+		assert_eq!(FixedPoint::<u8, 0>::from_int(255), Some(FixedPoint::from_bits(255)));
+		assert_eq!(FixedPoint::<u128, 127>::from_bits(1 << 126).to_f64(), 0.5);
+	}
+
+	#[test]
+	fn fixed_point_orders_like_value() {
+		let mut values = [5, -3, 0].map(FixedPoint::<i32, 16>::from_int).map(Option::unwrap);
+		values.sort();
+		assert_eq!(values.map(|value| value.to_int()), [-3, 0, 5]);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn fixed_point_max_encoded_len() {
+		assert_eq!(FixedPoint::<u64, 32>::max_encoded_len(), 8);
+	}
+}
@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{alloc::vec::Vec, encode_like::EncodeLike, Decode, Encode, Error, Input, Output};
+use crate::{
+	alloc::vec::Vec,
+	codec::{check_element_size, strict_element_size},
+	encode_like::EncodeLike,
+	CountedInput, Decode, DecodeWithMemTracking, Encode, Error, Input, Output,
+};
 
 impl<T: Encode, L: generic_array::ArrayLength<T>> Encode for generic_array::GenericArray<T, L> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
@@ -26,9 +31,19 @@ impl<T: Encode, L: generic_array::ArrayLength<T>> EncodeLike for generic_array::
 
 impl<T: Decode, L: generic_array::ArrayLength<T>> Decode for generic_array::GenericArray<T, L> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		// The items are decoded into a temporary `Vec`.
+		input.on_before_alloc_mem(L::to_usize().saturating_mul(core::mem::size_of::<T>()))?;
 		let mut r = Vec::with_capacity(L::to_usize());
-		for _ in 0..L::to_usize() {
-			r.push(T::decode(input)?);
+		if let Some(size) = strict_element_size::<T, I>(input) {
+			for index in 0..L::to_usize() {
+				let mut counted = CountedInput::new(&mut *input);
+				r.push(T::decode(&mut counted)?);
+				check_element_size(index, size, counted.count())?;
+			}
+		} else {
+			for _ in 0..L::to_usize() {
+				r.push(T::decode(input)?);
+			}
 		}
 		let i = generic_array::GenericArray::from_exact_iter(r);
 
@@ -39,11 +54,29 @@ impl<T: Decode, L: generic_array::ArrayLength<T>> Decode for generic_array::Gene
 	}
 }
 
+impl<T: DecodeWithMemTracking, L: generic_array::ArrayLength<T>> DecodeWithMemTracking
+	for generic_array::GenericArray<T, L>
+{
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::DecodeWithMemLimit;
 	use generic_array::{arr, typenum, GenericArray};
 
+	#[test]
+	fn generic_array_strict_decoding() {
+		use crate::{CodecConfig, DecodeWithConfig};
+
+		let config = CodecConfig::DEFAULT.with_strict_arrays(true);
+		let test = arr![u16; 1, 2, 3];
+		assert_eq!(
+			GenericArray::<u16, typenum::U3>::decode_with_config(&mut &test.encode()[..], &config),
+			Ok(test)
+		);
+	}
+
 	#[test]
 	fn generic_array() {
 		let test = arr![u8; 3, 4, 5];
@@ -62,4 +95,19 @@ mod tests {
 		let encoded = test.encode();
 		assert_eq!(test, GenericArray::<u64, typenum::U1>::decode(&mut &encoded[..]).unwrap());
 	}
+
+	#[test]
+	fn generic_array_with_mem_limit() {
+		let encoded = arr![u32; 1, 2, 3, 4].encode();
+		assert!(
+			GenericArray::<u32, typenum::U4>::decode_with_mem_limit(&mut &encoded[..], 16).is_err()
+		);
+
+		let decoded =
+			GenericArray::<Vec<u8>, typenum::U2>::decode_with_mem_limit(&mut &[4, 1, 0][..], 64);
+		assert_eq!(decoded.unwrap().as_slice(), [vec![1], vec![]]);
+		let encoded = (vec![1u8; 64], Vec::<u8>::new()).encode();
+		assert!(GenericArray::<Vec<u8>, typenum::U2>::decode_with_mem_limit(&mut &encoded[..], 64)
+			.is_err());
+	}
 }
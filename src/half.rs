@@ -0,0 +1,91 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Codec implementations for the half-precision floats of the `half` crate.
+//!
+//! `f16` and `bf16` are encoded as the bits of the value, like a `u16`, so every value, including
+//! each NaN, round-trips exactly.
+
+use crate::{Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output};
+use half::{bf16, f16};
+
+macro_rules! impl_half {
+	( $( $t:ty ),* ) => { $(
+		impl Encode for $t {
+			fn size_hint(&self) -> usize {
+				2
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				self.to_bits().encode_to(dest)
+			}
+		}
+
+		impl EncodeLike for $t {}
+
+		impl Decode for $t {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				u16::decode(input).map(<$t>::from_bits)
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(2)
+			}
+		}
+
+		impl DecodeWithMemTracking for $t {}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for $t {
+			fn max_encoded_len() -> usize {
+				2
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::ConstEncodedLen for $t {}
+	)* }
+}
+
+impl_half!(f16, bf16);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn half_floats_encode_as_le_bits() {
+		let value = f16::from_f32(1.5);
+		assert_eq!(value.encode(), value.to_bits().encode());
+		assert_eq!(f16::decode(&mut &value.encode()[..]), Ok(value));
+
+		let value = bf16::from_f32(-2.0);
+		assert_eq!(value.encode(), value.to_bits().encode());
+		assert_eq!(bf16::decode(&mut &value.encode()[..]), Ok(value));
+
+		// NaN payloads are preserved.
+		let nan = f16::from_bits(0x7e01);
+		assert_eq!(f16::decode(&mut &nan.encode()[..]).unwrap().to_bits(), 0x7e01);
+		assert!(f16::decode(&mut &[0][..]).is_err());
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn half_floats_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(f16::max_encoded_len(), 2);
+		assert_eq!(bf16::max_encoded_len(), 2);
+	}
+}
@@ -0,0 +1,311 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sharing of the repeated byte strings and strings of decoded values.
+
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+	alloc::{collections::BTreeSet, string::String, sync::Arc, vec::Vec},
+	CodecConfig, Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
+};
+
+/// The shared byte strings and strings decoded by an [`InterningInput`].
+///
+/// Each distinct value is allocated once, and decoding an equal value again returns a clone of
+/// the same `Arc`. Values stay in the interner until it is [cleared](Interner::clear) or dropped,
+/// so one interner can be used for many decodes.
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+	bytes: BTreeSet<Arc<[u8]>>,
+	strs: BTreeSet<Arc<str>>,
+}
+
+impl Interner {
+	/// Create an empty interner.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The shared instance of `bytes`, which is allocated if it isn't interned yet.
+	pub fn intern_bytes(&mut self, bytes: &[u8]) -> Arc<[u8]> {
+		if let Some(shared) = self.bytes.get(bytes) {
+			return shared.clone();
+		}
+		let shared = Arc::<[u8]>::from(bytes);
+		self.bytes.insert(shared.clone());
+		shared
+	}
+
+	/// The shared instance of `s`, which is allocated if it isn't interned yet.
+	pub fn intern_str(&mut self, s: &str) -> Arc<str> {
+		if let Some(shared) = self.strs.get(s) {
+			return shared.clone();
+		}
+		let shared = Arc::<str>::from(s);
+		self.strs.insert(shared.clone());
+		shared
+	}
+
+	/// The number of distinct values interned.
+	pub fn len(&self) -> usize {
+		self.bytes.len() + self.strs.len()
+	}
+
+	/// Whether no value is interned.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Forget the interned values.
+	///
+	/// The values are freed once the decoded values sharing them are dropped.
+	pub fn clear(&mut self) {
+		self.bytes.clear();
+		self.strs.clear();
+	}
+}
+
+/// An [`Input`] sharing the values of the [`Interned`] fields decoded from it through an
+/// [`Interner`].
+///
+/// Decoding many values with equal byte strings or strings, e.g. the keys of a state, allocates
+/// each distinct one once instead of once per value:
+///
+/// ```
+/// use std::sync::Arc;
+/// use parity_scale_codec::{decode_prefix, Decode, Encode, Interned, Interner, InterningInput};
+///
+/// let encoded = vec![&b"balance"[..], b"nonce", b"balance"].encode();
+///
+/// let mut interner = Interner::new();
+/// let mut input = InterningInput::new(&encoded[..], &mut interner);
+/// let keys = Vec::<Interned<Arc<[u8]>>>::decode(&mut input)?;
+/// assert_eq!(&keys[0][..], b"balance");
+/// assert!(Arc::ptr_eq(&keys[0], &keys[2]));
+/// assert_eq!(interner.len(), 2);
+///
+/// // Without an interner, each value is allocated separately.
+/// let (keys, _) = decode_prefix::<Vec<Interned<Arc<[u8]>>>>(&encoded)?;
+/// assert!(!Arc::ptr_eq(&keys[0], &keys[2]));
+/// # Ok::<(), parity_scale_codec::Error>(())
+/// ```
+pub struct InterningInput<'a, I> {
+	input: I,
+	interner: &'a mut Interner,
+}
+
+impl<'a, I: Input> InterningInput<'a, I> {
+	/// Decode from `input`, sharing the decoded values through `interner`.
+	pub fn new(input: I, interner: &'a mut Interner) -> Self {
+		Self { input, interner }
+	}
+
+	/// The wrapped input.
+	pub fn into_inner(self) -> I {
+		self.input
+	}
+}
+
+impl<I: Input> Input for InterningInput<'_, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.input.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.input.read_byte()
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.config()
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		self.input.seek_forward(n)
+	}
+
+	fn interner(&mut self) -> Option<&mut Interner> {
+		Some(self.interner)
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		self.input.read_remaining_into(into)
+	}
+}
+
+/// An `Arc<[u8]>` or `Arc<str>` which is shared with the equal values decoded from the same
+/// [`InterningInput`].
+///
+/// It is encoded like the wrapped value, i.e. like a `Vec<u8>` or `String`. Decoding from other
+/// inputs allocates a new value, like decoding a `Vec<u8>` or `String` does.
+///
+/// For blobs known at compile time, [`CowStatic`](crate::CowStatic) borrows them instead.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interned<T>(pub T);
+
+impl<T> From<T> for Interned<T> {
+	fn from(x: T) -> Self {
+		Interned(x)
+	}
+}
+
+impl<T> Deref for Interned<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for Interned<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<T: Encode> Encode for Interned<T> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn encode(&self) -> Vec<u8> {
+		self.0.encode()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+
+	fn encoded_size(&self) -> usize {
+		self.0.encoded_size()
+	}
+}
+
+impl<T: Encode> EncodeLike for Interned<T> {}
+impl<T: Encode> EncodeLike<T> for Interned<T> {}
+impl<T: Encode> EncodeLike<Interned<T>> for T {}
+
+impl Decode for Interned<Arc<[u8]>> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let bytes = Vec::<u8>::decode(input)?;
+		Ok(Interned(match input.interner() {
+			Some(interner) => interner.intern_bytes(&bytes),
+			None => bytes.into(),
+		}))
+	}
+}
+
+impl DecodeWithMemTracking for Interned<Arc<[u8]>> {}
+
+impl Decode for Interned<Arc<str>> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let s = String::decode(input)?;
+		Ok(Interned(match input.interner() {
+			Some(interner) => interner.intern_str(&s),
+			None => s.into(),
+		}))
+	}
+}
+
+impl DecodeWithMemTracking for Interned<Arc<str>> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::DecodeWithMemLimit;
+
+	#[derive(Debug, PartialEq, Eq)]
+	struct Entry {
+		key: Interned<Arc<str>>,
+		value: Interned<Arc<[u8]>>,
+	}
+
+	impl Decode for Entry {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+			Ok(Entry { key: Decode::decode(input)?, value: Decode::decode(input)? })
+		}
+	}
+
+	#[test]
+	fn equal_values_are_shared() {
+		let encoded = vec![("a", vec![1u8, 2]), ("b", vec![1, 2]), ("a", vec![3])].encode();
+
+		let mut interner = Interner::new();
+		let entries =
+			Vec::<Entry>::decode(&mut InterningInput::new(&encoded[..], &mut interner)).unwrap();
+		assert_eq!(&*entries[2].key.0, "a");
+		assert!(Arc::ptr_eq(&entries[0].key, &entries[2].key));
+		assert!(Arc::ptr_eq(&entries[0].value, &entries[1].value));
+		assert_eq!(interner.len(), 4);
+
+		// The interner is shared across decodes.
+		let again =
+			Vec::<Entry>::decode(&mut InterningInput::new(&encoded[..], &mut interner)).unwrap();
+		assert!(Arc::ptr_eq(&entries[1].key, &again[1].key));
+		assert_eq!(interner.len(), 4);
+
+		interner.clear();
+		assert!(interner.is_empty());
+		// Shared by the first and third entries of both decodes.
+		assert_eq!(Arc::strong_count(&entries[0].key), 4);
+	}
+
+	#[test]
+	fn interned_is_encoded_like_the_wrapped_value() {
+		let value = Interned(Arc::<str>::from("hello"));
+		assert_eq!(value.encode(), "hello".encode());
+		assert_eq!(Interned::<Arc<str>>::decode(&mut &value.encode()[..]), Ok(value));
+
+		assert_eq!(
+			Interned::<Arc<str>>::decode(&mut &vec![0xffu8].encode()[..]),
+			Err("Invalid utf8 sequence".into())
+		);
+	}
+
+	#[test]
+	fn wrapping_inputs_forward_the_interner() {
+		let encoded = vec!["key"; 3].encode();
+
+		let mut interner = Interner::new();
+		let mut input = InterningInput::new(&encoded[..], &mut interner);
+		let keys = Vec::<Interned<Arc<str>>>::decode_with_mem_limit(&mut input, 1024).unwrap();
+		assert!(Arc::ptr_eq(&keys[0], &keys[2]));
+
+		let mut input = InterningInput::new(&encoded[..], &mut interner)
+			.with_mem_limit(1024)
+			.with_depth_limit(8);
+		let keys2 = Vec::<Interned<Arc<str>>>::decode(&mut input).unwrap();
+		assert!(Arc::ptr_eq(&keys[0], &keys2[0]));
+	}
+}
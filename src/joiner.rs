@@ -14,15 +14,26 @@
 
 //! Trait
 
-use core::iter::Extend;
+use core::{iter::Extend, marker::PhantomData};
 
-use crate::codec::Codec;
+#[cfg(feature = "max-encoded-len")]
+use crate::MaxEncodedLen;
+use crate::{codec::Codec, Encode, EncodeAsRef, HasCompact};
 
 /// Trait to allow itself to be serialised into a value which can be extended
 /// by bytes.
 pub trait Joiner {
 	/// Append encoding of value to `Self`.
 	fn and<V: Codec + Sized>(self, value: &V) -> Self;
+
+	/// Append the compact encoding of value to `Self`.
+	fn and_compact<V: HasCompact>(mut self, value: &V) -> Self
+	where
+		Self: Sized + for<'a> Extend<&'a u8>,
+	{
+		encode_compact(value, |s| self.extend(s));
+		self
+	}
 }
 
 impl<T> Joiner for T
@@ -34,3 +45,99 @@ where
 		self
 	}
 }
+
+/// Call `f` with the compact encoding of `value`.
+fn encode_compact<V: HasCompact>(value: &V, f: impl FnOnce(&[u8])) {
+	<<V as HasCompact>::Type as EncodeAsRef<'_, V>>::RefType::from(value).using_encoded(f)
+}
+
+/// Builds a key, e.g. a storage key, from fixed-width and compact parts.
+///
+/// The parts are appended to the buffer `B` like with [`Joiner`], and their types are recorded in
+/// `P` as nested pairs, e.g. `(((), u8), Compact<u32>)`. With the `max-encoded-len` feature, this
+/// gives the maximum length of any key built the same way, to size a static buffer.
+///
+/// ```
+/// # use parity_scale_codec::{Compact, Encode, KeyBuilder};
+/// let key = KeyBuilder::new(Vec::new()).and(&7u8).and_compact(&300u32).and(&[1u8; 4]);
+/// # #[cfg(feature = "max-encoded-len")]
+/// assert_eq!(key.max_encoded_len(), 1 + 5 + 4);
+/// assert_eq!(key.finish(), (7u8, Compact(300u32), [1u8; 4]).encode());
+/// ```
+pub struct KeyBuilder<B, P = ()> {
+	buffer: B,
+	_parts: PhantomData<fn() -> P>,
+}
+
+impl<B: for<'a> Extend<&'a u8>> KeyBuilder<B> {
+	/// Start a key at the end of `buffer`.
+	pub fn new(buffer: B) -> Self {
+		KeyBuilder { buffer, _parts: PhantomData }
+	}
+}
+
+impl<B: for<'a> Extend<&'a u8>, P> KeyBuilder<B, P> {
+	/// Append the encoding of `value`.
+	pub fn and<V: Encode>(mut self, value: &V) -> KeyBuilder<B, (P, V)> {
+		value.using_encoded(|s| self.buffer.extend(s));
+		KeyBuilder { buffer: self.buffer, _parts: PhantomData }
+	}
+
+	/// Append the compact encoding of `value`.
+	pub fn and_compact<V: HasCompact>(mut self, value: &V) -> KeyBuilder<B, (P, V::Type)> {
+		encode_compact(value, |s| self.buffer.extend(s));
+		KeyBuilder { buffer: self.buffer, _parts: PhantomData }
+	}
+
+	/// The buffer with the appended parts.
+	pub fn finish(self) -> B {
+		self.buffer
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<B, P: MaxEncodedLen> KeyBuilder<B, P> {
+	/// The maximum length of the parts appended so far, for any values of their types.
+	///
+	/// It only depends on the types of the parts, see [`KeyBuilder::max_len`] to get it without
+	/// building a key.
+	pub fn max_encoded_len(&self) -> usize {
+		Self::max_len()
+	}
+
+	/// The maximum length of a key made of the parts `P`.
+	pub fn max_len() -> usize {
+		P::max_encoded_len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn joiner_and_compact() {
+		let key = Vec::new().and(&1u16).and_compact(&64u64).and_compact(&());
+		assert_eq!(key, [1, 0, 1, 1]);
+	}
+
+	#[test]
+	fn key_builder_works() {
+		let mut buffer = vec![0xff];
+		buffer = KeyBuilder::new(buffer).and(&[2u8; 2]).and_compact(&(1u32 << 30)).finish();
+		assert_eq!(buffer, [0xff, 2, 2, 3, 0, 0, 0, 0x40]);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn key_builder_max_len() {
+		use crate::Compact;
+
+		type Key = ((((), [u8; 16]), Compact<u64>), u32);
+		assert_eq!(KeyBuilder::<Vec<u8>, Key>::max_len(), 16 + 9 + 4);
+
+		let key = KeyBuilder::new(Vec::new()).and(&[0u8; 16]).and_compact(&u64::MAX).and(&0u32);
+		let max_len = key.max_encoded_len();
+		assert_eq!(key.finish().len(), max_len);
+	}
+}
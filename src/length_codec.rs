@@ -0,0 +1,325 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pluggable encodings of the length prefix of collections.
+
+use core::{
+	marker::PhantomData,
+	mem,
+	ops::{Deref, DerefMut},
+};
+
+use crate::{
+	alloc::vec::Vec,
+	codec::{compact_encode_len_to, decode_vec_with_len, encode_slice_no_len},
+	compact::{Compact, CompactLen},
+	encode_like::EncodeLike,
+	Decode, DecodeWithMemTracking, Encode, Error, Input, Le, Output,
+};
+
+/// An encoding of the length prefix of a collection.
+///
+/// SCALE encodes lengths as [`Compact<u32>`], which is implemented by [`ScaleCompact`]. Other
+/// implementations can be used with [`VecWith`] to interoperate with formats that are like SCALE
+/// except for the encoding of lengths.
+pub trait LengthCodec {
+	/// Encode `len` to `dest`.
+	///
+	/// If `len` can't be represented by this encoding, this is reported through
+	/// [`Output::on_encode_error`].
+	fn encode_len<W: Output + ?Sized>(len: usize, dest: &mut W);
+
+	/// The number of bytes `encode_len` writes for `len`.
+	///
+	/// This is 0 if `len` can't be represented by this encoding, as nothing is written then.
+	fn encoded_len_size(len: usize) -> usize;
+
+	/// Decode a length from `input`.
+	fn decode_len<I: Input>(input: &mut I) -> Result<usize, Error>;
+}
+
+/// Lengths encoded as [`Compact<u32>`], like SCALE does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScaleCompact;
+
+impl LengthCodec for ScaleCompact {
+	fn encode_len<W: Output + ?Sized>(len: usize, dest: &mut W) {
+		if let Err(e) = compact_encode_len_to(dest, len) {
+			dest.on_encode_error(e);
+		}
+	}
+
+	fn encoded_len_size(len: usize) -> usize {
+		u32::try_from(len).map_or(0, |len| Compact::<u32>::compact_len(&len))
+	}
+
+	fn decode_len<I: Input>(input: &mut I) -> Result<usize, Error> {
+		<Compact<u32>>::decode(input).map(|Compact(len)| len as usize)
+	}
+}
+
+/// Lengths encoded as unsigned [LEB128](https://en.wikipedia.org/wiki/LEB128).
+///
+/// Only the shortest encoding of a length is accepted when decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Leb128;
+
+impl LengthCodec for Leb128 {
+	fn encode_len<W: Output + ?Sized>(len: usize, dest: &mut W) {
+		leb128_encode(len as u64, dest)
+	}
+
+	fn encoded_len_size(len: usize) -> usize {
+		leb128_len(len as u64)
+	}
+
+	fn decode_len<I: Input>(input: &mut I) -> Result<usize, Error> {
+		usize::try_from(leb128_decode(input)?).map_err(|_| LEB128_OUT_OF_RANGE.into())
+	}
+}
+
+const LEB128_OUT_OF_RANGE: &str = "LEB128 integer is out of range";
+
+/// Encode `value` as unsigned LEB128: 7 bits per byte, with the high bit set on all bytes but
+/// the last.
+pub(crate) fn leb128_encode<W: Output + ?Sized>(mut value: u64, dest: &mut W) {
+	while value >= 0x80 {
+		dest.push_byte(value as u8 | 0x80);
+		value >>= 7;
+	}
+	dest.push_byte(value as u8);
+}
+
+/// The number of bytes [`leb128_encode`] writes for `value`.
+pub(crate) fn leb128_len(value: u64) -> usize {
+	let bits = u64::BITS - value.leading_zeros();
+	(bits.max(1) as usize).div_ceil(7)
+}
+
+/// Decode an unsigned LEB128 integer, only accepting its shortest encoding.
+pub(crate) fn leb128_decode<I: Input>(input: &mut I) -> Result<u64, Error> {
+	let mut value = 0u64;
+	let mut shift = 0;
+	loop {
+		let byte = input.read_byte()?;
+		let bits = (byte & 0x7f) as u64;
+		if shift >= u64::BITS || (bits << shift) >> shift != bits {
+			return Err(LEB128_OUT_OF_RANGE.into());
+		}
+		value |= bits << shift;
+
+		if byte & 0x80 == 0 {
+			if byte == 0 && shift > 0 {
+				return Err("LEB128 integer is not in its shortest form".into());
+			}
+			return Ok(value);
+		}
+		shift += 7;
+	}
+}
+
+/// Lengths encoded as a little endian `u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedU32LE;
+
+impl LengthCodec for FixedU32LE {
+	fn encode_len<W: Output + ?Sized>(len: usize, dest: &mut W) {
+		match u32::try_from(len) {
+			Ok(len) => Le(len).encode_to(dest),
+			Err(_) => dest.on_encode_error(
+				"Attempted to serialize a collection with too many elements.".into(),
+			),
+		}
+	}
+
+	fn encoded_len_size(len: usize) -> usize {
+		u32::try_from(len).map_or(0, |_| mem::size_of::<u32>())
+	}
+
+	fn decode_len<I: Input>(input: &mut I) -> Result<usize, Error> {
+		Le::<u32>::decode(input).map(|Le(len)| len as usize)
+	}
+}
+
+/// A `Vec<T>` whose length prefix is encoded with the [`LengthCodec`] `L`.
+///
+/// The elements are encoded as in `Vec<T>`, so `VecWith<ScaleCompact, T>` is encoded exactly like
+/// `Vec<T>`.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Leb128, VecWith};
+/// let value = VecWith::<Leb128, u8>::new(vec![1; 200]);
+/// assert_eq!(value.encode()[..3], [0xc8, 0x01, 1]);
+/// ```
+pub struct VecWith<L, T>(pub Vec<T>, PhantomData<L>);
+
+impl<L, T> VecWith<L, T> {
+	/// Wrap `vec`.
+	pub fn new(vec: Vec<T>) -> Self {
+		VecWith(vec, PhantomData)
+	}
+
+	/// Unwrap the inner `Vec`.
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+}
+
+impl<L, T> From<Vec<T>> for VecWith<L, T> {
+	fn from(vec: Vec<T>) -> Self {
+		Self::new(vec)
+	}
+}
+
+impl<L, T> Deref for VecWith<L, T> {
+	type Target = Vec<T>;
+
+	fn deref(&self) -> &Vec<T> {
+		&self.0
+	}
+}
+
+impl<L, T> DerefMut for VecWith<L, T> {
+	fn deref_mut(&mut self) -> &mut Vec<T> {
+		&mut self.0
+	}
+}
+
+impl<L, T: core::fmt::Debug> core::fmt::Debug for VecWith<L, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<L, T: Clone> Clone for VecWith<L, T> {
+	fn clone(&self) -> Self {
+		Self::new(self.0.clone())
+	}
+}
+
+impl<L, T: PartialEq> PartialEq for VecWith<L, T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<L, T: Eq> Eq for VecWith<L, T> {}
+
+impl<L, T> Default for VecWith<L, T> {
+	fn default() -> Self {
+		Self::new(Vec::new())
+	}
+}
+
+impl<L: LengthCodec, T: Encode> Encode for VecWith<L, T> {
+	fn size_hint(&self) -> usize {
+		L::encoded_len_size(self.0.len()).saturating_add(mem::size_of_val(&self.0[..]))
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		L::encode_len(self.0.len(), dest);
+		encode_slice_no_len(&self.0, dest)
+	}
+}
+
+impl<L: LengthCodec, T: Encode> EncodeLike for VecWith<L, T> {}
+
+impl<L: LengthCodec, T: Decode> Decode for VecWith<L, T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let len = L::decode_len(input)?;
+		decode_vec_with_len(input, len).map(Self::new)
+	}
+}
+
+impl<L: LengthCodec, T: DecodeWithMemTracking> DecodeWithMemTracking for VecWith<L, T> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check_len<L: LengthCodec>(len: usize, expected: &[u8]) {
+		let mut encoded = Vec::new();
+		L::encode_len(len, &mut encoded);
+		assert_eq!(encoded, expected);
+		assert_eq!(L::encoded_len_size(len), expected.len());
+		assert_eq!(L::decode_len(&mut &expected[..]).unwrap(), len);
+	}
+
+	#[test]
+	fn length_codecs_work() {
+		check_len::<ScaleCompact>(0, &[0]);
+		check_len::<ScaleCompact>(64, &[1, 1]);
+
+		check_len::<Leb128>(0, &[0]);
+		check_len::<Leb128>(127, &[0x7f]);
+		check_len::<Leb128>(128, &[0x80, 0x01]);
+		check_len::<Leb128>(624_485, &[0xe5, 0x8e, 0x26]);
+		#[cfg(target_pointer_width = "64")]
+		check_len::<Leb128>(
+			usize::MAX,
+			&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+		);
+
+		check_len::<FixedU32LE>(0, &[0, 0, 0, 0]);
+		check_len::<FixedU32LE>(0x0102_0304, &[4, 3, 2, 1]);
+	}
+
+	#[cfg(target_pointer_width = "64")]
+	#[test]
+	fn length_codecs_size_of_unrepresentable_lengths() {
+		let len = u32::MAX as usize + 1;
+		assert_eq!(ScaleCompact::encoded_len_size(len), 0);
+		assert_eq!(FixedU32LE::encoded_len_size(len), 0);
+		assert_eq!(ScaleCompact::encoded_len_size(len - 1), 5);
+		assert_eq!(FixedU32LE::encoded_len_size(len - 1), 4);
+	}
+
+	#[test]
+	fn leb128_rejects_invalid_lengths() {
+		assert_eq!(
+			Leb128::decode_len(&mut &[0x80, 0x00][..]),
+			Err("LEB128 integer is not in its shortest form".into())
+		);
+		assert_eq!(
+			Leb128::decode_len(&mut &[0xff; 11][..]),
+			Err("LEB128 integer is out of range".into())
+		);
+		#[cfg(target_pointer_width = "64")]
+		assert_eq!(
+			Leb128::decode_len(
+				&mut &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02][..]
+			),
+			Err("LEB128 integer is out of range".into())
+		);
+		assert!(Leb128::decode_len(&mut &[0x80][..]).is_err());
+	}
+
+	#[test]
+	fn vec_with_works() {
+		let vec = vec![1u16, 2, 3];
+
+		let value = VecWith::<ScaleCompact, _>::new(vec.clone());
+		assert_eq!(value.encode(), vec.encode());
+
+		let value = VecWith::<FixedU32LE, _>::new(vec.clone());
+		let encoded = value.encode();
+		assert_eq!(encoded, [3, 0, 0, 0, 1, 0, 2, 0, 3, 0]);
+		assert_eq!(VecWith::<FixedU32LE, u16>::decode(&mut &encoded[..]).unwrap(), value);
+
+		let value = VecWith::<Leb128, _>::new(vec![String::from("a"); 300]);
+		let encoded = value.encode();
+		assert_eq!(encoded[..2], [0xac, 0x02]);
+		assert_eq!(VecWith::<Leb128, String>::decode(&mut &encoded[..]).unwrap(), value);
+	}
+}
@@ -0,0 +1,193 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lenient decoding of damaged data.
+//!
+//! The functions of this module skip values which fail to decode instead of failing, to recover as
+//! much as possible from corrupted data, e.g. in forensic tooling. They accept encodings which the
+//! rest of the crate rejects, so they must never be used for consensus critical or untrusted
+//! data where the result matters.
+
+use core::mem;
+
+use crate::{
+	alloc::vec::Vec, codec::decode_len, ConfiguredInput, ConstEncodedLen, Decode, Error, Input,
+};
+
+/// The result of [`decode_lenient_vec`].
+#[derive(Debug)]
+pub struct LenientVec<T> {
+	/// The elements which were decoded successfully, in order.
+	pub values: Vec<T>,
+	/// The index in the encoded `Vec` and the error of each element which failed to decode.
+	pub errors: Vec<(usize, Error)>,
+}
+
+impl<T> LenientVec<T> {
+	/// Whether all elements were decoded successfully.
+	pub fn is_complete(&self) -> bool {
+		self.errors.is_empty()
+	}
+}
+
+/// Decode a `Vec<T>`, skipping the elements which fail to decode.
+///
+/// As `T` has a constant encoded length, each element is read as exactly that many bytes, so an
+/// element which fails to decode doesn't affect the following ones. If the input ends before all
+/// elements were read, the error is recorded at the index of the first missing element and the
+/// elements read so far are returned.
+///
+/// Only an invalid length prefix, or exceeding the memory limit of `input`, see
+/// [`DecodeWithMemLimit`](crate::DecodeWithMemLimit), is returned as error. If `T` is encoded as
+/// no bytes, all elements decode alike, so decoding stops at the first error.
+///
+/// ```
+/// # use parity_scale_codec::{lenient::decode_lenient_vec, Encode};
+/// let mut encoded = vec![true, false, true].encode();
+/// // Corrupt the second element.
+/// encoded[2] = 7;
+///
+/// let decoded = decode_lenient_vec::<bool, _>(&mut &encoded[..]).unwrap();
+/// assert_eq!(decoded.values, [true, true]);
+/// assert_eq!(decoded.errors.len(), 1);
+/// assert_eq!(decoded.errors[0].0, 1);
+/// ```
+pub fn decode_lenient_vec<T: Decode + ConstEncodedLen, I: Input>(
+	input: &mut I,
+) -> Result<LenientVec<T>, Error> {
+	let len = decode_len(input)?;
+
+	let encoded_len = T::max_encoded_len();
+	let mut buf = crate::alloc::vec![0; encoded_len];
+	let mut values = Vec::new();
+	let mut errors = Vec::new();
+	for index in 0..len {
+		if let Err(e) = input.read(&mut buf) {
+			reserve_chunk(input, &mut errors, len - index)?;
+			errors.push((index, e.chain("Input ended before all elements were read")));
+			break;
+		}
+
+		let config = *input.config();
+		match T::decode(&mut ConfiguredInput::new(&mut &buf[..], config)) {
+			Ok(value) => {
+				reserve_chunk(input, &mut values, len - index)?;
+				values.push(value);
+			},
+			Err(e) => {
+				reserve_chunk(input, &mut errors, len - index)?;
+				errors.push((index, e));
+				// Elements encoded as no bytes all decode alike, so the others would fail too.
+				if encoded_len == 0 {
+					break;
+				}
+			},
+		}
+	}
+
+	Ok(LenientVec { values, errors })
+}
+
+/// Reserve the next chunk of `vec` if it's full, tracking the memory like collections do.
+fn reserve_chunk<T, I: Input>(
+	input: &mut I,
+	vec: &mut Vec<T>,
+	remaining: usize,
+) -> Result<(), Error> {
+	if vec.len() < vec.capacity() {
+		return Ok(());
+	}
+
+	let chunk_len = input
+		.config()
+		.max_preallocation
+		.checked_div(mem::size_of::<T>())
+		.unwrap_or(usize::MAX)
+		.clamp(1, remaining.max(1));
+	input.on_before_alloc_mem(chunk_len.saturating_mul(mem::size_of::<T>()))?;
+	vec.reserve_exact(chunk_len);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Encode;
+	use core::num::NonZeroU32;
+
+	#[test]
+	fn decode_lenient_vec_works_for_valid_data() {
+		let encoded = vec![1u32, 2, 3].encode();
+		let decoded = decode_lenient_vec::<u32, _>(&mut &encoded[..]).unwrap();
+		assert!(decoded.is_complete());
+		assert_eq!(decoded.values, [1, 2, 3]);
+	}
+
+	#[test]
+	fn decode_lenient_vec_skips_invalid_elements() {
+		let mut encoded = vec![1u32, 2, 3, 4].encode();
+		// Zero the second and fourth elements.
+		encoded[5..9].fill(0);
+		encoded[13..17].fill(0);
+
+		let decoded = decode_lenient_vec::<NonZeroU32, _>(&mut &encoded[..]).unwrap();
+		assert!(!decoded.is_complete());
+		assert_eq!(decoded.values, [NonZeroU32::new(1).unwrap(), NonZeroU32::new(3).unwrap()]);
+		assert_eq!(decoded.errors.iter().map(|(index, _)| *index).collect::<Vec<_>>(), [1, 3]);
+	}
+
+	#[test]
+	fn decode_lenient_vec_keeps_elements_before_truncation() {
+		let encoded = vec![1u16, 2, 3].encode();
+		let decoded = decode_lenient_vec::<u16, _>(&mut &encoded[..encoded.len() - 1]).unwrap();
+		assert_eq!(decoded.values, [1, 2]);
+		assert_eq!(decoded.errors.len(), 1);
+		assert_eq!(decoded.errors[0].0, 2);
+
+		assert!(decode_lenient_vec::<u16, _>(&mut &[][..]).is_err());
+	}
+
+	/// A value encoded as no bytes which never decodes.
+	struct Invalid;
+
+	impl Encode for Invalid {}
+
+	impl Decode for Invalid {
+		fn decode<I: Input>(_: &mut I) -> Result<Self, Error> {
+			Err("Invalid".into())
+		}
+	}
+
+	impl crate::MaxEncodedLen for Invalid {
+		fn max_encoded_len() -> usize {
+			0
+		}
+	}
+
+	impl ConstEncodedLen for Invalid {}
+
+	#[test]
+	fn decode_lenient_vec_is_bounded() {
+		use crate::Compact;
+
+		let encoded = Compact(u32::MAX).encode();
+		let decoded = decode_lenient_vec::<Invalid, _>(&mut &encoded[..]).unwrap();
+		assert!(decoded.values.is_empty());
+		assert_eq!(decoded.errors.len(), 1);
+
+		let encoded = vec![1u32; 10_000].encode();
+		let mut input = (&encoded[..]).with_mem_limit(1024);
+		assert!(decode_lenient_vec::<u32, _>(&mut input).is_err());
+	}
+}
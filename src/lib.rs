@@ -41,51 +41,181 @@ pub mod alloc {
 	pub use std::{alloc, borrow, boxed, collections, rc, string, sync, vec};
 }
 
+mod any_scale;
+#[cfg(feature = "arena")]
+mod arena;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
+mod bounded_output;
 mod btree_utils;
 mod codec;
+pub mod codec_fuzz;
 mod compact;
+#[cfg(feature = "serde")]
+mod compact_serde;
+mod config;
 #[cfg(feature = "max-encoded-len")]
 mod const_encoded_len;
 mod counted_input;
+mod cow_static;
 mod decode_all;
+mod decode_context;
 mod decode_finished;
+pub mod delta;
 mod depth_limit;
+#[cfg(feature = "either")]
+mod either;
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
 mod encode_append;
 mod encode_like;
+mod encode_then_hash;
+mod encoded_cmp;
+mod endian;
+#[cfg(feature = "enum-stats")]
+pub mod enum_stats;
+#[cfg(feature = "enumflags2")]
+mod enumflags;
 mod error;
+mod fallible_output;
+mod fingerprint;
+mod fixed_point;
 #[cfg(feature = "generic-array")]
 mod generic_array;
+#[cfg(feature = "half")]
+mod half;
+#[cfg(target_has_atomic = "ptr")]
+mod interning;
 mod joiner;
 mod keyedvec;
+mod length_codec;
+#[cfg(feature = "max-encoded-len")]
+pub mod lenient;
+#[cfg(feature = "std")]
+mod lock;
 #[cfg(feature = "max-encoded-len")]
 mod max_encoded_len;
+mod max_len;
 mod mem_tracking;
+mod migrate;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod normalize;
+mod ordered_range;
+mod packed_option;
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_util;
+mod prefixed_raw;
+mod preflight;
+pub mod prelude;
+mod progress_input;
+mod rle;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod serde_scale;
+mod skipped;
+pub mod struct_encoder;
+mod tagged_codec;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+mod text_armor;
+#[cfg(feature = "std")]
+mod time;
+mod total_ord_f64;
+pub mod transcode;
+pub mod trivially_codable;
+mod unchecked_tail;
+mod usize_as;
+#[cfg(feature = "varint-compare")]
+pub mod varint_compare;
+mod wire_format;
 
+#[cfg(feature = "arena")]
+pub use self::arena::ArenaDecode;
+#[cfg(not(target_has_atomic = "ptr"))]
+pub use self::codec::Interner;
 #[cfg(feature = "std")]
-pub use self::codec::IoReader;
+pub use self::codec::{InputReader, IoReader, IoWriter, OutputWriter};
+#[cfg(feature = "embedded-io")]
+pub use self::embedded_io::{EmbeddedIoReader, EmbeddedIoWriter};
+#[cfg(feature = "enumflags2")]
+pub use self::enumflags::LossyBitFlags;
+#[cfg(target_has_atomic = "ptr")]
+pub use self::interning::{Interned, Interner, InterningInput};
+#[cfg(feature = "std")]
+pub use self::lock::Lockable;
+#[cfg(feature = "mmap")]
+pub use self::mmap::MmapInput;
+#[cfg(feature = "zeroize")]
+pub use self::secret::DecodeSecret;
+#[cfg(feature = "std")]
+pub use self::time::MonotonicDelta;
 pub use self::{
+	any_scale::AnyScale,
+	bounded_output::BoundedOutput,
 	codec::{
-		decode_vec_with_len, Codec, Decode, DecodeLength, Encode, EncodeAsRef, FullCodec,
-		FullEncode, Input, OptionBool, Output, WrapperTypeDecode, WrapperTypeEncode,
+		decode_vec_with_len, encode_map_from_iter, Codec, Decode, DecodeAndConvert, DecodeLength,
+		Encode, EncodeAsRef, FullCodec, FullEncode, Input, OptionBool, Output, WrapperTypeDecode,
+		WrapperTypeEncode,
+	},
+	compact::{Compact, CompactAs, CompactExt, CompactLen, CompactRef, HasCompact},
+	config::{
+		ByteOrder, CodecConfig, ConfiguredInput, ConfiguredOutput, DecodeWithConfig, LengthPrefix,
 	},
-	compact::{Compact, CompactAs, CompactLen, CompactRef, HasCompact},
-	counted_input::CountedInput,
-	decode_all::DecodeAll,
+	counted_input::{decode_with_diagnostics, CountedInput},
+	cow_static::{CowStatic, InternTable, NoIntern},
+	decode_all::{decode_all_repeated, decode_prefix, DecodeAll},
+	decode_context::{DecodeContext, DecodeWithContext, Recycle, DEFAULT_MAX_POOLED},
 	decode_finished::DecodeFinished,
-	depth_limit::DecodeLimit,
-	encode_append::EncodeAppend,
+	depth_limit::{DecodeLimit, DepthTrackingInput},
+	encode_append::{append_to_encoded_map, patch_compact_len_prefix, EncodeAppend},
 	encode_like::{EncodeLike, Ref},
+	encode_then_hash::{CoreHasher, EncodeThenHash, KeyHasher},
+	encoded_cmp::{encoded_cmp, encoded_cmp_bytes, encoded_eq},
+	endian::{Be, Le},
 	error::Error,
-	joiner::Joiner,
+	fallible_output::FallibleOutput,
+	fingerprint::{EncodingFingerprint, FingerprintHasher},
+	fixed_point::FixedPoint,
+	joiner::{Joiner, KeyBuilder},
 	keyedvec::KeyedVec,
+	length_codec::{FixedU32LE, Leb128, LengthCodec, ScaleCompact, VecWith},
+	max_len::{decode_with_max_encoded_len, decode_with_max_len, LenPrefixed},
 	mem_tracking::{DecodeWithMemLimit, DecodeWithMemTracking, MemTrackingInput},
+	migrate::{
+		decode_or_migrate, decode_or_migrate_with_depth_limit, migrate_decode, DecodeMigrate,
+	},
+	normalize::DecodeThenNormalize,
+	ordered_range::OrderedRange,
+	packed_option::{PackableOption, PackedOption},
+	prefixed_raw::{
+		decode_compact_len_prefixed, decode_compact_len_prefixed_bytes,
+		encode_compact_len_prefixed_bytes, PrefixedRaw, RawRemaining,
+	},
+	preflight::{preflight_decode, DecodeCostEstimate, Preflight},
+	progress_input::ProgressInput,
+	rle::Rle,
+	skipped::Skipped,
+	tagged_codec::TaggedCodec,
+	text_armor::{Base64Input, Base64Output, HexInput, HexOutput},
+	total_ord_f64::TotalOrdF64,
+	trivially_codable::TriviallyCodable,
+	unchecked_tail::UncheckedTail,
+	usize_as::{IsizeAs, UsizeAs},
+	wire_format::{self_check, WIRE_FORMAT_VERSION},
 };
 #[cfg(feature = "max-encoded-len")]
 pub use const_encoded_len::ConstEncodedLen;
+#[doc(hidden)]
+pub use encode_like::{EncodedFieldNames, FieldNameChar, SameEncodedFields};
+#[doc(hidden)]
+#[cfg(feature = "max-encoded-len")]
+pub use max_encoded_len::assert_max_encoded_len_bound;
 #[cfg(feature = "max-encoded-len")]
 pub use max_encoded_len::MaxEncodedLen;
+#[cfg(feature = "max-encoded-len")]
+pub use max_len::MaxEncodedLenWithMaxLen;
 
 /// Derive macro for [`MaxEncodedLen`][max_encoded_len::MaxEncodedLen].
 ///
@@ -135,4 +265,4 @@ pub use max_encoded_len::MaxEncodedLen;
 pub use parity_scale_codec_derive::MaxEncodedLen;
 
 #[cfg(feature = "bytes")]
-pub use self::codec::decode_from_bytes;
+pub use self::codec::{decode_from_bytes, BytesMutOutput};
@@ -0,0 +1,87 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Mutex, PoisonError, RwLock};
+
+/// A lock whose inner value can be encoded and decoded, used by the `#[codec(lock)]` field
+/// attribute of the derive macros.
+///
+/// A field marked with `#[codec(lock)]` is encoded as its inner value while the lock is held, and
+/// decoded by wrapping the decoded inner value into a new lock.
+///
+/// # Poisoning
+///
+/// A poisoned lock is still encoded: the panic that poisoned it can't have left the inner value in
+/// a state that is unsafe to read, only in one that may be logically inconsistent. Callers that
+/// care should check [`Mutex::is_poisoned`] or [`RwLock::is_poisoned`] before encoding.
+pub trait Lockable {
+	/// The type of the value protected by the lock.
+	type Inner;
+
+	/// Acquire the lock and call `f` with a reference to the inner value.
+	fn with_inner<R>(&self, f: impl FnOnce(&Self::Inner) -> R) -> R;
+
+	/// Create a new lock protecting `inner`.
+	fn from_inner(inner: Self::Inner) -> Self;
+}
+
+impl<T> Lockable for Mutex<T> {
+	type Inner = T;
+
+	fn with_inner<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+		f(&self.lock().unwrap_or_else(PoisonError::into_inner))
+	}
+
+	fn from_inner(inner: T) -> Self {
+		Mutex::new(inner)
+	}
+}
+
+impl<T> Lockable for RwLock<T> {
+	type Inner = T;
+
+	fn with_inner<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+		f(&self.read().unwrap_or_else(PoisonError::into_inner))
+	}
+
+	fn from_inner(inner: T) -> Self {
+		RwLock::new(inner)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{panic, sync::Arc};
+
+	#[test]
+	fn poisoned_lock_gives_access_to_inner_value() {
+		let mutex = Arc::new(Mutex::new(5u32));
+		let rw_lock = Arc::new(RwLock::new(6u32));
+
+		let (mutex_clone, rw_lock_clone) = (mutex.clone(), rw_lock.clone());
+		let _ = panic::catch_unwind(move || {
+			let _mutex_guard = mutex_clone.lock().unwrap();
+			let _rw_lock_guard = rw_lock_clone.write().unwrap();
+			panic!("poison the locks");
+		});
+
+		assert!(mutex.is_poisoned());
+		assert!(rw_lock.is_poisoned());
+		assert_eq!(mutex.with_inner(|v| *v), 5);
+		assert_eq!(rw_lock.with_inner(|v| *v), 6);
+		assert_eq!(Mutex::from_inner(7u32).with_inner(|v| *v), 7);
+		assert_eq!(RwLock::from_inner(8u32).with_inner(|v| *v), 8);
+	}
+}
@@ -17,7 +17,8 @@
 
 use crate::{alloc::boxed::Box, Compact, Encode};
 use core::{
-	marker::PhantomData,
+	cmp::{Ordering, Reverse},
+	marker::{PhantomData, PhantomPinned},
 	mem,
 	num::*,
 	ops::{Range, RangeInclusive},
@@ -39,6 +40,19 @@ pub trait MaxEncodedLen: Encode {
 	fn max_encoded_len() -> usize;
 }
 
+/// Panic if the maximum encoded length of `T` exceeds `bound`.
+///
+/// Called by the tests generated by `assert_mel_bound!`.
+#[doc(hidden)]
+pub fn assert_max_encoded_len_bound<T: MaxEncodedLen + ?Sized>(type_name: &str, bound: usize) {
+	let max_encoded_len = T::max_encoded_len();
+	assert!(
+		max_encoded_len <= bound,
+		"The maximum encoded length of `{type_name}` is {max_encoded_len}, which exceeds the bound \
+		of {bound}",
+	);
+}
+
 macro_rules! impl_primitives {
 	( $($t:ty),+ ) => {
 		$(
@@ -124,6 +138,12 @@ impl<T: MaxEncodedLen> MaxEncodedLen for Box<T> {
 	}
 }
 
+impl<T: MaxEncodedLen> MaxEncodedLen for core::pin::Pin<Box<T>> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
 #[cfg(target_has_atomic = "ptr")]
 impl<T: MaxEncodedLen> MaxEncodedLen for Arc<T> {
 	fn max_encoded_len() -> usize {
@@ -147,6 +167,24 @@ where
 	}
 }
 
+impl MaxEncodedLen for PhantomPinned {
+	fn max_encoded_len() -> usize {
+		0
+	}
+}
+
+impl MaxEncodedLen for Ordering {
+	fn max_encoded_len() -> usize {
+		1
+	}
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for Reverse<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
 impl<T> MaxEncodedLen for PhantomData<T> {
 	fn max_encoded_len() -> usize {
 		0
@@ -175,6 +213,20 @@ impl<T: MaxEncodedLen> MaxEncodedLen for RangeInclusive<T> {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn assert_max_encoded_len_bound_works() {
+		assert_max_encoded_len_bound::<(u32, u64)>("(u32, u64)", 12);
+		assert_max_encoded_len_bound::<Compact<u64>>("Compact<u64>", 100);
+	}
+
+	#[test]
+	#[should_panic(
+		expected = "The maximum encoded length of `u64` is 8, which exceeds the bound of 4"
+	)]
+	fn assert_max_encoded_len_bound_panics() {
+		assert_max_encoded_len_bound::<u64>("u64", 4);
+	}
+
 	macro_rules! test_compact_length {
 		($(fn $name:ident($t:ty);)*) => {
 			$(
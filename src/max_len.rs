@@ -0,0 +1,339 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Collections with a maximum number of items, see `#[codec(max_len = N)]`.
+
+#[cfg(feature = "max-encoded-len")]
+use crate::Encode;
+use crate::{
+	alloc::{
+		boxed::Box,
+		collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+		string::String,
+		vec::Vec,
+	},
+	codec::decode_len,
+	compact::ArrayVecWrapper,
+	CodecConfig, Decode, Error, Input,
+};
+use arrayvec::ArrayVec;
+
+/// A collection which is encoded as its number of items as `Compact<u32>`, followed by its items.
+///
+/// The number of items of these collections can be bounded when decoding with
+/// [`decode_with_max_len`], which is used for fields with `#[codec(max_len = N)]`.
+pub trait LenPrefixed {
+	/// The type of the items.
+	type Item;
+}
+
+impl<T> LenPrefixed for Vec<T> {
+	type Item = T;
+}
+
+impl<T> LenPrefixed for Box<[T]> {
+	type Item = T;
+}
+
+impl<T> LenPrefixed for VecDeque<T> {
+	type Item = T;
+}
+
+impl<T> LenPrefixed for LinkedList<T> {
+	type Item = T;
+}
+
+impl<T> LenPrefixed for BinaryHeap<T> {
+	type Item = T;
+}
+
+impl<T> LenPrefixed for BTreeSet<T> {
+	type Item = T;
+}
+
+impl<K, V> LenPrefixed for BTreeMap<K, V> {
+	type Item = (K, V);
+}
+
+impl LenPrefixed for String {
+	type Item = u8;
+}
+
+/// Decode a `T` with at most `max_len` items.
+///
+/// The number of items is checked before anything is allocated for them, so the memory used by
+/// decoding is bounded by `max_len` times the size of an item.
+///
+/// ```
+/// # use parity_scale_codec::{decode_with_max_len, Encode};
+/// let encoded = vec![1u32, 2, 3].encode();
+/// assert_eq!(decode_with_max_len::<Vec<u32>, _>(&mut &encoded[..], 3), Ok(vec![1, 2, 3]));
+/// assert!(decode_with_max_len::<Vec<u32>, _>(&mut &encoded[..], 2).is_err());
+/// ```
+pub fn decode_with_max_len<T: LenPrefixed + Decode, I: Input>(
+	input: &mut I,
+	max_len: usize,
+) -> Result<T, Error> {
+	let len = decode_len(input)?;
+	if len > max_len {
+		return Err("Collection has more items than its maximum length".into());
+	}
+
+	// The length prefix is re-encoded canonically, as the length codecs don't decode others.
+	let mut prefix = ArrayVecWrapper(ArrayVec::<u8, 10>::new());
+	input.config().length_prefix.encode_len(len, &mut prefix);
+	T::decode(&mut PrefixedInput { prefix: &prefix.0, input })
+}
+
+/// Decode a `T` from at most `max_encoded_len` bytes of `input`.
+///
+/// Decoding fails as soon as `T` reads more bytes, and `T` sees the remaining length of `input`
+/// as at most `max_encoded_len`, so collections with a longer length prefix are rejected before
+/// they are allocated. This is used for fields with `#[codec(mel(cap = N, check))]`.
+///
+/// ```
+/// # use parity_scale_codec::{decode_with_max_encoded_len, Encode};
+/// let encoded = vec![1u32, 2].encode();
+/// assert_eq!(decode_with_max_encoded_len::<Vec<u32>, _>(&mut &encoded[..], 9), Ok(vec![1, 2]));
+/// assert!(decode_with_max_encoded_len::<Vec<u32>, _>(&mut &encoded[..], 8).is_err());
+/// ```
+pub fn decode_with_max_encoded_len<T: Decode, I: Input>(
+	input: &mut I,
+	max_encoded_len: usize,
+) -> Result<T, Error> {
+	T::decode(&mut LimitedInput { input, remaining: max_encoded_len })
+}
+
+const MAX_ENCODED_LEN_ERR_MSG: &str = "Encoded value is longer than its maximum encoded length";
+
+/// An `Input` reading at most `remaining` bytes of `input`.
+struct LimitedInput<'a, I> {
+	input: &'a mut I,
+	remaining: usize,
+}
+
+impl<I: Input> LimitedInput<'_, I> {
+	fn consume(&mut self, len: usize) -> Result<(), Error> {
+		self.remaining = self.remaining.checked_sub(len).ok_or(MAX_ENCODED_LEN_ERR_MSG)?;
+		Ok(())
+	}
+}
+
+impl<I: Input> Input for LimitedInput<'_, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		let len = self.input.remaining_len()?;
+		Ok(Some(len.map_or(self.remaining, |len| len.min(self.remaining))))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.consume(into.len())?;
+		self.input.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.consume(1)?;
+		self.input.read_byte()
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		let start = into.len();
+		self.input.read_remaining_into(into)?;
+		self.consume(into.len() - start)
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+}
+
+/// The maximum encoded length of a collection with a maximum number of items.
+///
+/// This is implemented for all [`LenPrefixed`] collections whose items implement
+/// [`MaxEncodedLen`](crate::MaxEncodedLen), and used by the derived `MaxEncodedLen` for fields with
+/// `#[codec(max_len = N)]`.
+#[cfg(feature = "max-encoded-len")]
+pub trait MaxEncodedLenWithMaxLen: LenPrefixed + Encode {
+	/// The maximum encoded length of a value with at most `max_len` items.
+	fn max_encoded_len_with_max_len(max_len: usize) -> usize;
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: LenPrefixed + Encode> MaxEncodedLenWithMaxLen for T
+where
+	T::Item: crate::MaxEncodedLen,
+{
+	fn max_encoded_len_with_max_len(max_len: usize) -> usize {
+		use crate::{Compact, CompactLen, MaxEncodedLen};
+
+		let len = u32::try_from(max_len).unwrap_or(u32::MAX);
+		Compact::<u32>::compact_len(&len)
+			.saturating_add(max_len.saturating_mul(T::Item::max_encoded_len()))
+	}
+}
+
+/// An `Input` reading `prefix` before `input`.
+struct PrefixedInput<'a, I> {
+	prefix: &'a [u8],
+	input: &'a mut I,
+}
+
+impl<I: Input> Input for PrefixedInput<'_, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(self.input.remaining_len()?.map(|len| len.saturating_add(self.prefix.len())))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		let from_prefix = into.len().min(self.prefix.len());
+		self.prefix.read(&mut into[..from_prefix])?;
+		self.input.read(&mut into[from_prefix..])
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		if self.prefix.is_empty() {
+			self.input.read_byte()
+		} else {
+			self.prefix.read_byte()
+		}
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		into.extend_from_slice(core::mem::take(&mut self.prefix));
+		self.input.read_remaining_into(into)
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Compact, DecodeWithMemLimit, Encode};
+
+	#[test]
+	fn decode_with_max_len_works() {
+		let value = vec![String::from("a"); 100];
+		let encoded = value.encode();
+		assert_eq!(decode_with_max_len::<Vec<String>, _>(&mut &encoded[..], 100), Ok(value));
+		assert_eq!(
+			decode_with_max_len::<Vec<String>, _>(&mut &encoded[..], 99),
+			Err("Collection has more items than its maximum length".into())
+		);
+
+		let encoded = String::from("hello").encode();
+		assert_eq!(decode_with_max_len::<String, _>(&mut &encoded[..], 5), Ok("hello".into()));
+		assert!(decode_with_max_len::<String, _>(&mut &encoded[..], 4).is_err());
+
+		let value = BTreeMap::from([(1u8, 2u16), (3, 4)]);
+		let encoded = value.encode();
+		assert_eq!(decode_with_max_len::<BTreeMap<u8, u16>, _>(&mut &encoded[..], 2), Ok(value));
+	}
+
+	#[test]
+	fn decode_with_max_len_rejects_before_allocating() {
+		// A length of `u32::MAX` without any items.
+		let encoded = Compact(u32::MAX).encode();
+		assert_eq!(
+			decode_with_max_len::<Vec<u8>, _>(&mut &encoded[..], 10),
+			Err("Collection has more items than its maximum length".into())
+		);
+	}
+
+	#[test]
+	fn decode_with_max_len_tracks_memory() {
+		#[derive(Debug, PartialEq)]
+		struct Bounded(Vec<u32>);
+
+		impl Decode for Bounded {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				decode_with_max_len(input, 3).map(Bounded)
+			}
+		}
+
+		impl crate::DecodeWithMemTracking for Bounded {}
+
+		let encoded = vec![1u32, 2, 3].encode();
+		assert_eq!(
+			Bounded::decode_with_mem_limit(&mut &encoded[..], 13),
+			Ok(Bounded(vec![1, 2, 3]))
+		);
+		assert!(Bounded::decode_with_mem_limit(&mut &encoded[..], 12).is_err());
+	}
+
+	#[test]
+	fn decode_with_max_encoded_len_works() {
+		let encoded = String::from("hello").encode();
+		assert_eq!(
+			decode_with_max_encoded_len::<String, _>(&mut &encoded[..], 6),
+			Ok("hello".into())
+		);
+		assert_eq!(
+			decode_with_max_encoded_len::<(u8, u8), _>(&mut &[1, 2][..], 1),
+			Err(MAX_ENCODED_LEN_ERR_MSG.into())
+		);
+
+		// The length prefix is rejected before allocating, as the input looks too short.
+		let encoded = Compact(u32::MAX).encode();
+		assert_eq!(
+			decode_with_max_encoded_len::<Vec<u8>, _>(&mut &encoded[..], 10),
+			Err("Not enough data to decode vector".into())
+		);
+
+		// The rest of the input is left unread.
+		let mut input = &[1, 2, 3][..];
+		assert_eq!(decode_with_max_encoded_len::<u16, _>(&mut input, 2), Ok(0x0201));
+		assert_eq!(input, [3]);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn max_encoded_len_with_max_len_works() {
+		assert_eq!(Vec::<u32>::max_encoded_len_with_max_len(10), 1 + 40);
+		assert_eq!(String::max_encoded_len_with_max_len(100), 2 + 100);
+		assert_eq!(BTreeMap::<u8, u64>::max_encoded_len_with_max_len(1 << 30), 5 + 9 * (1 << 30));
+	}
+}
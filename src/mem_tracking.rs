@@ -13,60 +13,79 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Decode, Error, Input};
+use crate::{alloc::vec::Vec, CodecConfig, Decode, Error, Input};
+use core::{borrow::BorrowMut, marker::PhantomData};
 use impl_trait_for_tuples::impl_for_tuples;
 
 /// Marker trait used for identifying types that call the [`Input::on_before_alloc_mem`] hook
 /// while decoding.
 pub trait DecodeWithMemTracking: Decode {}
 
-const DECODE_OOM_MSG: &str = "Heap memory limit exceeded while decoding";
+pub(crate) const DECODE_OOM_MSG: &str = "Heap memory limit exceeded while decoding";
 
 #[impl_for_tuples(18)]
 impl DecodeWithMemTracking for Tuple {}
 
 /// `Input` implementation that can be used for limiting the heap memory usage while decoding.
-pub struct MemTrackingInput<'a, I> {
-	input: &'a mut I,
+///
+/// It wraps a `&mut I`, or owns the input `I` when built with [`Input::with_mem_limit`], in which
+/// case `Inner` is `I`.
+pub struct MemTrackingInput<'a, I, Inner = &'a mut I> {
+	input: Inner,
 	used_mem: usize,
 	mem_limit: usize,
+	_input: PhantomData<&'a mut I>,
 }
 
 impl<'a, I: Input> MemTrackingInput<'a, I> {
 	/// Create a new instance of `MemTrackingInput`.
 	pub fn new(input: &'a mut I, mem_limit: usize) -> Self {
-		Self { input, used_mem: 0, mem_limit }
+		Self { input, used_mem: 0, mem_limit, _input: PhantomData }
 	}
+}
 
+impl<'a, I: Input + 'a> MemTrackingInput<'a, I, I> {
+	/// Wrap the owned `input`, see [`Input::with_mem_limit`].
+	pub(crate) fn new_owned(input: I, mem_limit: usize) -> Self {
+		Self { input, used_mem: 0, mem_limit, _input: PhantomData }
+	}
+}
+
+impl<I, Inner> MemTrackingInput<'_, I, Inner> {
 	/// Get the `used_mem` field.
 	pub fn used_mem(&self) -> usize {
 		self.used_mem
 	}
+
+	/// The wrapped input.
+	pub fn into_inner(self) -> Inner {
+		self.input
+	}
 }
 
-impl<'a, I: Input> Input for MemTrackingInput<'a, I> {
+impl<I: Input, Inner: BorrowMut<I>> Input for MemTrackingInput<'_, I, Inner> {
 	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
-		self.input.remaining_len()
+		self.input.borrow_mut().remaining_len()
 	}
 
 	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
-		self.input.read(into)
+		self.input.borrow_mut().read(into)
 	}
 
 	fn read_byte(&mut self) -> Result<u8, Error> {
-		self.input.read_byte()
+		self.input.borrow_mut().read_byte()
 	}
 
 	fn descend_ref(&mut self) -> Result<(), Error> {
-		self.input.descend_ref()
+		self.input.borrow_mut().descend_ref()
 	}
 
 	fn ascend_ref(&mut self) {
-		self.input.ascend_ref()
+		self.input.borrow_mut().ascend_ref()
 	}
 
 	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
-		self.input.on_before_alloc_mem(size)?;
+		self.input.borrow_mut().on_before_alloc_mem(size)?;
 
 		self.used_mem = self.used_mem.saturating_add(size);
 		if self.used_mem >= self.mem_limit {
@@ -75,6 +94,32 @@ impl<'a, I: Input> Input for MemTrackingInput<'a, I> {
 
 		Ok(())
 	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.borrow().config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.borrow_mut().interner()
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		self.input.borrow_mut().seek_forward(n)
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		let start = into.len();
+		let result = self.input.borrow_mut().read_remaining_into(into);
+
+		// Inputs of unknown length, like readers, read to the end without calling
+		// `on_before_alloc_mem`, so the bytes are accounted once read.
+		self.used_mem = self.used_mem.saturating_add(into.len() - start);
+		if self.used_mem >= self.mem_limit {
+			return Err(DECODE_OOM_MSG.into());
+		}
+
+		result
+	}
 }
 
 /// Extension trait to [`Decode`] for decoding with a maximum memory limit.
@@ -0,0 +1,240 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of values which may still be encoded with an older layout.
+
+use crate::{Decode, DecodeAll, DecodeLimit, Error};
+
+/// Decode `bytes` as an `Old` and convert it to a `New` with `convert`.
+///
+/// This is the usual step of a storage migration, reading a value stored with the old layout of
+/// a type. Decoding fails if `bytes` isn't consumed completely, like
+/// [`DecodeAll`](crate::DecodeAll).
+///
+/// ```
+/// # use parity_scale_codec::{migrate_decode, Encode, Error};
+/// let stored = (7u32, true).encode();
+/// let migrated: (u64, u8, bool) =
+///     migrate_decode(&stored, |(balance, active): (u32, bool)| Ok((balance.into(), 0, active)))?;
+/// assert_eq!(migrated, (7, 0, true));
+/// # Ok::<(), Error>(())
+/// ```
+pub fn migrate_decode<Old, New, F>(mut bytes: &[u8], convert: F) -> Result<New, Error>
+where
+	Old: Decode,
+	F: FnOnce(Old) -> Result<New, Error>,
+{
+	Old::decode_all(&mut bytes).and_then(convert)
+}
+
+/// A type whose values may still be encoded with an older layout.
+///
+/// Its `Decode` implementation only decodes the current layout. The old layout is only tried for
+/// a complete buffer, with [`decode_all_or_migrate`](Self::decode_all_or_migrate), as the bytes
+/// following a value can't tell which layout the value was encoded with. It can be derived with
+/// `#[codec(migrate_from(OldType, "convert"))]` together with `Decode`.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// # use parity_scale_codec::{Decode, DecodeMigrate, Encode, Error};
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Account {
+///     balance: u64,
+///     nonce: u32,
+/// }
+///
+/// impl DecodeMigrate for Account {
+///     // The first layout had a `u32` balance and no nonce.
+///     type Old = u32;
+///
+///     fn migrate(balance: u32) -> Result<Self, Error> {
+///         Ok(Account { balance: balance.into(), nonce: 0 })
+///     }
+/// }
+///
+/// let current = Account { balance: 5, nonce: 1 }.encode();
+/// let decoded = Account::decode_all_or_migrate(&mut &current[..]);
+/// assert_eq!(decoded, Ok(Account { balance: 5, nonce: 1 }));
+/// let migrated = Account::decode_all_or_migrate(&mut &5u32.encode()[..]);
+/// assert_eq!(migrated, Ok(Account { balance: 5, nonce: 0 }));
+/// # }
+/// ```
+pub trait DecodeMigrate: Decode {
+	/// The older layout of the type.
+	type Old: Decode;
+
+	/// Convert a value decoded with the old layout.
+	fn migrate(old: Self::Old) -> Result<Self, Error>;
+
+	/// Decode all of `input` with the current layout, or if that fails, with the old layout.
+	///
+	/// See [`decode_or_migrate`].
+	fn decode_all_or_migrate(input: &mut &[u8]) -> Result<Self, Error> {
+		decode_or_migrate(input, Self::migrate)
+	}
+
+	/// Like [`decode_all_or_migrate`](Self::decode_all_or_migrate), with the given maximum
+	/// recursion depth for each layout.
+	fn decode_all_or_migrate_with_depth_limit(
+		limit: u32,
+		input: &mut &[u8],
+	) -> Result<Self, Error> {
+		decode_or_migrate_with_depth_limit(limit, input, Self::migrate)
+	}
+}
+
+/// Decode all of `input` as a `New`, or if that fails, as an `Old` converted with `convert`.
+///
+/// Both layouts must consume `input` completely, like [`DecodeAll`](crate::DecodeAll). So the
+/// old encodings must fail to decode with the new layout, e.g. because they have another length.
+/// Each layout is decoded from the start of `input`, without any state left by the other one.
+///
+/// ```
+/// # use parity_scale_codec::{decode_or_migrate, Encode, Error};
+/// // The first layout had a `u32` balance and no nonce.
+/// let migrate = |balance: u32| Ok((u64::from(balance), 0u32));
+/// assert_eq!(decode_or_migrate(&mut &(5u64, 1u32).encode()[..], migrate), Ok((5, 1)));
+/// assert_eq!(decode_or_migrate(&mut &5u32.encode()[..], migrate), Ok((5, 0)));
+/// # Ok::<(), Error>(())
+/// ```
+pub fn decode_or_migrate<New, Old>(
+	input: &mut &[u8],
+	convert: impl FnOnce(Old) -> Result<New, Error>,
+) -> Result<New, Error>
+where
+	New: Decode,
+	Old: Decode,
+{
+	let mut new_input = *input;
+	if let Ok(value) = New::decode_all(&mut new_input) {
+		*input = new_input;
+		return Ok(value);
+	}
+
+	Old::decode_all(input)
+		.map_err(|e| e.chain("Could not decode the value with its new or its old layout"))
+		.and_then(convert)
+}
+
+/// Like [`decode_or_migrate`], with the given maximum recursion depth for each layout.
+pub fn decode_or_migrate_with_depth_limit<New, Old>(
+	limit: u32,
+	input: &mut &[u8],
+	convert: impl FnOnce(Old) -> Result<New, Error>,
+) -> Result<New, Error>
+where
+	New: Decode,
+	Old: Decode,
+{
+	let mut new_input = *input;
+	if let Ok(value) = New::decode_all_with_depth_limit(limit, &mut new_input) {
+		*input = new_input;
+		return Ok(value);
+	}
+
+	Old::decode_all_with_depth_limit(limit, input)
+		.map_err(|e| e.chain("Could not decode the value with its new or its old layout"))
+		.and_then(convert)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Encode, Input};
+
+	#[derive(Debug, PartialEq)]
+	struct Account {
+		balance: u64,
+		frozen: bool,
+	}
+
+	impl Decode for Account {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+			Ok(Account { balance: u64::decode(input)?, frozen: bool::decode(input)? })
+		}
+	}
+
+	impl DecodeMigrate for Account {
+		type Old = u32;
+
+		fn migrate(balance: u32) -> Result<Self, Error> {
+			Ok(Account { balance: balance.into(), frozen: false })
+		}
+	}
+
+	#[test]
+	fn migrate_decode_decodes_the_old_layout() {
+		let stored = 5u32.encode();
+		assert_eq!(
+			migrate_decode(&stored, |balance: u32| Ok(Account {
+				balance: balance.into(),
+				frozen: true
+			})),
+			Ok(Account { balance: 5, frozen: true })
+		);
+		assert!(migrate_decode(&[0, 0, 0, 0, 0], |_: u32| Ok(())).is_err());
+		assert_eq!(
+			migrate_decode(&stored, |_: u32| Err::<(), _>("Too rich".into())),
+			Err("Too rich".into())
+		);
+	}
+
+	#[test]
+	fn decode_all_or_migrate_falls_back_to_the_old_layout() {
+		let new = Account { balance: 1 << 40, frozen: true };
+		let encoded = (1u64 << 40, true).encode();
+		let mut input = &encoded[..];
+		assert_eq!(Account::decode_all_or_migrate(&mut input), Ok(new));
+		assert!(input.is_empty());
+		assert_eq!(
+			Account::decode_all_or_migrate(&mut &7u32.encode()[..]),
+			Ok(Account { balance: 7, frozen: false })
+		);
+	}
+
+	#[test]
+	fn decode_all_or_migrate_errors() {
+		// An old value followed by other data isn't read by the new layout.
+		let encoded = (1u32, 2u32, 2u8).encode();
+		let err = Account::decode_all_or_migrate(&mut &encoded[..]).unwrap_err();
+		assert_eq!(err.root_cause(), "Input buffer has still data left after decoding!");
+
+		let err = Account::decode_all_or_migrate(&mut &[1u8, 2][..]).unwrap_err();
+		assert_eq!(err.root_cause(), "Not enough data to fill buffer");
+		assert_eq!(
+			err.chain_iter().next(),
+			Some("Could not decode the value with its new or its old layout")
+		);
+		assert_eq!(
+			decode_or_migrate(&mut &5u32.encode()[..], |_: u32| Err::<Account, _>(
+				"Too rich".into()
+			)),
+			Err("Too rich".into())
+		);
+	}
+
+	#[test]
+	fn each_layout_gets_the_whole_depth_limit() {
+		// The new layout descends into the box before failing, the old one must still be able to.
+		let encoded = Box::new(7u32).encode();
+		let decoded = decode_or_migrate_with_depth_limit(1, &mut &encoded[..], |b: Box<u32>| {
+			Ok(Box::new(Account { balance: (*b).into(), frozen: false }))
+		});
+		assert_eq!(decoded.map(|a| a.balance), Ok(7));
+		assert!(decode_or_migrate_with_depth_limit(0, &mut &encoded[..], |b: Box<u32>| {
+			Ok(Box::new(Account { balance: (*b).into(), frozen: false }))
+		})
+		.is_err());
+	}
+}
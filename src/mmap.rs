@@ -0,0 +1,159 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding from memory mapped files.
+
+use std::{fs::File, io};
+
+use memmap2::Mmap;
+
+use crate::{codec::BytesCursor, Error, Input};
+
+/// An [`Input`] reading from a read-only memory map of a file.
+///
+/// This avoids the copies and syscalls of reading a file through [`IoReader`](crate::IoReader),
+/// and knows the remaining length, so collections can be preallocated.
+///
+/// `bytes::Bytes` values are decoded without copying: they borrow the mapped pages, which stay
+/// mapped until all of them are dropped. Other types, like `Vec<u8>`, are copied out of the map.
+///
+/// ```no_run
+/// # use parity_scale_codec::{Decode, MmapInput};
+/// let file = std::fs::File::open("snapshot.scale")?;
+/// // SAFETY: the file is not modified while it is mapped.
+/// let mut input = unsafe { MmapInput::map(&file)? };
+/// let snapshot = Vec::<(u32, bytes::Bytes)>::decode(&mut input)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct MmapInput {
+	cursor: BytesCursor,
+	len: usize,
+}
+
+impl MmapInput {
+	/// Map `file` into memory and read from the start of it.
+	///
+	/// # Safety
+	///
+	/// The file must not be modified while it is mapped, see [`Mmap::map`]. This includes the
+	/// lifetime of any `bytes::Bytes` decoded from the input.
+	pub unsafe fn map(file: &File) -> io::Result<Self> {
+		Mmap::map(file).map(Self::new)
+	}
+
+	/// Read from the start of `mmap`.
+	pub fn new(mmap: Mmap) -> Self {
+		let len = mmap.len();
+		MmapInput {
+			cursor: BytesCursor { bytes: bytes::Bytes::from_owner(mmap), position: 0 },
+			len,
+		}
+	}
+
+	/// Read from the start of `mmap`, advising the kernel that it is read sequentially.
+	///
+	/// This allows more aggressive read-ahead, and pages to be freed soon after they were read.
+	#[cfg(unix)]
+	pub fn sequential(mmap: Mmap) -> io::Result<Self> {
+		mmap.advise(memmap2::Advice::Sequential)?;
+		Ok(Self::new(mmap))
+	}
+
+	/// The number of bytes read so far.
+	pub fn position(&self) -> usize {
+		self.len - (self.cursor.bytes.len() - self.cursor.position)
+	}
+}
+
+impl Input for MmapInput {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.cursor.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.cursor.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.cursor.read_byte()
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		self.cursor.seek_forward(n)
+	}
+
+	fn scale_internal_decode_bytes(&mut self) -> Result<bytes::Bytes, Error> {
+		self.cursor.scale_internal_decode_bytes()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Decode, Encode};
+	use std::io::Write;
+
+	fn map(content: &[u8]) -> MmapInput {
+		let mut file = tempfile();
+		file.write_all(content).unwrap();
+		unsafe { MmapInput::map(&file).unwrap() }
+	}
+
+	fn tempfile() -> File {
+		let path = std::env::temp_dir().join(format!(
+			"parity-scale-codec-mmap-{}-{:?}",
+			std::process::id(),
+			std::thread::current().id()
+		));
+		let file = File::options().read(true).write(true).create(true).truncate(true).open(&path);
+		std::fs::remove_file(&path).unwrap();
+		file.unwrap()
+	}
+
+	#[test]
+	fn mmap_input_decodes() {
+		let value = (vec![1u32, 2, 3], String::from("mapped"), bytes::Bytes::from_static(b"pages"));
+		let encoded = (&value, 7u8).encode();
+
+		let mut input = map(&encoded);
+		assert_eq!(input.remaining_len(), Ok(Some(encoded.len())));
+		assert_eq!(<(Vec<u32>, String, bytes::Bytes)>::decode(&mut input).unwrap(), value);
+		assert_eq!(input.remaining_len(), Ok(Some(1)));
+		assert_eq!(input.position(), encoded.len() - 1);
+		assert_eq!(input.read_byte(), Ok(7));
+		assert!(input.read_byte().is_err());
+	}
+
+	#[test]
+	fn mmap_input_bytes_outlive_input() {
+		let encoded =
+			vec![bytes::Bytes::from_static(b"first"), bytes::Bytes::from_static(b"second")]
+				.encode();
+
+		let mut input = map(&encoded);
+		let decoded = Vec::<bytes::Bytes>::decode(&mut input).unwrap();
+		drop(input);
+		assert_eq!(decoded, [&b"first"[..], &b"second"[..]]);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn mmap_input_sequential() {
+		let mut file = tempfile();
+		file.write_all(&42u64.encode()).unwrap();
+		let mmap = unsafe { Mmap::map(&file).unwrap() };
+		let mut input = MmapInput::sequential(mmap).unwrap();
+		assert_eq!(u64::decode(&mut input), Ok(42));
+	}
+}
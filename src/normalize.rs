@@ -0,0 +1,75 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Decode, Error, Input};
+
+/// A type that is decoded from a raw representation, whose invariants are re-established
+/// afterwards.
+///
+/// This is meant for types which can't accept every decoded value as it is, e.g. a fraction that
+/// must be stored reduced, or `Duration` whose nanoseconds must be below one second. The `Decode`
+/// implementation of such a type can forward to [`DecodeThenNormalize::decode_then_normalize`].
+///
+/// When deriving `Decode`, the `#[codec(normalize = "$path")]` attribute achieves the same: the
+/// function at `$path` is called with the decoded value and returns the value to use, or an error.
+///
+/// ```
+/// use parity_scale_codec::{decode_prefix, Decode, DecodeThenNormalize, Error, Input};
+///
+/// /// A fraction which is always stored reduced.
+/// #[derive(Debug, PartialEq)]
+/// struct Fraction {
+///     num: u32,
+///     den: u32,
+/// }
+///
+/// fn gcd(a: u32, b: u32) -> u32 {
+///     if b == 0 { a } else { gcd(b, a % b) }
+/// }
+///
+/// impl DecodeThenNormalize for Fraction {
+///     type Raw = (u32, u32);
+///
+///     fn normalize((num, den): (u32, u32)) -> Result<Self, Error> {
+///         if den == 0 {
+///             return Err("Denominator must not be zero".into());
+///         }
+///         let gcd = gcd(num, den);
+///         Ok(Fraction { num: num / gcd, den: den / gcd })
+///     }
+/// }
+///
+/// impl Decode for Fraction {
+///     fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+///         Self::decode_then_normalize(input)
+///     }
+/// }
+///
+/// let encoded = [2u8, 0, 0, 0, 4, 0, 0, 0];
+/// assert_eq!(decode_prefix::<Fraction>(&encoded), Ok((Fraction { num: 1, den: 2 }, &[][..])));
+/// assert!(decode_prefix::<Fraction>(&[2, 0, 0, 0, 0, 0, 0, 0]).is_err());
+/// ```
+pub trait DecodeThenNormalize: Sized {
+	/// The representation that is decoded from the input.
+	type Raw: Decode;
+
+	/// Create `Self` from its decoded representation, re-establishing its invariants, or return
+	/// an error if that is not possible.
+	fn normalize(raw: Self::Raw) -> Result<Self, Error>;
+
+	/// Decode `Self::Raw` from `input` and [`normalize`](Self::normalize) it.
+	fn decode_then_normalize<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Self::normalize(Self::Raw::decode(input)?)
+	}
+}
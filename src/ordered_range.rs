@@ -0,0 +1,163 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ranges whose decoding checks that they are ordered.
+
+use core::ops::{Range, RangeInclusive};
+
+use crate::{
+	Decode, DecodeLength, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
+};
+
+/// A [`Range`] or [`RangeInclusive`] encoded like it, but which fails to decode if its start is
+/// greater than its end.
+///
+/// `Range` and `RangeInclusive` decode any pair of bounds, like the fields of a struct, so a
+/// reversed range is only noticed when it is used. `OrderedRange` rejects it when decoding:
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, OrderedRange};
+/// # use core::ops::Range;
+/// let encoded = (5u32..10).encode();
+/// let (range, _) = decode_prefix::<OrderedRange<Range<u32>>>(&encoded).unwrap();
+/// assert_eq!(range.0, 5..10);
+///
+/// let encoded = (10u32..5).encode();
+/// assert!(decode_prefix::<Range<u32>>(&encoded).is_ok());
+/// assert!(decode_prefix::<OrderedRange<Range<u32>>>(&encoded).is_err());
+/// ```
+///
+/// An empty range, e.g. `5..5`, is ordered.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct OrderedRange<R>(pub R);
+
+impl<R> From<R> for OrderedRange<R> {
+	fn from(range: R) -> Self {
+		OrderedRange(range)
+	}
+}
+
+impl<R: Encode> Encode for OrderedRange<R> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn encode(&self) -> crate::alloc::vec::Vec<u8> {
+		self.0.encode()
+	}
+}
+
+impl<R: Encode> EncodeLike for OrderedRange<R> {}
+impl<T: Encode> EncodeLike<Range<T>> for OrderedRange<Range<T>> {}
+impl<T: Encode> EncodeLike<OrderedRange<Range<T>>> for Range<T> {}
+impl<T: Encode> EncodeLike<RangeInclusive<T>> for OrderedRange<RangeInclusive<T>> {}
+impl<T: Encode> EncodeLike<OrderedRange<RangeInclusive<T>>> for RangeInclusive<T> {}
+
+const UNORDERED_RANGE_MSG: &str = "Range start is greater than its end";
+
+impl<T: Decode + PartialOrd> Decode for OrderedRange<Range<T>> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let range = Range::<T>::decode(input)?;
+		if range.start > range.end {
+			return Err(UNORDERED_RANGE_MSG.into());
+		}
+		Ok(OrderedRange(range))
+	}
+}
+
+impl<T: Decode + PartialOrd> Decode for OrderedRange<RangeInclusive<T>> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let range = RangeInclusive::<T>::decode(input)?;
+		if range.start() > range.end() {
+			return Err(UNORDERED_RANGE_MSG.into());
+		}
+		Ok(OrderedRange(range))
+	}
+}
+
+impl<R> DecodeWithMemTracking for OrderedRange<R>
+where
+	OrderedRange<R>: Decode,
+	R: DecodeWithMemTracking,
+{
+}
+
+impl<R: DecodeLength> DecodeLength for OrderedRange<R> {
+	fn len(self_encoded: &[u8]) -> Result<usize, Error> {
+		R::len(self_encoded)
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<R: crate::MaxEncodedLen> crate::MaxEncodedLen for OrderedRange<R>
+where
+	OrderedRange<R>: Encode,
+{
+	fn max_encoded_len() -> usize {
+		R::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<R: crate::ConstEncodedLen> crate::ConstEncodedLen for OrderedRange<R> where
+	OrderedRange<R>: Encode
+{
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ordered_ranges_round_trip() {
+		let range = OrderedRange(3u64..7);
+		assert_eq!(range.encode(), (3u64..7).encode());
+		assert_eq!(OrderedRange::<Range<u64>>::decode(&mut &range.encode()[..]), Ok(range));
+
+		let range = OrderedRange(3u64..=3);
+		assert_eq!(range.encode(), (3u64..=3).encode());
+		assert_eq!(
+			OrderedRange::<RangeInclusive<u64>>::decode(&mut &range.encode()[..]),
+			Ok(range)
+		);
+
+		assert_eq!(<OrderedRange<Range<u16>> as DecodeLength>::len(&(1u16..4).encode()), Ok(3));
+	}
+
+	#[test]
+	fn unordered_ranges_are_rejected() {
+		let encoded = (7u64, 3u64).encode();
+		assert_eq!(
+			OrderedRange::<Range<u64>>::decode(&mut &encoded[..]),
+			Err(UNORDERED_RANGE_MSG.into())
+		);
+		assert_eq!(
+			OrderedRange::<RangeInclusive<u64>>::decode(&mut &encoded[..]),
+			Err(UNORDERED_RANGE_MSG.into())
+		);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn ordered_range_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(OrderedRange::<Range<u32>>::max_encoded_len(), 8);
+		assert_eq!(OrderedRange::<RangeInclusive<u128>>::max_encoded_len(), 32);
+	}
+}
@@ -0,0 +1,219 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::num::{
+	NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+	NonZeroU32, NonZeroU64, NonZeroU8,
+};
+
+use crate::{
+	codec::{Decode, Encode, Input, Output},
+	encode_like::EncodeLike,
+	DecodeWithMemTracking, Error,
+};
+
+/// A type that has a packed representation in which one value is never used, so that this value
+/// can represent `None` in a [`PackedOption`].
+pub trait PackableOption: Sized {
+	/// The packed representation, which is what is encoded.
+	type Packed: Encode + Decode + PartialEq;
+
+	/// The packed value representing `None`. This must not be returned by [`Self::pack`].
+	const NONE: Self::Packed;
+
+	/// Convert the value into its packed representation.
+	fn pack(&self) -> Self::Packed;
+
+	/// Convert the packed representation back into the value.
+	///
+	/// This is never called with [`Self::NONE`].
+	fn unpack(packed: Self::Packed) -> Result<Self, Error>;
+}
+
+/// An optional value that is encoded with the same width as the value itself.
+///
+/// `Option<T>` is encoded with an additional byte that tells if a value is present. For types
+/// implementing [`PackableOption`], `PackedOption<T>` instead encodes `None` as a value that `T`
+/// never uses, e.g. zero for non-zero integers.
+///
+/// `PackedOption<bool>` has the same encoding as [`OptionBool`](crate::OptionBool).
+///
+/// ```
+/// # use core::num::NonZeroU32;
+/// # use parity_scale_codec::{Encode, PackedOption};
+/// assert_eq!(PackedOption(NonZeroU32::new(5)).encode(), vec![5, 0, 0, 0]);
+/// assert_eq!(PackedOption::<NonZeroU32>(None).encode(), vec![0, 0, 0, 0]);
+/// assert_eq!(NonZeroU32::new(5).encode(), vec![1, 5, 0, 0, 0]);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackedOption<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for PackedOption<T> {
+	fn from(x: Option<T>) -> Self {
+		PackedOption(x)
+	}
+}
+
+impl<T> From<PackedOption<T>> for Option<T> {
+	fn from(x: PackedOption<T>) -> Self {
+		x.0
+	}
+}
+
+impl<T: PackableOption> Encode for PackedOption<T> {
+	fn size_hint(&self) -> usize {
+		match self.0 {
+			Some(ref t) => t.pack().size_hint(),
+			None => T::NONE.size_hint(),
+		}
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		match self.0 {
+			Some(ref t) => t.pack().encode_to(dest),
+			None => T::NONE.encode_to(dest),
+		}
+	}
+}
+
+impl<T: PackableOption> EncodeLike for PackedOption<T> {}
+
+impl<T: PackableOption> Decode for PackedOption<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let packed = T::Packed::decode(input)?;
+		if packed == T::NONE {
+			Ok(PackedOption(None))
+		} else {
+			T::unpack(packed).map(|t| PackedOption(Some(t)))
+		}
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		T::Packed::encoded_fixed_size()
+	}
+}
+
+impl<T: PackableOption> DecodeWithMemTracking for PackedOption<T> where
+	T::Packed: DecodeWithMemTracking
+{
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: PackableOption> crate::MaxEncodedLen for PackedOption<T>
+where
+	T::Packed: crate::MaxEncodedLen,
+{
+	fn max_encoded_len() -> usize {
+		T::Packed::max_encoded_len()
+	}
+}
+
+impl PackableOption for bool {
+	type Packed = u8;
+
+	const NONE: u8 = 0;
+
+	fn pack(&self) -> u8 {
+		if *self {
+			1
+		} else {
+			2
+		}
+	}
+
+	fn unpack(packed: u8) -> Result<Self, Error> {
+		match packed {
+			1 => Ok(true),
+			2 => Ok(false),
+			_ => Err("unexpected first byte decoding PackedOption<bool>".into()),
+		}
+	}
+}
+
+macro_rules! impl_packable_option_for_non_zero {
+	( $( $name:ty: $packed:ty ),* $(,)? ) => { $(
+		impl PackableOption for $name {
+			type Packed = $packed;
+
+			const NONE: $packed = 0;
+
+			fn pack(&self) -> $packed {
+				self.get()
+			}
+
+			fn unpack(packed: $packed) -> Result<Self, Error> {
+				Self::new(packed).ok_or_else(|| Error::from("cannot create non-zero number from 0"))
+			}
+		}
+	)* }
+}
+
+impl_packable_option_for_non_zero! {
+	NonZeroI8: i8,
+	NonZeroI16: i16,
+	NonZeroI32: i32,
+	NonZeroI64: i64,
+	NonZeroI128: i128,
+	NonZeroU8: u8,
+	NonZeroU16: u16,
+	NonZeroU32: u32,
+	NonZeroU64: u64,
+	NonZeroU128: u128,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::vec::Vec, OptionBool};
+
+	#[test]
+	fn packed_option_bool_is_option_bool() {
+		for value in [None, Some(true), Some(false)] {
+			let encoded = PackedOption(value).encode();
+			assert_eq!(encoded, OptionBool(value).encode());
+			assert_eq!(
+				PackedOption::<bool>::decode(&mut &encoded[..]).unwrap(),
+				PackedOption(value)
+			);
+		}
+
+		assert!(PackedOption::<bool>::decode(&mut &[3][..]).is_err());
+	}
+
+	#[test]
+	fn packed_option_non_zero() {
+		let values = vec![
+			PackedOption(NonZeroU16::new(1)),
+			PackedOption(None),
+			PackedOption(NonZeroU16::new(u16::MAX)),
+		];
+		let encoded = values.encode();
+		assert_eq!(encoded, vec![12, 1, 0, 0, 0, 255, 255]);
+		assert_eq!(Vec::<PackedOption<NonZeroU16>>::decode(&mut &encoded[..]).unwrap(), values);
+
+		let value = PackedOption(NonZeroI64::new(-1));
+		assert_eq!(value.encode(), (-1i64).encode());
+		assert_eq!(PackedOption::<NonZeroI64>::decode(&mut &value.encode()[..]).unwrap(), value);
+		assert_eq!(PackedOption::<NonZeroI64>::encoded_fixed_size(), Some(8));
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn packed_option_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(PackedOption::<NonZeroU32>::max_encoded_len(), 4);
+		assert_eq!(PackedOption::<bool>::max_encoded_len(), 1);
+	}
+}
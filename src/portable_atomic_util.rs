@@ -0,0 +1,66 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Codec implementations for `portable_atomic_util::Arc`, which is encoded exactly like
+//! `alloc::sync::Arc` but is also available on targets without atomic pointers.
+
+use portable_atomic_util::Arc;
+
+use crate::{
+	alloc::boxed::Box, codec::WrapperTypeEncode, encode_like::EncodeLike, Decode,
+	DecodeWithMemTracking, Encode, Error, Input, WrapperTypeDecode,
+};
+
+impl<T: ?Sized> WrapperTypeEncode for Arc<T> {}
+impl<T: ?Sized + Encode> EncodeLike for Arc<T> {}
+impl<T: Encode> EncodeLike<T> for Arc<T> {}
+impl<T: Encode> EncodeLike<Arc<T>> for T {}
+
+impl<T> WrapperTypeDecode for Arc<T> {
+	type Wrapped = T;
+
+	fn decode_wrapped<I: Input>(input: &mut I) -> Result<Self, Error>
+	where
+		Self::Wrapped: Decode,
+	{
+		Box::<T>::decode(input).map(|output| output.into())
+	}
+}
+
+// `Arc<T>` uses `Box::<T>::decode()` internally, so it supports `DecodeWithMemTracking`.
+impl<T: DecodeWithMemTracking> DecodeWithMemTracking for Arc<T> {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: crate::MaxEncodedLen> crate::MaxEncodedLen for Arc<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::vec::Vec, DecodeWithMemLimit};
+
+	#[test]
+	fn portable_arc_encode_decode() {
+		let value = Arc::new(vec![1u32, 2, 3]);
+		let encoded = value.encode();
+		assert_eq!(encoded, vec![1u32, 2, 3].encode());
+		assert_eq!(Arc::<Vec<u32>>::decode(&mut &encoded[..]).unwrap(), value);
+
+		assert!(Arc::<Vec<u32>>::decode_with_mem_limit(&mut &encoded[..], 1).is_err());
+		assert_eq!(Arc::<Vec<u32>>::decode_with_mem_limit(&mut &encoded[..], 1024).unwrap(), value);
+	}
+}
@@ -0,0 +1,272 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte strings holding nested encodings, prefixed by their length.
+
+use crate::{
+	alloc::vec::Vec, codec::decode_vec_with_len, CodecConfig, Compact, CompactLen, Decode, Encode,
+	EncodeLike, Error, Input, Output,
+};
+
+/// Write `bytes` to `dest` prefixed by their length as `Compact<u32>`, like a `Vec<u8>`.
+///
+/// This is the usual way of nesting an encoding which is already available as bytes, e.g. a call
+/// encoded elsewhere, into another encoding, without decoding and encoding it again.
+///
+/// Unlike encoding a `&[u8]`, the length prefix is always a `Compact<u32>`, regardless of the
+/// [`CodecConfig::length_prefix`](crate::CodecConfig::length_prefix) of `dest`.
+///
+/// If `bytes` is longer than `u32::MAX`, this is reported through [`Output::on_encode_error`], like
+/// for collections.
+pub fn encode_compact_len_prefixed_bytes<W: Output + ?Sized>(bytes: &[u8], dest: &mut W) {
+	if bytes.len() > u32::MAX as usize {
+		return dest
+			.on_encode_error("Attempted to serialize a collection with too many elements.".into());
+	}
+	Compact(bytes.len() as u32).encode_to(dest);
+	dest.write(bytes);
+}
+
+/// Read bytes prefixed by their length as `Compact<u32>`, as written by
+/// [`encode_compact_len_prefixed_bytes`].
+///
+/// The bytes are allocated in chunks as they are read, like a `Vec<u8>`.
+pub fn decode_compact_len_prefixed_bytes<I: Input>(input: &mut I) -> Result<Vec<u8>, Error> {
+	let Compact(len) = <Compact<u32>>::decode(input)?;
+	decode_vec_with_len(input, len as usize)
+}
+
+/// Decode a `T` nested in bytes prefixed by their length as `Compact<u32>`, e.g. the encoding of
+/// a [`PrefixedRaw`] or of a `Vec<u8>` holding the encoding of a `T`.
+///
+/// The `T` is read from `input` through a [`RawRemaining`], so the bytes aren't copied, and the
+/// hooks of `input`, e.g. the memory and depth limits, also apply to decoding `T`. Decoding fails
+/// if `T` doesn't use all the bytes.
+///
+/// ```
+/// # use parity_scale_codec::{decode_compact_len_prefixed, Encode, PrefixedRaw};
+/// let call = (3u8, 1_000u64).encode();
+/// let extrinsic = (1u32, PrefixedRaw(&call)).encode();
+///
+/// let mut input = &extrinsic[4..];
+/// assert_eq!(decode_compact_len_prefixed::<(u8, u64), _>(&mut input), Ok((3, 1_000)));
+/// assert!(decode_compact_len_prefixed::<u8, _>(&mut &extrinsic[4..]).is_err());
+/// ```
+pub fn decode_compact_len_prefixed<T: Decode, I: Input>(input: &mut I) -> Result<T, Error> {
+	let mut nested = RawRemaining::new(input)?;
+	let value = T::decode(&mut nested)?;
+	if nested.remaining() != 0 {
+		return Err("Nested encoding has trailing bytes".into());
+	}
+	Ok(value)
+}
+
+/// An [`Input`] reading the bytes of a nested encoding prefixed by its length as `Compact<u32>`,
+/// e.g. of a [`PrefixedRaw`], from the input it is nested in.
+///
+/// Reading fails once the bytes of the nested encoding are all read, and
+/// [`Input::remaining_len`] is the number of its bytes left, so the nested value can be decoded
+/// like from a buffer, without copying the bytes out of the input first. Everything else is
+/// forwarded to the outer input.
+///
+/// ```
+/// # use parity_scale_codec::{Decode, Encode, Input, PrefixedRaw, RawRemaining};
+/// let encoded = (PrefixedRaw(&[1, 2, 3]), 4u8).encode();
+/// let mut input = &encoded[..];
+///
+/// let mut nested = RawRemaining::new(&mut input)?;
+/// assert_eq!(nested.remaining_len(), Ok(Some(3)));
+/// assert_eq!(u16::decode(&mut nested), Ok(0x0201));
+/// assert!(u16::decode(&mut nested).is_err());
+/// assert_eq!(u8::decode(&mut nested), Ok(3));
+/// assert_eq!(u8::decode(&mut input), Ok(4));
+/// # Ok::<(), parity_scale_codec::Error>(())
+/// ```
+pub struct RawRemaining<'a, I> {
+	input: &'a mut I,
+	remaining: usize,
+}
+
+impl<'a, I: Input> RawRemaining<'a, I> {
+	/// Read the length prefix from `input`.
+	pub fn new(input: &'a mut I) -> Result<Self, Error> {
+		let Compact(len) = <Compact<u32>>::decode(input)?;
+		if input.remaining_len()?.is_some_and(|remaining| remaining < len as usize) {
+			return Err("Not enough data to fill buffer".into());
+		}
+		Ok(Self { input, remaining: len as usize })
+	}
+
+	/// The number of bytes of the nested encoding not read yet.
+	pub fn remaining(&self) -> usize {
+		self.remaining
+	}
+
+	/// Take `len` bytes from the nested encoding.
+	fn take(&mut self, len: usize) -> Result<(), Error> {
+		self.remaining = self.remaining.checked_sub(len).ok_or("Not enough data to fill buffer")?;
+		Ok(())
+	}
+}
+
+impl<I: Input> Input for RawRemaining<'_, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(Some(self.remaining))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.take(into.len())?;
+		self.input.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.take(1)?;
+		self.input.read_byte()
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+
+	fn seek_forward(&mut self, n: usize) -> Result<(), Error> {
+		self.take(n)?;
+		self.input.seek_forward(n)
+	}
+}
+
+/// Raw bytes, e.g. an encoding done elsewhere, encoded prefixed by their length as
+/// `Compact<u32>`.
+///
+/// With the default [`CodecConfig`], it is encoded like a `Vec<u8>` with the bytes, see
+/// [`encode_compact_len_prefixed_bytes`], and its encoding can be decoded as a `Vec<u8>`, or as
+/// the nested value with [`decode_compact_len_prefixed`]. Bytes without a length prefix, which
+/// extend to the end of the input, are [`UncheckedTail`](crate::UncheckedTail).
+///
+/// ```
+/// # use parity_scale_codec::{Decode, Encode, PrefixedRaw};
+/// let inner = 42u32.encode();
+/// let encoded = PrefixedRaw(&inner).encode();
+/// assert_eq!(encoded, [16, 42, 0, 0, 0]);
+/// assert_eq!(encoded, inner.encode());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PrefixedRaw<'a>(pub &'a [u8]);
+
+impl Encode for PrefixedRaw<'_> {
+	fn size_hint(&self) -> usize {
+		Compact::<u32>::compact_len(&(self.0.len() as u32)) + self.0.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		encode_compact_len_prefixed_bytes(self.0, dest)
+	}
+}
+
+impl EncodeLike for PrefixedRaw<'_> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{ConfiguredOutput, DecodeWithMemLimit, LengthPrefix};
+
+	#[test]
+	fn prefixed_raw_is_encoded_like_vec() {
+		for len in [0, 1, 63, 64, 16_383, 16_384, 100_000] {
+			let bytes = vec![7u8; len];
+			let encoded = PrefixedRaw(&bytes).encode();
+			assert_eq!(encoded, bytes.encode());
+			assert_eq!(PrefixedRaw(&bytes).size_hint(), encoded.len());
+			assert_eq!(decode_compact_len_prefixed_bytes(&mut &encoded[..]), Ok(bytes));
+		}
+	}
+
+	#[test]
+	fn prefix_is_compact_regardless_of_config() {
+		let mut encoded = Vec::new();
+		let config = CodecConfig { length_prefix: LengthPrefix::FixedU32LE, ..Default::default() };
+		PrefixedRaw(&[1, 2]).encode_to(&mut ConfiguredOutput::new(&mut encoded, config));
+		assert_eq!(encoded, [8, 1, 2]);
+	}
+
+	#[test]
+	fn nested_decode_checks_the_length() {
+		let nested = (1u16, PrefixedRaw(&2u32.encode()), 3u8).encode();
+
+		let mut input = &nested[2..];
+		assert_eq!(decode_compact_len_prefixed::<u32, _>(&mut input), Ok(2));
+		assert_eq!(input, [3]);
+
+		assert_eq!(
+			decode_compact_len_prefixed::<u16, _>(&mut &nested[2..]),
+			Err("Nested encoding has trailing bytes".into())
+		);
+		assert!(decode_compact_len_prefixed::<u64, _>(&mut &nested[2..]).is_err());
+
+		// The length can't exceed the input.
+		let mut encoded = Compact(100u32).encode();
+		encoded.extend([1, 2]);
+		assert!(RawRemaining::new(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn raw_remaining_limits_reads_to_the_nested_encoding() {
+		let encoded = (PrefixedRaw(&[1, 2, 3, 4, 5]), 6u8).encode();
+		let mut input = &encoded[..];
+		let mut nested = RawRemaining::new(&mut input).unwrap();
+		assert_eq!(nested.read_byte(), Ok(1));
+		nested.seek_forward(2).unwrap();
+		assert_eq!(nested.remaining(), 2);
+		assert!(nested.read(&mut [0; 3]).is_err());
+		assert!(nested.seek_forward(3).is_err());
+		assert_eq!(Vec::<u8>::decode(&mut nested), Ok(vec![5]));
+		assert_eq!(nested.read_byte(), Err("Not enough data to fill buffer".into()));
+		assert_eq!(u8::decode(&mut input), Ok(6));
+
+		// Nested vectors can't claim more items than the nested encoding has bytes.
+		let encoded = PrefixedRaw(&Compact(u32::MAX).encode()).encode();
+		assert!(Vec::<u8>::decode_with_mem_limit(
+			&mut RawRemaining::new(&mut &encoded[..]).unwrap(),
+			1024
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn nested_decode_keeps_the_hooks() {
+		let encoded = PrefixedRaw(&Box::new(Box::new(5u32)).encode()).encode();
+		let decode = |depth_limit| {
+			decode_compact_len_prefixed::<Box<Box<u32>>, _>(
+				&mut (&encoded[..]).with_depth_limit(depth_limit),
+			)
+		};
+		assert_eq!(decode(2), Ok(Box::new(Box::new(5))));
+		assert!(decode(1).is_err());
+	}
+}
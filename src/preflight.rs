@@ -0,0 +1,330 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Estimation of the cost of decoding a value before decoding it.
+
+use core::{marker::PhantomData, mem};
+
+use crate::{
+	alloc::{
+		boxed::Box,
+		collections::{BTreeMap, BTreeSet, VecDeque},
+		string::String,
+		vec::Vec,
+	},
+	btree_utils::mem_size_of_btree,
+	codec::decode_len,
+	depth_limit::DepthTrackingInput,
+	Compact, Decode, Error, Input,
+};
+
+/// An estimate of the resources needed to decode a value, see [`preflight_decode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeCostEstimate {
+	/// The number of bytes the decoded value allocates on the heap, as reported to
+	/// [`Input::on_before_alloc_mem`] when decoding it.
+	pub heap_bytes: usize,
+	/// The number of items of all collections, where the items of a `String` are its bytes.
+	pub elements: usize,
+	/// The number of bytes of the encoding.
+	pub encoded_len: usize,
+}
+
+impl DecodeCostEstimate {
+	/// Add an allocation of `bytes` bytes.
+	pub fn add_heap_bytes(&mut self, bytes: usize) {
+		self.heap_bytes = self.heap_bytes.saturating_add(bytes);
+	}
+
+	/// Add a collection of `len` items of type `T`, which are allocated on the heap.
+	pub fn add_elements<T>(&mut self, len: usize) {
+		self.elements = self.elements.saturating_add(len);
+		self.add_heap_bytes(len.saturating_mul(mem::size_of::<T>()));
+	}
+}
+
+/// Types whose encoding can be walked to estimate the cost of decoding it, without decoding it.
+///
+/// It can be derived with `#[derive(Preflight)]` for types deriving `Decode`, taking their
+/// attributes into account.
+pub trait Preflight: Decode {
+	/// The size of the encoding of every value, if it is fixed and decoding doesn't allocate.
+	///
+	/// Collections of such types are walked by skipping the bytes of their items.
+	const HEAP_FREE_SIZE: Option<usize> = None;
+
+	/// Walk the encoding of a `Self` in `input`, adding the cost of decoding it to `estimate`.
+	///
+	/// Like decoding, this fails if the encoding is invalid, but the value isn't constructed, so
+	/// invalid values, e.g. strings which aren't UTF-8, may not be detected.
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error>;
+}
+
+/// Walk the encoding of a `T` at the start of `bytes`, and estimate the cost of decoding it.
+///
+/// Only length prefixes and tags are read, so the cost of decoding can be checked before anything
+/// is allocated, e.g. to reject payloads which would allocate too much. The walk fails if the
+/// recursion depth exceeds `depth_limit`, like [`DecodeLimit`](crate::DecodeLimit), so recursive
+/// types can't overflow the stack:
+///
+/// ```
+/// # use core::mem::size_of;
+/// # use parity_scale_codec::{preflight_decode, Compact, Encode};
+/// // A vector claiming to have 100 000 vectors of 10 000 `u64` each.
+/// let mut payload = Compact(100_000u32).encode();
+/// payload.extend((0..100_000).flat_map(|_| Compact(10_000u32).encode()));
+///
+/// // There aren't enough bytes for the items.
+/// assert!(preflight_decode::<Vec<Vec<u64>>>(&payload, 8).is_err());
+///
+/// let encoded = vec![vec![1u64; 10]; 3].encode();
+/// let estimate = preflight_decode::<Vec<Vec<u64>>>(&encoded, 8).unwrap();
+/// assert_eq!(estimate.elements, 33);
+/// assert_eq!(estimate.heap_bytes, 3 * size_of::<Vec<u64>>() + 30 * size_of::<u64>());
+/// assert!(preflight_decode::<Vec<Vec<u64>>>(&encoded, 0).is_err());
+/// ```
+pub fn preflight_decode<T: Preflight>(
+	bytes: &[u8],
+	depth_limit: u32,
+) -> Result<DecodeCostEstimate, Error> {
+	let mut input = bytes;
+	let mut estimate = DecodeCostEstimate::default();
+	T::preflight(&mut DepthTrackingInput::new(&mut input, depth_limit), &mut estimate)?;
+	estimate.encoded_len = bytes.len() - input.len();
+	Ok(estimate)
+}
+
+/// Walk `len` items of type `T`.
+fn preflight_items<T: Preflight, I: Input>(
+	input: &mut I,
+	len: usize,
+	estimate: &mut DecodeCostEstimate,
+) -> Result<(), Error> {
+	if let Some(size) = T::HEAP_FREE_SIZE {
+		return input.seek_forward(len.checked_mul(size).ok_or("Not enough data to fill buffer")?);
+	}
+
+	input.descend_ref()?;
+	for _ in 0..len {
+		T::preflight(input, estimate)?;
+	}
+	input.ascend_ref();
+	Ok(())
+}
+
+macro_rules! impl_heap_free {
+	( $( $ty:ty ),* ) => { $(
+		impl Preflight for $ty {
+			const HEAP_FREE_SIZE: Option<usize> = Some(mem::size_of::<$ty>());
+
+			fn preflight<I: Input>(
+				input: &mut I,
+				_estimate: &mut DecodeCostEstimate,
+			) -> Result<(), Error> {
+				Self::skip(input)
+			}
+		}
+	)* };
+}
+
+impl_heap_free!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, bool, f32, f64, ());
+
+impl<T> Preflight for PhantomData<T> {
+	const HEAP_FREE_SIZE: Option<usize> = Some(0);
+
+	fn preflight<I: Input>(
+		_input: &mut I,
+		_estimate: &mut DecodeCostEstimate,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+}
+
+impl<T> Preflight for Compact<T>
+where
+	Compact<T>: Decode,
+{
+	fn preflight<I: Input>(input: &mut I, _estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		Self::skip(input)
+	}
+}
+
+impl<T: Preflight, const N: usize> Preflight for [T; N] {
+	const HEAP_FREE_SIZE: Option<usize> = match T::HEAP_FREE_SIZE {
+		Some(size) => size.checked_mul(N),
+		None => None,
+	};
+
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		preflight_items::<T, I>(input, N, estimate)
+	}
+}
+
+impl<T: Preflight> Preflight for Option<T> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		match input.read_byte()? {
+			0 => Ok(()),
+			1 => T::preflight(input, estimate),
+			_ => Err("unexpected first byte decoding Option".into()),
+		}
+	}
+}
+
+impl<T: Preflight, E: Preflight> Preflight for Result<T, E> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		match input.read_byte()? {
+			0 => T::preflight(input, estimate),
+			1 => E::preflight(input, estimate),
+			_ => Err("unexpected first byte decoding Result".into()),
+		}
+	}
+}
+
+impl<T: Preflight> Preflight for Box<T> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		estimate.add_heap_bytes(mem::size_of::<T>());
+		input.descend_ref()?;
+		T::preflight(input, estimate)?;
+		input.ascend_ref();
+		Ok(())
+	}
+}
+
+impl Preflight for String {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		let len = decode_len(input)?;
+		estimate.add_elements::<u8>(len);
+		input.seek_forward(len)
+	}
+}
+
+impl<T: Preflight> Preflight for Vec<T> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		let len = decode_len(input)?;
+		estimate.add_elements::<T>(len);
+		preflight_items::<T, I>(input, len, estimate)
+	}
+}
+
+impl<T: Preflight> Preflight for VecDeque<T> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		Vec::<T>::preflight(input, estimate)
+	}
+}
+
+impl<T: Preflight + Ord> Preflight for BTreeSet<T> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		let len = decode_len(input)?;
+		estimate.elements = estimate.elements.saturating_add(len);
+		estimate.add_heap_bytes(mem_size_of_btree::<T>(len));
+		preflight_items::<T, I>(input, len, estimate)
+	}
+}
+
+impl<K: Preflight + Ord, V: Preflight> Preflight for BTreeMap<K, V> {
+	fn preflight<I: Input>(input: &mut I, estimate: &mut DecodeCostEstimate) -> Result<(), Error> {
+		let len = decode_len(input)?;
+		estimate.elements = estimate.elements.saturating_add(len);
+		estimate.add_heap_bytes(mem_size_of_btree::<(K, V)>(len));
+		preflight_items::<(K, V), I>(input, len, estimate)
+	}
+}
+
+/// The sum of `sizes`, if they are all known.
+const fn sum_sizes(sizes: &[Option<usize>]) -> Option<usize> {
+	let mut sum = 0;
+	let mut i = 0;
+	while i < sizes.len() {
+		match sizes[i] {
+			Some(size) => sum += size,
+			None => return None,
+		}
+		i += 1;
+	}
+	Some(sum)
+}
+
+macro_rules! impl_for_tuples {
+	( $( $ty:ident ),+ ) => {
+		impl<$( $ty: Preflight ),+> Preflight for ($( $ty, )+) {
+			const HEAP_FREE_SIZE: Option<usize> = sum_sizes(&[$( $ty::HEAP_FREE_SIZE ),+]);
+
+			fn preflight<I: Input>(
+				input: &mut I,
+				estimate: &mut DecodeCostEstimate,
+			) -> Result<(), Error> {
+				$( $ty::preflight(input, estimate)?; )+
+				Ok(())
+			}
+		}
+	};
+}
+
+impl_for_tuples!(A);
+impl_for_tuples!(A, B);
+impl_for_tuples!(A, B, C);
+impl_for_tuples!(A, B, C, D);
+impl_for_tuples!(A, B, C, D, E);
+impl_for_tuples!(A, B, C, D, E, F);
+impl_for_tuples!(A, B, C, D, E, F, G);
+impl_for_tuples!(A, B, C, D, E, F, G, H);
+impl_for_tuples!(A, B, C, D, E, F, G, H, J);
+impl_for_tuples!(A, B, C, D, E, F, G, H, J, K);
+impl_for_tuples!(A, B, C, D, E, F, G, H, J, K, L);
+impl_for_tuples!(A, B, C, D, E, F, G, H, J, K, L, M);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::vec, Encode, MemTrackingInput};
+
+	/// The memory reported by decoding `value`.
+	fn decoded_heap_bytes<T: Encode + Decode>(value: &T) -> usize {
+		let encoded = value.encode();
+		let mut input = &encoded[..];
+		let mut input = MemTrackingInput::new(&mut input, usize::MAX);
+		T::decode(&mut input).unwrap();
+		input.used_mem()
+	}
+
+	fn check<T: Encode + Preflight>(value: T, elements: usize) {
+		let encoded = (&value, 7u8).encode();
+		let estimate = preflight_decode::<T>(&encoded, 16).unwrap();
+		assert_eq!(estimate.encoded_len, encoded.len() - 1);
+		assert_eq!(estimate.elements, elements);
+		assert_eq!(estimate.heap_bytes, decoded_heap_bytes(&value));
+	}
+
+	#[test]
+	fn preflight_matches_decoding() {
+		check(5u32, 0);
+		check((1u8, Compact(300u64), [2u16; 3]), 0);
+		check(vec![1u64, 2, 3], 3);
+		check(vec![vec![1u8], vec![], vec![2, 3]], 6);
+		check(String::from("abc"), 3);
+		check((Some(Box::new(4u128)), Option::<Vec<u8>>::None), 0);
+		check(Result::<Vec<u16>, ()>::Ok(vec![1]), 1);
+		check(BTreeMap::from([(1u32, String::from("a")), (2, String::from("bc"))]), 5);
+		check(BTreeSet::from([[1u8; 2], [2; 2]]), 2);
+		check(VecDeque::from(vec![(); 4]), 4);
+	}
+
+	#[test]
+	fn preflight_rejects_invalid_encodings() {
+		assert!(preflight_decode::<Option<u8>>(&[2], 16).is_err());
+		assert!(preflight_decode::<Vec<u32>>(&[8, 1, 0, 0, 0], 16).is_err());
+		assert!(preflight_decode::<Vec<Vec<u8>>>(&Compact(u32::MAX).encode(), 16).is_err());
+		assert!(preflight_decode::<String>(&[12, b'a'], 16).is_err());
+	}
+}
@@ -0,0 +1,33 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The commonly used traits and types, to be imported with `use parity_scale_codec::prelude::*`.
+//!
+//! Untrusted input should be decoded with limits on the memory used and the recursion depth,
+//! which only takes one line with the prelude:
+//!
+//! ```
+//! use parity_scale_codec::prelude::*;
+//!
+//! let encoded = vec![Some(1u32), None].encode();
+//! let mut input = encoded.as_slice().with_mem_limit(1 << 20).with_depth_limit(64);
+//! let decoded = Vec::<Option<u32>>::decode(&mut input).unwrap();
+//! assert_eq!(decoded, [Some(1), None]);
+//! ```
+
+pub use crate::{
+	Compact, CountedInput, Decode, DecodeAll, DecodeLimit, DecodeWithMemLimit,
+	DecodeWithMemTracking, DepthTrackingInput, Encode, EncodeLike, Error, HasCompact, Input,
+	MemTrackingInput, Output,
+};
@@ -0,0 +1,193 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An input reporting the progress of decoding.
+
+use crate::{alloc::vec::Vec, CodecConfig, Error, Input};
+
+/// An [`Input`] calling a callback with the number of bytes read so far, every time another `every`
+/// bytes have been read, e.g. to render a progress bar while decoding a very large value.
+///
+/// It composes with the other inputs, e.g. [`MemTrackingInput`](crate::MemTrackingInput), and can
+/// be built with [`Input::with_progress`]:
+///
+/// ```
+/// # use parity_scale_codec::{Decode, Encode, Input};
+/// let encoded = vec![0u8; 10_000].encode();
+///
+/// let mut reports = Vec::new();
+/// let input = encoded.as_slice().with_mem_limit(1 << 20);
+/// let mut input = input.with_progress(4096, |read| reports.push(read));
+/// let decoded = Vec::<u8>::decode(&mut input).unwrap();
+/// assert_eq!(decoded.len(), 10_000);
+/// assert_eq!(input.bytes_read(), 10_002);
+/// drop(input);
+/// assert_eq!(reports, [4096, 8192]);
+/// ```
+///
+/// Large reads are split at the multiples of `every`, so each multiple is reported even when the
+/// decoder reads a whole vector of bytes at once. The total is only reported when it is a multiple
+/// of `every`, it can be queried with [`ProgressInput::bytes_read`].
+pub struct ProgressInput<I, F> {
+	input: I,
+	callback: F,
+	every: u64,
+	bytes_read: u64,
+	next_report: u64,
+}
+
+impl<I: Input, F: FnMut(u64)> ProgressInput<I, F> {
+	/// Wrap `input`, calling `callback` every time another `every` bytes have been read.
+	///
+	/// An `every` of zero is treated as one.
+	pub fn new(input: I, every: u64, callback: F) -> Self {
+		let every = every.max(1);
+		Self { input, callback, every, bytes_read: 0, next_report: every }
+	}
+
+	/// The number of bytes read so far.
+	pub fn bytes_read(&self) -> u64 {
+		self.bytes_read
+	}
+
+	/// The wrapped input.
+	pub fn into_inner(self) -> I {
+		self.input
+	}
+
+	fn on_read(&mut self, len: usize) {
+		self.bytes_read = self.bytes_read.saturating_add(len as u64);
+		if self.bytes_read >= self.next_report {
+			(self.callback)(self.bytes_read);
+			self.next_report = self.next_report.saturating_add(self.every);
+		}
+	}
+}
+
+impl<I: Input, F: FnMut(u64)> Input for ProgressInput<I, F> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, mut into: &mut [u8]) -> Result<(), Error> {
+		while !into.is_empty() {
+			let until_report = self.next_report.saturating_sub(self.bytes_read);
+			let len = into.len().min(until_report.try_into().unwrap_or(usize::MAX));
+			let (chunk, rest) = into.split_at_mut(len);
+			self.input.read(chunk)?;
+			self.on_read(len);
+			into = rest;
+		}
+		Ok(())
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		let byte = self.input.read_byte()?;
+		self.on_read(1);
+		Ok(byte)
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+
+	fn seek_forward(&mut self, mut n: usize) -> Result<(), Error> {
+		while n > 0 {
+			let until_report = self.next_report.saturating_sub(self.bytes_read);
+			let len = n.min(until_report.try_into().unwrap_or(usize::MAX));
+			self.input.seek_forward(len)?;
+			self.on_read(len);
+			n -= len;
+		}
+		Ok(())
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		let start = into.len();
+		let result = self.input.read_remaining_into(into);
+
+		let mut n = into.len() - start;
+		while n > 0 {
+			let until_report = self.next_report.saturating_sub(self.bytes_read);
+			let len = n.min(until_report.try_into().unwrap_or(usize::MAX));
+			self.on_read(len);
+			n -= len;
+		}
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Decode, Encode};
+
+	#[test]
+	fn reports_every_multiple_of_the_interval() {
+		let encoded = (0..100u32).collect::<Vec<_>>().encode();
+
+		let mut reports = Vec::new();
+		let mut input = ProgressInput::new(encoded.as_slice(), 100, |read| reports.push(read));
+		assert_eq!(Vec::<u32>::decode(&mut input).unwrap().len(), 100);
+		assert_eq!(input.bytes_read(), 402);
+		assert!(input.into_inner().is_empty());
+		assert_eq!(reports, [100, 200, 300, 400]);
+
+		let mut reports = Vec::new();
+		let mut input = ProgressInput::new(&[4u8, 1, 2][..], 0, |read| reports.push(read));
+		assert_eq!(<(u8, u8, u8)>::decode(&mut input), Ok((4, 1, 2)));
+		assert_eq!(reports, [1, 2, 3]);
+	}
+
+	#[test]
+	fn failed_reads_are_not_counted() {
+		let mut reports = Vec::new();
+		let mut slice = &[1u8, 2, 3, 4, 5][..];
+		let borrowed: &mut dyn Input = &mut slice;
+		let mut input = borrowed.with_progress(4, |read| reports.push(read));
+		assert_eq!(u16::decode(&mut input), Ok(0x0201));
+		// The read is split at 4 bytes, which are read before the rest fails.
+		assert!(u32::decode(&mut input).is_err());
+		assert_eq!(input.bytes_read(), 4);
+		assert_eq!(reports, [4]);
+		assert_eq!(slice, [5]);
+	}
+
+	#[test]
+	fn skipped_bytes_are_counted() {
+		let encoded = vec![7u8; 250].encode();
+
+		let mut reports = Vec::new();
+		let mut input = encoded.as_slice().with_progress(100, |read| reports.push(read));
+		Vec::<u8>::skip(&mut input).unwrap();
+		assert_eq!(input.bytes_read(), 252);
+		assert_eq!(reports, [100, 200]);
+	}
+}
@@ -0,0 +1,287 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Run-length encoding of byte and bit sequences.
+
+use crate::{
+	alloc::vec::Vec,
+	codec::{decode_len, decode_vec_chunked_into, encode_len_to},
+	compact::CompactLen,
+	Compact, Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
+};
+
+/// A `Vec<u8>` or `BitVec` encoded as runs of equal values, for sequences which are mostly made
+/// of long runs, e.g. sparse bitmaps.
+///
+/// A `Rle<Vec<u8>>` is encoded as the number of runs, followed by each run as its length and its
+/// byte.
+///
+/// A `Rle<BitVec>` is encoded as the number of runs, followed by the length of each run. The runs
+/// alternate between zeros and ones, starting with zeros.
+///
+/// The numbers of runs and their lengths are encoded like the length prefix of a `Vec`, as
+/// `Compact<u32>` with the default [`CodecConfig`](crate::CodecConfig).
+///
+/// Only the canonical encoding is accepted when decoding: a run is never empty, except for the
+/// first run of a `BitVec` starting with a one, and consecutive runs of a `Vec<u8>` have different
+/// bytes. So each value has exactly one encoding.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, Rle};
+/// let bytes = Rle([vec![0; 1000], vec![7; 3]].concat());
+/// assert_eq!(bytes.encode(), [8, 0xa1, 0x0f, 0, 12, 7]);
+/// assert_eq!(decode_prefix::<Rle<Vec<u8>>>(&bytes.encode()).unwrap().0, bytes);
+/// ```
+///
+/// As a short encoding can decode to a large value, untrusted input should be decoded with a
+/// memory limit, see [`DecodeWithMemLimit`](crate::DecodeWithMemLimit). Runs are allocated in
+/// chunks, so the limit is hit before the memory of a long run is allocated. The decoded value is
+/// limited to `u32::MAX` bytes, like other collections.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rle<T>(pub T);
+
+impl<T> From<T> for Rle<T> {
+	fn from(x: T) -> Self {
+		Rle(x)
+	}
+}
+
+/// The runs of equal bytes of `bytes`.
+fn byte_runs(bytes: &[u8]) -> impl Iterator<Item = (usize, u8)> + '_ {
+	bytes.chunk_by(|a, b| a == b).map(|run| (run.len(), run[0]))
+}
+
+impl Encode for Rle<Vec<u8>> {
+	fn size_hint(&self) -> usize {
+		let (runs, size) = byte_runs(&self.0).fold((0u32, 0), |(runs, size), (run_len, _)| {
+			(runs + 1, size + Compact::<u32>::compact_len(&(run_len as u32)) + 1)
+		});
+		Compact::<u32>::compact_len(&runs) + size
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		if self.0.len() > u32::MAX as usize {
+			return dest.on_encode_error(
+				"Attempted to serialize a collection with too many elements.".into(),
+			);
+		}
+		encode_len_to(dest, byte_runs(&self.0).count());
+		for (run_len, byte) in byte_runs(&self.0) {
+			encode_len_to(dest, run_len);
+			dest.push_byte(byte);
+		}
+	}
+}
+
+impl EncodeLike for Rle<Vec<u8>> {}
+
+impl Decode for Rle<Vec<u8>> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let runs = decode_len(input)?;
+
+		let mut bytes = Vec::new();
+		let mut previous = None;
+		for _ in 0..runs {
+			let run_len = check_run_len(input, bytes.len(), u32::MAX as usize)?;
+			let byte = input.read_byte()?;
+			if previous == Some(byte) {
+				return Err("Rle runs of the same byte are not merged".into());
+			}
+			previous = Some(byte);
+
+			decode_vec_chunked_into(input, run_len, &mut bytes, |_, bytes, chunk_len| {
+				bytes.resize(bytes.len() + chunk_len, byte);
+				Ok(())
+			})?;
+		}
+
+		Ok(Rle(bytes))
+	}
+}
+
+impl DecodeWithMemTracking for Rle<Vec<u8>> {}
+
+/// Decode the length of a non-empty run, which appended to `len` items gives at most `max` items.
+fn check_run_len<I: Input>(input: &mut I, len: usize, max: usize) -> Result<usize, Error> {
+	let run_len = decode_len(input)?;
+	if run_len == 0 {
+		return Err("Rle run is empty".into());
+	}
+	if run_len > max - len {
+		return Err("Rle is too long".into());
+	}
+	Ok(run_len)
+}
+
+#[cfg(feature = "bit-vec")]
+mod bit_vec {
+	use super::*;
+	use crate::bit_vec::ARCH32BIT_BITSLICE_MAX_BITS;
+	use bitvec::{order::BitOrder, slice::BitSlice, store::BitStore, vec::BitVec};
+
+	/// The lengths of the alternating runs of zeros and ones of `bits`, starting with zeros.
+	fn bit_runs<T: BitStore, O: BitOrder>(
+		bits: &BitSlice<T, O>,
+	) -> impl Iterator<Item = usize> + '_ {
+		let mut rest = bits;
+		let mut ones = false;
+		core::iter::from_fn(move || {
+			if rest.is_empty() {
+				return None;
+			}
+			let run_len =
+				if ones { rest.first_zero() } else { rest.first_one() }.unwrap_or(rest.len());
+			rest = &rest[run_len..];
+			ones = !ones;
+			Some(run_len)
+		})
+	}
+
+	impl<T: BitStore, O: BitOrder> Encode for Rle<BitVec<T, O>> {
+		fn size_hint(&self) -> usize {
+			let (runs, size) = bit_runs(&self.0).fold((0u32, 0), |(runs, size), run_len| {
+				(runs + 1, size + Compact::<u32>::compact_len(&(run_len as u32)))
+			});
+			Compact::<u32>::compact_len(&runs) + size
+		}
+
+		fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+			if self.0.len() > ARCH32BIT_BITSLICE_MAX_BITS {
+				return dest
+					.on_encode_error("Attempted to encode a BitSlice with too many bits.".into());
+			}
+			encode_len_to(dest, bit_runs(&self.0).count());
+			for run_len in bit_runs(&self.0) {
+				encode_len_to(dest, run_len);
+			}
+		}
+	}
+
+	impl<T: BitStore, O: BitOrder> EncodeLike for Rle<BitVec<T, O>> {}
+
+	impl<T: BitStore, O: BitOrder> Decode for Rle<BitVec<T, O>> {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+			let runs = decode_len(input)?;
+
+			let mut bits = BitVec::new();
+			for run in 0..runs {
+				// A `BitVec` starting with a one has an empty first run of zeros.
+				let run_len = if run == 0 && runs > 1 {
+					let run_len = decode_len(input)?;
+					if run_len > ARCH32BIT_BITSLICE_MAX_BITS {
+						return Err("Rle is too long".into());
+					}
+					run_len
+				} else {
+					check_run_len(input, bits.len(), ARCH32BIT_BITSLICE_MAX_BITS)?
+				};
+
+				// Like `decode_vec_chunked_into`, the memory is allocated in chunks.
+				let chunk_len = input.config().max_preallocation.saturating_mul(8).max(1);
+				let mut rest = run_len;
+				while rest > 0 {
+					let len = rest.min(chunk_len);
+					input.on_before_alloc_mem(len.div_ceil(8))?;
+					bits.resize(bits.len() + len, run % 2 == 1);
+					rest -= len;
+				}
+			}
+
+			Ok(Rle(bits))
+		}
+	}
+
+	impl<T: BitStore, O: BitOrder> DecodeWithMemTracking for Rle<BitVec<T, O>> {}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::DecodeWithMemLimit;
+
+	fn check_bytes(bytes: Vec<u8>, expected: &[u8]) {
+		let value = Rle(bytes);
+		let encoded = value.encode();
+		assert_eq!(encoded, expected);
+		assert_eq!(value.size_hint(), encoded.len());
+		assert_eq!(Rle::<Vec<u8>>::decode(&mut &encoded[..]).unwrap(), value);
+	}
+
+	#[test]
+	fn rle_bytes_works() {
+		check_bytes(vec![], &[0]);
+		check_bytes(vec![5], &[4, 4, 5]);
+		check_bytes(vec![0, 0, 0, 1, 1, 0], &[12, 12, 0, 8, 1, 4, 0]);
+		check_bytes(vec![0; 1 << 20], &[4, 2, 0, 0x40, 0, 0]);
+	}
+
+	#[test]
+	fn rle_bytes_rejects_non_canonical_encodings() {
+		assert_eq!(
+			Rle::<Vec<u8>>::decode(&mut &[8, 4, 0, 4, 0][..]),
+			Err("Rle runs of the same byte are not merged".into())
+		);
+		assert_eq!(Rle::<Vec<u8>>::decode(&mut &[4, 0, 1][..]), Err("Rle run is empty".into()));
+		assert_eq!(
+			check_run_len(&mut &[8][..], u32::MAX as usize - 1, u32::MAX as usize),
+			Err("Rle is too long".into())
+		);
+	}
+
+	#[test]
+	fn rle_bytes_respects_mem_limit() {
+		// A run of `u32::MAX` bytes in 7 bytes.
+		let encoded = [4, 3, 0xff, 0xff, 0xff, 0xff, 0];
+		assert!(Rle::<Vec<u8>>::decode_with_mem_limit(&mut &encoded[..], 1024).is_err());
+
+		let encoded = Rle(vec![1; 1000]).encode();
+		assert!(Rle::<Vec<u8>>::decode_with_mem_limit(&mut &encoded[..], 1001).is_ok());
+	}
+
+	#[cfg(feature = "bit-vec")]
+	#[test]
+	fn rle_bit_vec_works() {
+		use bitvec::{bitvec, order::Msb0, vec::BitVec};
+
+		fn check(bits: BitVec<u8, Msb0>, expected: &[u8]) {
+			let value = Rle(bits);
+			let encoded = value.encode();
+			assert_eq!(encoded, expected);
+			assert_eq!(value.size_hint(), encoded.len());
+			assert_eq!(Rle::<BitVec<u8, Msb0>>::decode(&mut &encoded[..]).unwrap(), value);
+		}
+
+		check(bitvec![u8, Msb0;], &[0]);
+		check(bitvec![u8, Msb0; 0, 0, 0], &[4, 12]);
+		check(bitvec![u8, Msb0; 1, 1, 0], &[12, 0, 8, 4]);
+		check(bitvec![u8, Msb0; 0, 1, 0, 0], &[12, 4, 4, 8]);
+
+		let mut sparse = bitvec![u8, Msb0; 0; 100_000];
+		sparse.set(50_000, true);
+		let expected =
+			[&[12][..], &Compact(50_000u32).encode(), &[4], &Compact(49_999u32).encode()].concat();
+		check(sparse, &expected);
+
+		for non_canonical in [&[4, 0][..], &[12, 0, 0, 4], &[12, 4, 0, 4]] {
+			assert_eq!(
+				Rle::<BitVec<u8, Msb0>>::decode(&mut &non_canonical[..]),
+				Err("Rle run is empty".into())
+			);
+		}
+
+		// A run of `ARCH32BIT_BITSLICE_MAX_BITS` zeros in 5 bytes.
+		let encoded = [4, 0xff, 0xff, 0xff, 0x7f];
+		assert!(Rle::<BitVec<u8, Msb0>>::decode_with_mem_limit(&mut &encoded[..], 1024).is_err());
+	}
+}
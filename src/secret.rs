@@ -0,0 +1,147 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of secrets into zeroized memory.
+
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+	codec::WrapperTypeEncode, encode_like::EncodeLike, Decode, DecodeWithMemTracking, Error, Input,
+};
+
+/// Decode a secret, e.g. a private key, without copying it through intermediate buffers.
+///
+/// Unlike [`Decode`], which may copy the data through intermediate buffers and branch on its
+/// value, implementations read the secret straight into a destination provided by the caller with
+/// [`Input::read`] and don't inspect it. A partially decoded value is zeroized if reading fails.
+///
+/// Only [`decode_secret_into`](Self::decode_secret_into) decodes in place, so it leaves no copy
+/// behind if `dest` is the final location of the secret, e.g. in a `Box`. [`Zeroizing<T>`]
+/// implements [`Decode`] using [`decode_secret`](Self::decode_secret), which returns the secret by
+/// value: moving it may leave copies on the stack that aren't zeroized. It can be used as the type
+/// of a field when deriving `Decode`. Newtypes around secrets can implement the trait by
+/// forwarding to the implementation of the wrapped type:
+///
+/// ```
+/// use parity_scale_codec::{decode_prefix, DecodeSecret, Error, Input};
+/// use zeroize::{Zeroize, Zeroizing};
+///
+/// struct SecretKey([u8; 64]);
+///
+/// impl Zeroize for SecretKey {
+///     fn zeroize(&mut self) {
+///         self.0.zeroize()
+///     }
+/// }
+///
+/// impl DecodeSecret for SecretKey {
+///     fn zeroed() -> Self {
+///         SecretKey([0; 64])
+///     }
+///
+///     fn decode_secret_into<I: Input>(input: &mut I, dest: &mut Self) -> Result<(), Error> {
+///         <[u8; 64]>::decode_secret_into(input, &mut dest.0)
+///     }
+/// }
+///
+/// let (key, _) = decode_prefix::<Zeroizing<SecretKey>>(&[7u8; 64]).unwrap();
+/// assert_eq!(key.0, [7; 64]);
+///
+/// // Decode in place, on the heap, into memory that is zeroized when dropped.
+/// let mut key = Box::new(Zeroizing::new(SecretKey::zeroed()));
+/// SecretKey::decode_secret_into(&mut &[7u8; 64][..], &mut key).unwrap();
+/// assert_eq!(key.0, [7; 64]);
+/// ```
+pub trait DecodeSecret: Zeroize + Sized {
+	/// The value that the secret is decoded into, e.g. all zeros.
+	fn zeroed() -> Self;
+
+	/// Decode the secret from `input` into `dest`, which is [`zeroed`](Self::zeroed).
+	///
+	/// On error, `dest` may contain a partially decoded secret. It is zeroized by the caller.
+	fn decode_secret_into<I: Input>(input: &mut I, dest: &mut Self) -> Result<(), Error>;
+
+	/// Decode the secret from `input`, wrapped into [`Zeroizing`] so it is zeroized when dropped.
+	///
+	/// The secret is returned by value, so moving it may leave copies behind, see
+	/// [`decode_secret_into`](Self::decode_secret_into) to decode in place.
+	fn decode_secret<I: Input>(input: &mut I) -> Result<Zeroizing<Self>, Error> {
+		let mut secret = Zeroizing::new(Self::zeroed());
+		Self::decode_secret_into(input, &mut secret)?;
+		Ok(secret)
+	}
+}
+
+impl<const N: usize> DecodeSecret for [u8; N] {
+	fn zeroed() -> Self {
+		[0; N]
+	}
+
+	fn decode_secret_into<I: Input>(input: &mut I, dest: &mut Self) -> Result<(), Error> {
+		input.read(&mut dest[..])
+	}
+}
+
+impl<T: Zeroize> WrapperTypeEncode for Zeroizing<T> {}
+impl<T: Zeroize + crate::Encode> EncodeLike for Zeroizing<T> {}
+impl<T: Zeroize + crate::Encode> EncodeLike<T> for Zeroizing<T> {}
+
+impl<T: DecodeSecret> Decode for Zeroizing<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		T::decode_secret(input)
+	}
+}
+
+impl<T: DecodeSecret + DecodeWithMemTracking> DecodeWithMemTracking for Zeroizing<T> {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: Zeroize + crate::MaxEncodedLen> crate::MaxEncodedLen for Zeroizing<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{DecodeWithMemLimit, Encode};
+
+	#[test]
+	fn decode_secret_array() {
+		let encoded = [1u8, 2, 3, 4, 5];
+		let mut input = &encoded[..];
+		let secret = <[u8; 4]>::decode_secret(&mut input).unwrap();
+		assert_eq!(*secret, [1, 2, 3, 4]);
+		assert_eq!(input, &[5]);
+
+		assert_eq!(secret.encode(), [1, 2, 3, 4]);
+		assert_eq!(Zeroizing::<[u8; 4]>::decode(&mut &encoded[..]).unwrap(), secret);
+		assert_eq!(
+			Zeroizing::<[u8; 4]>::decode_with_mem_limit(&mut &encoded[..], 1).unwrap(),
+			secret
+		);
+
+		assert_eq!(
+			<[u8; 8]>::decode_secret(&mut &encoded[..]),
+			Err("Not enough data to fill buffer".into())
+		);
+	}
+
+	#[test]
+	fn decode_secret_in_tuple() {
+		let encoded = (1u16, [9u8; 32], 2u16).encode();
+		let (a, secret, b) = <(u16, Zeroizing<[u8; 32]>, u16)>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!((a, *secret, b), (1, [9; 32], 2));
+	}
+}
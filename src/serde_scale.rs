@@ -0,0 +1,714 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A serde [`Serializer`] and [`Deserializer`] producing and consuming SCALE.
+//!
+//! Types which only implement `serde::Serialize` and `serde::Deserialize` can be transported
+//! over SCALE, with the same encoding as if `Encode` and `Decode` were derived for them:
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use parity_scale_codec::{serde_scale, Decode, Encode};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Transfer {
+//!     to: [u8; 4],
+//!     amount: u64,
+//!     memo: Option<String>,
+//! }
+//!
+//! let transfer = Transfer { to: [1, 2, 3, 4], amount: 100, memo: Some("rent".into()) };
+//! let encoded = serde_scale::to_vec(&transfer).unwrap();
+//! assert_eq!(encoded, ([1u8, 2, 3, 4], 100u64, Some(String::from("rent"))).encode());
+//! assert_eq!(serde_scale::from_slice::<Transfer>(&encoded).unwrap(), transfer);
+//! ```
+//!
+//! The data model of serde is mapped as follows:
+//!
+//! - Booleans, integers and floats are encoded like the respective Rust types. A `char` is encoded
+//!   like its `u32` value.
+//! - Strings and byte arrays are encoded like `String` and `Vec<u8>`.
+//! - Options are encoded like `Option<T>`. Unlike `Encode`, `Option<bool>` is not encoded as a
+//!   single byte.
+//! - Sequences and maps are encoded like `Vec<T>` and `BTreeMap<K, V>`: their length followed by
+//!   their elements. Their length must be known when serializing them.
+//! - Units, unit structs, newtype structs, tuples, tuple structs and structs are encoded as the
+//!   concatenation of their fields, without names.
+//! - Enum variants are encoded as their index as `u8`, followed by their fields. The index of a
+//!   variant is its position in the enum, like the default index of derived `Encode`.
+//!
+//! SCALE is not self-describing, so `Deserializer::deserialize_any` is not supported. This
+//! excludes e.g. untagged enums and flattened structs. Borrowed strings and byte slices can't be
+//! deserialized, use `String` and `Vec<u8>` instead.
+
+use core::fmt::Display;
+
+use serde::{de, ser, Serialize};
+
+use crate::{
+	alloc::{string::String, vec::Vec},
+	codec::{decode_len, encode_len_to},
+	decode_all::DECODE_ALL_ERR_MSG,
+	Decode, Encode, Error, Input, Output,
+};
+
+impl ser::Error for Error {
+	fn custom<T: Display>(msg: T) -> Self {
+		Error::from("Could not serialize with serde").chain(msg.to_string())
+	}
+}
+
+impl de::Error for Error {
+	fn custom<T: Display>(msg: T) -> Self {
+		Error::from("Could not deserialize with serde").chain(msg.to_string())
+	}
+}
+
+/// Serialize `value` to a new `Vec<u8>`.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+	let mut dest = Vec::new();
+	to_output(value, &mut dest)?;
+	Ok(dest)
+}
+
+/// Serialize `value` to `dest`.
+///
+/// On error, part of the value may have been written to `dest` already.
+pub fn to_output<T: Serialize + ?Sized, W: Output + ?Sized>(
+	value: &T,
+	dest: &mut W,
+) -> Result<(), Error> {
+	value.serialize(&mut Serializer::new(dest))
+}
+
+/// Deserialize a `T` from `bytes`, which must be consumed completely.
+pub fn from_slice<T: de::DeserializeOwned>(mut bytes: &[u8]) -> Result<T, Error> {
+	let value = from_input(&mut bytes)?;
+	if !bytes.is_empty() {
+		return Err(DECODE_ALL_ERR_MSG.into());
+	}
+	Ok(value)
+}
+
+/// Deserialize a `T` from `input`.
+pub fn from_input<T: de::DeserializeOwned, I: Input>(input: &mut I) -> Result<T, Error> {
+	T::deserialize(&mut Deserializer::new(input))
+}
+
+/// A serde serializer writing SCALE to an [`Output`], see the [module docs](self).
+pub struct Serializer<'a, W: Output + ?Sized> {
+	dest: &'a mut W,
+}
+
+impl<'a, W: Output + ?Sized> Serializer<'a, W> {
+	/// Create a serializer writing to `dest`.
+	pub fn new(dest: &'a mut W) -> Self {
+		Serializer { dest }
+	}
+
+	fn variant_index(&mut self, variant_index: u32) -> Result<(), Error> {
+		let index = u8::try_from(variant_index)
+			.map_err(|_| Error::from("Enums can have at most 256 variants"))?;
+		self.dest.push_byte(index);
+		Ok(())
+	}
+
+	fn len(&mut self, len: Option<usize>) -> Result<(), Error> {
+		let len = len.ok_or("The length of sequences and maps must be known")?;
+		encode_len_to(self.dest, len);
+		Ok(())
+	}
+}
+
+impl<'a, 'b, W: Output + ?Sized> ser::Serializer for &'b mut Serializer<'a, W> {
+	type Ok = ();
+	type Error = Error;
+	type SerializeSeq = Self;
+	type SerializeTuple = Self;
+	type SerializeTupleStruct = Self;
+	type SerializeTupleVariant = Self;
+	type SerializeMap = Self;
+	type SerializeStruct = Self;
+	type SerializeStructVariant = Self;
+
+	fn serialize_bool(self, v: bool) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_i8(self, v: i8) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_i16(self, v: i16) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_i32(self, v: i32) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_i64(self, v: i64) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_i128(self, v: i128) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_u8(self, v: u8) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_u16(self, v: u16) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_u32(self, v: u32) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_u64(self, v: u64) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_u128(self, v: u128) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_f32(self, v: f32) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_f64(self, v: f64) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_char(self, v: char) -> Result<(), Error> {
+		u32::from(v).encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_str(self, v: &str) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+		v.encode_to(self.dest);
+		Ok(())
+	}
+
+	fn serialize_none(self) -> Result<(), Error> {
+		self.dest.push_byte(0);
+		Ok(())
+	}
+
+	fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+		self.dest.push_byte(1);
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<(), Error> {
+		Ok(())
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+		Ok(())
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		variant_index: u32,
+		_variant: &'static str,
+	) -> Result<(), Error> {
+		self.variant_index(variant_index)
+	}
+
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> Result<(), Error> {
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(
+		self,
+		_name: &'static str,
+		variant_index: u32,
+		_variant: &'static str,
+		value: &T,
+	) -> Result<(), Error> {
+		self.variant_index(variant_index)?;
+		value.serialize(self)
+	}
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self, Error> {
+		self.len(len)?;
+		Ok(self)
+	}
+
+	fn serialize_tuple(self, _len: usize) -> Result<Self, Error> {
+		Ok(self)
+	}
+
+	fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+		Ok(self)
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> Result<Self, Error> {
+		self.variant_index(variant_index)?;
+		Ok(self)
+	}
+
+	fn serialize_map(self, len: Option<usize>) -> Result<Self, Error> {
+		self.len(len)?;
+		Ok(self)
+	}
+
+	fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+		Ok(self)
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> Result<Self, Error> {
+		self.variant_index(variant_index)?;
+		Ok(self)
+	}
+
+	fn is_human_readable(&self) -> bool {
+		false
+	}
+}
+
+/// Implement the traits serializing the elements of compound types, which are all concatenated.
+macro_rules! impl_serialize_compound {
+	( $( $trait:ident :: $method:ident ( $( $key:ident )? ) ),* $(,)? ) => {
+		$(
+			impl<'a, 'b, W: Output + ?Sized> ser::$trait for &'b mut Serializer<'a, W> {
+				type Ok = ();
+				type Error = Error;
+
+				fn $method<T: Serialize + ?Sized>(
+					&mut self,
+					$( $key: &'static str, )?
+					value: &T,
+				) -> Result<(), Error> {
+					$( let _ = $key; )?
+					value.serialize(&mut **self)
+				}
+
+				fn end(self) -> Result<(), Error> {
+					Ok(())
+				}
+			}
+		)*
+	};
+}
+
+impl_serialize_compound!(
+	SerializeSeq::serialize_element(),
+	SerializeTuple::serialize_element(),
+	SerializeTupleStruct::serialize_field(),
+	SerializeTupleVariant::serialize_field(),
+	SerializeStruct::serialize_field(key),
+	SerializeStructVariant::serialize_field(key),
+);
+
+impl<'a, 'b, W: Output + ?Sized> ser::SerializeMap for &'b mut Serializer<'a, W> {
+	type Ok = ();
+	type Error = Error;
+
+	fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+		key.serialize(&mut **self)
+	}
+
+	fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+		value.serialize(&mut **self)
+	}
+
+	fn end(self) -> Result<(), Error> {
+		Ok(())
+	}
+}
+
+/// A serde deserializer reading SCALE from an [`Input`], see the [module docs](self).
+pub struct Deserializer<'a, I: Input> {
+	input: &'a mut I,
+}
+
+impl<'a, I: Input> Deserializer<'a, I> {
+	/// Create a deserializer reading from `input`.
+	pub fn new(input: &'a mut I) -> Self {
+		Deserializer { input }
+	}
+
+	/// Visit the `len` elements of a sequence, tuple or struct, or the `len` entries of a map.
+	fn visit_elements<'de, V: de::Visitor<'de>>(
+		&mut self,
+		len: usize,
+		map: bool,
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		self.input.descend_ref()?;
+		let mut access = Access { de: self, len };
+		let value =
+			if map { visitor.visit_map(&mut access)? } else { visitor.visit_seq(&mut access)? };
+		if access.len != 0 {
+			return Err("Not all elements were deserialized".into());
+		}
+		self.input.ascend_ref();
+		Ok(value)
+	}
+}
+
+/// Forward the deserialization of primitive types to their `Decode` implementations.
+macro_rules! forward_to_decode {
+	( $( $method:ident => $visit:ident : $ty:ty ),* $(,)? ) => {
+		$(
+			fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+				visitor.$visit(<$ty>::decode(self.input)?)
+			}
+		)*
+	};
+}
+
+impl<'de, 'a, 'b, I: Input> de::Deserializer<'de> for &'b mut Deserializer<'a, I> {
+	type Error = Error;
+
+	forward_to_decode!(
+		deserialize_bool => visit_bool: bool,
+		deserialize_i8 => visit_i8: i8,
+		deserialize_i16 => visit_i16: i16,
+		deserialize_i32 => visit_i32: i32,
+		deserialize_i64 => visit_i64: i64,
+		deserialize_i128 => visit_i128: i128,
+		deserialize_u8 => visit_u8: u8,
+		deserialize_u16 => visit_u16: u16,
+		deserialize_u32 => visit_u32: u32,
+		deserialize_u64 => visit_u64: u64,
+		deserialize_u128 => visit_u128: u128,
+		deserialize_f32 => visit_f32: f32,
+		deserialize_f64 => visit_f64: f64,
+		deserialize_str => visit_string: String,
+		deserialize_string => visit_string: String,
+		deserialize_bytes => visit_byte_buf: Vec<u8>,
+		deserialize_byte_buf => visit_byte_buf: Vec<u8>,
+	);
+
+	fn deserialize_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+		Err("SCALE is not self-describing, `deserialize_any` is not supported".into())
+	}
+
+	fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+		let c = char::from_u32(u32::decode(self.input)?).ok_or("Invalid `char` representation")?;
+		visitor.visit_char(c)
+	}
+
+	fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.input.read_byte()? {
+			0 => visitor.visit_none(),
+			1 => visitor.visit_some(self),
+			_ => Err("unexpected first byte decoding Option".into()),
+		}
+	}
+
+	fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+		visitor.visit_unit()
+	}
+
+	fn deserialize_unit_struct<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		visitor.visit_unit()
+	}
+
+	fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		visitor.visit_newtype_struct(self)
+	}
+
+	fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+		let len = decode_len(self.input)?;
+		self.visit_elements(len, false, visitor)
+	}
+
+	fn deserialize_tuple<V: de::Visitor<'de>>(
+		self,
+		len: usize,
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		self.visit_elements(len, false, visitor)
+	}
+
+	fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		len: usize,
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		self.visit_elements(len, false, visitor)
+	}
+
+	fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+		let len = decode_len(self.input)?;
+		self.visit_elements(len, true, visitor)
+	}
+
+	fn deserialize_struct<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		self.visit_elements(fields.len(), false, visitor)
+	}
+
+	fn deserialize_enum<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		visitor.visit_enum(self)
+	}
+
+	fn deserialize_identifier<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+		Err("SCALE doesn't encode identifiers".into())
+	}
+
+	fn deserialize_ignored_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+		Err("SCALE is not self-describing, values can't be ignored".into())
+	}
+
+	fn is_human_readable(&self) -> bool {
+		false
+	}
+}
+
+/// Gives access to the remaining `len` elements or entries of a compound type.
+struct Access<'b, 'a, I: Input> {
+	de: &'b mut Deserializer<'a, I>,
+	len: usize,
+}
+
+impl<'de, 'b, 'a, I: Input> de::SeqAccess<'de> for Access<'b, 'a, I> {
+	type Error = Error;
+
+	fn next_element_seed<T: de::DeserializeSeed<'de>>(
+		&mut self,
+		seed: T,
+	) -> Result<Option<T::Value>, Error> {
+		if self.len == 0 {
+			return Ok(None);
+		}
+		self.len -= 1;
+		seed.deserialize(&mut *self.de).map(Some)
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.len)
+	}
+}
+
+impl<'de, 'b, 'a, I: Input> de::MapAccess<'de> for Access<'b, 'a, I> {
+	type Error = Error;
+
+	fn next_key_seed<K: de::DeserializeSeed<'de>>(
+		&mut self,
+		seed: K,
+	) -> Result<Option<K::Value>, Error> {
+		if self.len == 0 {
+			return Ok(None);
+		}
+		self.len -= 1;
+		seed.deserialize(&mut *self.de).map(Some)
+	}
+
+	fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+		seed.deserialize(&mut *self.de)
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.len)
+	}
+}
+
+impl<'de, 'a, 'b, I: Input> de::EnumAccess<'de> for &'b mut Deserializer<'a, I> {
+	type Error = Error;
+	type Variant = Self;
+
+	fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+		use de::IntoDeserializer;
+
+		let index = self
+			.input
+			.read_byte()
+			.map_err(|e| e.chain("Could not decode the variant index"))?;
+		let index: de::value::U32Deserializer<Error> = u32::from(index).into_deserializer();
+		let variant = seed.deserialize(index)?;
+		Ok((variant, self))
+	}
+}
+
+impl<'de, 'a, 'b, I: Input> de::VariantAccess<'de> for &'b mut Deserializer<'a, I> {
+	type Error = Error;
+
+	fn unit_variant(self) -> Result<(), Error> {
+		Ok(())
+	}
+
+	fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+		seed.deserialize(self)
+	}
+
+	fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+		self.visit_elements(len, false, visitor)
+	}
+
+	fn struct_variant<V: de::Visitor<'de>>(
+		self,
+		fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Error> {
+		self.visit_elements(fields.len(), false, visitor)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		alloc::collections::BTreeMap, CodecConfig, Compact, ConfiguredInput, ConfiguredOutput,
+		LengthPrefix,
+	};
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	enum Event {
+		Ping,
+		Data(Vec<u8>),
+		Moved(i32, i32),
+		Named { id: u64, name: String, tags: BTreeMap<u8, bool> },
+	}
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	struct Envelope {
+		version: u16,
+		events: Vec<Event>,
+		reply_to: Option<(u32, u128)>,
+		unit: (),
+	}
+
+	#[test]
+	fn serde_scale_matches_derived_encoding() {
+		let tags: BTreeMap<u8, bool> = [(1, true), (2, false)].into_iter().collect();
+		let value = Envelope {
+			version: 3,
+			events: vec![
+				Event::Ping,
+				Event::Data(vec![1, 2, 3]),
+				Event::Moved(-1, i32::MAX),
+				Event::Named { id: u64::MAX, name: "name".into(), tags: tags.clone() },
+			],
+			reply_to: Some((7, 1 << 100)),
+			unit: (),
+		};
+
+		// The encoding `#[derive(Encode)]` would produce.
+		let mut expected = 3u16.encode();
+		Compact(4u32).encode_to(&mut expected);
+		expected.push(0);
+		(1u8, vec![1u8, 2, 3]).encode_to(&mut expected);
+		(2u8, -1i32, i32::MAX).encode_to(&mut expected);
+		(3u8, u64::MAX, "name", &tags).encode_to(&mut expected);
+		Some((7u32, 1u128 << 100)).encode_to(&mut expected);
+
+		let encoded = to_vec(&value).unwrap();
+		assert_eq!(encoded, expected);
+		assert_eq!(from_slice::<Envelope>(&encoded).unwrap(), value);
+	}
+
+	#[test]
+	fn serde_scale_primitives() {
+		assert_eq!(to_vec(&'a').unwrap(), 97u32.encode());
+		assert_eq!(from_slice::<char>(&97u32.encode()).unwrap(), 'a');
+		assert!(from_slice::<char>(&0xd800u32.encode()).is_err());
+		assert_eq!(from_slice::<(f32, f64)>(&(1.5f32, -2.5f64).encode()).unwrap(), (1.5, -2.5));
+		assert_eq!(from_slice::<bool>(&[2]), Err("Invalid boolean representation".into()));
+		assert_eq!(from_slice::<u8>(&[1, 2]), Err(DECODE_ALL_ERR_MSG.into()));
+	}
+
+	#[test]
+	fn serde_scale_honors_config() {
+		let config = CodecConfig::DEFAULT.with_length_prefix(LengthPrefix::FixedU32LE);
+		let value = vec![String::from("a")];
+
+		let mut encoded = Vec::new();
+		to_output(&value, &mut ConfiguredOutput::new(&mut encoded, config)).unwrap();
+		assert_eq!(encoded, [1, 0, 0, 0, 1, 0, 0, 0, b'a']);
+
+		let mut bytes = &encoded[..];
+		let mut input = ConfiguredInput::new(&mut bytes, config);
+		assert_eq!(from_input::<Vec<String>, _>(&mut input).unwrap(), value);
+	}
+
+	#[test]
+	fn serde_scale_rejects_unsupported() {
+		struct UnknownLength;
+
+		impl Serialize for UnknownLength {
+			fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				use ser::SerializeSeq;
+				serializer.serialize_seq(None)?.end()
+			}
+		}
+
+		assert_eq!(
+			to_vec(&UnknownLength),
+			Err("The length of sequences and maps must be known".into())
+		);
+		assert!(from_slice::<Event>(&[4]).is_err());
+		assert!(from_slice::<de::IgnoredAny>(&[0]).is_err());
+	}
+}
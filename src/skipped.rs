@@ -0,0 +1,127 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+	codec::{Decode, Encode, Input, Output},
+	encode_like::EncodeLike,
+	DecodeWithMemTracking, Error,
+};
+
+/// A value that is not encoded.
+///
+/// Encoding writes nothing and decoding returns `T::default()`. This is the equivalent of the
+/// `#[codec(skip)]` field attribute of the derive macros, for places where attributes are not
+/// available, e.g. in manual implementations or as a generic argument.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, Skipped};
+/// let value = (1u8, Skipped(vec![2u8, 3]), 4u8);
+/// assert_eq!(value.encode(), vec![1, 4]);
+///
+/// let (decoded, _) = decode_prefix::<(u8, Skipped<Vec<u8>>, u8)>(&[1, 4]).unwrap();
+/// assert_eq!(decoded, (1, Skipped(Vec::new()), 4));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Skipped<T>(pub T);
+
+impl<T> Deref for Skipped<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for Skipped<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<T> From<T> for Skipped<T> {
+	fn from(x: T) -> Self {
+		Skipped(x)
+	}
+}
+
+impl<T> Encode for Skipped<T> {
+	fn size_hint(&self) -> usize {
+		0
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, _dest: &mut W) {}
+
+	fn encoded_size(&self) -> usize {
+		0
+	}
+}
+
+impl<T> EncodeLike for Skipped<T> {}
+
+impl<T: Default> Decode for Skipped<T> {
+	fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+		Ok(Skipped(T::default()))
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(0)
+	}
+}
+
+impl<T: Default> DecodeWithMemTracking for Skipped<T> {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T> crate::MaxEncodedLen for Skipped<T> {
+	fn max_encoded_len() -> usize {
+		0
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T> crate::ConstEncodedLen for Skipped<T> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		alloc::{string::String, vec::Vec},
+		decode_prefix,
+	};
+
+	#[test]
+	fn skipped_is_not_encoded() {
+		let value = Skipped(String::from("not on the wire"));
+		assert!(value.encode().is_empty());
+		assert_eq!(value.encoded_size(), 0);
+
+		let encoded = (1u32, value, vec![Skipped(5u8); 3]).encode();
+		assert_eq!(encoded, (1u32, vec![(); 3]).encode());
+
+		let (decoded, rest) =
+			decode_prefix::<(u32, Skipped<String>, Vec<Skipped<u8>>)>(&encoded).unwrap();
+		assert!(rest.is_empty());
+		assert_eq!(decoded, (1, Skipped(String::new()), vec![Skipped(0); 3]));
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn skipped_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(Skipped::<u64>::max_encoded_len(), 0);
+		assert_eq!(<(u8, Skipped<u64>)>::max_encoded_len(), 1);
+	}
+}
@@ -0,0 +1,285 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hand-written encoders of structs which can only encode the fields in order, see
+//! [`scale_struct_encoder`](crate::scale_struct_encoder).
+
+use crate::{Encode, Output};
+
+/// Where the fields of a struct encoder go: an output or a size hint.
+pub trait FieldSink {
+	/// The result of the encoder, once all fields are encoded.
+	type Finished;
+
+	/// Add the next field.
+	fn field<T: Encode + ?Sized>(&mut self, value: &T);
+
+	/// The result, after all fields were added.
+	fn finish(self) -> Self::Finished;
+}
+
+/// A [`FieldSink`] encoding the fields to an output.
+pub struct Writer<'a, W: Output + ?Sized>(pub &'a mut W);
+
+impl<W: Output + ?Sized> FieldSink for Writer<'_, W> {
+	type Finished = ();
+
+	fn field<T: Encode + ?Sized>(&mut self, value: &T) {
+		value.encode_to(self.0)
+	}
+
+	fn finish(self) {}
+}
+
+/// A [`FieldSink`] adding up the size hints of the fields.
+#[derive(Default)]
+pub struct SizeHint(pub usize);
+
+impl FieldSink for SizeHint {
+	type Finished = usize;
+
+	fn field<T: Encode + ?Sized>(&mut self, value: &T) {
+		self.0 += value.size_hint();
+	}
+
+	fn finish(self) -> usize {
+		self.0
+	}
+}
+
+/// A struct declared in [`scale_struct_encoder`](crate::scale_struct_encoder), which creates the
+/// encoder of its fields.
+pub trait StructEncoder {
+	/// The encoder of the fields, before the first field is encoded.
+	type Encoder<S: FieldSink>;
+
+	/// Create the encoder of the fields, adding them to `sink`.
+	fn encoder<S: FieldSink>(sink: S) -> Self::Encoder<S>;
+}
+
+/// Declare a struct together with an encoder of its fields, which only compiles if the fields are
+/// encoded in the declared order.
+///
+/// A hand-written `Encode` implementation can encode fields in the wrong order, or forget one,
+/// e.g. when the struct changes. The macro takes the struct definition, preceded by the name of
+/// the encoder, and emits the struct unchanged. The encoder has a method for each field, which is
+/// only available after the previous fields were encoded, and `finish` is only available after
+/// all of them were. Each method takes a value which encodes like the field, see `EncodeLike`.
+/// A field encoded as another type is declared with `field: Type as EncodedType`.
+///
+/// The struct implements [`StructEncoder`], whose `encoder` creates the encoder from a
+/// [`Writer`] to encode to an output, or from a [`SizeHint`] to add up the size hints of the
+/// fields, for `Encode::size_hint`:
+///
+/// ```
+/// use parity_scale_codec::{
+///     scale_struct_encoder,
+///     struct_encoder::{SizeHint, StructEncoder, Writer},
+///     Compact, Encode, Output,
+/// };
+///
+/// scale_struct_encoder! {
+///     /// Encoder of the fields of `Header`.
+///     encoder HeaderEncoder;
+///
+///     /// A block header.
+///     pub struct Header {
+///         pub number: u64 as Compact<u64>,
+///         parent: [u8; 4],
+///         digest: Vec<u8>,
+///     }
+/// }
+///
+/// impl Encode for Header {
+///     fn size_hint(&self) -> usize {
+///         Self::encoder(SizeHint::default())
+///             .number(&Compact(self.number))
+///             .parent(&self.parent)
+///             .digest(&self.digest)
+///             .finish()
+///     }
+///
+///     fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+///         Self::encoder(Writer(dest))
+///             .number(&Compact(self.number))
+///             .parent(&self.parent)
+///             .digest(&self.digest)
+///             .finish()
+///     }
+/// }
+///
+/// let header = Header { number: 1, parent: [2; 4], digest: vec![3] };
+/// assert_eq!(header.encode(), (Compact(1u64), [2u8; 4], vec![3u8]).encode());
+/// ```
+///
+/// Encoding the fields out of order doesn't compile:
+///
+/// ```compile_fail
+/// # use parity_scale_codec::{scale_struct_encoder, struct_encoder::{StructEncoder, Writer}};
+/// scale_struct_encoder! {
+///     encoder PairEncoder;
+///     struct Pair { a: u8, b: u16 }
+/// }
+///
+/// let mut dest = Vec::new();
+/// Pair::encoder(Writer(&mut dest)).b(&2u16).a(&1u8).finish();
+/// ```
+///
+/// Only structs with named fields and without generics are supported.
+#[macro_export]
+macro_rules! scale_struct_encoder {
+	(
+		$( #[$enc_attr:meta] )*
+		encoder $encoder:ident;
+
+		$( #[$attr:meta] )*
+		$vis:vis struct $name:ident {
+			$(
+				$( #[$field_attr:meta] )*
+				$field_vis:vis $field:ident : $ty:ty $( as $encoded:ty )?
+			),* $(,)?
+		}
+	) => {
+		$( #[$attr] )*
+		$vis struct $name {
+			$(
+				$( #[$field_attr] )*
+				$field_vis $field: $ty,
+			)*
+		}
+
+		$( #[$enc_attr] )*
+		#[must_use = "all fields must be encoded, followed by `finish`"]
+		$vis struct $encoder<S, const FIELD: usize> {
+			sink: S,
+		}
+
+		impl $crate::struct_encoder::StructEncoder for $name {
+			type Encoder<S: $crate::struct_encoder::FieldSink> = $encoder<S, 0>;
+
+			fn encoder<S: $crate::struct_encoder::FieldSink>(sink: S) -> $encoder<S, 0> {
+				$encoder { sink }
+			}
+		}
+
+		$crate::scale_struct_encoder!(
+			@field $vis $encoder [0] $( $field: [$ty $( , $encoded )?], )*
+		);
+	};
+	(@encoded $ty:ty) => { $ty };
+	(@encoded $ty:ty, $encoded:ty) => { $encoded };
+	(@field $vis:vis $name:ident [$( $step:tt )*] $field:ident: [$( $ty:tt )*], $( $rest:tt )*) => {
+		impl<S: $crate::struct_encoder::FieldSink> $name<S, { $( $step )* }> {
+			#[doc = ::core::concat!("Encode the field `", ::core::stringify!($field), "`.")]
+			#[allow(clippy::wrong_self_convention)]
+			$vis fn $field<T>(mut self, value: &T) -> $name<S, { $( $step )* + 1 }>
+			where
+				T: $crate::EncodeLike<$crate::scale_struct_encoder!(@encoded $( $ty )*)> + ?Sized,
+			{
+				$crate::struct_encoder::FieldSink::field(&mut self.sink, value);
+				$name { sink: self.sink }
+			}
+		}
+
+		$crate::scale_struct_encoder!(@field $vis $name [$( $step )* + 1] $( $rest )*);
+	};
+	(@field $vis:vis $name:ident [$( $step:tt )*]) => {
+		impl<S: $crate::struct_encoder::FieldSink> $name<S, { $( $step )* }> {
+			/// Finish the encoding, after all fields were encoded.
+			$vis fn finish(self) -> S::Finished {
+				$crate::struct_encoder::FieldSink::finish(self.sink)
+			}
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{SizeHint, StructEncoder, Writer};
+	use crate::{alloc::vec::Vec, Compact, Encode, Output};
+
+	scale_struct_encoder! {
+		encoder TransferEncoder;
+
+		#[derive(Debug, PartialEq)]
+		struct Transfer {
+			amount: u128 as Compact<u128>,
+			memo: Vec<u8>,
+			nonce: u32,
+		}
+	}
+
+	impl Encode for Transfer {
+		fn size_hint(&self) -> usize {
+			Self::encoder(SizeHint::default())
+				.amount(&Compact(self.amount))
+				.memo(&self.memo)
+				.nonce(&self.nonce)
+				.finish()
+		}
+
+		fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+			Self::encoder(Writer(dest))
+				.amount(&Compact(self.amount))
+				.memo(&self.memo)
+				.nonce(&self.nonce)
+				.finish()
+		}
+	}
+
+	#[test]
+	fn struct_encoder_encodes_fields_in_order() {
+		let transfer = Transfer { amount: 1 << 40, memo: vec![1, 2], nonce: 7 };
+		let expected = (Compact(1u128 << 40), vec![1u8, 2], 7u32).encode();
+		assert_eq!(transfer.encode(), expected);
+		let size_hint = Compact(transfer.amount).size_hint() + transfer.memo.size_hint() + 4;
+		assert_eq!(transfer.size_hint(), size_hint);
+	}
+
+	scale_struct_encoder! {
+		encoder MethodNamesEncoder;
+
+		struct MethodNames {
+			new: u8,
+			size_hint: u16,
+			finish: u32,
+		}
+	}
+
+	#[test]
+	fn struct_encoder_fields_can_be_named_like_methods() {
+		let value = MethodNames { new: 1, size_hint: 2, finish: 3 };
+		let mut dest = Vec::new();
+		MethodNames::encoder(Writer(&mut dest))
+			.new(&value.new)
+			.size_hint(&value.size_hint)
+			.finish(&value.finish)
+			.finish();
+		assert_eq!(dest, (1u8, 2u16, 3u32).encode());
+	}
+
+	scale_struct_encoder! {
+		encoder EmptyEncoder;
+
+		struct Empty {}
+	}
+
+	#[test]
+	fn struct_encoder_without_fields_works() {
+		let mut dest = Vec::new();
+		Empty::encoder(Writer(&mut dest)).finish();
+		assert!(dest.is_empty());
+		assert_eq!(Empty::encoder(SizeHint::default()).finish(), 0);
+	}
+}
@@ -0,0 +1,264 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding of values of one of several registered types, see [`register_scale_type`].
+
+/// A type encoding values of one of several types, with a tag telling which type it is.
+///
+/// It is implemented by the enums generated by [`register_scale_type`].
+pub trait TaggedCodec: crate::Encode + crate::Decode {
+	/// The tags of the registered types, in the order of registration.
+	const TAGS: &'static [u8];
+
+	/// The tag of the type of the value, which is the first byte of its encoding.
+	fn tag(&self) -> u8;
+}
+
+/// Generate an enum with a variant for each of a set of types, which is encoded as the tag of
+/// the type followed by the encoding of the value.
+///
+/// This replaces matching on hand-written tags to encode and decode values of different types,
+/// e.g. the messages of plugins which are stored together. Each type is registered with a `u8` tag,
+/// followed by the name of the variant and, if it is different, the type:
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// # use parity_scale_codec::{decode_prefix, register_scale_type, Decode, Encode, TaggedCodec};
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// pub struct Ping(u32);
+///
+/// register_scale_type! {
+///     /// A message of any plugin.
+///     #[derive(Debug, PartialEq)]
+///     pub enum Message {
+///         0 => Ping,
+///         1 => Text(String),
+///     }
+/// }
+///
+/// let messages: Vec<Message> = vec![Ping(7).into(), Message::Text("hi".into())];
+/// let encoded = messages.encode();
+/// assert_eq!(encoded[1..6], [0, 7, 0, 0, 0]);
+/// assert_eq!(decode_prefix::<Vec<Message>>(&encoded).unwrap().0, messages);
+/// assert_eq!(messages[1].tag(), 1);
+/// assert!(decode_prefix::<Message>(&[2]).is_err());
+/// # }
+/// ```
+///
+/// The enum implements `Encode`, `EncodeLike`, `Decode` and [`TaggedCodec`], as well as
+/// `DecodeWithMemTracking` if all the types implement it. It implements `From` each of the types
+/// registered by name, like `Ping` above, but not the types given in parentheses, which may be the
+/// same for several variants. Using a tag twice is a compile error:
+///
+/// ```compile_fail
+/// # use parity_scale_codec::register_scale_type;
+/// register_scale_type! {
+///     enum Number {
+///         0 => Small(u8),
+///         0 => Large(u64),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! register_scale_type {
+	(
+		$( #[$attr:meta] )*
+		$vis:vis enum $name:ident {
+			$( $tag:literal => $variant:ident $( ( $ty:ty ) )? ),* $(,)?
+		}
+	) => {
+		$( #[$attr] )*
+		$vis enum $name {
+			$(
+				#[allow(missing_docs)]
+				$variant( $crate::register_scale_type!(@type $variant $( $ty )?) ),
+			)*
+		}
+
+		const _: () = {
+			let tags: &[u8] = &[$( $tag ),*];
+			let mut i = 0;
+			while i < tags.len() {
+				let mut j = i + 1;
+				while j < tags.len() {
+					if tags[i] == tags[j] {
+						::core::panic!("Found types that have the same tag. Use different tags.");
+					}
+					j += 1;
+				}
+				i += 1;
+			}
+		};
+
+		impl $crate::TaggedCodec for $name {
+			const TAGS: &'static [u8] = &[$( $tag ),*];
+
+			fn tag(&self) -> u8 {
+				match self {
+					$( $name::$variant(_) => $tag, )*
+				}
+			}
+		}
+
+		impl $crate::Encode for $name {
+			fn size_hint(&self) -> usize {
+				match self {
+					$( $name::$variant(value) => 1 + $crate::Encode::size_hint(value), )*
+				}
+			}
+
+			fn encode_to<W: $crate::Output + ?Sized>(&self, dest: &mut W) {
+				dest.push_byte($crate::TaggedCodec::tag(self));
+				match self {
+					$( $name::$variant(value) => $crate::Encode::encode_to(value, dest), )*
+				}
+			}
+		}
+
+		impl $crate::EncodeLike for $name {}
+
+		impl $crate::Decode for $name {
+			fn decode<I: $crate::Input>(input: &mut I) -> ::core::result::Result<Self, $crate::Error> {
+				let tag = input.read_byte().map_err(|e| {
+					e.chain(::core::concat!(
+						"Could not decode `", ::core::stringify!($name), "`, failed to read tag"
+					))
+				})?;
+				match tag {
+					$(
+						$tag => $crate::Decode::decode(input).map($name::$variant).map_err(|e| {
+							e.chain(::core::concat!(
+								"Could not decode `", ::core::stringify!($name), "::",
+								::core::stringify!($variant), "`"
+							))
+						}),
+					)*
+					_ => ::core::result::Result::Err(::core::concat!(
+						"Could not decode `", ::core::stringify!($name), "`, tag isn't registered"
+					).into()),
+				}
+			}
+		}
+
+		// The bounds are higher-ranked so they don't have to hold, as the enum isn't generic.
+		impl $crate::DecodeWithMemTracking for $name
+		where
+			$(
+				for<'__codec_a> $crate::register_scale_type!(@type $variant $( $ty )?):
+					$crate::DecodeWithMemTracking,
+			)*
+		{
+		}
+
+		$( $crate::register_scale_type!(@from $name $variant $( $ty )?); )*
+	};
+	(@type $variant:ident) => { $variant };
+	(@type $variant:ident $ty:ty) => { $ty };
+	// Only the types registered by name are converted from, as several variants may have the same
+	// type.
+	(@from $name:ident $variant:ident) => {
+		impl ::core::convert::From<$variant> for $name {
+			fn from(value: $variant) -> Self {
+				$name::$variant(value)
+			}
+		}
+	};
+	(@from $name:ident $variant:ident $ty:ty) => {};
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		alloc::vec::Vec, mem_tracking::DecodeWithMemTracking, Decode, Encode, Error, Input, Output,
+		TaggedCodec,
+	};
+
+	#[derive(Debug, PartialEq)]
+	struct Unit;
+
+	impl Encode for Unit {
+		fn encode_to<W: Output + ?Sized>(&self, _: &mut W) {}
+	}
+
+	impl Decode for Unit {
+		fn decode<I: Input>(_: &mut I) -> Result<Self, Error> {
+			Ok(Unit)
+		}
+	}
+
+	impl DecodeWithMemTracking for Unit {}
+
+	register_scale_type! {
+		#[derive(Debug, PartialEq)]
+		enum Value {
+			3 => Number(u64),
+			1 => Bytes(Vec<u8>),
+			2 => Unit,
+			4 => OtherNumber(u64),
+		}
+	}
+
+	struct NoMemTracking;
+
+	impl Encode for NoMemTracking {
+		fn encode_to<W: Output + ?Sized>(&self, _: &mut W) {}
+	}
+
+	impl Decode for NoMemTracking {
+		fn decode<I: Input>(_: &mut I) -> Result<Self, Error> {
+			Ok(NoMemTracking)
+		}
+	}
+
+	register_scale_type! {
+		enum Untracked {
+			0 => Tracked(u8),
+			1 => NotTracked(NoMemTracking),
+		}
+	}
+
+	#[test]
+	fn register_scale_type_works() {
+		assert_eq!(Value::TAGS, [3, 1, 2, 4]);
+		for (value, encoded) in [
+			(Value::Number(5), vec![3, 5, 0, 0, 0, 0, 0, 0, 0]),
+			(Value::Bytes(vec![9]), vec![1, 4, 9]),
+			(Unit.into(), vec![2]),
+			(Value::OtherNumber(6), vec![4, 6, 0, 0, 0, 0, 0, 0, 0]),
+		] {
+			assert_eq!(value.encode(), encoded);
+			assert_eq!(value.tag(), encoded[0]);
+			assert_eq!(Value::decode(&mut &encoded[..]), Ok(value));
+		}
+	}
+
+	#[test]
+	fn register_scale_type_rejects_unknown_tags() {
+		assert_eq!(
+			Value::decode(&mut &[0][..]),
+			Err(Error::from("Could not decode `Value`, tag isn't registered"))
+		);
+		assert!(Value::decode(&mut &[3, 1][..]).is_err());
+		assert!(Value::decode(&mut &[][..]).is_err());
+	}
+
+	#[test]
+	fn register_scale_type_implements_decode_with_mem_tracking() {
+		fn is_tracked<T: DecodeWithMemTracking>() {}
+		is_tracked::<Value>();
+
+		// `Untracked` doesn't implement `DecodeWithMemTracking`, but decodes.
+		assert!(matches!(Untracked::decode(&mut &[1][..]), Ok(Untracked::NotTracked(_))));
+	}
+}
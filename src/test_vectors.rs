@@ -0,0 +1,317 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks of the encoding of types against test vectors from JSON or YAML fixtures.
+//!
+//! Conformance suites shared between SCALE implementations list test vectors of a type, its
+//! encoding as hex and its value. The types of a fixture are registered with
+//! [`test_vector_registry`](crate::test_vector_registry) under the names used in the fixture,
+//! and [`Registry::check_json`] or [`Registry::check_yaml`] checks that each encoding decodes to
+//! the value and that the value encodes to the encoding:
+//!
+//! ```
+//! let registry = parity_scale_codec::test_vector_registry! {
+//!     "u32" => u32,
+//!     "Option<bool>" => Option<bool>,
+//!     "Vec<u16>" => Vec<u16>,
+//! };
+//!
+//! let fixture = r#"[
+//!     { "type": "u32", "hex": "0x2a000000", "value": 42 },
+//!     { "type": "Option<bool>", "hex": "0x0101", "value": true },
+//!     { "type": "Vec<u16>", "hex": "0x080100ffff", "value": [1, 65535] },
+//!     { "type": "Option<bool>", "hex": "0x03", "error": true }
+//! ]"#;
+//! assert_eq!(registry.check_json(fixture), Ok(4));
+//!
+//! let fixture = r#"
+//! - { type: u32, hex: "0x2a000000", value: 42 }
+//! - type: Vec<u16>
+//!   hex: "0x080100ffff"
+//!   value: [1, 65535]
+//! "#;
+//! assert_eq!(registry.check_yaml(fixture), Ok(2));
+//! ```
+//!
+//! The fixture is an array of test vectors, which are objects with:
+//! * `type`: the name of the type, as registered.
+//! * `hex`: the encoding, as hex with an optional `0x` prefix.
+//! * `value`: the value, as deserialized by `serde_json`, or
+//! * `error`: `true` if the encoding is invalid and must fail to decode, instead of `value`.
+//!
+//! A YAML fixture is read like the JSON fixture of the same data, so the values of both are
+//! deserialized the same way.
+
+use std::{collections::BTreeMap, fmt, path::Path, string::String, vec::Vec};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Decode, DecodeAll, Encode, HexInput, Input};
+
+/// Check a test vector of the encoding `encoded`, which is the encoding of `value`, or invalid if
+/// `value` is `None`.
+type Check = fn(encoded: &[u8], value: Option<&Value>) -> Result<(), String>;
+
+fn check<T>(encoded: &[u8], value: Option<&Value>) -> Result<(), String>
+where
+	T: Encode + Decode + DeserializeOwned + PartialEq + fmt::Debug,
+{
+	let decoded = T::decode_all(&mut &encoded[..]);
+	let Some(value) = value else {
+		return match decoded {
+			Ok(decoded) => Err(format!("invalid encoding decoded to {decoded:?}")),
+			Err(_) => Ok(()),
+		};
+	};
+
+	let expected = T::deserialize(value).map_err(|e| format!("invalid value: {e}"))?;
+	let decoded = decoded.map_err(|e| format!("failed to decode: {e}"))?;
+	if decoded != expected {
+		return Err(format!("decoded {decoded:?}, expected {expected:?}"));
+	}
+	let reencoded = expected.encode();
+	if reencoded != encoded {
+		return Err(format!("encoded to {}", to_hex(&reencoded)));
+	}
+	Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().fold(String::from("0x"), |hex, byte| hex + &format!("{byte:02x}"))
+}
+
+/// The bytes of `hex`, with an optional `0x` prefix.
+fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+	let hex = hex.strip_prefix("0x").unwrap_or(hex);
+	if hex.len() % 2 != 0 {
+		return Err("invalid hex: odd number of digits".into());
+	}
+	let mut bytes = vec![0; hex.len() / 2];
+	HexInput::new(hex.as_bytes())
+		.read(&mut bytes)
+		.map_err(|e| format!("invalid hex: {e}"))?;
+	Ok(bytes)
+}
+
+/// The content of the fixture at `path`.
+fn read_fixture(path: &Path) -> Result<String, Vec<Failure>> {
+	std::fs::read_to_string(path).map_err(|e| {
+		vec![Failure { vector: None, message: format!("reading {}: {e}", path.display()) }]
+	})
+}
+
+/// A failed test vector, or an invalid fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+	/// The index of the test vector in the fixture, or `None` if the fixture is invalid.
+	pub vector: Option<usize>,
+	/// What failed.
+	pub message: String,
+}
+
+impl fmt::Display for Failure {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.vector {
+			Some(vector) => write!(f, "test vector {vector}: {}", self.message),
+			None => write!(f, "invalid fixture: {}", self.message),
+		}
+	}
+}
+
+/// The types which test vectors can be checked for, by name.
+///
+/// See [the module documentation](self).
+#[derive(Default)]
+pub struct Registry {
+	checks: BTreeMap<String, Check>,
+}
+
+impl Registry {
+	/// Create a registry without types.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register `T` under the name `name`, replacing the type registered before under this name.
+	pub fn register<T>(&mut self, name: &str) -> &mut Self
+	where
+		T: Encode + Decode + DeserializeOwned + PartialEq + fmt::Debug,
+	{
+		self.checks.insert(name.into(), check::<T>);
+		self
+	}
+
+	/// Check the test vectors of the JSON `fixture`, and return their number.
+	///
+	/// All test vectors are checked, and all failures are returned.
+	pub fn check_json(&self, fixture: &str) -> Result<usize, Vec<Failure>> {
+		self.check_fixture(serde_json::from_str(fixture).map_err(|e| e.to_string()))
+	}
+
+	/// Check the test vectors of the YAML `fixture`, and return their number, see
+	/// [`Self::check_json`].
+	pub fn check_yaml(&self, fixture: &str) -> Result<usize, Vec<Failure>> {
+		self.check_fixture(serde_yaml_ng::from_str(fixture).map_err(|e| e.to_string()))
+	}
+
+	/// Check the test vectors of the JSON fixture at `path`, see [`Self::check_json`].
+	pub fn check_json_file(&self, path: impl AsRef<Path>) -> Result<usize, Vec<Failure>> {
+		self.check_json(&read_fixture(path.as_ref())?)
+	}
+
+	/// Check the test vectors of the YAML fixture at `path`, see [`Self::check_yaml`].
+	pub fn check_yaml_file(&self, path: impl AsRef<Path>) -> Result<usize, Vec<Failure>> {
+		self.check_yaml(&read_fixture(path.as_ref())?)
+	}
+
+	fn check_fixture(&self, fixture: Result<Value, String>) -> Result<usize, Vec<Failure>> {
+		let invalid = |message: String| vec![Failure { vector: None, message }];
+		let vectors = match fixture {
+			Ok(Value::Array(vectors)) => vectors,
+			Ok(_) => return Err(invalid("expected an array of test vectors".into())),
+			Err(e) => return Err(invalid(e)),
+		};
+
+		let failures = vectors
+			.iter()
+			.enumerate()
+			.filter_map(|(i, vector)| {
+				let message = self.check_vector(vector).err()?;
+				Some(Failure { vector: Some(i), message })
+			})
+			.collect::<Vec<_>>();
+		if failures.is_empty() {
+			Ok(vectors.len())
+		} else {
+			Err(failures)
+		}
+	}
+
+	fn check_vector(&self, vector: &Value) -> Result<(), String> {
+		let field = |name| vector.get(name).ok_or_else(|| format!("missing `{name}`"));
+		let name = field("type")?.as_str().ok_or("`type` isn't a string")?;
+		let check = self.checks.get(name).ok_or_else(|| format!("`{name}` isn't registered"))?;
+		let hex = field("hex")?.as_str().ok_or("`hex` isn't a string")?;
+		let encoded = from_hex(hex)?;
+
+		let value = match vector.get("error") {
+			Some(Value::Bool(true)) => None,
+			None | Some(Value::Bool(false)) => Some(field("value")?),
+			Some(_) => return Err("`error` isn't a boolean".into()),
+		};
+		check(&encoded, value).map_err(|e| format!("`{name}` {hex}: {e}"))
+	}
+}
+
+/// Create a [`Registry`](crate::test_vectors::Registry) of types by name, for checking test
+/// vectors.
+///
+/// ```
+/// let registry = parity_scale_codec::test_vector_registry! {
+///     "u8" => u8,
+///     "String" => String,
+/// };
+/// assert_eq!(registry.check_json(r#"[{ "type": "String", "hex": "0c616263", "value": "abc" }]"#), Ok(1));
+/// ```
+#[macro_export]
+macro_rules! test_vector_registry {
+	( $( $name:literal => $ty:ty ),* $(,)? ) => {{
+		let mut registry = $crate::test_vectors::Registry::new();
+		$( registry.register::<$ty>($name); )*
+		registry
+	}};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Compact;
+
+	fn registry() -> Registry {
+		test_vector_registry! {
+			"u64" => u64,
+			"Compact<u32>" => Compact<u32>,
+			"(u8, Vec<bool>)" => (u8, Vec<bool>),
+		}
+	}
+
+	#[test]
+	fn valid_test_vectors_pass() {
+		let fixture = r#"[
+			{ "type": "u64", "hex": "0x0100000000000000", "value": 1 },
+			{ "type": "Compact<u32>", "hex": "0xfd03", "value": 255 },
+			{ "type": "(u8, Vec<bool>)", "hex": "0x07080100", "value": [7, [true, false]] },
+			{ "type": "(u8, Vec<bool>)", "hex": "0x070402", "error": true },
+			{ "type": "Compact<u32>", "hex": "0x0100", "error": true }
+		]"#;
+		assert_eq!(registry().check_json(fixture), Ok(5));
+	}
+
+	#[test]
+	fn yaml_test_vectors_are_checked_like_json() {
+		// YAML can't be indented with tabs.
+		let fixture = "- { type: u64, hex: \"0x0100000000000000\", value: 1 }\n\
+			- { type: \"(u8, Vec<bool>)\", hex: \"0x07080100\", value: [7, [true, false]] }\n\
+			- { type: Compact<u32>, hex: \"0x0100\", error: true }\n\
+			- { type: Compact<u32>, hex: \"0x0400\", value: 1 }\n";
+		let failures = registry().check_yaml(fixture).unwrap_err();
+		assert_eq!(
+			failures.iter().map(ToString::to_string).collect::<Vec<_>>(),
+			[
+				"test vector 3: `Compact<u32>` 0x0400: failed to decode: Input buffer has still data \
+				 left after decoding!"
+			]
+		);
+		assert!(registry().check_yaml("type: u64").is_err());
+		assert!(registry().check_yaml("- [").is_err());
+	}
+
+	#[test]
+	fn failing_test_vectors_are_reported() {
+		let fixture = r#"[
+			{ "type": "u64", "hex": "0x01", "value": 1 },
+			{ "type": "u64", "hex": "0x0200000000000000", "value": 1 },
+			{ "type": "Compact<u32>", "hex": "0x0400", "value": 1 },
+			{ "type": "u32", "hex": "0x01000000", "value": 1 },
+			{ "type": "u64", "hex": "0x0100000000000000", "error": true },
+			{ "type": "u64", "hex": "0x0100000000000000", "value": 1 },
+			{ "type": "u64", "hex": "0x0g", "value": 1 }
+		]"#;
+		let failures = registry().check_json(fixture).unwrap_err();
+		let failures = failures.iter().map(ToString::to_string).collect::<Vec<_>>();
+		assert_eq!(
+			failures,
+			[
+				"test vector 0: `u64` 0x01: failed to decode: Not enough data to fill buffer",
+				"test vector 1: `u64` 0x0200000000000000: decoded 2, expected 1",
+				"test vector 2: `Compact<u32>` 0x0400: failed to decode: Input buffer has still \
+				 data left after decoding!",
+				"test vector 3: `u32` isn't registered",
+				"test vector 4: `u64` 0x0100000000000000: invalid encoding decoded to 1",
+				"test vector 6: invalid hex: Invalid hex digit",
+			]
+		);
+	}
+
+	#[test]
+	fn invalid_fixtures_are_reported() {
+		assert_eq!(
+			registry().check_json("{}").unwrap_err()[0].to_string(),
+			"invalid fixture: expected an array of test vectors"
+		);
+		assert!(registry().check_json("[").is_err());
+		assert!(registry().check_json_file("/nonexistent/fixture.json").is_err());
+	}
+}
@@ -0,0 +1,579 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hex and base64 text armoring of encodings, without buffering the encoding.
+
+use crate::{alloc::vec::Vec, CodecConfig, Error, Input, Output};
+
+/// The number of bytes converted at once, so the inner input or output sees large slices.
+const CHUNK: usize = 48;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const INVALID_HEX_MSG: &str = "Invalid hex digit";
+const INVALID_BASE64_MSG: &str = "Invalid base64 character";
+const BASE64_PADDING_MSG: &str = "Invalid base64 padding";
+const NOT_ENOUGH_DATA_MSG: &str = "Not enough data to fill buffer";
+
+/// An [`Output`] writing the lowercase hex encoding of the bytes written to it to an inner
+/// output.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, HexOutput};
+/// let mut output = HexOutput::new(Vec::new());
+/// (1u16, vec![0xabu8]).encode_to(&mut output);
+/// assert_eq!(output.into_inner(), b"010004ab");
+/// ```
+pub struct HexOutput<W> {
+	inner: W,
+}
+
+impl<W: Output> HexOutput<W> {
+	/// Create a new `HexOutput` writing to `inner`.
+	pub fn new(inner: W) -> Self {
+		Self { inner }
+	}
+
+	/// The inner output.
+	pub fn into_inner(self) -> W {
+		self.inner
+	}
+}
+
+impl<W: Output> Output for HexOutput<W> {
+	fn write(&mut self, bytes: &[u8]) {
+		let mut text = [0; 2 * CHUNK];
+		for chunk in bytes.chunks(CHUNK) {
+			for (byte, digits) in chunk.iter().zip(text.chunks_exact_mut(2)) {
+				digits[0] = HEX_DIGITS[usize::from(byte >> 4)];
+				digits[1] = HEX_DIGITS[usize::from(byte & 0xf)];
+			}
+			self.inner.write(&text[..2 * chunk.len()]);
+		}
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.inner.config()
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		self.inner.on_encode_error(error)
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.inner.is_exhausted()
+	}
+}
+
+/// The value of the hex digit `digit`, in lower or upper case.
+fn hex_value(digit: u8) -> Result<u8, Error> {
+	match digit {
+		b'0'..=b'9' => Ok(digit - b'0'),
+		b'a'..=b'f' => Ok(digit - b'a' + 10),
+		b'A'..=b'F' => Ok(digit - b'A' + 10),
+		_ => Err(INVALID_HEX_MSG.into()),
+	}
+}
+
+/// An [`Input`] reading the bytes hex encoded by an inner input, in lower or upper case.
+///
+/// ```
+/// # use parity_scale_codec::{Decode, HexInput};
+/// let mut input = HexInput::new(&b"010004AB"[..]);
+/// assert_eq!(<(u16, Vec<u8>)>::decode(&mut input), Ok((1, vec![0xab])));
+/// ```
+pub struct HexInput<I> {
+	inner: I,
+}
+
+impl<I: Input> HexInput<I> {
+	/// Create a new `HexInput` reading from `inner`.
+	pub fn new(inner: I) -> Self {
+		Self { inner }
+	}
+
+	/// The inner input.
+	pub fn into_inner(self) -> I {
+		self.inner
+	}
+}
+
+impl<I: Input> Input for HexInput<I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(self.inner.remaining_len()?.map(|len| len / 2))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		let mut text = [0; 2 * CHUNK];
+		for chunk in into.chunks_mut(CHUNK) {
+			let text = &mut text[..2 * chunk.len()];
+			self.inner.read(text)?;
+			for (byte, digits) in chunk.iter_mut().zip(text.chunks_exact(2)) {
+				*byte = hex_value(digits[0])? << 4 | hex_value(digits[1])?;
+			}
+		}
+		Ok(())
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		let mut text = Vec::new();
+		self.inner.read_remaining_into(&mut text)?;
+		if text.len() % 2 != 0 {
+			return Err(NOT_ENOUGH_DATA_MSG.into());
+		}
+
+		self.on_before_alloc_mem(text.len() / 2)?;
+		into.reserve(text.len() / 2);
+		for digits in text.chunks_exact(2) {
+			into.push(hex_value(digits[0])? << 4 | hex_value(digits[1])?);
+		}
+		Ok(())
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.inner.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.inner.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.inner.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.inner.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.inner.interner()
+	}
+}
+
+/// The base64 characters of the 1 to 3 bytes of `group`, padded with `=`.
+fn base64_quad(group: &[u8]) -> [u8; 4] {
+	let bits = group
+		.iter()
+		.enumerate()
+		.fold(0u32, |bits, (i, &byte)| bits | u32::from(byte) << (16 - 8 * i));
+	let mut quad = [b'='; 4];
+	for (i, char) in quad.iter_mut().take(group.len() + 1).enumerate() {
+		*char = BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize];
+	}
+	quad
+}
+
+/// An [`Output`] writing the base64 encoding of the bytes written to it to an inner output, with
+/// the standard alphabet and padding.
+///
+/// The last bytes are only written by [`Self::finish`], as base64 encodes groups of 3 bytes, so it
+/// must be called once everything is written.
+///
+/// ```
+/// # use parity_scale_codec::{Base64Output, Encode};
+/// let mut output = Base64Output::new(Vec::new());
+/// (1u16, vec![0xabu8]).encode_to(&mut output);
+/// assert_eq!(output.finish(), b"AQAEqw==");
+/// ```
+#[must_use = "the last bytes are only written by `Base64Output::finish`"]
+pub struct Base64Output<W> {
+	inner: W,
+	pending: [u8; 3],
+	pending_len: usize,
+}
+
+impl<W: Output> Base64Output<W> {
+	/// Create a new `Base64Output` writing to `inner`.
+	pub fn new(inner: W) -> Self {
+		Self { inner, pending: [0; 3], pending_len: 0 }
+	}
+
+	/// Write the last bytes, with padding, and return the inner output.
+	pub fn finish(mut self) -> W {
+		if self.pending_len > 0 {
+			self.inner.write(&base64_quad(&self.pending[..self.pending_len]));
+		}
+		self.inner
+	}
+}
+
+impl<W: Output> Output for Base64Output<W> {
+	fn write(&mut self, mut bytes: &[u8]) {
+		if self.pending_len > 0 {
+			let len = bytes.len().min(3 - self.pending_len);
+			self.pending[self.pending_len..][..len].copy_from_slice(&bytes[..len]);
+			self.pending_len += len;
+			bytes = &bytes[len..];
+			if self.pending_len < 3 {
+				return;
+			}
+			self.inner.write(&base64_quad(&self.pending));
+			self.pending_len = 0;
+		}
+
+		let mut groups = bytes.chunks_exact(3);
+		let mut text = [0; CHUNK / 3 * 4];
+		loop {
+			let mut len = 0;
+			// `text` is zipped first, so no group is consumed once it's full.
+			for (quad, group) in text.chunks_exact_mut(4).zip(groups.by_ref()) {
+				quad.copy_from_slice(&base64_quad(group));
+				len += 4;
+			}
+			if len == 0 {
+				break;
+			}
+			self.inner.write(&text[..len]);
+		}
+
+		let rest = groups.remainder();
+		self.pending[..rest.len()].copy_from_slice(rest);
+		self.pending_len = rest.len();
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.inner.config()
+	}
+
+	fn on_encode_error(&mut self, error: Error) {
+		self.inner.on_encode_error(error)
+	}
+
+	fn is_exhausted(&self) -> bool {
+		self.inner.is_exhausted()
+	}
+}
+
+/// The value of the base64 character `char`.
+fn base64_value(char: u8) -> Result<u32, Error> {
+	match char {
+		b'A'..=b'Z' => Ok(u32::from(char - b'A')),
+		b'a'..=b'z' => Ok(u32::from(char - b'a' + 26)),
+		b'0'..=b'9' => Ok(u32::from(char - b'0' + 52)),
+		b'+' => Ok(62),
+		b'/' => Ok(63),
+		_ => Err(INVALID_BASE64_MSG.into()),
+	}
+}
+
+/// The bytes of the base64 characters `quad`, and their number, which is less than 3 if the quad
+/// is padded.
+fn decode_base64_quad(quad: &[u8]) -> Result<([u8; 3], usize), Error> {
+	let len = match quad {
+		[.., b'=', b'='] => 1,
+		[.., b'='] => 2,
+		_ => 3,
+	};
+	let mut bits = 0;
+	for (i, &char) in quad[..len + 1].iter().enumerate() {
+		bits |= base64_value(char)? << (18 - 6 * i);
+	}
+	// Only the canonical encoding is accepted, with the unused bits of a padded quad set to zero.
+	if bits & (0xff_ffff >> (8 * len)) != 0 {
+		return Err(BASE64_PADDING_MSG.into());
+	}
+	Ok(([(bits >> 16) as u8, (bits >> 8) as u8, bits as u8], len))
+}
+
+/// An [`Input`] reading the bytes base64 encoded by an inner input, with the standard alphabet and
+/// padding.
+///
+/// ```
+/// # use parity_scale_codec::{Base64Input, Decode};
+/// let mut input = Base64Input::new(&b"AQAEqw=="[..]);
+/// assert_eq!(<(u16, Vec<u8>)>::decode(&mut input), Ok((1, vec![0xab])));
+/// ```
+pub struct Base64Input<I> {
+	inner: I,
+	buffer: [u8; 3],
+	buffer_pos: usize,
+	buffer_len: usize,
+	/// Whether a padded quad was read, which ends the encoding.
+	finished: bool,
+}
+
+impl<I: Input> Base64Input<I> {
+	/// Create a new `Base64Input` reading from `inner`.
+	pub fn new(inner: I) -> Self {
+		Self { inner, buffer: [0; 3], buffer_pos: 0, buffer_len: 0, finished: false }
+	}
+
+	/// The inner input.
+	///
+	/// Bytes decoded from the inner input but not read yet are lost.
+	pub fn into_inner(self) -> I {
+		self.inner
+	}
+
+	/// Read the next quad into the buffer.
+	fn fill_buffer(&mut self) -> Result<(), Error> {
+		if self.finished {
+			return Err(NOT_ENOUGH_DATA_MSG.into());
+		}
+		let mut quad = [0; 4];
+		self.inner.read(&mut quad)?;
+		let (bytes, len) = decode_base64_quad(&quad)?;
+		self.buffer = bytes;
+		self.buffer_pos = 0;
+		self.buffer_len = len;
+		self.finished = len < 3;
+		Ok(())
+	}
+}
+
+impl<I: Input> Input for Base64Input<I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		let buffered = self.buffer_len - self.buffer_pos;
+		if self.finished {
+			return Ok(Some(buffered));
+		}
+		// Over-reports the length by the padding of the last quad.
+		Ok(self.inner.remaining_len()?.map(|len| buffered + len / 4 * 3))
+	}
+
+	fn read_remaining_into(&mut self, into: &mut Vec<u8>) -> Result<(), Error> {
+		let max_chunk_len = self.config().max_preallocation.max(3);
+		loop {
+			self.on_before_alloc_mem(self.buffer_len - self.buffer_pos)?;
+			into.extend_from_slice(&self.buffer[self.buffer_pos..self.buffer_len]);
+			self.buffer_pos = self.buffer_len;
+			if self.finished {
+				return Ok(());
+			}
+
+			let text_len = self
+				.inner
+				.remaining_len()?
+				.ok_or("Cannot read the rest of an input of unknown length")?;
+			match text_len / 4 {
+				0 if text_len == 0 => return Ok(()),
+				0 => return Err(NOT_ENOUGH_DATA_MSG.into()),
+				// The last quad may be padded, so it's read on its own.
+				1 => self.fill_buffer()?,
+				quads => {
+					let chunk_len = (3 * (quads - 1)).min(max_chunk_len / 3 * 3);
+					self.on_before_alloc_mem(chunk_len)?;
+					let start = into.len();
+					into.resize(start + chunk_len, 0);
+					if let Err(e) = self.read(&mut into[start..]) {
+						into.truncate(start);
+						return Err(e);
+					}
+				},
+			}
+		}
+	}
+
+	fn read(&mut self, mut into: &mut [u8]) -> Result<(), Error> {
+		let buffered = (self.buffer_len - self.buffer_pos).min(into.len());
+		into[..buffered].copy_from_slice(&self.buffer[self.buffer_pos..][..buffered]);
+		self.buffer_pos += buffered;
+		into = &mut into[buffered..];
+
+		// Whole quads are decoded directly into `into`.
+		let mut text = [0; CHUNK / 3 * 4];
+		while into.len() >= 3 {
+			if self.finished {
+				return Err(NOT_ENOUGH_DATA_MSG.into());
+			}
+			let groups = (into.len() / 3).min(CHUNK / 3);
+			let text = &mut text[..4 * groups];
+			self.inner.read(text)?;
+			for (i, quad) in text.chunks_exact(4).enumerate() {
+				let (bytes, len) = decode_base64_quad(quad)?;
+				if len < 3 {
+					self.finished = true;
+					return Err(NOT_ENOUGH_DATA_MSG.into());
+				}
+				into[3 * i..][..3].copy_from_slice(&bytes);
+			}
+			into = &mut into[3 * groups..];
+		}
+
+		if !into.is_empty() {
+			self.fill_buffer()?;
+			if self.buffer_len < into.len() {
+				return Err(NOT_ENOUGH_DATA_MSG.into());
+			}
+			into.copy_from_slice(&self.buffer[..into.len()]);
+			self.buffer_pos = into.len();
+		}
+		Ok(())
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.inner.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.inner.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.inner.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.inner.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.inner.interner()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		alloc::{string::String, vec::Vec},
+		Decode, Encode, UncheckedTail,
+	};
+
+	fn hex(bytes: &[u8]) -> Vec<u8> {
+		let mut output = HexOutput::new(Vec::new());
+		output.write(bytes);
+		output.into_inner()
+	}
+
+	fn base64(bytes: &[u8]) -> Vec<u8> {
+		let mut output = Base64Output::new(Vec::new());
+		output.write(bytes);
+		output.finish()
+	}
+
+	#[test]
+	fn hex_works() {
+		assert_eq!(hex(&[]), b"");
+		assert_eq!(hex(&[0x00, 0x7f, 0xff]), b"007fff");
+		let bytes = (0..=255).collect::<Vec<u8>>();
+		let text = hex(&bytes);
+		let mut input = HexInput::new(&text[..]);
+		assert_eq!(input.remaining_len(), Ok(Some(256)));
+		let mut decoded = [0; 256];
+		input.read(&mut decoded).unwrap();
+		assert_eq!(decoded[..], bytes[..]);
+
+		assert_eq!(HexInput::new(&b"0g"[..]).read_byte(), Err(INVALID_HEX_MSG.into()));
+		assert!(HexInput::new(&b"0"[..]).read_byte().is_err());
+	}
+
+	#[test]
+	fn base64_works() {
+		// Test vectors of RFC 4648.
+		for (bytes, text) in [
+			(&b""[..], &b""[..]),
+			(b"f", b"Zg=="),
+			(b"fo", b"Zm8="),
+			(b"foo", b"Zm9v"),
+			(b"foob", b"Zm9vYg=="),
+			(b"fooba", b"Zm9vYmE="),
+			(b"foobar", b"Zm9vYmFy"),
+		] {
+			assert_eq!(base64(bytes), text);
+			let mut input = Base64Input::new(text);
+			let mut decoded = vec![0; bytes.len()];
+			input.read(&mut decoded).unwrap();
+			assert_eq!(decoded, bytes);
+			assert_eq!(input.remaining_len(), Ok(Some(0)));
+		}
+	}
+
+	#[test]
+	fn base64_output_handles_split_writes() {
+		let bytes = (0..200).collect::<Vec<u8>>();
+		let mut output = Base64Output::new(Vec::new());
+		for chunk in bytes.chunks(7) {
+			let (first, second) = chunk.split_at(chunk.len() / 2);
+			output.write(first);
+			output.write(second);
+		}
+		assert_eq!(output.finish(), base64(&bytes));
+	}
+
+	#[test]
+	fn base64_input_handles_split_reads() {
+		let bytes = (0..200).collect::<Vec<u8>>();
+		let text = base64(&bytes);
+		let mut input = Base64Input::new(&text[..]);
+		let mut decoded = Vec::new();
+		let mut len = 0;
+		while decoded.len() < bytes.len() {
+			len += 1;
+			let mut chunk = vec![0; len.min(bytes.len() - decoded.len())];
+			input.read(&mut chunk).unwrap();
+			decoded.extend(chunk);
+		}
+		assert_eq!(decoded, bytes);
+		assert!(input.read_byte().is_err());
+	}
+
+	#[test]
+	fn base64_input_rejects_invalid_text() {
+		assert_eq!(
+			Base64Input::new(&b"Zm9v!A=="[..]).read(&mut [0; 4]),
+			Err(INVALID_BASE64_MSG.into())
+		);
+		assert_eq!(Base64Input::new(&b"Zh=="[..]).read_byte(), Err(BASE64_PADDING_MSG.into()));
+		assert_eq!(
+			Base64Input::new(&b"Zg==Zg=="[..]).read(&mut [0; 2]),
+			Err(NOT_ENOUGH_DATA_MSG.into())
+		);
+		assert_eq!(
+			Base64Input::new(&b"Zg=="[..]).read(&mut [0; 3]),
+			Err(NOT_ENOUGH_DATA_MSG.into())
+		);
+	}
+
+	#[test]
+	fn encodings_round_trip_through_text() {
+		let value = (vec![1u64, 2, 3], Some(String::from("text")), 42u8);
+
+		let mut output = HexOutput::new(Vec::new());
+		value.encode_to(&mut output);
+		let text = output.into_inner();
+		assert_eq!(text, hex(&value.encode()));
+		assert_eq!(Decode::decode(&mut HexInput::new(&text[..])), Ok(value.clone()));
+
+		let mut output = Base64Output::new(Vec::new());
+		value.encode_to(&mut output);
+		let text = output.finish();
+		assert_eq!(text, base64(&value.encode()));
+		assert_eq!(Decode::decode(&mut Base64Input::new(&text[..])), Ok(value));
+	}
+
+	#[test]
+	fn base64_input_reads_the_remaining_bytes() {
+		for len in [0, 1, 2, 3, 4, 5, 200] {
+			let value = (len as u8, UncheckedTail((0..len as u8).collect()));
+			let text = base64(&value.encode());
+			assert_eq!(Decode::decode(&mut Base64Input::new(&text[..])), Ok(value));
+		}
+
+		let text = base64(&[1, 2, 3, 4]);
+		assert_eq!(text, b"AQIDBA==");
+		assert_eq!(
+			UncheckedTail::decode(&mut Base64Input::new(&text[..])),
+			Ok(UncheckedTail(vec![1, 2, 3, 4]))
+		);
+		assert_eq!(
+			UncheckedTail::decode(&mut Base64Input::new(&b"AQIDBA="[..])),
+			Err(NOT_ENOUGH_DATA_MSG.into())
+		);
+	}
+}
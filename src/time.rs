@@ -0,0 +1,226 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding of `SystemTime` and of differences of `Instant`s.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "max-encoded-len")]
+use crate::{ConstEncodedLen, MaxEncodedLen};
+use crate::{
+	Decode, DecodeThenNormalize, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
+};
+
+/// `SystemTime` is encoded as the signed offset from the Unix epoch, as an `i64` number of
+/// seconds followed by a `u32` number of nanoseconds (always less than 10^9) added to them.
+///
+/// Times after the Unix epoch are encoded like the `Duration` since the epoch, while times
+/// before it have a negative number of seconds, e.g. half a second before the epoch is encoded as
+/// `(-1i64, 500_000_000u32)`.
+impl Encode for SystemTime {
+	fn size_hint(&self) -> usize {
+		Duration::ZERO.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		match self.duration_since(UNIX_EPOCH) {
+			Ok(since_epoch) => since_epoch.encode_to(dest),
+			Err(e) => {
+				let before_epoch = e.duration();
+				let (secs, nanos) = match before_epoch.subsec_nanos() {
+					0 => (i128::from(before_epoch.as_secs()), 0),
+					nanos => (i128::from(before_epoch.as_secs()) + 1, 1_000_000_000 - nanos),
+				};
+				// No platform represents times more than `i64::MAX` seconds before the epoch.
+				(i64::try_from(-secs).unwrap_or(i64::MIN), nanos).encode_to(dest)
+			},
+		}
+	}
+}
+
+impl EncodeLike for SystemTime {}
+
+impl Decode for SystemTime {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let (secs, nanos) = <(i64, u32)>::decode(input)
+			.map_err(|e| e.chain("Could not decode `SystemTime(i64, u32)`"))?;
+		let subsec = Duration::normalize((0, nanos))?;
+		let time = if secs >= 0 {
+			UNIX_EPOCH.checked_add(Duration::from_secs(secs.unsigned_abs()))
+		} else {
+			UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+		};
+		time.and_then(|time| time.checked_add(subsec)).ok_or_else(|| {
+			"Could not decode `SystemTime`: Time is not representable on this platform".into()
+		})
+	}
+}
+
+impl DecodeWithMemTracking for SystemTime {}
+
+#[cfg(feature = "max-encoded-len")]
+impl MaxEncodedLen for SystemTime {
+	fn max_encoded_len() -> usize {
+		Duration::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl ConstEncodedLen for SystemTime {}
+
+/// The time elapsed between two [`Instant`]s, encoded as a `Duration`.
+///
+/// `Instant` itself can't be encoded, as it is measured from an unspecified point, e.g. the boot
+/// of the machine. Only the difference of two instants is meaningful, which this type makes
+/// explicit.
+///
+/// # Portability
+///
+/// A delta is only meaningful relative to the clock it was measured with. After decoding it in
+/// another process, after a reboot or on another machine, it can still be applied to a new base
+/// with [`MonotonicDelta::after`], but the time between encoding and decoding is not accounted
+/// for: e.g. a deadline stored as "5 seconds after the checkpoint" is 5 seconds after the restore.
+/// Use `SystemTime` for points in time which must survive a restart, keeping in mind that it is
+/// not monotonic.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, MonotonicDelta};
+/// # use std::time::{Duration, Instant};
+/// let started = Instant::now();
+/// let deadline = started + Duration::from_secs(5);
+/// let encoded = MonotonicDelta::between(started, deadline).encode();
+///
+/// let restored = Instant::now();
+/// let (delta, _) = decode_prefix::<MonotonicDelta>(&encoded).unwrap();
+/// assert_eq!(delta.after(restored), Some(restored + Duration::from_secs(5)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonotonicDelta(pub Duration);
+
+impl MonotonicDelta {
+	/// The time elapsed from `earlier` to `later`, or zero if `later` is before `earlier`.
+	pub fn between(earlier: Instant, later: Instant) -> Self {
+		MonotonicDelta(later.saturating_duration_since(earlier))
+	}
+
+	/// The time elapsed since `earlier`.
+	pub fn since(earlier: Instant) -> Self {
+		Self::between(earlier, Instant::now())
+	}
+
+	/// The instant `self` after `base`, or `None` if it is not representable.
+	pub fn after(self, base: Instant) -> Option<Instant> {
+		base.checked_add(self.0)
+	}
+}
+
+impl From<Duration> for MonotonicDelta {
+	fn from(duration: Duration) -> Self {
+		MonotonicDelta(duration)
+	}
+}
+
+impl Encode for MonotonicDelta {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+}
+
+impl EncodeLike for MonotonicDelta {}
+impl EncodeLike<Duration> for MonotonicDelta {}
+impl EncodeLike<MonotonicDelta> for Duration {}
+
+impl Decode for MonotonicDelta {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Duration::decode(input).map(MonotonicDelta)
+	}
+}
+
+impl DecodeWithMemTracking for MonotonicDelta {}
+
+#[cfg(feature = "max-encoded-len")]
+impl MaxEncodedLen for MonotonicDelta {
+	fn max_encoded_len() -> usize {
+		Duration::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl ConstEncodedLen for MonotonicDelta {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn system_time_works() {
+		let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123);
+		let encoded = time.encode();
+		assert_eq!(encoded, Duration::new(1_700_000_000, 123).encode());
+		assert_eq!(time.size_hint(), encoded.len());
+		assert_eq!(SystemTime::decode(&mut &encoded[..]), Ok(time));
+
+		let now = SystemTime::now();
+		assert_eq!(SystemTime::decode(&mut &now.encode()[..]), Ok(now));
+		assert_eq!(SystemTime::decode(&mut &[0; 12][..]), Ok(UNIX_EPOCH));
+	}
+
+	#[test]
+	fn system_time_decoding_is_validated() {
+		let invalid_nanos = (0u64, 1_000_000_000u32).encode();
+		assert!(SystemTime::decode(&mut &invalid_nanos[..]).is_err());
+	}
+
+	#[test]
+	fn system_time_before_epoch_works() {
+		let half_second_before = UNIX_EPOCH - Duration::from_millis(500);
+		let encoded = half_second_before.encode();
+		assert_eq!(encoded, (-1i64, 500_000_000u32).encode());
+		assert_eq!(SystemTime::decode(&mut &encoded[..]), Ok(half_second_before));
+
+		let second_before = UNIX_EPOCH - Duration::from_secs(1);
+		assert_eq!(second_before.encode(), (-1i64, 0u32).encode());
+		assert_eq!(SystemTime::decode(&mut &second_before.encode()[..]), Ok(second_before));
+
+		let long_before = UNIX_EPOCH - Duration::new(1_700_000_000, 123);
+		assert_eq!(SystemTime::decode(&mut &long_before.encode()[..]), Ok(long_before));
+	}
+
+	#[test]
+	fn monotonic_delta_works() {
+		let start = Instant::now();
+		let later = start + Duration::from_millis(1500);
+		let delta = MonotonicDelta::between(start, later);
+		assert_eq!(delta, MonotonicDelta(Duration::from_millis(1500)));
+		assert_eq!(MonotonicDelta::between(later, start), MonotonicDelta(Duration::ZERO));
+
+		let encoded = delta.encode();
+		assert_eq!(encoded, Duration::from_millis(1500).encode());
+		let decoded = MonotonicDelta::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded.after(start), Some(later));
+		assert_eq!(MonotonicDelta(Duration::MAX).after(start), None);
+	}
+
+	#[cfg(feature = "max-encoded-len")]
+	#[test]
+	fn max_encoded_len_is_exact() {
+		let max = UNIX_EPOCH + Duration::new(u32::MAX.into(), 999_999_999);
+		assert_eq!(max.encode().len(), SystemTime::max_encoded_len());
+		assert_eq!(MonotonicDelta(Duration::MAX).encode().len(), MonotonicDelta::max_encoded_len());
+	}
+}
@@ -0,0 +1,210 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A deterministic `f64` wrapper with a total order.
+
+use core::{cmp::Ordering, hash};
+
+#[cfg(feature = "max-encoded-len")]
+use crate::{ConstEncodedLen, MaxEncodedLen};
+use crate::{Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output};
+
+/// The bits of the single NaN representation of [`TotalOrdF64`].
+const CANONICAL_NAN: u64 = 0x7ff8_0000_0000_0000;
+
+/// An `f64` with a single encoding per value, and a total order.
+///
+/// NaNs are canonicalized to a single quiet NaN on construction. Unless `PRESERVE_NEG_ZERO` is
+/// set, `-0.0` is normalized to `0.0` as well. So values which compare equal have the same
+/// encoding, and can be used in types whose encoding must be deterministic, e.g. consensus types.
+///
+/// The value is encoded as the little-endian bits of the canonical `f64`, like an `f64`. Only
+/// canonical values are accepted when decoding.
+///
+/// Values are ordered by [`f64::total_cmp`], so `-0.0` is less than `0.0` if it is preserved, and
+/// NaN is greater than all other values.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, TotalOrdF64};
+/// let nan: TotalOrdF64 = TotalOrdF64::new(-f64::NAN);
+/// assert_eq!(nan, f64::NAN.into());
+/// assert!(nan > f64::INFINITY.into());
+/// assert_eq!(TotalOrdF64::<false>::new(-0.0).encode(), 0.0f64.encode());
+/// assert!(decode_prefix::<TotalOrdF64>(&(-0.0f64).encode()).is_err());
+///
+/// // With `PRESERVE_NEG_ZERO`, `-0.0` is kept and ordered before `0.0`.
+/// assert!(TotalOrdF64::<true>::new(-0.0) < TotalOrdF64::new(0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TotalOrdF64<const PRESERVE_NEG_ZERO: bool = false>(f64);
+
+impl<const PRESERVE_NEG_ZERO: bool> TotalOrdF64<PRESERVE_NEG_ZERO> {
+	/// Canonicalize `value`.
+	pub fn new(value: f64) -> Self {
+		if value.is_nan() {
+			TotalOrdF64(f64::from_bits(CANONICAL_NAN))
+		} else if value == 0.0 && !PRESERVE_NEG_ZERO {
+			TotalOrdF64(0.0)
+		} else {
+			TotalOrdF64(value)
+		}
+	}
+
+	/// The canonical value.
+	pub fn get(self) -> f64 {
+		self.0
+	}
+
+	/// The bits of the canonical value, which are encoded.
+	pub fn to_bits(self) -> u64 {
+		self.0.to_bits()
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> From<f64> for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn from(value: f64) -> Self {
+		Self::new(value)
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> From<TotalOrdF64<PRESERVE_NEG_ZERO>> for f64 {
+	fn from(value: TotalOrdF64<PRESERVE_NEG_ZERO>) -> Self {
+		value.0
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> PartialEq for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn eq(&self, other: &Self) -> bool {
+		self.to_bits() == other.to_bits()
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> Eq for TotalOrdF64<PRESERVE_NEG_ZERO> {}
+
+impl<const PRESERVE_NEG_ZERO: bool> PartialOrd for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> Ord for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.0.total_cmp(&other.0)
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> hash::Hash for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.to_bits().hash(state)
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> Encode for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn size_hint(&self) -> usize {
+		8
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.to_bits().encode_to(dest)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.to_bits().using_encoded(f)
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> EncodeLike for TotalOrdF64<PRESERVE_NEG_ZERO> {}
+
+impl<const PRESERVE_NEG_ZERO: bool> Decode for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let bits = u64::decode(input)?;
+		let value = Self::new(f64::from_bits(bits));
+		if value.to_bits() != bits {
+			return Err("Non-canonical `TotalOrdF64` representation".into());
+		}
+		Ok(value)
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(8)
+	}
+}
+
+impl<const PRESERVE_NEG_ZERO: bool> DecodeWithMemTracking for TotalOrdF64<PRESERVE_NEG_ZERO> {}
+
+#[cfg(feature = "max-encoded-len")]
+impl<const PRESERVE_NEG_ZERO: bool> MaxEncodedLen for TotalOrdF64<PRESERVE_NEG_ZERO> {
+	fn max_encoded_len() -> usize {
+		8
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<const PRESERVE_NEG_ZERO: bool> ConstEncodedLen for TotalOrdF64<PRESERVE_NEG_ZERO> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn total_ord_f64_canonicalizes() {
+		let nans = [f64::NAN, -f64::NAN, f64::from_bits(0x7ff0_0000_0000_0001)];
+		for nan in nans {
+			assert_eq!(TotalOrdF64::<false>::new(nan).to_bits(), CANONICAL_NAN);
+			assert_eq!(TotalOrdF64::<true>::new(nan).to_bits(), CANONICAL_NAN);
+		}
+
+		assert_eq!(TotalOrdF64::<false>::new(-0.0).to_bits(), 0);
+		assert_eq!(TotalOrdF64::<true>::new(-0.0).to_bits(), (-0.0f64).to_bits());
+		assert_eq!(TotalOrdF64::<false>::new(-1.5).get(), -1.5);
+	}
+
+	#[test]
+	fn total_ord_f64_orders_totally() {
+		let mut values: Vec<TotalOrdF64<true>> =
+			[f64::NAN, 1.0, -0.0, f64::NEG_INFINITY, 0.0, -f64::NAN, f64::INFINITY]
+				.into_iter()
+				.map(TotalOrdF64::new)
+				.collect();
+		values.sort();
+		values.dedup();
+		let sorted: Vec<u64> = values.iter().map(|v| v.to_bits()).collect();
+		let expected: Vec<u64> = [f64::NEG_INFINITY, -0.0, 0.0, 1.0, f64::INFINITY]
+			.iter()
+			.map(|v| v.to_bits())
+			.chain([CANONICAL_NAN])
+			.collect();
+		assert_eq!(sorted, expected);
+	}
+
+	#[test]
+	fn total_ord_f64_codec() {
+		for value in [0.0, -2.5, f64::MAX, f64::NAN, f64::NEG_INFINITY] {
+			let value = TotalOrdF64::<false>::new(value);
+			let encoded = value.encode();
+			assert_eq!(encoded, value.get().to_bits().to_le_bytes());
+			assert_eq!(TotalOrdF64::decode(&mut &encoded[..]), Ok(value));
+		}
+
+		let non_canonical = [(-f64::NAN).to_bits(), 0x7ff0_0000_0000_0001, (-0.0f64).to_bits()];
+		for bits in non_canonical {
+			assert_eq!(
+				TotalOrdF64::<false>::decode(&mut &bits.encode()[..]),
+				Err("Non-canonical `TotalOrdF64` representation".into())
+			);
+		}
+		let neg_zero = (-0.0f64).encode();
+		assert_eq!(TotalOrdF64::<true>::decode(&mut &neg_zero[..]), Ok(TotalOrdF64::new(-0.0)));
+	}
+}
@@ -0,0 +1,190 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Primitives to rewrite encoded data while streaming it from an [`Input`] to an [`Output`].
+//!
+//! Large encoded blobs can be migrated without decoding them into memory at once, by copying the
+//! parts that stay the same and only decoding the values that need to be replaced:
+//!
+//! ```
+//! use parity_scale_codec::{transcode, Encode};
+//!
+//! // A version byte followed by a list of `u32` and a list of `u16`.
+//! let old = (1u8, vec![1u32, 2, 3], vec![4u16, 5]).encode();
+//!
+//! let mut input = &old[..];
+//! let mut new = Vec::new();
+//! // Bump the version.
+//! transcode::splice(&mut input, &mut new, |version: u8| version + 1).unwrap();
+//! // Keep the list of `u32` as it is.
+//! transcode::copy_value::<Vec<u32>, _, _>(&mut input, &mut new).unwrap();
+//! // Drop the list of `u16`.
+//! transcode::skip_value::<Vec<u16>, _>(&mut input).unwrap();
+//!
+//! assert_eq!(new, (2u8, vec![1u32, 2, 3]).encode());
+//! ```
+
+use crate::{CodecConfig, Decode, Encode, Error, Input, Output};
+
+/// The size of the buffer used by [`copy_bytes`].
+const COPY_BUFFER_SIZE: usize = 4096;
+
+/// Copy exactly `len` bytes from `input` to `output`.
+///
+/// The bytes are copied in chunks through a fixed size buffer, so the memory usage doesn't depend
+/// on `len`.
+pub fn copy_bytes<I: Input, O: Output + ?Sized>(
+	input: &mut I,
+	output: &mut O,
+	mut len: usize,
+) -> Result<(), Error> {
+	if input.remaining_len()?.is_some_and(|remaining| remaining < len) {
+		return Err("Not enough data to fill buffer".into());
+	}
+
+	let mut buf = [0u8; COPY_BUFFER_SIZE];
+	while len > 0 {
+		let chunk = &mut buf[..len.min(COPY_BUFFER_SIZE)];
+		input.read(chunk)?;
+		output.write(chunk);
+		len -= chunk.len();
+	}
+
+	Ok(())
+}
+
+/// Copy the encoding of a single `T` from `input` to `output`, unchanged.
+///
+/// The value is read with [`Decode::skip`], and every byte read is written to `output` as it is.
+/// The memory usage is therefore the one of `T::skip`.
+pub fn copy_value<T: Decode, I: Input, O: Output + ?Sized>(
+	input: &mut I,
+	output: &mut O,
+) -> Result<(), Error> {
+	T::skip(&mut CopyingInput { input, output })
+}
+
+/// Skip the encoding of a single `T` in `input`, without writing anything to the output.
+pub fn skip_value<T: Decode, I: Input>(input: &mut I) -> Result<(), Error> {
+	T::skip(input)
+}
+
+/// Decode a single `T` from `input`, and write the encoding of the value returned by `f` in its
+/// place to `output`.
+pub fn splice<T: Decode, R: Encode, I: Input, O: Output + ?Sized>(
+	input: &mut I,
+	output: &mut O,
+	f: impl FnOnce(T) -> R,
+) -> Result<(), Error> {
+	f(T::decode(input)?).encode_to(output);
+	Ok(())
+}
+
+/// An [`Input`] that writes all the bytes read from the inner input to an [`Output`].
+struct CopyingInput<'a, I, O: ?Sized> {
+	input: &'a mut I,
+	output: &'a mut O,
+}
+
+impl<I: Input, O: Output + ?Sized> Input for CopyingInput<'_, I, O> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.input.read(into)?;
+		self.output.write(into);
+		Ok(())
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		let byte = self.input.read_byte()?;
+		self.output.push_byte(byte);
+		Ok(byte)
+	}
+
+	fn read_remaining_into(&mut self, into: &mut crate::alloc::vec::Vec<u8>) -> Result<(), Error> {
+		let start = into.len();
+		let result = self.input.read_remaining_into(into);
+		self.output.write(&into[start..]);
+		result
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+
+	fn on_before_alloc_mem(&mut self, size: usize) -> Result<(), Error> {
+		self.input.on_before_alloc_mem(size)
+	}
+
+	fn config(&self) -> &CodecConfig {
+		self.input.config()
+	}
+
+	fn interner(&mut self) -> Option<&mut crate::Interner> {
+		self.input.interner()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{alloc::vec::Vec, Compact};
+
+	#[test]
+	fn copy_bytes_works() {
+		let data = (0..10_000u32).map(|i| i as u8).collect::<Vec<_>>();
+
+		let mut input = &data[..];
+		let mut output = Vec::new();
+		copy_bytes(&mut input, &mut output, 9_000).unwrap();
+		assert_eq!(output, data[..9_000]);
+		assert_eq!(input, &data[9_000..]);
+
+		assert_eq!(
+			copy_bytes(&mut input, &mut output, 1_001),
+			Err("Not enough data to fill buffer".into())
+		);
+		copy_bytes(&mut input, &mut output, 1_000).unwrap();
+		assert_eq!(output, data);
+		assert!(input.is_empty());
+	}
+
+	#[test]
+	fn copy_skip_and_splice_values() {
+		let data = (vec![1u16, 2], Compact(300u32), 7u64, Some(5u8)).encode();
+
+		let mut input = &data[..];
+		let mut output = Vec::new();
+		copy_value::<Vec<u16>, _, _>(&mut input, &mut output).unwrap();
+		// Re-encode a compact length as a fixed width integer.
+		splice(&mut input, &mut output, |len: Compact<u32>| len.0).unwrap();
+		skip_value::<u64, _>(&mut input).unwrap();
+		copy_value::<Option<u8>, _, _>(&mut input, &mut output).unwrap();
+
+		assert!(input.is_empty());
+		assert_eq!(output, (vec![1u16, 2], 300u32, Some(5u8)).encode());
+	}
+
+	#[test]
+	fn copy_value_fails_on_invalid_data() {
+		let mut output = Vec::new();
+		assert!(copy_value::<Vec<u32>, _, _>(&mut &[8, 1, 0, 0, 0][..], &mut output).is_err());
+	}
+}
@@ -0,0 +1,193 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bulk encoding and decoding of types whose encoding is their memory representation.
+//!
+//! The collections of this crate copy slices of integers in one go instead of encoding each item,
+//! e.g. for `Vec<u32>`. The functions of this module give the same fast paths to other collections,
+//! like arenas or custom vectors, for any [`TriviallyCodable`] item type:
+//!
+//! ```
+//! use parity_scale_codec::{trivially_codable, Encode};
+//!
+//! let items = [1u32, 2, 3];
+//! let mut encoded = Vec::new();
+//! trivially_codable::encode_slice(&items, &mut encoded);
+//! assert_eq!(encoded, (1u32, 2u32, 3u32).encode());
+//!
+//! let mut decoded = [0u32; 3];
+//! trivially_codable::decode_slice_into(&mut &encoded[..], &mut decoded).unwrap();
+//! assert_eq!(decoded, items);
+//! ```
+
+use core::mem;
+
+use crate::{alloc::vec::Vec, codec::decode_vec_chunked, Decode, Encode, Error, Input, Output};
+
+/// A type whose SCALE encoding is its memory representation on little endian targets.
+///
+/// The functions of [this module](self) encode and decode slices of such types with a single copy
+/// when [`CodecConfig::byte_order`](crate::CodecConfig::byte_order) is the byte order of the
+/// target, e.g. with the default configuration on little endian targets, and item by item
+/// otherwise.
+///
+/// It is implemented for the integer and float primitives and arrays of them. Other types, like a
+/// `#[repr(transparent)]` wrapper of an integer, can implement it if they fulfill the contract.
+///
+/// # Safety
+///
+/// When the byte order of the configuration of the [`Output`] or [`Input`] is the byte order of the
+/// target:
+/// * `Encode` must write exactly the `size_of::<Self>()` bytes of the memory representation of the
+///   value. In particular, the type must not have padding bytes.
+/// * Any `size_of::<Self>()` bytes must be a valid value, which `Decode` decodes from its memory
+///   representation without error.
+pub unsafe trait TriviallyCodable: Encode + Decode + Copy {}
+
+macro_rules! impl_trivially_codable {
+	( $( $t:ty ),* ) => { $(
+		// SAFETY: Primitives are encoded as their bytes in the configured byte order and have no
+		// invalid values.
+		unsafe impl TriviallyCodable for $t {}
+	)* }
+}
+
+impl_trivially_codable!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+// SAFETY: An array is encoded as its items, without padding between them.
+unsafe impl<T: TriviallyCodable, const N: usize> TriviallyCodable for [T; N] {}
+
+/// Encode the items of `slice` to `dest`, without a length prefix.
+pub fn encode_slice<T: TriviallyCodable, W: Output + ?Sized>(slice: &[T], dest: &mut W) {
+	if dest.config().byte_order.is_native() {
+		// SAFETY: `T` has no padding, so all bytes of the slice are initialized.
+		let bytes = unsafe {
+			core::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), mem::size_of_val(slice))
+		};
+		dest.write(bytes)
+	} else {
+		for item in slice {
+			item.encode_to(dest)
+		}
+	}
+}
+
+/// Decode `dst.len()` items from `input` into `dst`.
+///
+/// If decoding fails, `dst` contains valid but unspecified values.
+pub fn decode_slice_into<T: TriviallyCodable, I: Input>(
+	input: &mut I,
+	dst: &mut [T],
+) -> Result<(), Error> {
+	if input.config().byte_order.is_native() {
+		// SAFETY: Any bytes are a valid `T`, so writing to them can't create an invalid value.
+		let bytes = unsafe {
+			core::slice::from_raw_parts_mut(dst.as_mut_ptr().cast::<u8>(), mem::size_of_val(dst))
+		};
+		input.read(bytes)
+	} else {
+		for item in dst {
+			*item = T::decode(input)?;
+		}
+		Ok(())
+	}
+}
+
+/// Decode a `Vec` of `len` items from `input`, without a length prefix.
+///
+/// Like for the collections of this crate, the memory is allocated in chunks as the items are
+/// read, and reported to [`Input::on_before_alloc_mem`].
+pub fn decode_vec_with_len<T: TriviallyCodable, I: Input>(
+	input: &mut I,
+	len: usize,
+) -> Result<Vec<T>, Error> {
+	if input
+		.remaining_len()?
+		.is_some_and(|remaining| remaining / mem::size_of::<T>().max(1) < len)
+	{
+		return Err("Not enough data to decode vector".into());
+	}
+
+	decode_vec_chunked(input, len, |input, decoded_vec, chunk_len| {
+		let start = decoded_vec.len();
+		// SAFETY: Any bytes are a valid `T`, including zeros.
+		decoded_vec.resize(start + chunk_len, unsafe { mem::zeroed() });
+		decode_slice_into(input, &mut decoded_vec[start..])
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MemTrackingInput;
+
+	/// A third-party item type opting into the fast paths.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	#[repr(transparent)]
+	struct Id(u32);
+
+	impl Encode for Id {
+		fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+			self.0.using_encoded(f)
+		}
+	}
+
+	impl Decode for Id {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+			u32::decode(input).map(Id)
+		}
+	}
+
+	// SAFETY: `Id` is encoded like `u32`, which is its memory representation.
+	unsafe impl TriviallyCodable for Id {}
+
+	#[test]
+	fn slices_are_encoded_like_their_items() {
+		let items = [Id(1), Id(u32::MAX), Id(1 << 20)];
+		let mut encoded = Vec::new();
+		encode_slice(&items, &mut encoded);
+		assert_eq!(encoded, (1u32, u32::MAX, 1u32 << 20).encode());
+
+		let mut decoded = [Id(0); 3];
+		decode_slice_into(&mut &encoded[..], &mut decoded).unwrap();
+		assert_eq!(decoded, items);
+
+		let arrays = [[1u16, 2], [3, 4]];
+		let mut encoded = Vec::new();
+		encode_slice(&arrays, &mut encoded);
+		assert_eq!(encoded, arrays.encode());
+	}
+
+	#[test]
+	fn decode_vec_with_len_works() {
+		let items = (0..10_000).map(Id).collect::<Vec<_>>();
+		let mut encoded = Vec::new();
+		encode_slice(&items, &mut encoded);
+		assert_eq!(decode_vec_with_len::<Id, _>(&mut &encoded[..], items.len()), Ok(items.clone()));
+
+		assert!(decode_vec_with_len::<Id, _>(&mut &encoded[1..], items.len()).is_err());
+		assert!(decode_vec_with_len::<Id, _>(&mut &encoded[..], items.len() + 1).is_err());
+	}
+
+	#[test]
+	fn decode_vec_with_len_tracks_memory() {
+		let encoded = [0u8; 400];
+		let mut bytes = &encoded[..];
+		let mut input = MemTrackingInput::new(&mut bytes, 400);
+		assert!(decode_vec_with_len::<u32, _>(&mut input, 100).is_err());
+		let mut bytes = &encoded[..];
+		let mut input = MemTrackingInput::new(&mut bytes, 401);
+		assert_eq!(decode_vec_with_len::<u32, _>(&mut input, 100), Ok(vec![0; 100]));
+	}
+}
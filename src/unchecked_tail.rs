@@ -0,0 +1,144 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bytes extending to the end of the input.
+
+use crate::{
+	alloc::vec::Vec, Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, Output,
+};
+
+/// Bytes encoded without a length prefix, so decoding consumes all remaining bytes of the input.
+///
+/// This is for formats ending with a payload of unspecified length, e.g. a header followed by the
+/// message body. It must be the last field of such a type, as nothing can be decoded after it.
+///
+/// Decoding uses [`Input::read_remaining_into`], so it fails for inputs of unknown length which
+/// don't override it.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, UncheckedTail};
+/// let message = (7u16, UncheckedTail(b"body".to_vec()));
+/// let encoded = message.encode();
+/// assert_eq!(encoded, [7, 0, b'b', b'o', b'd', b'y']);
+/// assert_eq!(decode_prefix::<(u16, UncheckedTail)>(&encoded), Ok((message, &[][..])));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UncheckedTail(pub Vec<u8>);
+
+impl From<Vec<u8>> for UncheckedTail {
+	fn from(bytes: Vec<u8>) -> Self {
+		UncheckedTail(bytes)
+	}
+}
+
+impl Encode for UncheckedTail {
+	fn size_hint(&self) -> usize {
+		self.0.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		dest.write(&self.0)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		f(&self.0)
+	}
+}
+
+impl EncodeLike for UncheckedTail {}
+
+impl Decode for UncheckedTail {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let mut bytes = Vec::new();
+		input.read_remaining_into(&mut bytes)?;
+		Ok(UncheckedTail(bytes))
+	}
+}
+
+impl DecodeWithMemTracking for UncheckedTail {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{decode_prefix, DecodeWithMemLimit, IoReader};
+
+	#[test]
+	fn unchecked_tail_consumes_the_input() {
+		let encoded = (1u8, UncheckedTail(vec![2, 3])).encode();
+		assert_eq!(encoded, [1, 2, 3]);
+
+		let (first, rest) = decode_prefix::<u8>(&encoded).unwrap();
+		assert_eq!(first, 1);
+		assert_eq!(decode_prefix::<UncheckedTail>(rest), Ok((UncheckedTail(vec![2, 3]), &[][..])));
+		assert_eq!(decode_prefix::<UncheckedTail>(&[]), Ok((UncheckedTail(vec![]), &[][..])));
+	}
+
+	#[test]
+	fn unchecked_tail_from_reader() {
+		let mut input = IoReader(&[1u8, 2, 3][..]);
+		assert_eq!(u8::decode(&mut input), Ok(1));
+		assert_eq!(UncheckedTail::decode(&mut input), Ok(UncheckedTail(vec![2, 3])));
+	}
+
+	#[test]
+	fn read_remaining_into_default_is_chunked_and_tracked() {
+		/// Over-reports its length by `self.1` bytes.
+		struct Unsliced<'a>(&'a [u8], usize);
+
+		impl Input for Unsliced<'_> {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				Ok(Some(if self.0.is_empty() { 0 } else { self.0.len() + self.1 }))
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				self.0.read(into)
+			}
+		}
+
+		let payload = vec![5u8; 40_000];
+		let mut into = vec![1];
+		Unsliced(&payload, 0).read_remaining_into(&mut into).unwrap();
+		assert_eq!(into, [&[1][..], &payload].concat());
+
+		assert!(UncheckedTail::decode_with_mem_limit(&mut Unsliced(&payload, 0), 40_000).is_err());
+		assert!(UncheckedTail::decode_with_mem_limit(&mut Unsliced(&payload, 0), 40_001).is_ok());
+
+		// A length which is too large ends with the input.
+		for over in [1, 2, 100] {
+			let mut into = Vec::new();
+			Unsliced(&payload, over).read_remaining_into(&mut into).unwrap();
+			assert_eq!(into, payload);
+		}
+	}
+
+	#[test]
+	fn read_remaining_into_is_forwarded_by_wrappers() {
+		let payload = vec![5u8; 40_000];
+
+		let mut input = IoReader(&payload[..]).with_mem_limit(40_001);
+		assert_eq!(UncheckedTail::decode(&mut input), Ok(UncheckedTail(payload.clone())));
+		assert_eq!(input.used_mem(), 40_000);
+
+		let mut input = IoReader(&payload[..]).with_mem_limit(40_000);
+		assert!(UncheckedTail::decode(&mut input).is_err());
+
+		let mut reports = Vec::new();
+		let mut input = IoReader(&payload[..])
+			.with_depth_limit(1)
+			.with_progress(16_384, |read| reports.push(read));
+		assert_eq!(UncheckedTail::decode(&mut input), Ok(UncheckedTail(payload.clone())));
+		assert_eq!(input.bytes_read(), 40_000);
+		assert_eq!(reports, [16_384, 32_768]);
+	}
+}
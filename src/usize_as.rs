@@ -0,0 +1,217 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding of `usize` and `isize` as fixed width integers.
+
+use core::{marker::PhantomData, mem};
+
+use crate::{
+	codec::{Decode, Encode, EncodeAsRef, Input, TypeInfo},
+	encode_like::EncodeLike,
+	DecodeWithMemTracking, Error, Output,
+};
+
+/// A `usize` encoded as the fixed width integer `R`, which is `u32` or `u64`.
+///
+/// `usize` and `isize` don't implement `Encode` and `Decode`, as their size depends on the target,
+/// so their encoding would too. This wrapper makes the width of the encoding explicit, either by
+/// using it as the type of a field or with `#[codec(encoded_as = "UsizeAs<u32>")]` on a `usize`
+/// field.
+///
+/// Encoding a value which doesn't fit in `R` is reported through [`Output::on_encode_error`], which
+/// panics for most outputs, like encoding a collection with more than `u32::MAX` items. Decoding a
+/// value which doesn't fit in `usize` fails.
+///
+/// ```
+/// # use parity_scale_codec::{decode_prefix, Encode, UsizeAs};
+/// let len = UsizeAs::<u32>::new(5);
+/// assert_eq!(len.encode(), 5u32.encode());
+/// assert_eq!(decode_prefix::<UsizeAs<u32>>(&len.encode()), Ok((len, &[][..])));
+/// ```
+///
+/// When the width of the encoding matches the target, e.g. `UsizeAs<u64>` on 64 bit targets,
+/// collections of them are encoded and decoded in bulk, like collections of `u64`.
+///
+/// A raw `usize` can't be encoded, not even through a generic function:
+///
+/// ```compile_fail
+/// # use parity_scale_codec::Encode;
+/// fn encode<T: Encode>(value: T) -> Vec<u8> {
+///     value.encode()
+/// }
+/// encode(5usize);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct UsizeAs<R>(pub usize, PhantomData<fn() -> R>);
+
+/// An `isize` encoded as the fixed width integer `R`, which is `i32` or `i64`.
+///
+/// See [`UsizeAs`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct IsizeAs<R>(pub isize, PhantomData<fn() -> R>);
+
+macro_rules! impl_size_as {
+	( $( $wrapper:ident<$repr:ident>($t:ident; $ty_info:ident) ),* ) => { $(
+		impl $wrapper<$repr> {
+			#[doc = concat!("Wrap `value`, to be encoded as `", stringify!($repr), "`.")]
+			pub const fn new(value: $t) -> Self {
+				Self(value, PhantomData)
+			}
+		}
+
+		impl EncodeLike for $wrapper<$repr> {}
+
+		impl Encode for $wrapper<$repr> {
+			// Collections are copied in bulk if the memory representation is the encoding.
+			const TYPE_INFO: TypeInfo = if mem::size_of::<$t>() == mem::size_of::<$repr>() {
+				TypeInfo::$ty_info
+			} else {
+				TypeInfo::Unknown
+			};
+
+			fn size_hint(&self) -> usize {
+				mem::size_of::<$repr>()
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				match <$repr>::try_from(self.0) {
+					Ok(value) => value.encode_to(dest),
+					Err(_) => dest.on_encode_error(
+						concat!(
+							"Attempted to encode a ",
+							stringify!($t),
+							" which doesn't fit in ",
+							stringify!($repr),
+						)
+						.into(),
+					),
+				}
+			}
+		}
+
+		impl Decode for $wrapper<$repr> {
+			const TYPE_INFO: TypeInfo = <Self as Encode>::TYPE_INFO;
+
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				let value = <$t>::try_from(<$repr>::decode(input)?)
+					.map_err(|_| concat!("Value doesn't fit in ", stringify!($t)))?;
+				Ok(Self::new(value))
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(mem::size_of::<$repr>())
+			}
+		}
+
+		impl DecodeWithMemTracking for $wrapper<$repr> {}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for $wrapper<$repr> {
+			fn max_encoded_len() -> usize {
+				mem::size_of::<$repr>()
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::ConstEncodedLen for $wrapper<$repr> {}
+
+		impl<'a> EncodeAsRef<'a, $t> for $wrapper<$repr> {
+			type RefType = $wrapper<$repr>;
+		}
+
+		impl From<&$t> for $wrapper<$repr> {
+			fn from(x: &$t) -> Self {
+				Self::new(*x)
+			}
+		}
+
+		impl From<$t> for $wrapper<$repr> {
+			fn from(x: $t) -> Self {
+				Self::new(x)
+			}
+		}
+
+		impl From<$wrapper<$repr>> for $t {
+			fn from(x: $wrapper<$repr>) -> $t {
+				x.0
+			}
+		}
+	)* }
+}
+
+impl_size_as!(
+	UsizeAs<u32>(usize; U32),
+	UsizeAs<u64>(usize; U64),
+	IsizeAs<i32>(isize; I32),
+	IsizeAs<i64>(isize; I64)
+);
+
+// SAFETY: The wrappers are `#[repr(transparent)]`, and on targets where `usize` has the width of
+// the encoding, they are encoded as the little endian bytes of the value, and have no invalid
+// values.
+#[cfg(target_pointer_width = "32")]
+unsafe impl crate::TriviallyCodable for UsizeAs<u32> {}
+#[cfg(target_pointer_width = "32")]
+unsafe impl crate::TriviallyCodable for IsizeAs<i32> {}
+#[cfg(target_pointer_width = "64")]
+unsafe impl crate::TriviallyCodable for UsizeAs<u64> {}
+#[cfg(target_pointer_width = "64")]
+unsafe impl crate::TriviallyCodable for IsizeAs<i64> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::alloc::vec::Vec;
+
+	#[test]
+	fn size_as_encodes_like_fixed_width_integers() {
+		assert_eq!(UsizeAs::<u32>::new(0x0102).encode(), 0x0102u32.encode());
+		assert_eq!(UsizeAs::<u64>::new(0x0102).encode(), 0x0102u64.encode());
+		assert_eq!(IsizeAs::<i32>::new(-2).encode(), (-2i32).encode());
+		assert_eq!(IsizeAs::<i64>::new(-2).encode(), (-2i64).encode());
+
+		assert_eq!(UsizeAs::<u32>::decode(&mut &7u32.encode()[..]).unwrap().0, 7);
+		assert_eq!(IsizeAs::<i64>::decode(&mut &(-7i64).encode()[..]).unwrap().0, -7);
+		assert_eq!(UsizeAs::<u64>::encoded_fixed_size(), Some(8));
+	}
+
+	#[test]
+	fn vec_of_size_as_works() {
+		let values = (0..1000).map(UsizeAs::<u64>::new).collect::<Vec<_>>();
+		let encoded = values.encode();
+		assert_eq!(encoded, (0..1000u64).collect::<Vec<_>>().encode());
+		assert_eq!(Vec::<UsizeAs<u64>>::decode(&mut &encoded[..]).unwrap(), values);
+
+		let values = [IsizeAs::<i32>::new(-1), IsizeAs::<i32>::new(1)];
+		assert_eq!(<[IsizeAs<i32>; 2]>::decode(&mut &values.encode()[..]).unwrap(), values);
+	}
+
+	#[cfg(target_pointer_width = "32")]
+	#[test]
+	fn decoding_too_large_value_fails() {
+		assert_eq!(
+			UsizeAs::<u64>::decode(&mut &u64::MAX.encode()[..]),
+			Err("Value doesn't fit in usize".into())
+		);
+	}
+
+	#[cfg(all(target_pointer_width = "64", not(feature = "no-panic-decode")))]
+	#[test]
+	#[should_panic(expected = "Attempted to encode a usize which doesn't fit in u32")]
+	fn encoding_too_large_value_panics() {
+		UsizeAs::<u32>::new(usize::MAX).encode();
+	}
+}
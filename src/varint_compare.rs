@@ -0,0 +1,291 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of the SCALE compact encoding with other variable-length integer encodings.
+//!
+//! This module compares [`CompactU64`], i.e. `Compact<u64>`, with the [`Leb128`] encoding of
+//! lengths, applied to `u64`, and with [`PrefixVarint`], on the integers an application actually
+//! encodes, e.g. to justify the choice of a wire format. `PrefixVarint` is only implemented for
+//! this comparison and is not meant to be used in encodings.
+//!
+//! [`compare`] encodes and decodes a distribution of integers with each scheme, and reports their
+//! sizes and timings:
+//!
+//! ```
+//! use parity_scale_codec::{
+//!     varint_compare::{compare, Scheme},
+//!     Leb128,
+//! };
+//!
+//! // Mostly small values, e.g. lengths or balances in the smallest unit.
+//! let values = (0..10_000u64).map(|i| i % 100 + (i % 1000 == 0) as u64 * u64::MAX).collect::<Vec<_>>();
+//! let reports = compare(&values, 3);
+//!
+//! let leb128 = reports.iter().find(|report| report.name == Leb128::NAME).unwrap();
+//! assert_eq!(leb128.values, values.len());
+//! assert!(leb128.total_bytes < 2 * values.len());
+//! ```
+//!
+//! The `varint` benchmark compares the schemes with criterion on a few distributions.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+	length_codec::{leb128_decode, leb128_encode, leb128_len},
+	Compact, CompactLen, Decode, Encode, Error, Leb128,
+};
+
+/// A variable-length encoding of `u64`.
+pub trait Scheme {
+	/// The name of the scheme in reports.
+	const NAME: &'static str;
+
+	/// Append the encoding of `value` to `dest`.
+	fn encode(value: u64, dest: &mut Vec<u8>);
+
+	/// The number of bytes `encode` appends for `value`.
+	fn encoded_len(value: u64) -> usize;
+
+	/// Decode a value from the start of `input`, advancing it past the value.
+	///
+	/// Only the shortest encoding of a value is accepted.
+	fn decode(input: &mut &[u8]) -> Result<u64, Error>;
+}
+
+/// The SCALE compact encoding of `u64`, i.e. [`Compact<u64>`].
+///
+/// Unlike [`ScaleCompact`](crate::ScaleCompact), which encodes lengths as `Compact<u32>`, it
+/// covers the whole range of the compared values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactU64;
+
+impl Scheme for CompactU64 {
+	const NAME: &'static str = "SCALE compact";
+
+	fn encode(value: u64, dest: &mut Vec<u8>) {
+		Compact(value).encode_to(dest)
+	}
+
+	fn encoded_len(value: u64) -> usize {
+		Compact::<u64>::compact_len(&value)
+	}
+
+	fn decode(input: &mut &[u8]) -> Result<u64, Error> {
+		Compact::<u64>::decode(input).map(|Compact(value)| value)
+	}
+}
+
+/// Unsigned LEB128, e.g. used by Protocol Buffers and WebAssembly.
+impl Scheme for Leb128 {
+	const NAME: &'static str = "LEB128";
+
+	fn encode(value: u64, dest: &mut Vec<u8>) {
+		leb128_encode(value, dest)
+	}
+
+	fn encoded_len(value: u64) -> usize {
+		leb128_len(value)
+	}
+
+	fn decode(input: &mut &[u8]) -> Result<u64, Error> {
+		leb128_decode(input)
+	}
+}
+
+/// A prefix varint, as used by e.g. the `vint64` crate: the number of trailing zeros of the first
+/// byte is the number of bytes following it. The value is stored little endian after these
+/// length bits, so values of up to 56 bits take one byte per 7 bits, and larger values 9 bytes.
+///
+/// Unlike LEB128, the length is known from the first byte, so decoding doesn't branch per byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixVarint;
+
+impl Scheme for PrefixVarint {
+	const NAME: &'static str = "PrefixVarint";
+
+	fn encode(value: u64, dest: &mut Vec<u8>) {
+		let len = Self::encoded_len(value);
+		if len == 9 {
+			dest.push(0);
+			dest.extend_from_slice(&value.to_le_bytes());
+		} else {
+			let encoded = (value << len) | (1 << (len - 1));
+			dest.extend_from_slice(&encoded.to_le_bytes()[..len]);
+		}
+	}
+
+	fn encoded_len(value: u64) -> usize {
+		let bits = significant_bits(value);
+		if bits > 56 {
+			9
+		} else {
+			bits.div_ceil(7) as usize
+		}
+	}
+
+	fn decode(input: &mut &[u8]) -> Result<u64, Error> {
+		let first = *input.first().ok_or("Not enough data to decode PrefixVarint")?;
+		let len = first.trailing_zeros() as usize + 1;
+		if input.len() < len {
+			return Err("Not enough data to decode PrefixVarint".into());
+		}
+		let (bytes, rest) = input.split_at(len);
+		let value = if len == 9 {
+			u64::from_le_bytes(bytes[1..].try_into().expect("8 bytes were split off; qed"))
+		} else {
+			let mut buf = [0; 8];
+			buf[..len].copy_from_slice(bytes);
+			u64::from_le_bytes(buf) >> len
+		};
+		if Self::encoded_len(value) != len {
+			return Err("PrefixVarint value is not in its shortest form".into());
+		}
+		*input = rest;
+		Ok(value)
+	}
+}
+
+/// The number of bits needed to represent `value`, at least 1.
+fn significant_bits(value: u64) -> u32 {
+	(u64::BITS - value.leading_zeros()).max(1)
+}
+
+/// The results of a scheme for a distribution of values, see [`compare`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+	/// The name of the scheme, see [`Scheme::NAME`].
+	pub name: &'static str,
+	/// The number of values.
+	pub values: usize,
+	/// The number of bytes of the encoding of all values.
+	pub total_bytes: usize,
+	/// The length of the longest encoded value.
+	pub max_bytes: usize,
+	/// The fastest time it took to encode all values.
+	pub encode_time: Duration,
+	/// The fastest time it took to decode all values.
+	pub decode_time: Duration,
+}
+
+impl Report {
+	/// The average number of bytes per value.
+	pub fn mean_bytes(&self) -> f64 {
+		self.total_bytes as f64 / self.values.max(1) as f64
+	}
+}
+
+/// Run `scheme` on `values`, timing the fastest of `rounds` encodings and decodings.
+///
+/// # Panics
+///
+/// If the values don't decode to themselves, which would be a bug of the scheme.
+pub fn run<S: Scheme>(values: &[u64], rounds: usize) -> Report {
+	let mut encoded = Vec::new();
+	let mut encode_time = Duration::MAX;
+	let mut decode_time = Duration::MAX;
+	for _ in 0..rounds.max(1) {
+		encoded.clear();
+		let start = Instant::now();
+		for &value in values {
+			S::encode(value, &mut encoded);
+		}
+		encode_time = encode_time.min(start.elapsed());
+
+		let mut input = &encoded[..];
+		let start = Instant::now();
+		for &value in values {
+			assert_eq!(S::decode(&mut input), Ok(value), "{} roundtrip", S::NAME);
+		}
+		decode_time = decode_time.min(start.elapsed());
+	}
+
+	Report {
+		name: S::NAME,
+		values: values.len(),
+		total_bytes: encoded.len(),
+		max_bytes: values.iter().map(|&value| S::encoded_len(value)).max().unwrap_or(0),
+		encode_time,
+		decode_time,
+	}
+}
+
+/// Compare [`CompactU64`], [`Leb128`] and [`PrefixVarint`] on `values`, see [`run`].
+pub fn compare(values: &[u64], rounds: usize) -> Vec<Report> {
+	vec![
+		run::<CompactU64>(values, rounds),
+		run::<Leb128>(values, rounds),
+		run::<PrefixVarint>(values, rounds),
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check<S: Scheme>() {
+		let mut values = vec![0, 1, u64::MAX, u64::MAX - 1];
+		for bit in 0..64 {
+			values.extend([(1 << bit) - 1, 1 << bit, (1 << bit) + 1]);
+		}
+		for value in values {
+			let mut encoded = Vec::new();
+			S::encode(value, &mut encoded);
+			assert_eq!(encoded.len(), S::encoded_len(value), "{} {value}", S::NAME);
+			let mut input = &encoded[..];
+			assert_eq!(S::decode(&mut input), Ok(value), "{} {value}", S::NAME);
+			assert!(input.is_empty());
+			assert!(S::decode(&mut &encoded[..encoded.len() - 1]).is_err());
+		}
+	}
+
+	#[test]
+	fn schemes_roundtrip() {
+		check::<CompactU64>();
+		check::<Leb128>();
+		check::<PrefixVarint>();
+	}
+
+	#[test]
+	fn schemes_encodings() {
+		fn encode<S: Scheme>(value: u64) -> Vec<u8> {
+			let mut encoded = Vec::new();
+			S::encode(value, &mut encoded);
+			encoded
+		}
+
+		assert_eq!(encode::<Leb128>(300), [0xac, 0x02]);
+		assert_eq!(encode::<PrefixVarint>(127), [0xff]);
+		assert_eq!(encode::<PrefixVarint>(128), [0x02, 0x02]);
+		assert_eq!(encode::<PrefixVarint>(u64::MAX), [&[0][..], &[0xff; 8]].concat());
+
+		assert!(Leb128::decode(&mut &[0x80, 0x00][..]).is_err());
+		assert!(PrefixVarint::decode(&mut &[0x02, 0x00][..]).is_err());
+	}
+
+	#[test]
+	fn compare_reports_sizes() {
+		let reports = compare(&[1, 100, 1 << 20], 1);
+		let sizes =
+			reports.iter().map(|r| (r.name, r.total_bytes, r.max_bytes)).collect::<Vec<_>>();
+		assert_eq!(
+			sizes,
+			[
+				("SCALE compact", 1 + 2 + 4, 4),
+				("LEB128", 1 + 1 + 3, 3),
+				("PrefixVarint", 1 + 1 + 3, 3)
+			]
+		);
+		assert_eq!(reports[0].mean_bytes(), 7.0 / 3.0);
+	}
+}
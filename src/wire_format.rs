@@ -0,0 +1,124 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wire format versioning and a runtime self-test of the encoding.
+
+use crate::{
+	alloc::{string::String, vec::Vec},
+	Compact, Decode, Encode, Error, OptionBool,
+};
+
+/// The version of the SCALE wire format implemented by this crate.
+///
+/// This is only bumped if the encoding of any type implemented by this crate changes in a way that
+/// is not backwards compatible.
+pub const WIRE_FORMAT_VERSION: u32 = 1;
+
+/// Encode a fixed set of values and compare them against the bytes expected by
+/// [`WIRE_FORMAT_VERSION`]. The expected bytes are also decoded and compared against the values.
+///
+/// This is cheap enough to be called on startup. It catches changes to the wire format that can
+/// be introduced e.g. by patching this crate with a fork.
+///
+/// Returns an error describing the first value that doesn't match.
+pub fn self_check() -> Result<(), Error> {
+	check("u8", 0x12u8, &[0x12])?;
+	check("u16", 0x1234u16, &[0x34, 0x12])?;
+	check("u32", 0x1234_5678u32, &[0x78, 0x56, 0x34, 0x12])?;
+	check("u64", 0x0102_0304_0506_0708u64, &[8, 7, 6, 5, 4, 3, 2, 1])?;
+	check(
+		"u128",
+		0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128,
+		&[16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1],
+	)?;
+	check("i32", -2i32, &[0xfe, 0xff, 0xff, 0xff])?;
+	check("bool", true, &[1])?;
+
+	check("Compact<u8>(0)", Compact(0u8), &[0x00])?;
+	check("Compact<u8>(63)", Compact(63u8), &[0xfc])?;
+	check("Compact<u8>(64)", Compact(64u8), &[0x01, 0x01])?;
+	check("Compact<u16>(16383)", Compact(16383u16), &[0xfd, 0xff])?;
+	check("Compact<u32>(16384)", Compact(16384u32), &[0x02, 0x00, 0x01, 0x00])?;
+	check("Compact<u32>(2^30 - 1)", Compact((1u32 << 30) - 1), &[0xfe, 0xff, 0xff, 0xff])?;
+	check("Compact<u32>(2^30)", Compact(1u32 << 30), &[0x03, 0x00, 0x00, 0x00, 0x40])?;
+	check("Compact<u32>(u32::MAX)", Compact(u32::MAX), &[0x03, 0xff, 0xff, 0xff, 0xff])?;
+	check(
+		"Compact<u64>(u64::MAX)",
+		Compact(u64::MAX),
+		&[0x13, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+	)?;
+	check(
+		"Compact<u128>(2^64)",
+		Compact(1u128 << 64),
+		&[0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+	)?;
+	check(
+		"Compact<u128>(u128::MAX)",
+		Compact(u128::MAX),
+		&[
+			0x33, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+			0xff, 0xff, 0xff,
+		],
+	)?;
+
+	check("Option<u32>::None", Option::<u32>::None, &[0])?;
+	check("Option<u32>::Some", Some(1u32), &[1, 1, 0, 0, 0])?;
+	check("OptionBool(Some(false))", OptionBool(Some(false)), &[2])?;
+	check("Result<u8, u8>::Ok", Result::<u8, u8>::Ok(1), &[0, 1])?;
+	check("Result<u8, u8>::Err", Result::<u8, u8>::Err(2), &[1, 2])?;
+	check("Vec<u8>", Vec::from([1u8, 2, 3]), &[0x0c, 1, 2, 3])?;
+	check("String", String::from("abc"), &[0x0c, b'a', b'b', b'c'])?;
+	check("(u8, u16)", (1u8, 2u16), &[1, 2, 0])?;
+
+	Ok(())
+}
+
+fn check<T: Encode + Decode + PartialEq>(
+	name: &'static str,
+	value: T,
+	expected: &[u8],
+) -> Result<(), Error> {
+	if value.encode() != expected {
+		return Err(Error::from("unexpected encoding").chain(name));
+	}
+
+	match T::decode(&mut &expected[..]) {
+		Ok(decoded) if decoded == value => Ok(()),
+		Ok(_) => Err(Error::from("unexpected decoded value").chain(name)),
+		Err(e) => Err(e.chain(name)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn self_check_works() {
+		assert_eq!(self_check(), Ok(()));
+	}
+
+	#[cfg(feature = "chain-error")]
+	#[test]
+	fn check_reports_mismatch() {
+		assert_eq!(
+			check("u16", 1u16, &[0, 1]).unwrap_err().to_string(),
+			"u16:\n\tunexpected encoding\n",
+		);
+		assert_eq!(
+			check("Compact<u8>", Compact(64u8), &[0x00, 0x01]).unwrap_err().to_string(),
+			"Compact<u8>:\n\tunexpected encoding\n",
+		);
+	}
+}
@@ -0,0 +1,86 @@
+use parity_scale_codec::{decode_prefix, Compact, Encode};
+use parity_scale_codec_derive::{Decode as DeriveDecode, Encode as DeriveEncode};
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[codec(append_with = "Packet::checksum")]
+#[codec(document_layout)]
+struct Packet {
+	kind: u8,
+	payload: Vec<u8>,
+}
+
+impl Packet {
+	fn checksum(&self) -> u8 {
+		self.payload.iter().fold(self.kind, |sum, byte| sum.wrapping_add(*byte))
+	}
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[repr(transparent)]
+#[codec(append_with = "Words::count")]
+struct Words(String);
+
+impl Words {
+	fn count(&self) -> Compact<u32> {
+		Compact(self.0.split_whitespace().count() as u32)
+	}
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[codec(append_with = "Shape::sides")]
+enum Shape {
+	Circle(u32),
+	Polygon(Vec<(i32, i32)>),
+}
+
+impl Shape {
+	fn sides(&self) -> u16 {
+		match self {
+			Shape::Circle(_) => 0,
+			Shape::Polygon(points) => points.len() as u16,
+		}
+	}
+}
+
+#[test]
+fn appended_value_is_encoded_last() {
+	let packet = Packet { kind: 1, payload: vec![2, 3] };
+	let encoded = packet.encode();
+	assert_eq!(encoded, [1, 8, 2, 3, 6]);
+	assert_eq!(packet.size_hint(), packet.kind.size_hint() + packet.payload.size_hint() + 1);
+	assert_eq!(decode_prefix::<Packet>(&encoded), Ok((packet, &[][..])));
+	assert_eq!(
+		Packet::ENCODING_LAYOUT,
+		"struct Packet\n  kind: u8\n  payload: Vec<u8>\n  appended: Packet::checksum\n"
+	);
+
+	let words = Words("two words".into());
+	assert_eq!(words.encode(), ("two words", Compact(2u32)).encode());
+	assert_eq!(decode_prefix::<Words>(&words.encode()), Ok((words, &[][..])));
+
+	let shape = Shape::Polygon(vec![(0, 0), (1, 0), (0, 1)]);
+	assert_eq!(
+		shape.encode(),
+		[&[1][..], &vec![(0, 0), (1, 0), (0, 1)].encode(), &[3, 0]].concat()
+	);
+	assert_eq!(decode_prefix::<Shape>(&shape.encode()), Ok((shape, &[][..])));
+	assert_eq!(Shape::Circle(5).encode(), [0, 5, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn wrong_appended_value_is_rejected() {
+	assert!(decode_prefix::<Packet>(&[1, 8, 2, 3, 7]).is_err());
+	// The appended value is missing.
+	assert!(decode_prefix::<Packet>(&[1, 8, 2, 3]).is_err());
+	assert!(decode_prefix::<Words>(&("two words", Compact(3u32)).encode()).is_err());
+	assert!(decode_prefix::<Shape>(&[0, 5, 0, 0, 0, 1, 0]).is_err());
+}
+
+#[cfg(feature = "chain-error")]
+#[test]
+fn wrong_appended_value_error() {
+	assert_eq!(
+		decode_prefix::<Packet>(&[1, 8, 2, 3, 7]).unwrap_err().to_string(),
+		"Could not decode `Packet`, the appended value doesn't match `Packet::checksum`",
+	);
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use parity_scale_codec::Decode;
+use parity_scale_codec::{decode_prefix, decode_with_diagnostics};
 use parity_scale_codec_derive::Decode as DeriveDecode;
 
 #[derive(DeriveDecode, Debug)]
@@ -41,7 +41,7 @@ fn full_error_struct_named() {
 "#;
 
 	assert_eq!(
-		Wrapper::<StructNamed>::decode(&mut &encoded[..]).unwrap_err().to_string(),
+		decode_prefix::<Wrapper<StructNamed>>(&encoded).unwrap_err().to_string(),
 		String::from(err),
 	);
 }
@@ -55,7 +55,7 @@ fn full_error_struct_unnamed() {
 "#;
 
 	assert_eq!(
-		Wrapper::<StructUnnamed>::decode(&mut &encoded[..]).unwrap_err().to_string(),
+		decode_prefix::<Wrapper<StructUnnamed>>(&encoded).unwrap_err().to_string(),
 		String::from(err),
 	);
 }
@@ -65,7 +65,7 @@ fn full_error_enum_unknown_variant() {
 	let encoded = [2];
 	let err = r#"Could not decode `E`, variant doesn't exist"#;
 
-	assert_eq!(E::decode(&mut &encoded[..]).unwrap_err().to_string(), String::from(err),);
+	assert_eq!(decode_prefix::<E>(&encoded).unwrap_err().to_string(), String::from(err),);
 }
 
 #[test]
@@ -75,7 +75,7 @@ fn full_error_enum_named_field() {
 	Not enough data to fill buffer
 "#;
 
-	assert_eq!(E::decode(&mut &encoded[..]).unwrap_err().to_string(), String::from(err),);
+	assert_eq!(decode_prefix::<E>(&encoded).unwrap_err().to_string(), String::from(err),);
 }
 
 #[test]
@@ -85,5 +85,23 @@ fn full_error_enum_unnamed_field() {
 	Not enough data to fill buffer
 "#;
 
-	assert_eq!(E::decode(&mut &encoded[..]).unwrap_err().to_string(), String::from(err),);
+	assert_eq!(decode_prefix::<E>(&encoded).unwrap_err().to_string(), String::from(err),);
+}
+
+#[test]
+fn full_error_with_diagnostics() {
+	let encoded = [1, 0, 0, 1, 0];
+	// The first line names the type with `type_name`, whose output isn't stable.
+	let nested = r#"	Could not decode `Wrapper.0`:
+		Could not decode `E::VariantUnnamed.0`:
+			Not enough data to fill buffer
+"#;
+
+	let err = decode_with_diagnostics::<Wrapper<(u8, u16, E)>, _>(&mut &encoded[..])
+		.unwrap_err()
+		.to_string();
+	let (context, rest) = err.split_once('\n').unwrap();
+	assert!(context.starts_with("Could not decode `"), "{err}");
+	assert!(context.ends_with("` at byte offset 4:"), "{err}");
+	assert_eq!(rest, nested);
 }
@@ -0,0 +1,86 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parity_scale_codec::{codec_fuzz, decode_prefix, Encode};
+use parity_scale_codec_derive::{CodecFuzz, Decode as DeriveDecode, Encode as DeriveEncode};
+
+// Each type gets a generated `codec_fuzz_exhaustive_*` test.
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, CodecFuzz)]
+enum Direction {
+	North,
+	#[codec(index = 5)]
+	East,
+	South,
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, CodecFuzz)]
+struct Point {
+	x: i8,
+	#[codec(skip)]
+	cached: u64,
+	y: u8,
+}
+
+// Names which are the same in snake case get different tests.
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, CodecFuzz)]
+struct HttpFlag(bool);
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, CodecFuzz)]
+struct Http_flag(u8);
+
+// Too large or generic for the exhaustive test, but the fuzz target is generated.
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, CodecFuzz)]
+enum Command {
+	Stop,
+	Move(Direction, u8),
+	Turn { clockwise: bool },
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, CodecFuzz)]
+struct Message<T> {
+	#[codec(compact)]
+	id: u64,
+	payload: Vec<T>,
+}
+
+#[test]
+fn exhaustive_tests_are_generated_for_small_types() {
+	codec_fuzz_exhaustive_Direction();
+	codec_fuzz_exhaustive_Point();
+	codec_fuzz_exhaustive_HttpFlag();
+	codec_fuzz_exhaustive_Http_flag();
+}
+
+#[test]
+fn roundtrip_of_large_types() {
+	let encoded =
+		Message { id: 7, payload: vec![Command::Stop, Command::Turn { clockwise: true }] }.encode();
+	codec_fuzz::roundtrip::<Message<Command>>(&encoded);
+	assert!(decode_prefix::<Message<Command>>(&encoded).is_ok());
+}
+
+#[cfg(fuzzing)]
+#[test]
+fn fuzz_targets_are_generated() {
+	for data in [&[][..], &[0], &[1, 5, 3], &[2, 1], &[2, 2], &[0xff; 5]] {
+		Direction::codec_fuzz_roundtrip(data);
+		Command::codec_fuzz_roundtrip(data);
+		Point::codec_fuzz_roundtrip(data);
+		Message::<Command>::codec_fuzz_roundtrip(data);
+	}
+}
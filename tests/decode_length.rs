@@ -0,0 +1,60 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "derive")]
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use parity_scale_codec::{DecodeLength, Encode, Error};
+
+#[derive(Encode, DecodeLength)]
+enum Calls<A, B> {
+	V1(Vec<A>),
+	#[codec(index = 7)]
+	V2 {
+		calls: VecDeque<B>,
+		#[codec(skip)]
+		_cached: u64,
+	},
+	#[codec(skip)]
+	#[allow(dead_code)]
+	Unused(u8, u8),
+	Map(BTreeMap<u32, A>),
+	Set(BTreeSet<u8>),
+}
+
+#[test]
+fn decode_length_reads_the_length_of_the_variant() {
+	fn len(value: &Calls<u32, String>) -> Result<usize, Error> {
+		<Calls<u32, String> as DecodeLength>::len(&value.encode())
+	}
+
+	assert_eq!(len(&Calls::V1(vec![1, 2, 3])), Ok(3));
+	assert_eq!(len(&Calls::V2 { calls: VecDeque::from(vec!["a".into(); 70]), _cached: 1 }), Ok(70));
+	assert_eq!(len(&Calls::Map(BTreeMap::from([(1, 2)]))), Ok(1));
+	assert_eq!(len(&Calls::Set(BTreeSet::new())), Ok(0));
+}
+
+#[test]
+fn decode_length_rejects_invalid_variants() {
+	assert_eq!(
+		<Calls<u8, u8> as DecodeLength>::len(&[]),
+		Err("Could not decode `Calls`, failed to read variant byte".into())
+	);
+	assert_eq!(
+		<Calls<u8, u8> as DecodeLength>::len(&[9, 0]),
+		Err("Could not decode `Calls`, variant doesn't exist".into())
+	);
+	assert!(<Calls<u8, u8> as DecodeLength>::len(&[7]).is_err());
+}
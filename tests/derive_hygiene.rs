@@ -0,0 +1,119 @@
+#![cfg(feature = "derive")]
+
+use parity_scale_codec::{decode_prefix, Compact, Encode};
+
+macro_rules! make_enum {
+	($name:ident, $( #[$attr:meta] )* { $( $variant:ident ( $ty:ty ) = $index:literal ),* }) => {
+		$( #[$attr] )*
+		#[derive(Debug, PartialEq)]
+		enum $name {
+			$(
+				#[codec(index = $index)]
+				$variant($ty),
+			)*
+			Other { #[codec(compact)] value: u64, list: Vec<u8> },
+		}
+	};
+}
+
+macro_rules! outer {
+	($($tt:tt)*) => { make_enum!($($tt)*); };
+}
+
+outer!(Nested, #[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode, ::parity_scale_codec::DecodeWithMemTracking)] {
+	A(u8) = 3,
+	B(Compact<u32>) = 7
+});
+
+macro_rules! make_struct {
+	($name:ident { $( $field:ident : $ty:ty ),* }) => {
+		#[derive(Debug, PartialEq, ::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+		struct $name {
+			$( $field: $ty, )*
+			#[codec(compact)]
+			tail: u32,
+			#[codec(skip)]
+			skipped: u8,
+		}
+	};
+}
+
+make_struct!(Fields { a: u16, b: Vec<u8> });
+
+#[test]
+fn derives_from_nested_macros() {
+	let value = Nested::B(Compact(5));
+	assert_eq!(value.encode(), [7, 20]);
+	assert_eq!(decode_prefix::<Nested>(&value.encode()), Ok((value, &[][..])));
+	let value = Nested::Other { value: 1, list: vec![2] };
+	assert_eq!(decode_prefix::<Nested>(&value.encode()), Ok((value, &[][..])));
+
+	let value = Fields { a: 1, b: vec![2], tail: 3, skipped: 0 };
+	assert_eq!(value.encode(), [1, 0, 4, 2, 12]);
+	assert_eq!(decode_prefix::<Fields>(&value.encode()), Ok((value, &[][..])));
+}
+
+macro_rules! make_all {
+	($name:ident, $field:ident, $other:ident) => {
+		#[derive(
+			Debug,
+			PartialEq,
+			::parity_scale_codec::Encode,
+			::parity_scale_codec::Decode,
+			::parity_scale_codec::MaxEncodedLen,
+			::parity_scale_codec::Preflight,
+			::parity_scale_codec::EncodingFingerprint,
+		)]
+		#[codec(document_layout)]
+		struct $name {
+			$field: u32,
+			#[codec(skip, default = "double")]
+			$other: u64,
+			#[codec(encoded_as = "Compact<u64>")]
+			third: u64,
+		}
+	};
+}
+
+fn double(x: &u32) -> u64 {
+	*x as u64 * 2
+}
+
+#[cfg(feature = "max-encoded-len")]
+make_all!(All, first, second);
+
+mod facade {
+	pub use parity_scale_codec as codec;
+
+	#[macro_export]
+	macro_rules! facade_type {
+		($name:ident($ty:ty)) => {
+			#[derive(
+				Debug, PartialEq, $crate::facade::codec::Encode, $crate::facade::codec::Decode,
+			)]
+			#[codec(crate = $crate::facade::codec)]
+			struct $name($ty, #[codec(compact)] u128);
+		};
+	}
+}
+
+facade_type!(ThroughFacade(bool));
+
+#[cfg(feature = "max-encoded-len")]
+#[test]
+fn derives_all_traits_from_macros() {
+	use parity_scale_codec::MaxEncodedLen;
+
+	let value = All { first: 4, second: 8, third: 1 };
+	assert_eq!(value.encode(), [4, 0, 0, 0, 4]);
+	assert_eq!(decode_prefix::<All>(&value.encode()), Ok((value, &[][..])));
+	assert_eq!(All::max_encoded_len(), 13);
+	assert_eq!(All::ENCODING_LAYOUT, "struct All\n  first: u32\n  third: u64 as Compact<u64>\n");
+}
+
+#[test]
+fn derives_from_macros_with_other_crate_paths() {
+	let value = ThroughFacade(true, 1);
+	assert_eq!(value.encode(), [1, 4]);
+	assert_eq!(decode_prefix::<ThroughFacade>(&value.encode()), Ok((value, &[][..])));
+}
@@ -0,0 +1,88 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "derive")]
+
+use parity_scale_codec::{decode_prefix, AnyScale, Compact, Decode, Encode, EncodingFingerprint};
+
+#[derive(Debug, PartialEq, Encode, Decode, EncodingFingerprint)]
+struct Transfer<T> {
+	#[codec(compact)]
+	amount: u64,
+	memo: T,
+	#[codec(skip)]
+	_cached: u8,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, EncodingFingerprint)]
+enum Job {
+	Resize(u32, u32),
+	#[codec(index = 3)]
+	Delete {
+		id: Vec<u8>,
+	},
+	#[codec(skip)]
+	#[allow(dead_code)]
+	Internal(std::cell::Cell<u8>),
+}
+
+mod renamed {
+	use super::*;
+
+	#[derive(Encode, EncodingFingerprint)]
+	pub struct Transfer<T> {
+		#[codec(compact)]
+		pub value: u64,
+		pub data: T,
+	}
+
+	#[derive(Encode, EncodingFingerprint)]
+	#[allow(dead_code)]
+	pub enum Job {
+		Resize(u32, u32),
+		Delete { id: Vec<u8> },
+	}
+
+	#[derive(Encode, EncodingFingerprint)]
+	pub struct Tuple(#[codec(encoded_as = "Compact<u64>")] pub u64, pub String);
+}
+
+#[test]
+fn fingerprint_depends_on_encoding() {
+	assert_eq!(
+		Transfer::<String>::FINGERPRINT,
+		renamed::Transfer::<String>::FINGERPRINT,
+		"field names and skipped fields don't change the fingerprint"
+	);
+	assert_ne!(Transfer::<String>::FINGERPRINT, Transfer::<Vec<u16>>::FINGERPRINT);
+	assert_ne!(Job::FINGERPRINT, renamed::Job::FINGERPRINT, "variant indexes change it");
+	assert_ne!(renamed::Tuple::FINGERPRINT, Transfer::<String>::FINGERPRINT);
+}
+
+#[test]
+fn any_scale_downcasts_derived_types() {
+	let queue = vec![
+		AnyScale::new(&Job::Delete { id: vec![1, 2] }),
+		AnyScale::new(&Transfer { amount: 5, memo: String::from("rent"), _cached: 0 }),
+	];
+	let queue = decode_prefix::<Vec<AnyScale>>(&queue.encode()).unwrap().0;
+
+	assert_eq!(queue[0].try_downcast_decode::<Job>(), Ok(Job::Delete { id: vec![1, 2] }));
+	assert!(queue[0].try_downcast_decode::<Transfer<String>>().is_err());
+	assert_eq!(
+		queue[1].try_downcast_decode::<Transfer<String>>(),
+		Ok(Transfer { amount: 5, memo: String::from("rent"), _cached: 0 })
+	);
+	assert!(!queue[1].is::<Transfer<Vec<u8>>>());
+}
@@ -0,0 +1,81 @@
+#![cfg(all(feature = "derive", feature = "enum-stats"))]
+
+use parity_scale_codec::{
+	decode_prefix,
+	enum_stats::{self, Counters, Counts},
+	Encode,
+};
+use parity_scale_codec_derive::{Decode as DeriveDecode, Encode as DeriveEncode};
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+enum Message {
+	Ping,
+	#[codec(index = 5)]
+	Data(Vec<u8>),
+	Transfer {
+		amount: u64,
+	},
+	#[allow(dead_code)]
+	Unused,
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+enum Wrapper<T> {
+	Inner(T),
+}
+
+static COUNTERS: Counters = Counters::new();
+
+fn counts(name: &str) -> Option<(u8, Counts)> {
+	COUNTERS
+		.snapshot()
+		.into_iter()
+		.find(|(variant, _)| variant.name == name)
+		.map(|(variant, counts)| (variant.index, counts))
+}
+
+// All checks are in one test, as the recorder is global.
+#[test]
+fn enum_variants_are_counted() {
+	enum_stats::set_recorder(&COUNTERS).ok().unwrap();
+	assert!(enum_stats::set_recorder(&COUNTERS).is_err());
+
+	let messages = vec![
+		Message::Ping,
+		Message::Data(vec![1, 2]),
+		Message::Ping,
+		Message::Transfer { amount: 7 },
+	];
+	let encoded = Wrapper::Inner(messages).encode();
+	let decoded = decode_prefix::<Wrapper<Vec<Message>>>(&encoded).unwrap().0;
+	assert_eq!(decoded.encode(), encoded);
+
+	assert_eq!(counts("Ping"), Some((0, Counts { encoded: 4, decoded: 2 })));
+	assert_eq!(counts("Data"), Some((5, Counts { encoded: 2, decoded: 1 })));
+	assert_eq!(counts("Transfer"), Some((2, Counts { encoded: 2, decoded: 1 })));
+	assert_eq!(counts("Unused"), None);
+
+	let (inner, inner_counts) = COUNTERS
+		.snapshot()
+		.into_iter()
+		.find(|(variant, _)| variant.name == "Inner")
+		.unwrap();
+	assert_eq!(inner.enum_name, "enum_stats::Wrapper");
+	assert_eq!(inner_counts, Counts { encoded: 2, decoded: 1 });
+
+	// Measuring the encoded size is not counted.
+	COUNTERS.clear();
+	let message = Message::Transfer { amount: 7 };
+	assert_eq!(message.encoded_size(), 9);
+	assert!(message.fits_within(9));
+	enum_stats::without_recording(|| message.encode());
+	assert_eq!(COUNTERS.snapshot().len(), 0);
+	message.encode();
+	assert_eq!(counts("Transfer"), Some((2, Counts { encoded: 1, decoded: 0 })));
+
+	// Failed decodes are not counted.
+	COUNTERS.clear();
+	assert!(decode_prefix::<Message>(&[5, 8, 1]).is_err());
+	assert!(decode_prefix::<Message>(&[9]).is_err());
+	assert_eq!(COUNTERS.snapshot().len(), 0);
+}
@@ -0,0 +1,87 @@
+use parity_scale_codec::HasCompact;
+use parity_scale_codec_derive::{Decode as DeriveDecode, Encode as DeriveEncode};
+use std::{marker::PhantomData, sync::Mutex};
+
+#[derive(DeriveEncode, DeriveDecode)]
+#[codec(document_layout)]
+struct Struct<T: HasCompact> {
+	a: u32,
+	#[codec(compact)]
+	b: T,
+	#[codec(skip)]
+	_c: Vec<u8>,
+	#[codec(encoded_as = "<u64 as HasCompact>::Type")]
+	d: u64,
+	#[codec(lock)]
+	e: Mutex<Option<Vec<(u8, bool)>>>,
+}
+
+#[derive(DeriveEncode)]
+#[codec(document_layout)]
+struct Tuple(u8, #[codec(compact)] u128, &'static [u8]);
+
+#[derive(DeriveEncode, DeriveDecode)]
+#[codec(document_layout)]
+enum Enum {
+	A,
+	#[codec(index = 5)]
+	B(u16, [u8; 32]),
+	#[codec(skip)]
+	#[allow(dead_code)]
+	Skipped,
+	C {
+		#[codec(compact)]
+		x: u64,
+	},
+	#[codec(index = 9)]
+	D,
+}
+
+#[derive(DeriveEncode)]
+#[codec(document_layout)]
+struct Unit;
+
+const INDEX: usize = 7;
+
+#[derive(DeriveEncode)]
+#[codec(document_layout)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Discriminants {
+	A = 3u8,
+	#[codec(index = INDEX)]
+	B(PhantomData<fn(&mut [u8]) -> u16>),
+	#[codec(index = 0x10)]
+	C,
+}
+
+#[test]
+fn struct_layout() {
+	assert_eq!(
+		Struct::<u32>::ENCODING_LAYOUT,
+		"struct Struct\n  a: u32\n  b: compact T\n  d: u64 as <u64 as HasCompact>::Type\n  \
+		e: lock Mutex<Option<Vec<(u8, bool)>>>\n",
+	);
+	assert_eq!(
+		Tuple::ENCODING_LAYOUT,
+		"struct Tuple\n  0: u8\n  1: compact u128\n  2: &'static [u8]\n"
+	);
+	assert_eq!(Unit::ENCODING_LAYOUT, "struct Unit\n");
+}
+
+#[test]
+fn enum_layout() {
+	assert_eq!(
+		Enum::ENCODING_LAYOUT,
+		"enum Enum\n  0: A\n  5: B\n    0: u16\n    1: [u8; 32]\n  2: C\n    x: compact u64\n  9: D\n",
+	);
+}
+
+#[test]
+fn discriminant_layout() {
+	assert_eq!(
+		Discriminants::ENCODING_LAYOUT,
+		"enum Discriminants\n  3: A\n  INDEX: B\n    0: PhantomData<fn(&mut [u8]) -> u16>\n  \
+		16: C\n",
+	);
+}
@@ -0,0 +1,49 @@
+use parity_scale_codec::{Compact, Encode, EncodeLike};
+use parity_scale_codec_derive::{Decode as DeriveDecode, Encode as DeriveEncode};
+
+#[derive(DeriveEncode, DeriveDecode, Debug, PartialEq)]
+#[codec(like_plain(Plain<T>))]
+struct Wire<T> {
+	#[codec(compact)]
+	nonce: u64,
+	#[codec(encoded_as = "Compact<u32>")]
+	count: u32,
+	value: T,
+	#[codec(skip)]
+	cache: u8,
+}
+
+#[derive(DeriveEncode, DeriveDecode, Debug, PartialEq)]
+struct Plain<T> {
+	nonce: Compact<u64>,
+	count: Compact<u32>,
+	value: T,
+	#[codec(skip)]
+	cache: (),
+}
+
+#[derive(DeriveEncode)]
+#[codec(like_plain(PlainTuple))]
+struct WireTuple(#[codec(compact)] u128, Vec<u8>);
+
+#[derive(DeriveEncode)]
+struct PlainTuple(Compact<u128>, Vec<u8>);
+
+fn encode_as<T: Encode, V: EncodeLike<T>>(value: &V) -> Vec<u8> {
+	value.encode()
+}
+
+#[test]
+fn like_plain_twins_encode_alike() {
+	let wire = Wire { nonce: 1 << 40, count: 3, value: vec![true], cache: 9 };
+	let plain = Plain { nonce: Compact(1 << 40), count: Compact(3), value: vec![true], cache: () };
+	assert_eq!(wire.encode(), plain.encode());
+	assert_eq!(encode_as::<Plain<Vec<bool>>, _>(&wire), plain.encode());
+	assert_eq!(encode_as::<Wire<Vec<bool>>, _>(&plain), wire.encode());
+
+	let wire = WireTuple(u128::MAX, vec![1, 2]);
+	assert_eq!(
+		encode_as::<PlainTuple, _>(&wire),
+		PlainTuple(Compact(u128::MAX), vec![1, 2]).encode()
+	);
+}
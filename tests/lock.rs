@@ -0,0 +1,91 @@
+#![cfg(feature = "std")]
+
+use parity_scale_codec::{decode_prefix, DecodeWithMemTracking, Encode};
+use parity_scale_codec_derive::{
+	Decode as DeriveDecode, DecodeWithMemTracking as DeriveDecodeWithMemTracking,
+	Encode as DeriveEncode,
+};
+use std::sync::{Mutex, RwLock};
+
+#[derive(Debug, Default, PartialEq, Clone, DeriveEncode, DeriveDecode)]
+struct Inner {
+	hits: u64,
+	names: Vec<String>,
+}
+
+#[derive(Debug, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+struct Snapshot<T> {
+	id: u32,
+	#[codec(lock)]
+	inner: Mutex<Vec<T>>,
+	#[codec(lock)]
+	counter: RwLock<u16>,
+}
+
+#[derive(Debug, DeriveEncode, DeriveDecode)]
+struct SingleLock(#[codec(lock)] Mutex<Inner>);
+
+#[derive(Debug, DeriveEncode, DeriveDecode)]
+enum Metrics {
+	Empty,
+	Locked(#[codec(lock)] RwLock<Inner>, u8),
+}
+
+#[test]
+fn lock_fields_are_encoded_as_inner_value() {
+	let snapshot = Snapshot { id: 1, inner: Mutex::new(vec![1u8, 2]), counter: RwLock::new(3) };
+	let encoded = snapshot.encode();
+	assert_eq!(encoded, (1u32, vec![1u8, 2], 3u16).encode());
+	assert_eq!(snapshot.size_hint(), 1u32.size_hint());
+
+	let decoded = decode_prefix::<Snapshot<u8>>(&encoded).unwrap().0;
+	assert_eq!(decoded.id, 1);
+	assert_eq!(*decoded.inner.lock().unwrap(), vec![1, 2]);
+	assert_eq!(*decoded.counter.read().unwrap(), 3);
+
+	let inner = Inner { hits: 5, names: vec!["a".into()] };
+	let single = SingleLock(Mutex::new(inner.clone()));
+	let encoded = single.encode();
+	assert_eq!(encoded, inner.encode());
+	let (decoded, _) = decode_prefix::<SingleLock>(&encoded).unwrap();
+	assert_eq!(*decoded.0.lock().unwrap(), inner);
+
+	let metrics = Metrics::Locked(RwLock::new(inner.clone()), 7);
+	let encoded = metrics.encode();
+	assert_eq!(encoded, (1u8, inner.clone(), 7u8).encode());
+	match decode_prefix::<Metrics>(&encoded).unwrap().0 {
+		Metrics::Locked(lock, 7) => assert_eq!(*lock.read().unwrap(), inner),
+		other => panic!("unexpected {other:?}"),
+	}
+	assert!(matches!(decode_prefix::<Metrics>(&[0u8]).unwrap().0, Metrics::Empty));
+}
+
+#[test]
+fn size_hint_does_not_take_the_locks() {
+	let snapshot = Snapshot { id: 1, inner: Mutex::new(vec![1u8, 2]), counter: RwLock::new(3) };
+	let _inner = snapshot.inner.lock().unwrap();
+	let _counter = snapshot.counter.write().unwrap();
+	assert_eq!(snapshot.size_hint(), 4);
+}
+
+#[test]
+fn poisoned_lock_fields_are_still_encoded() {
+	let single = std::sync::Arc::new(SingleLock(Mutex::new(Inner { hits: 1, names: vec![] })));
+	let single_clone = single.clone();
+	let _ = std::thread::spawn(move || {
+		let mut guard = single_clone.0.lock().unwrap();
+		guard.hits = 2;
+		panic!("poison the lock");
+	})
+	.join();
+
+	assert!(single.0.is_poisoned());
+	assert_eq!(single.encode(), Inner { hits: 2, names: vec![] }.encode());
+}
+
+#[test]
+fn lock_fields_support_mem_tracking() {
+	fn assert_decode_with_mem_tracking<T: DecodeWithMemTracking>() {}
+
+	assert_decode_with_mem_tracking::<Snapshot<u32>>();
+}
@@ -16,7 +16,10 @@
 //! Tests for MaxEncodedLen derive macro
 #![cfg(all(feature = "derive", feature = "max-encoded-len"))]
 
-use parity_scale_codec::{Compact, Decode, Encode, MaxEncodedLen};
+use parity_scale_codec::{
+	decode_prefix, Compact, CompactAs, Decode, DecodeWithMemTracking, Encode, HasCompact,
+	MaxEncodedLen,
+};
 
 #[derive(Encode, MaxEncodedLen)]
 struct Primitives {
@@ -100,6 +103,53 @@ fn compact_struct_max_length() {
 	assert_eq!(CompactStruct::max_encoded_len(), Compact::<u64>::max_encoded_len());
 }
 
+#[cfg(feature = "std")]
+#[derive(Encode, MaxEncodedLen)]
+struct LockField<T> {
+	#[codec(lock)]
+	t: std::sync::Mutex<T>,
+	#[codec(lock)]
+	v: std::sync::RwLock<u64>,
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn lock_field_max_length() {
+	assert_eq!(
+		LockField::<u32>::max_encoded_len(),
+		u32::max_encoded_len() + u64::max_encoded_len()
+	);
+}
+
+const MAX_MEMO_LEN: usize = 34;
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct CappedFields<T> {
+	#[codec(mel(cap = 10))]
+	tags: Vec<T>,
+	#[codec(mel(cap = MAX_MEMO_LEN, check))]
+	memo: String,
+	nonce: u32,
+}
+
+#[test]
+fn mel_cap_field_max_length() {
+	// `T` doesn't need to implement `MaxEncodedLen`.
+	assert_eq!(CappedFields::<String>::max_encoded_len(), 10 + MAX_MEMO_LEN + 4);
+}
+
+#[test]
+fn mel_cap_check_field_decoding() {
+	let value = CappedFields { tags: vec![7u8; 20], memo: "a".repeat(33), nonce: 1 };
+	let encoded = value.encode();
+	assert_eq!(decode_prefix::<CappedFields<u8>>(&encoded), Ok((value, &[][..])));
+
+	// Only the field with `check` is checked.
+	let value = CappedFields::<u8> { tags: vec![], memo: "a".repeat(34), nonce: 1 };
+	let err = decode_prefix::<CappedFields<u8>>(&value.encode()).unwrap_err();
+	assert!(err.to_string().contains("Could not decode `CappedFields::memo`"), "{err}");
+}
+
 #[derive(Encode, MaxEncodedLen)]
 struct TwoGenerics<T, U> {
 	t: T,
@@ -284,3 +334,99 @@ fn skip_enum_struct_test() {
 	let _ = Enum::<NoCodecType, NoCodecNoDefaultType>::A(NoCodecNoDefaultType);
 	let _ = StructNamed::<NoCodecType> { a: NoCodecType, b: 0 }.a;
 }
+
+/// A third-party `CompactAs` wrapper that is generic over a marker type.
+#[derive(Encode, Decode, MaxEncodedLen, CompactAs, Clone, Copy)]
+struct GenericBalance<T>(u64, #[codec(skip)] core::marker::PhantomData<T>);
+
+// Compact fields are bounded by `<T as HasCompact>::Type: MaxEncodedLen`, so `GenericBalance<T>`
+// doesn't require `T: MaxEncodedLen`.
+#[allow(dead_code)]
+#[derive(Encode, MaxEncodedLen)]
+enum CompactAsGenericsEnum<T, B, C> {
+	A(#[codec(compact)] GenericBalance<T>),
+	B {
+		#[codec(compact)]
+		b: B,
+		c: C,
+	},
+}
+
+/// A marker type implementing none of the codec traits.
+struct NoMaxEncodedLen;
+
+#[test]
+fn compact_as_generics_enum_max_length() {
+	assert_eq!(
+		CompactAsGenericsEnum::<NoMaxEncodedLen, u32, u8>::max_encoded_len(),
+		Compact::<u64>::max_encoded_len() + 1
+	);
+	assert_eq!(
+		CompactAsGenericsEnum::<NoMaxEncodedLen, u32, u128>::max_encoded_len(),
+		Compact::<u32>::max_encoded_len() + u128::max_encoded_len() + 1
+	);
+}
+
+// `encoded_as` types using generics are bounded by the derived trait, so no where clause is
+// needed, also combined with const generics.
+#[derive(Debug, PartialEq, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen)]
+struct EncodedAsConstGenerics<T: HasCompact, const N: usize> {
+	#[codec(encoded_as = "<T as HasCompact>::Type")]
+	a: T,
+	b: [T; N],
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen)]
+enum EncodedAsConstGenericsEnum<T: HasCompact, const N: usize> {
+	A(#[codec(encoded_as = "<T as HasCompact>::Type")] T, [T; N]),
+	B {
+		#[codec(encoded_as = "<T as HasCompact>::Type")]
+		a: T,
+	},
+}
+
+#[test]
+fn encoded_as_const_generics_max_length() {
+	assert_eq!(
+		EncodedAsConstGenerics::<u64, 3>::max_encoded_len(),
+		Compact::<u64>::max_encoded_len() + 3 * 8
+	);
+	assert_eq!(
+		EncodedAsConstGenericsEnum::<u32, 2>::max_encoded_len(),
+		1 + Compact::<u32>::max_encoded_len() + 2 * 4
+	);
+
+	let value = EncodedAsConstGenerics::<u64, 2> { a: 1, b: [2, 3] };
+	let encoded = value.encode();
+	assert_eq!(encoded, [&[4][..], &2u64.encode(), &3u64.encode()].concat());
+	assert_eq!(decode_prefix::<EncodedAsConstGenerics<u64, 2>>(&encoded).unwrap().0, value);
+
+	for value in [
+		EncodedAsConstGenericsEnum::<u64, 1>::A(u64::MAX, [7]),
+		EncodedAsConstGenericsEnum::B { a: 1 << 20 },
+	] {
+		let encoded = value.encode();
+		assert!(encoded.len() <= EncodedAsConstGenericsEnum::<u64, 1>::max_encoded_len());
+		assert_eq!(decode_prefix::<EncodedAsConstGenericsEnum<u64, 1>>(&encoded).unwrap().0, value);
+	}
+}
+
+#[derive(Encode, MaxEncodedLen)]
+struct AccountData {
+	free: u128,
+	#[codec(compact)]
+	nonce: u32,
+}
+
+const ACCOUNT_DATA_BUDGET: usize = 21;
+
+parity_scale_codec::assert_mel_bound!(AccountData, ACCOUNT_DATA_BUDGET);
+parity_scale_codec::assert_mel_bound!(EncodedAsConstGenerics<u64, 3>, 64,);
+parity_scale_codec::assert_mel_bound!(Option<[u8; 32]>, 33);
+
+#[test]
+fn assert_mel_bound_generates_tests() {
+	assert_mel_bound_account_data();
+	assert_mel_bound_encoded_as_const_generics_u64_3();
+	assert_mel_bound_option_u8_32();
+}
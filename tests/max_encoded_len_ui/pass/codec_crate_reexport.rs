@@ -0,0 +1,48 @@
+//! `#[codec(crate = path)]` works with multi-segment paths, in macros using `$crate` and for all
+//! derives, including `MaxEncodedLen` without `Encode` and `CompactAs`.
+
+pub mod reexport {
+	pub use parity_scale_codec as codec;
+}
+
+macro_rules! define {
+	($name:ident) => {
+		#[derive($crate::reexport::codec::Encode, $crate::reexport::codec::MaxEncodedLen)]
+		#[codec(crate = $crate::reexport::codec)]
+		struct $name {
+			#[codec(compact)]
+			a: u64,
+		}
+	};
+}
+
+define!(Example);
+
+#[derive(
+	parity_scale_codec::Encode,
+	parity_scale_codec::Decode,
+	parity_scale_codec::MaxEncodedLen,
+	parity_scale_codec::CompactAs,
+)]
+#[codec(crate = crate::reexport::codec)]
+struct Wrapper(u32);
+
+#[derive(parity_scale_codec::MaxEncodedLen)]
+#[codec(crate = reexport::codec)]
+struct MelOnly(u8);
+
+impl reexport::codec::Encode for MelOnly {
+	fn encode_to<T: reexport::codec::Output + ?Sized>(&self, dest: &mut T) {
+		self.0.encode_to(dest)
+	}
+}
+
+fn main() {
+	use reexport::codec::MaxEncodedLen;
+
+	assert_eq!(Example::max_encoded_len(), 9);
+	assert_eq!(Wrapper::max_encoded_len(), 4);
+	assert_eq!(MelOnly::max_encoded_len(), 1);
+	let _ = Example { a: 0 }.a;
+	let _ = reexport::codec::Compact(Wrapper(0)).0 .0;
+}
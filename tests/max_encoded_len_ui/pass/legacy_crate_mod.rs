@@ -0,0 +1,11 @@
+//! The legacy `#[max_encoded_len_mod(path)]` attribute is still accepted.
+
+use parity_scale_codec::{self as codec, Encode, MaxEncodedLen};
+
+#[derive(Encode, MaxEncodedLen)]
+#[max_encoded_len_mod(codec)]
+struct Example(u16);
+
+fn main() {
+	assert_eq!(Example::max_encoded_len(), 2);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "max-encoded-len")]
+
+use parity_scale_codec::{
+	decode_prefix, Compact, DecodeWithMemTracking, Encode, Error, MaxEncodedLen,
+};
+use parity_scale_codec_derive::{
+	Decode as DeriveDecode, DecodeWithMemTracking as DeriveDecodeWithMemTracking,
+	Encode as DeriveEncode, MaxEncodedLen as DeriveMaxEncodedLen,
+};
+use std::collections::BTreeMap;
+
+const MAX_NAMES: usize = 4;
+
+#[derive(
+	Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking, DeriveMaxEncodedLen,
+)]
+struct Account<T> {
+	#[codec(max_len = 32)]
+	name: String,
+	#[codec(max_len = MAX_NAMES)]
+	aliases: Vec<T>,
+	#[codec(max_len = 2)]
+	balances: BTreeMap<u8, u64>,
+	nonce: u32,
+}
+
+#[derive(
+	Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking, DeriveMaxEncodedLen,
+)]
+#[codec(dyn_input)]
+enum Message {
+	Ping,
+	Data(#[codec(max_len = 1000)] Vec<u8>),
+}
+
+fn account() -> Account<u16> {
+	Account {
+		name: "alice".into(),
+		aliases: vec![1, 2, 3, 4],
+		balances: BTreeMap::from([(0, 10), (1, 20)]),
+		nonce: 7,
+	}
+}
+
+#[test]
+fn max_len_fields_are_encoded_like_their_type() {
+	let value = account();
+	let encoded = value.encode();
+	assert_eq!(encoded, (&value.name, &value.aliases, &value.balances, value.nonce).encode());
+	assert_eq!(decode_prefix::<Account<u16>>(&encoded), Ok((value, &[][..])));
+
+	let encoded = Message::Data(vec![1; 1000]).encode();
+	assert_eq!(decode_prefix::<Message>(&encoded), Ok((Message::Data(vec![1; 1000]), &[][..])));
+}
+
+#[test]
+fn max_len_fields_reject_longer_values() {
+	let mut value = account();
+	value.aliases.push(5);
+	let encoded = value.encode();
+	let error: Error = decode_prefix::<Account<u16>>(&encoded).unwrap_err();
+	assert!(error.to_string().contains("Collection has more items than its maximum length"));
+
+	// A huge length without any data is rejected before allocating anything.
+	let encoded = [&[1][..], &Compact(u32::MAX).encode()].concat();
+	assert!(decode_prefix::<Message>(&encoded).is_err());
+}
+
+#[test]
+fn max_len_fields_bound_max_encoded_len() {
+	assert_eq!(
+		Account::<u16>::max_encoded_len(),
+		(1 + 32) + (1 + MAX_NAMES * 2) + (1 + 2 * (1 + 8)) + 4
+	);
+	assert_eq!(Message::max_encoded_len(), 1 + 2 + 1000);
+}
+
+fn check_decode_with_mem_tracking<T: DecodeWithMemTracking>() {}
+
+#[test]
+fn max_len_fields_support_mem_tracking() {
+	check_decode_with_mem_tracking::<Account<u32>>();
+	check_decode_with_mem_tracking::<Message>();
+}
@@ -0,0 +1,77 @@
+use parity_scale_codec::{decode_prefix, DecodeMigrate, DecodeWithMemTracking, Encode, Error};
+use parity_scale_codec_derive::{
+	Decode as DeriveDecode, DecodeWithMemTracking as DeriveDecodeWithMemTracking,
+	Encode as DeriveEncode,
+};
+
+/// The first layout of `Account`.
+#[derive(DeriveDecode)]
+struct AccountV1 {
+	balance: u32,
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+#[codec(migrate_from(AccountV1, "Account::from_v1"))]
+struct Account {
+	balance: u64,
+	nonce: u32,
+}
+
+impl Account {
+	fn from_v1(old: AccountV1) -> Result<Self, Error> {
+		Ok(Account { balance: old.balance.into(), nonce: 0 })
+	}
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[codec(migrate_from(u8, "Status::from_flag"))]
+enum Status<T> {
+	#[codec(index = 5)]
+	Active(T),
+	#[codec(index = 6)]
+	Frozen,
+}
+
+impl<T> Status<T> {
+	fn from_flag(flag: u8) -> Result<Self, Error> {
+		match flag {
+			0 => Ok(Status::Frozen),
+			_ => Err("Active accounts can't be migrated".into()),
+		}
+	}
+}
+
+#[test]
+fn current_layout_is_decoded() {
+	let account = Account { balance: 1 << 40, nonce: 3 };
+	assert_eq!(Account::decode_all_or_migrate(&mut &account.encode()[..]), Ok(account));
+
+	let status = Status::Active(7u16);
+	assert_eq!(Status::decode_all_or_migrate(&mut &status.encode()[..]), Ok(status));
+}
+
+#[test]
+fn old_layout_is_migrated() {
+	assert_eq!(
+		Account::decode_all_or_migrate(&mut &9u32.encode()[..]),
+		Ok(Account { balance: 9, nonce: 0 })
+	);
+
+	assert_eq!(Status::<u16>::decode_all_or_migrate(&mut &[0][..]), Ok(Status::Frozen));
+	assert!(Status::<u16>::decode_all_or_migrate(&mut &[1][..]).is_err());
+}
+
+#[test]
+fn decode_only_reads_the_current_layout() {
+	assert!(decode_prefix::<Account>(&9u32.encode()).is_err());
+
+	// An old value followed by a current one isn't read as garbage.
+	let encoded = [&[8][..], &3u32.encode(), &(1u64, 2u32).encode()].concat();
+	assert!(decode_prefix::<Vec<Account>>(&encoded).is_err());
+}
+
+#[test]
+fn derived_decode_is_tracked() {
+	fn assert_tracked<T: DecodeWithMemTracking>() {}
+	assert_tracked::<Account>();
+}
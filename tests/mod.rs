@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use parity_scale_codec::{
-	Compact, CompactAs, Decode, DecodeWithMemTracking, Encode, EncodeAsRef, Error, HasCompact,
-	Output,
+	decode_prefix, Compact, CompactAs, Decode, DecodeAndConvert, DecodeWithMemTracking, Encode,
+	EncodeAsRef, Error, HasCompact, Output,
 };
 use parity_scale_codec_derive::{
 	Decode as DeriveDecode, DecodeWithMemTracking as DeriveDecodeWithMemTracking,
@@ -273,7 +273,7 @@ fn encoded_as_with_has_compact_works() {
 		let encoded = TestHasCompact { bar: n }.encode();
 		println!("{}", n);
 		assert_eq!(encoded.len(), l);
-		assert_eq!(<TestHasCompact<u64>>::decode(&mut &encoded[..]).unwrap().bar, n);
+		assert_eq!(decode_prefix::<TestHasCompact<u64>>(&encoded).unwrap().0.bar, n);
 	}
 }
 
@@ -283,7 +283,7 @@ fn compact_with_has_compact_works() {
 		let encoded = TestHasCompact { bar: n }.encode();
 		println!("{}", n);
 		assert_eq!(encoded.len(), l);
-		assert_eq!(<TestCompactHasCompact<u64>>::decode(&mut &encoded[..]).unwrap().bar, n);
+		assert_eq!(decode_prefix::<TestCompactHasCompact<u64>>(&encoded).unwrap().0.bar, n);
 	}
 }
 
@@ -301,17 +301,105 @@ fn enum_compact_and_encoded_as_with_has_compact_works() {
 			let encoded = value.encode();
 			println!("{:?}", value);
 			assert_eq!(encoded.len(), l);
-			assert_eq!(&<TestHasCompactEnum<u64>>::decode(&mut &encoded[..]).unwrap(), value);
+			assert_eq!(&decode_prefix::<TestHasCompactEnum<u64>>(&encoded).unwrap().0, value);
 		}
 	}
 }
 
+/// An even number.
+#[derive(Debug, PartialEq)]
+struct Even(u32);
+
+/// The encoding of an `Even`, which is half of it.
+#[derive(DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+struct HalfOfEven(#[codec(compact)] u32);
+
+impl From<&Even> for HalfOfEven {
+	fn from(even: &Even) -> Self {
+		HalfOfEven(even.0 / 2)
+	}
+}
+
+impl EncodeAsRef<'_, Even> for HalfOfEven {
+	type RefType = HalfOfEven;
+}
+
+impl DecodeAndConvert<Even> for HalfOfEven {
+	fn convert(self) -> Result<Even, Error> {
+		self.0.checked_mul(2).map(Even).ok_or_else(|| "Even number is too big".into())
+	}
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+struct TestDecodedVia {
+	#[codec(decoded_via = "HalfOfEven")]
+	even: Even,
+	other: u8,
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+enum TestDecodedViaEnum {
+	Unnamed(#[codec(decoded_via = "HalfOfEven")] Even),
+	Named {
+		#[codec(decoded_via = "HalfOfEven")]
+		even: Even,
+	},
+}
+
+#[test]
+fn decoded_via_works() {
+	let value = TestDecodedVia { even: Even(10), other: 1 };
+	let encoded = value.encode();
+	assert_eq!(encoded, [Compact(5u32).encode(), vec![1]].concat());
+	assert_eq!(value.size_hint(), encoded.len());
+	assert_eq!(decode_prefix::<TestDecodedVia>(&encoded).unwrap().0, value);
+
+	for value in [TestDecodedViaEnum::Unnamed(Even(4)), TestDecodedViaEnum::Named { even: Even(6) }]
+	{
+		let encoded = value.encode();
+		assert_eq!(decode_prefix::<TestDecodedViaEnum>(&encoded).unwrap().0, value);
+	}
+
+	let too_big = [Compact(u32::MAX).encode(), vec![1]].concat();
+	let error = decode_prefix::<TestDecodedVia>(&too_big).unwrap_err().to_string();
+	assert!(error.contains("Could not decode `TestDecodedVia::even`"));
+	assert!(error.contains("Even number is too big"));
+	assert!(decode_prefix::<TestDecodedViaEnum>(&[0, 0xff, 0xff, 0xff, 0xff]).is_err());
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+#[codec(inline)]
+enum InlineEnum {
+	A(u8),
+	B { a: u16, b: Vec<u8> },
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[codec(never_inline)]
+struct NeverInline(u32);
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[repr(transparent)]
+struct Transparent(u64);
+
+#[test]
+fn inline_attributes_work() {
+	let value = InlineEnum::B { a: 1, b: vec![2, 3] };
+	assert_eq!(decode_prefix::<InlineEnum>(&value.encode()).unwrap().0, value);
+	assert_eq!(decode_prefix::<InlineEnum>(&[0, 7]).unwrap().0, InlineEnum::A(7));
+
+	assert_eq!(NeverInline(5).encode(), 5u32.encode());
+	assert_eq!(decode_prefix::<NeverInline>(&[5, 0, 0, 0]).unwrap().0, NeverInline(5));
+
+	assert_eq!(decode_prefix::<Transparent>(&Transparent(9).encode()).unwrap().0, Transparent(9));
+}
+
 #[test]
 fn compact_meta_attribute_works() {
 	for &(n, l) in U64_TEST_COMPACT_VALUES {
 		let encoded = TestCompactAttribute { bar: n }.encode();
 		assert_eq!(encoded.len(), l);
-		assert_eq!(TestCompactAttribute::decode(&mut &encoded[..]).unwrap().bar, n);
+		assert_eq!(decode_prefix::<TestCompactAttribute>(&encoded).unwrap().0.bar, n);
 	}
 }
 
@@ -324,7 +412,7 @@ fn enum_compact_meta_attribute_works() {
 		{
 			let encoded = value.encode();
 			assert_eq!(encoded.len(), l);
-			assert_eq!(&TestCompactAttributeEnum::decode(&mut &encoded[..]).unwrap(), value);
+			assert_eq!(&decode_prefix::<TestCompactAttributeEnum>(&encoded).unwrap().0, value);
 		}
 	}
 }
@@ -353,7 +441,7 @@ fn associated_type_bounds() {
 
 	let value: Struct<TraitImplementor, u64> = Struct { field: (vec![1, 2, 3], 42) };
 	let encoded = value.encode();
-	let decoded: Struct<TraitImplementor, u64> = Struct::decode(&mut &encoded[..]).unwrap();
+	let (decoded, _) = decode_prefix::<Struct<TraitImplementor, u64>>(&encoded).unwrap();
 	assert_eq!(value, decoded);
 }
 
@@ -392,6 +480,37 @@ fn generic_bound_encoded_as() {
 	a.encode();
 }
 
+#[test]
+fn endian_wrappers_in_derive() {
+	use parity_scale_codec::{Be, Le};
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	struct Measurement {
+		id: u16,
+		#[codec(encoded_as = "Be<f64>")]
+		value: f64,
+		#[codec(encoded_as = "Be<u32>")]
+		timestamp: u32,
+		flags: Le<u16>,
+		raw: Be<i16>,
+	}
+
+	let m = Measurement {
+		id: 0x0102,
+		value: 1.5,
+		timestamp: 0x0304_0506,
+		flags: Le(0x0708),
+		raw: Be(-2),
+	};
+	let encoded = m.encode();
+
+	let mut expected = vec![0x02, 0x01];
+	expected.extend_from_slice(&1.5f64.to_be_bytes());
+	expected.extend_from_slice(&[0x03, 0x04, 0x05, 0x06, 0x08, 0x07, 0xff, 0xfe]);
+	assert_eq!(encoded, expected);
+	assert_eq!(decode_prefix::<Measurement>(&encoded).unwrap().0, m);
+}
+
 #[test]
 fn generic_bound_hascompact() {
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
@@ -504,6 +623,44 @@ fn private_type_in_where_bound() {
 	val.encode();
 }
 
+mod forest {
+	use super::*;
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	#[codec(recursive)]
+	pub struct Forest<T> {
+		pub trees: Vec<super::tree::Tree<T>>,
+	}
+}
+
+mod tree {
+	use super::*;
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	#[codec(recursive)]
+	pub enum Tree<T> {
+		Leaf(T),
+		Node(Box<super::forest::Forest<T>>),
+	}
+}
+
+#[test]
+fn mutually_recursive_types_with_recursive_attribute() {
+	use forest::Forest;
+	use parity_scale_codec::DecodeWithMemLimit;
+	use tree::Tree;
+
+	let value = Forest {
+		trees: vec![Tree::Leaf(1u32), Tree::Node(Box::new(Forest { trees: vec![Tree::Leaf(2)] }))],
+	};
+	let encoded = value.encode();
+	assert_eq!(decode_prefix::<Forest<u32>>(&encoded), Ok((value, &[][..])));
+	assert_eq!(
+		Forest::<u32>::decode_with_mem_limit(&mut &encoded[..], 1024),
+		decode_prefix::<Forest<u32>>(&encoded).map(|(value, _)| value)
+	);
+}
+
 #[test]
 fn encode_decode_empty_enum() {
 	#[derive(DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking, PartialEq, Debug)]
@@ -513,7 +670,7 @@ fn encode_decode_empty_enum() {
 	impls_encode_decode::<EmptyEnumDerive>();
 
 	assert_eq!(
-		EmptyEnumDerive::decode(&mut &[1, 2, 3][..]),
+		decode_prefix::<EmptyEnumDerive>(&[1, 2, 3]),
 		Err("Could not decode `EmptyEnumDerive`, variant doesn't exist".into())
 	);
 }
@@ -522,7 +679,7 @@ fn encode_decode_empty_enum() {
 fn codec_vec_u8() {
 	for v in [vec![0u8; 0], vec![0u8; 10], vec![0u8; 100], vec![0u8; 1000]].iter() {
 		let e = v.encode();
-		assert_eq!(v, &Vec::<u8>::decode(&mut &e[..]).unwrap());
+		assert_eq!(v, &decode_prefix::<Vec<u8>>(&e).unwrap().0);
 	}
 }
 
@@ -543,10 +700,7 @@ fn recursive_type() {
 #[test]
 fn crafted_input_for_vec_u8() {
 	assert_eq!(
-		Vec::<u8>::decode(&mut &Compact(u32::MAX).encode()[..])
-			.err()
-			.unwrap()
-			.to_string(),
+		decode_prefix::<Vec<u8>>(&Compact(u32::MAX).encode()).err().unwrap().to_string(),
 		"Not enough data to decode vector",
 	);
 }
@@ -561,7 +715,7 @@ fn crafted_input_for_vec_t() {
 	};
 
 	assert_eq!(
-		Vec::<u32>::decode(&mut &Compact(u32::MAX).encode()[..])
+		decode_prefix::<Vec<u32>>(&Compact(u32::MAX).encode())
 			.err()
 			.unwrap()
 			.to_string(),
@@ -590,6 +744,45 @@ fn output_trait_object() {
 	let _: Box<dyn Output>;
 }
 
+#[test]
+fn input_and_output_are_object_safe() {
+	use parity_scale_codec::Input;
+
+	fn assert_object_safe(_: Option<&dyn Input>, _: Option<&dyn Output>) {}
+	assert_object_safe(None, None);
+}
+
+#[test]
+fn boxed_trait_objects_round_trip() {
+	use parity_scale_codec::{Input, IoReader};
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+	struct Value {
+		a: u32,
+		b: Vec<String>,
+		c: Compact<u64>,
+	}
+
+	let value = Value { a: 7, b: vec!["hello".into(), "world".into()], c: Compact(1 << 40) };
+
+	let mut encoded = Vec::new();
+	let mut output: Box<dyn Output + '_> = Box::new(&mut encoded);
+	value.encode_to(&mut output);
+	drop(output);
+	assert_eq!(encoded, value.encode());
+
+	let mut input: Box<dyn Input + '_> = Box::new(&encoded[..]);
+	assert_eq!(input.remaining_len(), Ok(Some(encoded.len())));
+	assert_eq!(Value::decode(&mut input).unwrap(), value);
+	assert_eq!(input.remaining_len(), Ok(Some(0)));
+
+	// Hooks are forwarded, so limits apply through the box.
+	let input: Box<dyn Input + '_> = Box::new(&encoded[..]);
+	assert!(Value::decode(&mut input.with_mem_limit(4)).is_err());
+	let mut input: Box<dyn Input + '_> = Box::new(IoReader(&encoded[..]));
+	assert_eq!(Value::decode(&mut input).unwrap(), value);
+}
+
 #[test]
 fn custom_trait_bound() {
 	#[derive(DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
@@ -615,7 +808,7 @@ fn custom_trait_bound() {
 		Something::<NotEncode, u32> { hello: Hello { _phantom: Default::default() }, val: 32u32 }
 			.encode();
 
-	Something::<NotEncode, u32>::decode(&mut &encoded[..]).unwrap();
+	decode_prefix::<Something<NotEncode, u32>>(&encoded).unwrap();
 }
 
 #[test]
@@ -646,7 +839,7 @@ fn bit_vec_works() {
 	let v2 = MyStruct { v: original_vec_clone, x: 42 }.encode();
 	assert_eq!(v1, v2);
 
-	let v1 = MyStruct::decode(&mut &v1[..]).unwrap();
+	let v1 = decode_prefix::<MyStruct>(&v1).unwrap().0;
 	let v2 = MyStruct::decode_all(&mut &v2[..]).unwrap();
 	assert_eq!(v1.x, v2.x);
 }
@@ -692,6 +885,38 @@ fn decoding_a_huge_boxed_newtype_array_does_not_overflow_the_stack() {
 	assert!(HugeArrayNewtypeBox::decode(&mut data.as_slice()).is_err());
 }
 
+#[test]
+fn decoding_big_shared_arrays_does_not_use_the_stack() {
+	use parity_scale_codec::DecodeWithMemLimit;
+	use std::{rc::Rc, sync::Arc};
+
+	const LEN: usize = 1 << 20;
+
+	// The arrays are much bigger than the stack of the thread, so they must be decoded in place.
+	std::thread::Builder::new()
+		.stack_size(64 * 1024)
+		.spawn(|| {
+			let data = (0..LEN).map(|i| i as u8).collect::<Vec<_>>();
+
+			let boxed = Box::<[u8; LEN]>::decode(&mut data.as_slice()).unwrap();
+			assert_eq!(boxed[..], data[..]);
+			let rc = Rc::<[u8; LEN]>::decode(&mut data.as_slice()).unwrap();
+			assert_eq!(rc[..], data[..]);
+			let arc = Arc::<[u8; LEN]>::decode(&mut data.as_slice()).unwrap();
+			assert_eq!(arc[..], data[..]);
+
+			let arc =
+				Arc::<[u8; LEN]>::decode_with_mem_limit(&mut data.as_slice(), LEN + 1).unwrap();
+			assert_eq!(arc[..], data[..]);
+			assert!(Arc::<[u8; LEN]>::decode_with_mem_limit(&mut data.as_slice(), LEN).is_err());
+			assert!(Rc::<[u8; LEN]>::decode_with_mem_limit(&mut data.as_slice(), LEN).is_err());
+			assert!(Arc::<[u8; LEN]>::decode(&mut &data[1..]).is_err());
+		})
+		.unwrap()
+		.join()
+		.unwrap();
+}
+
 #[test]
 fn decoding_two_indirectly_boxed_arrays_works() {
 	// This test will fail if the check for `#[repr(transparent)]` in the derive crate
@@ -903,7 +1128,7 @@ fn deserializing_of_big_recursively_nested_enum_works() {
 	let data = obj.encode();
 
 	// This should not overflow the stack.
-	let obj_d = Enum::decode(&mut &data[..]).unwrap();
+	let obj_d = decode_prefix::<Enum>(&data).unwrap().0;
 
 	// NOTE: Not using `assert_eq` since we don't want to print out such a big object if this fails.
 	assert!(obj == obj_d);
@@ -912,3 +1137,89 @@ fn deserializing_of_big_recursively_nested_enum_works() {
 	let obj_d2 = Enum::decode_with_depth_limit(40, &mut &data[..]).unwrap();
 	assert!(obj == obj_d2);
 }
+
+#[test]
+fn dyn_input_decode_works() {
+	use parity_scale_codec::{DecodeLimit, DecodeWithMemLimit, Input};
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	#[codec(dyn_input)]
+	struct Struct<T> {
+		a: T,
+		#[codec(compact)]
+		b: u64,
+		c: Vec<Enum>,
+	}
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	#[codec(dyn_input)]
+	enum Enum {
+		A,
+		B(Box<Enum>),
+	}
+
+	let value =
+		Struct { a: 1u16, b: 2, c: vec![Enum::A, Enum::B(Box::new(Enum::B(Box::new(Enum::A))))] };
+	let encoded = value.encode();
+
+	assert_eq!(decode_prefix::<Struct<u16>>(&encoded).unwrap().0, value);
+	let mut input = &encoded[..];
+	let mut dyn_input: &mut dyn Input = &mut input;
+	assert_eq!(Struct::<u16>::decode(&mut dyn_input).unwrap(), value);
+
+	// Depth and memory tracking still apply when decoding through `dyn Input`.
+	assert!(Struct::<u16>::decode_with_depth_limit(2, &mut &encoded[..]).is_err());
+	assert_eq!(Struct::<u16>::decode_with_depth_limit(4, &mut &encoded[..]).unwrap(), value);
+	assert!(Struct::<u16>::decode_with_mem_limit(&mut &encoded[..], 4).is_err());
+}
+
+#[test]
+fn input_can_be_implemented_for_local_references_and_boxes() {
+	use parity_scale_codec::{Error, Input};
+
+	struct Zeros;
+
+	impl Input for &mut Zeros {
+		fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+			Ok(None)
+		}
+
+		fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+			into.fill(0);
+			Ok(())
+		}
+	}
+
+	assert_eq!(u32::decode(&mut &mut Zeros), Ok(0));
+
+	impl Input for Box<Zeros> {
+		fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+			Ok(None)
+		}
+
+		fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+			into.fill(0);
+			Ok(())
+		}
+	}
+
+	assert_eq!(u32::decode(&mut Box::new(Zeros)), Ok(0));
+}
+
+#[test]
+fn encoded_as_usize_as_works() {
+	use parity_scale_codec::UsizeAs;
+
+	#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	struct Position {
+		#[codec(encoded_as = "UsizeAs<u32>")]
+		offset: usize,
+		#[codec(encoded_as = "UsizeAs<u64>")]
+		len: usize,
+	}
+
+	let position = Position { offset: 3, len: 4 };
+	let encoded = position.encode();
+	assert_eq!(encoded, (3u32, 4u64).encode());
+	assert_eq!(decode_prefix::<Position>(&encoded).unwrap().0, position);
+}
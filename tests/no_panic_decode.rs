@@ -0,0 +1,105 @@
+//! Decoding arbitrary bytes returns an error instead of panicking. Encoding values which can't be
+//! encoded panics, also with the `no-panic-decode` feature, unless the output keeps the error.
+
+use std::{
+	collections::{BTreeMap, BTreeSet, VecDeque},
+	panic::catch_unwind,
+	rc::Rc,
+	sync::Arc,
+};
+
+use parity_scale_codec::{
+	decode_prefix, Compact, Decode, DecodeLimit, DecodeWithMemLimit, Encode, FallibleOutput,
+	IoWriter, UsizeAs,
+};
+
+/// Deterministic pseudo-random bytes, xorshift64*.
+fn bytes(seed: u64, len: usize) -> Vec<u8> {
+	let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+	(0..len)
+		.map(|_| {
+			state ^= state >> 12;
+			state ^= state << 25;
+			state ^= state >> 27;
+			(state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 56) as u8
+		})
+		.collect()
+}
+
+fn decode_all_prefixes<T: Decode + DecodeWithMemLimit>(input: &[u8]) {
+	for end in 0..=input.len() {
+		let input = &input[..end];
+		let _ = decode_prefix::<T>(input);
+		let _ = T::decode_with_depth_limit(4, &mut &input[..]);
+		let _ = T::decode_with_mem_limit(&mut &input[..], 1024);
+	}
+}
+
+#[test]
+fn decoding_arbitrary_bytes_does_not_panic() {
+	for seed in 0..64 {
+		let input = bytes(seed, 64);
+		let result = catch_unwind(|| {
+			decode_all_prefixes::<Compact<u8>>(&input);
+			decode_all_prefixes::<Compact<u16>>(&input);
+			decode_all_prefixes::<Compact<u32>>(&input);
+			decode_all_prefixes::<Compact<u64>>(&input);
+			decode_all_prefixes::<Compact<u128>>(&input);
+			decode_all_prefixes::<(bool, Option<i16>, Result<u8, ()>)>(&input);
+			decode_all_prefixes::<String>(&input);
+			decode_all_prefixes::<Vec<Vec<u32>>>(&input);
+			decode_all_prefixes::<VecDeque<u64>>(&input);
+			decode_all_prefixes::<BTreeMap<u8, String>>(&input);
+			decode_all_prefixes::<BTreeSet<Compact<u32>>>(&input);
+			decode_all_prefixes::<Box<[u16; 8]>>(&input);
+			decode_all_prefixes::<Rc<[u8; 20_000]>>(&input);
+			decode_all_prefixes::<Arc<Option<Vec<u8>>>>(&input);
+			decode_all_prefixes::<[u64; 4096]>(&input);
+		});
+		assert!(result.is_ok(), "Decoding panicked for seed {seed}");
+	}
+}
+
+/// A writer which fails after `capacity` bytes.
+struct FullWriter {
+	capacity: usize,
+}
+
+impl std::io::Write for FullWriter {
+	fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+		if bytes.len() > self.capacity {
+			return Err(std::io::ErrorKind::WriteZero.into());
+		}
+		self.capacity -= bytes.len();
+		Ok(bytes.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn encoding_errors_panic_by_default() {
+	let too_large = UsizeAs::<u32>::new(usize::MAX);
+	assert!(catch_unwind(|| too_large.encode()).is_err());
+	assert!(catch_unwind(|| (1u32, 2u32).encode_to(&mut FullWriter { capacity: 6 })).is_err());
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn encoding_errors_are_kept() {
+	let mut dest = Vec::new();
+	let mut output = FallibleOutput::new(&mut dest);
+	(1u8, UsizeAs::<u32>::new(usize::MAX)).encode_to(&mut output);
+	assert_eq!(output.finish(), Err("Attempted to encode a usize which doesn't fit in u32".into()));
+
+	let mut output = IoWriter::new(FullWriter { capacity: 6 });
+	(1u32, 2u32).encode_to(&mut output);
+	assert_eq!(output.finish().map(drop).unwrap_err().kind(), std::io::ErrorKind::WriteZero);
+
+	let mut output = IoWriter::new(FullWriter { capacity: 6 });
+	UsizeAs::<u32>::new(usize::MAX).encode_to(&mut output);
+	assert!(output.finish().is_err());
+}
@@ -0,0 +1,109 @@
+use parity_scale_codec::{decode_prefix, DecodeThenNormalize, Encode, Error};
+use parity_scale_codec_derive::{Decode as DeriveDecode, Encode as DeriveEncode};
+
+fn gcd(a: u32, b: u32) -> u32 {
+	if b == 0 {
+		a
+	} else {
+		gcd(b, a % b)
+	}
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[codec(normalize = "Fraction::reduce")]
+struct Fraction {
+	num: u32,
+	den: u32,
+}
+
+impl Fraction {
+	fn reduce(self) -> Result<Self, Error> {
+		if self.den == 0 {
+			return Err("Denominator must not be zero".into());
+		}
+		let gcd = gcd(self.num, self.den);
+		Ok(Fraction { num: self.num / gcd, den: self.den / gcd })
+	}
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[codec(normalize = "sorted")]
+enum Set<T: Ord> {
+	Empty,
+	Items(Vec<T>),
+}
+
+fn sorted<T: Ord>(set: Set<T>) -> Result<Set<T>, Error> {
+	Ok(match set {
+		Set::Items(items) if items.is_empty() => Set::Empty,
+		Set::Items(mut items) => {
+			items.sort();
+			items.dedup();
+			Set::Items(items)
+		},
+		Set::Empty => Set::Empty,
+	})
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode)]
+#[repr(transparent)]
+#[codec(normalize = "Even::check")]
+struct Even(u32);
+
+impl Even {
+	fn check(self) -> Result<Self, Error> {
+		if self.0 % 2 == 0 {
+			Ok(self)
+		} else {
+			Err("Not even".into())
+		}
+	}
+}
+
+#[test]
+fn normalize_struct() {
+	let encoded = (6u32, 8u32).encode();
+	assert_eq!(decode_prefix::<Fraction>(&encoded).unwrap().0, Fraction { num: 3, den: 4 });
+
+	let encoded = (6u32, 0u32).encode();
+	assert!(decode_prefix::<Fraction>(&encoded).is_err());
+
+	// Decoding errors are returned without calling the normalization function.
+	assert!(decode_prefix::<Fraction>(&[1, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn normalize_enum() {
+	let encoded = Set::Items(vec![3u8, 1, 3, 2]).encode();
+	assert_eq!(decode_prefix::<Set<u8>>(&encoded).unwrap().0, Set::Items(vec![1, 2, 3]));
+
+	let encoded = Set::<u8>::Items(vec![]).encode();
+	assert_eq!(decode_prefix::<Set<u8>>(&encoded).unwrap().0, Set::Empty);
+
+	assert!(decode_prefix::<Set<u8>>(&[2]).is_err());
+}
+
+#[test]
+fn normalize_transparent_struct() {
+	assert_eq!(decode_prefix::<Even>(&2u32.encode()).unwrap().0, Even(2));
+	assert!(decode_prefix::<Even>(&3u32.encode()).is_err());
+}
+
+#[cfg(feature = "chain-error")]
+#[test]
+fn normalize_error_is_chained() {
+	let encoded = (6u32, 0u32).encode();
+	assert_eq!(
+		decode_prefix::<Fraction>(&encoded).unwrap_err().to_string(),
+		"Could not decode `Fraction`, normalization failed:\n\tDenominator must not be zero\n",
+	);
+}
+
+#[test]
+fn duration_is_normalized() {
+	use std::time::Duration;
+
+	assert_eq!(Duration::normalize((1, 5)).unwrap(), Duration::new(1, 5));
+	assert!(Duration::normalize((1, 1_000_000_000)).is_err());
+	assert!(decode_prefix::<Duration>(&(1u64, 1_000_000_000u32).encode()).is_err());
+}
@@ -0,0 +1,81 @@
+// Copyright 2026 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "derive")]
+
+use parity_scale_codec::{
+	preflight_decode, Compact, Decode, DecodeWithMemTracking, Encode, MemTrackingInput, Preflight,
+};
+
+#[derive(Debug, PartialEq, Encode, Decode, DecodeWithMemTracking, Preflight)]
+struct Transfer<T> {
+	#[codec(compact)]
+	amount: u64,
+	#[codec(encoded_as = "Compact<u32>")]
+	nonce: u32,
+	memo: Vec<T>,
+	#[codec(skip)]
+	cached: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, DecodeWithMemTracking, Preflight)]
+enum Tree {
+	Leaf(String),
+	#[codec(index = 5)]
+	Node(Vec<Tree>, Box<Tree>),
+}
+
+fn check<T: Encode + DecodeWithMemTracking + Preflight>(value: &T, elements: usize) {
+	let encoded = value.encode();
+	let estimate = preflight_decode::<T>(&encoded, 16).unwrap();
+	assert_eq!(estimate.encoded_len, encoded.len());
+	assert_eq!(estimate.elements, elements);
+
+	let mut input = &encoded[..];
+	let mut input = MemTrackingInput::new(&mut input, usize::MAX);
+	T::decode(&mut input).unwrap();
+	assert_eq!(estimate.heap_bytes, input.used_mem());
+}
+
+#[test]
+fn derived_preflight_walks_fields() {
+	let transfer = Transfer { amount: 1 << 40, nonce: 3, memo: vec![1u16, 2], cached: None };
+	check(&transfer, 2);
+	check(&Transfer::<()> { amount: 0, nonce: 0, memo: vec![], cached: None }, 0);
+}
+
+#[test]
+fn derived_preflight_walks_variants() {
+	let leaf = |s: &str| Tree::Leaf(s.into());
+	check(&leaf("abc"), 3);
+	check(&Tree::Node(vec![leaf("a"), leaf("")], Box::new(leaf("bc"))), 5);
+
+	assert!(preflight_decode::<Tree>(&[1], 16).is_err());
+	assert!(preflight_decode::<Tree>(&[5, 4, 0], 16).is_err());
+}
+
+#[test]
+fn derived_preflight_is_depth_limited() {
+	// A deeply nested tree would overflow the stack without a depth limit.
+	let mut payload = [5, 0].repeat(1_000_000);
+	payload.extend([0, 0]);
+	assert_eq!(
+		preflight_decode::<Tree>(&payload, 64).map_err(|e| e.to_string()),
+		Err("Maximum recursion depth reached when decoding".into())
+	);
+
+	let nested = Tree::Node(vec![], Box::new(Tree::Node(vec![], Box::new(Tree::Leaf("".into())))));
+	assert!(preflight_decode::<Tree>(&nested.encode(), 2).is_ok());
+	assert!(preflight_decode::<Tree>(&nested.encode(), 1).is_err());
+}
@@ -13,10 +13,101 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// The derive macros generated cases are made for, with the prefix of their file names.
+#[cfg(feature = "derive")]
+const DERIVES: &[(&str, &str)] = &[("Encode", "encode"), ("Decode", "decode")];
+
+/// Misuses which every derive macro in `DERIVES` must reject, as the name of the case and the
+/// item deriving the macro.
+#[cfg(feature = "derive")]
+fn generated_cases() -> Vec<(&'static str, String)> {
+	let variants = (0..=256).map(|i| format!("\tV{i},\n")).collect::<String>();
+	vec![
+		("union", "union Union {\n\ta: u8,\n\tb: u16,\n}\n".into()),
+		("too_many_variants", format!("enum Enum {{\n{variants}}}\n")),
+		(
+			"compact_and_skip",
+			"struct Struct {\n\t#[codec(compact)]\n\t#[codec(skip)]\n\tfield: u32,\n}\n".into(),
+		),
+	]
+}
+
+/// Writes the cases of `generated_cases` to `tests/scale_codec_ui/generated` when running with
+/// `TRYBUILD=overwrite`, and otherwise checks that the files are up to date.
+#[cfg(feature = "derive")]
+fn generate_cases() {
+	let dir = std::path::Path::new("tests/scale_codec_ui/generated");
+	let overwrite = std::env::var_os("TRYBUILD").is_some_and(|var| var == "overwrite");
+	for (derive, prefix) in DERIVES {
+		for (case, item) in generated_cases() {
+			let path = dir.join(format!("{prefix}_{case}.rs"));
+			let source =
+				format!("#[derive(::parity_scale_codec::{derive})]\n{item}\nfn main() {{}}\n");
+			if overwrite {
+				std::fs::create_dir_all(dir).unwrap();
+				std::fs::write(&path, source).unwrap();
+			} else {
+				let generated = std::fs::read_to_string(&path).unwrap_or_default();
+				assert!(
+					generated == source,
+					"{} is outdated, run with `TRYBUILD=overwrite` to generate it",
+					path.display(),
+				);
+			}
+		}
+	}
+}
+
 #[test]
 #[cfg(feature = "derive")]
 fn scale_codec_ui_tests() {
+	generate_cases();
+
 	let t = trybuild::TestCases::new();
 	t.compile_fail("tests/scale_codec_ui/*.rs");
+	t.compile_fail("tests/scale_codec_ui/generated/*.rs");
 	t.pass("tests/scale_codec_ui/pass/*.rs");
 }
+
+/// Builds a crate deriving `Encode` with `parity-scale-codec-derive` as its only dependency, which
+/// trybuild can't do, as it always adds `parity-scale-codec` to the dependencies.
+#[test]
+#[cfg(feature = "derive")]
+fn crate_missing_from_cargo_toml() {
+	let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+	let dir = root.join("target/tests/crate_missing_from_cargo_toml");
+	std::fs::create_dir_all(dir.join("src")).unwrap();
+	let manifest = format!(
+		"[package]\nname = \"crate-missing\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+		[dependencies]\nparity-scale-codec-derive = {{ path = {:?} }}\n\n[workspace]\n",
+		root.join("derive"),
+	);
+	std::fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+	std::fs::write(
+		dir.join("src/lib.rs"),
+		"#[derive(parity_scale_codec_derive::Encode)]\npub struct Struct;\n",
+	)
+	.unwrap();
+	// The lock file of this crate pins the dependencies of the derive, like trybuild does.
+	if let Ok(lock) = std::fs::read(root.join("Cargo.lock")) {
+		std::fs::write(dir.join("Cargo.lock"), lock).unwrap();
+	}
+
+	let output = std::process::Command::new(env!("CARGO"))
+		.arg("check")
+		.arg("--quiet")
+		.current_dir(&dir)
+		.env("CARGO_TARGET_DIR", root.join("target/tests/target"))
+		.output()
+		.unwrap();
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	let expected = format!(
+		"error: Could not find `parity-scale-codec`: the type has no `#[codec(crate = ...)]` \
+		attribute and `parity-scale-codec` is not a dependency in `{}`, also not renamed. Add \
+		`parity-scale-codec` to the dependencies, or give the path of the crate or of a crate \
+		re-exporting it with `#[codec(crate = path::to::parity_scale_codec)]`.",
+		dir.join("Cargo.toml").display(),
+	);
+	assert!(!output.status.success());
+	assert!(stderr.contains(&expected), "unexpected error:\n{stderr}");
+}
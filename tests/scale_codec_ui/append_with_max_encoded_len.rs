@@ -0,0 +1,14 @@
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::MaxEncodedLen)]
+#[codec(append_with = "Packet::checksum")]
+struct Packet {
+	kind: u8,
+	payload: [u8; 4],
+}
+
+impl Packet {
+	fn checksum(&self) -> u8 {
+		self.payload.iter().fold(self.kind, |sum, byte| sum.wrapping_add(*byte))
+	}
+}
+
+fn main() {}
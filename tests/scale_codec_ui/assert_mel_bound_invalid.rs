@@ -0,0 +1,9 @@
+use parity_scale_codec::{assert_mel_bound, Encode};
+
+#[derive(Encode)]
+struct NotMaxEncodedLen(Vec<u8>);
+
+assert_mel_bound!(NotMaxEncodedLen, 8);
+assert_mel_bound!(u32, 8u8);
+
+fn main() {}
@@ -0,0 +1,57 @@
+#[derive(::parity_scale_codec::Encode)]
+#[codec(reserve_index = 2)]
+enum ImplicitIndexNotReserved {
+	A,
+	#[cfg(all())]
+	B,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(reserve_index = 1)]
+enum FollowsGated {
+	A,
+	#[cfg(all())]
+	#[codec(index = 1)]
+	B,
+	C,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(reserve_index = 2)]
+enum NotReserved {
+	A,
+	#[cfg(all())]
+	#[codec(index = 1)]
+	B,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(reserve_index = 0)]
+enum UsesReserved {
+	A,
+}
+
+const FIVE: u8 = 5;
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(reserve_index = 2)]
+enum ExprNotReserved {
+	A,
+	#[cfg(all())]
+	#[codec(index = FIVE)]
+	B,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+enum PlainFollowsGated {
+	A,
+	#[cfg(all())]
+	B,
+	C,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(reserve_index = 0)]
+struct Struct;
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(::parity_scale_codec::CompactAs)]
+#[codec(crate = ::parity_scale_codec)]
+enum Example<T> {
+	A(T),
+}
+
+fn main() {}
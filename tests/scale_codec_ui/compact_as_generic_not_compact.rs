@@ -0,0 +1,14 @@
+#[derive(::parity_scale_codec::CompactAs)]
+#[codec(crate = ::parity_scale_codec)]
+struct Wrapper<T>(T);
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(crate = ::parity_scale_codec)]
+struct Example {
+	#[codec(compact)]
+	a: Wrapper<u32>,
+	#[codec(compact)]
+	b: Wrapper<String>,
+}
+
+fn main() {}
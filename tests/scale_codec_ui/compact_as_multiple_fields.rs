@@ -0,0 +1,8 @@
+#[derive(::parity_scale_codec::CompactAs)]
+#[codec(crate = ::parity_scale_codec)]
+struct Example<T> {
+	a: T,
+	b: T,
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+#[derive(::parity_scale_codec::Encode)]
+#[codec(crate = ::renamed_parity_scale_codec)]
+struct Renamed;
+
+fn main() {}
@@ -0,0 +1,17 @@
+#[derive(::parity_scale_codec::DecodeLength)]
+#[codec(crate = ::parity_scale_codec)]
+struct NotAnEnum(Vec<u8>);
+
+#[derive(::parity_scale_codec::DecodeLength)]
+#[codec(crate = ::parity_scale_codec)]
+enum TwoFields {
+	A(Vec<u8>, Vec<u8>),
+}
+
+#[derive(::parity_scale_codec::DecodeLength)]
+#[codec(crate = ::parity_scale_codec)]
+enum Compact {
+	A(#[codec(compact)] u32),
+}
+
+fn main() {}
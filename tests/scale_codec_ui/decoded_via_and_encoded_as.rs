@@ -0,0 +1,8 @@
+#[derive(::parity_scale_codec::Decode)]
+struct Struct {
+	#[codec(encoded_as = "u32")]
+	#[codec(decoded_via = "u32")]
+	field: u32,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#[derive(::parity_scale_codec::Decode)]
+#[codec(document_layout)]
+struct DecodeOnly {
+	a: u32,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+#[codec(document_layout)]
+enum DecodeOnlyEnum<T> {
+	A(T),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[derive(::parity_scale_codec::Decode)]
+enum Explicit {
+	#[codec(index = 1)]
+	A,
+	#[codec(index = 1)]
+	B,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+enum Implicit {
+	A,
+	#[codec(index = 0)]
+	B,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+const ONE: isize = 1;
+
+// `B` has the index 1, as variants without discriminant are indexed by their position.
+#[derive(::parity_scale_codec::Decode)]
+enum Enum {
+	A = 5,
+	B,
+	C = ONE,
+}
+
+const PING: u8 = 3;
+
+#[derive(::parity_scale_codec::Decode)]
+enum Indexed {
+	#[codec(index = PING)]
+	Ping,
+	#[codec(index = PING + 0)]
+	Pong,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct Example {
+	#[codec(compact, max_len = 4)]
+	a: u32,
+}
+
+fn main() {}
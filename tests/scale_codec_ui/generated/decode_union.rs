@@ -0,0 +1,7 @@
+#[derive(::parity_scale_codec::Decode)]
+union Union {
+	a: u8,
+	b: u16,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(::parity_scale_codec::Encode)]
+struct Struct {
+	#[codec(compact)]
+	#[codec(skip)]
+	field: u32,
+}
+
+fn main() {}
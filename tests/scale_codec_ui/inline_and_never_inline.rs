@@ -0,0 +1,9 @@
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+#[codec(inline)]
+#[codec(never_inline)]
+struct Struct {
+	a: u8,
+	b: u16,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+#[derive(::parity_scale_codec::Encode)]
+#[codec(like_plain(Plain))]
+struct MissingField {
+	a: u8,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(like_plain(Plain))]
+struct CompactField {
+	#[codec(compact)]
+	a: u8,
+	b: u16,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(like_plain(Plain))]
+struct Reordered {
+	b: u16,
+	a: u8,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(like_plain(Plain))]
+struct SkippedField {
+	a: u8,
+	#[codec(skip)]
+	b: u16,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+struct Plain {
+	a: u8,
+	b: u16,
+}
+
+fn main() {}
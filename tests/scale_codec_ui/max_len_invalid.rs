@@ -0,0 +1,16 @@
+#[derive(::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct TooLarge {
+	#[codec(max_len = 4294967296)]
+	a: Vec<u8>,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct WithCompact {
+	#[codec(compact)]
+	#[codec(max_len = 3)]
+	b: u32,
+}
+
+fn main() {}
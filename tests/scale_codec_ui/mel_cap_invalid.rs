@@ -0,0 +1,23 @@
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct MissingCap {
+	#[codec(mel(check))]
+	a: Vec<u8>,
+}
+
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct UnknownArgument {
+	#[codec(mel(cap = 3, strict))]
+	a: Vec<u8>,
+}
+
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct WithMaxLen {
+	#[codec(max_len = 3)]
+	#[codec(mel(cap = 4))]
+	a: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(::parity_scale_codec::Decode)]
+#[codec(migrate_from(u32))]
+struct Account {
+	balance: u64,
+}
+
+fn main() {}
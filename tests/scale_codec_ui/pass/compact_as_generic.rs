@@ -0,0 +1,38 @@
+#[derive(::parity_scale_codec::CompactAs)]
+#[codec(crate = ::parity_scale_codec)]
+struct Wrapper<T>(T);
+
+#[derive(::parity_scale_codec::CompactAs)]
+#[codec(crate = ::parity_scale_codec)]
+struct WithMarker<T, M: Default> {
+	a: T,
+	#[codec(skip)]
+	_marker: M,
+}
+
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct Example<T, M: Default> {
+	#[codec(compact)]
+	a: Wrapper<T>,
+	#[codec(compact)]
+	b: WithMarker<T, M>,
+}
+
+#[derive(::parity_scale_codec::Encode, ::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+enum ExampleEnum<T> {
+	A(#[codec(compact)] Wrapper<T>),
+	B {
+		#[codec(compact)]
+		b: Wrapper<T>,
+	},
+}
+
+fn assert_codec<T: ::parity_scale_codec::Encode + ::parity_scale_codec::Decode>() {}
+
+fn main() {
+	assert_codec::<Example<u32, ()>>();
+	assert_codec::<Example<u128, ::std::marker::PhantomData<u8>>>();
+	assert_codec::<ExampleEnum<u64>>();
+}
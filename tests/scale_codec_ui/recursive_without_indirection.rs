@@ -0,0 +1,14 @@
+#[derive(::parity_scale_codec::Decode)]
+enum Expr {
+	Literal(u32),
+	Negate(Option<(u8, Expr)>),
+}
+
+#[derive(::parity_scale_codec::Decode)]
+struct Tree {
+	value: u32,
+	children: Vec<Tree>,
+	parent: Option<Box<Tree>>,
+}
+
+fn main() {}
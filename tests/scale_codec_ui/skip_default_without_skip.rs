@@ -0,0 +1,13 @@
+#[derive(::parity_scale_codec::Decode)]
+#[codec(crate = ::parity_scale_codec)]
+struct Example {
+	a: u32,
+	#[codec(default = "make_b", compact)]
+	b: u64,
+}
+
+fn make_b(_: &u32) -> u64 {
+	0
+}
+
+fn main() {}
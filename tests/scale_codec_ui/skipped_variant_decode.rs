@@ -0,0 +1,52 @@
+#[derive(::parity_scale_codec::Decode)]
+enum MissingIndex {
+	#[codec(skip, decode_as = "B")]
+	A,
+	B,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+enum UnknownTarget {
+	#[codec(index = 0)]
+	#[codec(skip, decode_as = "C")]
+	A,
+	B,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+enum SkippedTarget {
+	#[codec(index = 0)]
+	#[codec(skip, decode_as = "C")]
+	A,
+	B,
+	#[codec(skip)]
+	C,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+enum DuplicateIndex {
+	#[codec(index = 0)]
+	#[codec(skip, decode_error = "A was removed")]
+	A,
+	B,
+}
+
+const ZERO: u8 = 0;
+
+#[derive(::parity_scale_codec::Decode)]
+enum DuplicateIndexExpr {
+	#[codec(index = ZERO)]
+	#[codec(skip, decode_error = "A was removed")]
+	A,
+	B,
+}
+
+#[derive(::parity_scale_codec::Decode)]
+enum InvalidRedirect {
+	#[codec(index = 0)]
+	#[codec(skip, decode_via = "B")]
+	A,
+	B,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+const TOO_LARGE: u16 = 256;
+const ONE: u8 = 1;
+
+#[derive(::parity_scale_codec::Encode)]
+enum OutOfRange {
+	#[codec(index = TOO_LARGE)]
+	A,
+}
+
+// `B` has the index 1, its position.
+#[derive(::parity_scale_codec::Decode)]
+enum Duplicate {
+	#[codec(index = ONE - 1)]
+	A,
+	B,
+	#[codec(index = ONE)]
+	C,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(::parity_scale_codec::Encode)]
+#[codec(variant_index)]
+enum Enum {
+	A,
+	#[codec(skip)]
+	B,
+}
+
+#[derive(::parity_scale_codec::Encode)]
+#[codec(variant_index)]
+struct Struct {
+	a: u8,
+}
+
+fn main() {}
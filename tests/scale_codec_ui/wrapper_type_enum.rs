@@ -0,0 +1,11 @@
+#[derive(::parity_scale_codec::WrapperTypeEncode)]
+#[codec(crate = ::parity_scale_codec)]
+enum Example {
+	A(u32),
+}
+
+#[derive(::parity_scale_codec::WrapperTypeDecode)]
+#[codec(crate = ::parity_scale_codec)]
+struct Pair(u32, u32);
+
+fn main() {}
@@ -1,4 +1,6 @@
-use parity_scale_codec::{Compact, Decode, DecodeWithMemTracking, Encode, HasCompact};
+use parity_scale_codec::{
+	decode_prefix, Compact, Decode, DecodeWithMemTracking, Encode, HasCompact,
+};
 use parity_scale_codec_derive::{
 	CompactAs as DeriveCompactAs, Decode as DeriveDecode,
 	DecodeWithMemTracking as DeriveDecodeWithMemTracking, Encode as DeriveEncode,
@@ -161,3 +163,44 @@ fn test_encoding() {
 	assert_eq!(s_skip_cas, SSkipcas::decode(&mut s_skip_cas_encoded).unwrap());
 	assert_eq!(uh, Uh::decode(&mut uh_encoded).unwrap());
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, DeriveCompactAs)]
+struct GenericU<T>(T);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, DeriveCompactAs)]
+struct GenericSkip<T, M: Default> {
+	#[codec(skip)]
+	marker: M,
+	x: T,
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+struct GenericUcas<T> {
+	#[codec(compact)]
+	x: GenericU<T>,
+}
+
+#[derive(Debug, PartialEq, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+enum GenericEcas<T, M: Default> {
+	A(#[codec(compact)] GenericU<T>),
+	B {
+		#[codec(compact)]
+		x: GenericSkip<T, M>,
+	},
+}
+
+#[test]
+fn test_generic_compact_as_encoding() {
+	let x = 3u64;
+	let ucas = GenericUcas { x: GenericU(x) };
+	let a = GenericEcas::<u64, ()>::A(GenericU(x));
+	let b = GenericEcas::<u64, ()>::B { x: GenericSkip { marker: (), x } };
+
+	assert_eq!(ucas.encode(), Compact(x).encode());
+	assert_eq!(a.encode(), (0u8, Compact(x)).encode());
+	assert_eq!(b.encode(), (1u8, Compact(x)).encode());
+
+	assert_eq!(ucas, decode_prefix::<GenericUcas<u64>>(&ucas.encode()).unwrap().0);
+	assert_eq!(a, decode_prefix::<GenericEcas<u64, ()>>(&a.encode()).unwrap().0);
+	assert_eq!(b, decode_prefix::<GenericEcas<u64, ()>>(&b.encode()).unwrap().0);
+}
@@ -1,4 +1,4 @@
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{decode_prefix, Decode, Encode};
 use parity_scale_codec_derive::{
 	Decode as DeriveDecode, DecodeWithMemTracking as DeriveDecodeWithMemTracking,
 	Encode as DeriveEncode,
@@ -74,3 +74,62 @@ fn skip_enum_struct_inner_variant() {
 	let encoded = Enum::Data { some_named: 1, ignore: Some(1) }.encode();
 	assert_eq!(vec![0, 1, 0, 0, 0], encoded);
 }
+
+#[test]
+fn skip_with_default_function() {
+	#[derive(PartialEq, Debug)]
+	struct NoDefault(u32);
+
+	fn make_no_default() -> NoDefault {
+		NoDefault(7)
+	}
+
+	#[allow(clippy::ptr_arg)]
+	fn sum(a: &u32, b: &Vec<u32>) -> u64 {
+		b.iter().map(|x| *x as u64).sum::<u64>() + *a as u64
+	}
+
+	#[derive(PartialEq, Debug, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	struct StructNamed {
+		#[codec(skip, default = "make_no_default")]
+		first: NoDefault,
+		a: u32,
+		#[codec(skip)]
+		ignored: u8,
+		b: Vec<u32>,
+		#[codec(skip, default = "sum")]
+		total: u64,
+	}
+
+	#[derive(PartialEq, Debug, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	struct StructUnnamed(Vec<u8>, #[codec(skip, default = "Vec::len")] usize);
+
+	#[derive(PartialEq, Debug, DeriveEncode, DeriveDecode, DeriveDecodeWithMemTracking)]
+	enum Enum {
+		A {
+			a: u32,
+			b: Vec<u32>,
+			#[codec(skip, default = "sum")]
+			total: u64,
+		},
+		B(#[codec(skip, default = "make_no_default")] NoDefault),
+	}
+
+	let sn = StructNamed { first: NoDefault(7), a: 1, ignored: 0, b: vec![2, 3], total: 6 };
+	let encoded = sn.encode();
+	assert_eq!(encoded, (1u32, vec![2u32, 3]).encode());
+	assert_eq!(decode_prefix::<StructNamed>(&encoded).unwrap().0, sn);
+
+	let su = StructUnnamed(vec![1, 2, 3], 3);
+	let encoded = su.encode();
+	assert_eq!(encoded, vec![1u8, 2, 3].encode());
+	assert_eq!(decode_prefix::<StructUnnamed>(&encoded).unwrap().0, su);
+
+	let ea = Enum::A { a: 5, b: vec![1], total: 6 };
+	let encoded = ea.encode();
+	assert_eq!(encoded, (0u8, 5u32, vec![1u32]).encode());
+	assert_eq!(decode_prefix::<Enum>(&encoded).unwrap().0, ea);
+
+	let eb = Enum::B(NoDefault(7));
+	assert_eq!(decode_prefix::<Enum>(&eb.encode()).unwrap().0, eb);
+}
@@ -0,0 +1,43 @@
+//! Conformance of the encodings of the primitive types with the test vectors of the fixtures.
+
+#![cfg(feature = "test-vectors")]
+
+use parity_scale_codec::{test_vector_registry, Compact};
+
+#[test]
+fn fixture_test_vectors_pass() {
+	let registry = test_vector_registry! {
+		"bool" => bool,
+		"u16" => u16,
+		"i64" => i64,
+		"Compact<u32>" => Compact<u32>,
+		"Compact<u64>" => Compact<u64>,
+		"Option<u8>" => Option<u8>,
+		"Option<bool>" => Option<bool>,
+		"Result<u8, bool>" => Result<u8, bool>,
+		"Vec<u16>" => Vec<u16>,
+		"String" => String,
+		"(u8, Vec<bool>)" => (u8, Vec<bool>),
+		"[u8; 3]" => [u8; 3],
+		"u8" => u8,
+		"i8" => i8,
+		"u32" => u32,
+		"Option<Vec<u8>>" => Option<Vec<u8>>,
+		"(bool, u16)" => (bool, u16),
+		"[u16; 2]" => [u16; 2],
+	};
+
+	let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/test_vectors.json");
+	match registry.check_json_file(path) {
+		Ok(count) => assert_eq!(count, 25),
+		Err(failures) =>
+			panic!("{}", failures.iter().map(|f| format!("\n{f}")).collect::<String>()),
+	}
+
+	let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/test_vectors.yaml");
+	match registry.check_yaml_file(path) {
+		Ok(count) => assert_eq!(count, 12),
+		Err(failures) =>
+			panic!("{}", failures.iter().map(|f| format!("\n{f}")).collect::<String>()),
+	}
+}
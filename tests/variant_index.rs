@@ -0,0 +1,39 @@
+use parity_scale_codec::Encode;
+use parity_scale_codec_derive::{Decode as DeriveDecode, Encode as DeriveEncode};
+
+#[derive(DeriveEncode, DeriveDecode)]
+#[codec(variant_index)]
+enum Enum<T> {
+	A,
+	#[codec(index = 5)]
+	B(u16, T),
+	C {
+		value: T,
+	},
+	#[codec(index = 9)]
+	D,
+}
+
+#[derive(DeriveEncode)]
+#[codec(variant_index)]
+#[repr(u8)]
+enum Discriminants {
+	A = 3,
+	B = 1 + 3,
+}
+
+const B_INDEX: u8 = Discriminants::B.scale_variant_index();
+
+#[test]
+fn scale_variant_index_is_first_byte_of_encoding() {
+	for value in [Enum::A, Enum::B(1, 2u32), Enum::C { value: 3 }, Enum::D] {
+		assert_eq!(value.scale_variant_index(), value.encode()[0]);
+	}
+	assert_eq!(Enum::<u32>::A.scale_variant_index(), 0);
+	assert_eq!(Enum::B(1, "b").scale_variant_index(), 5);
+	assert_eq!(Enum::C { value: () }.scale_variant_index(), 2);
+	assert_eq!(Enum::<()>::D.scale_variant_index(), 9);
+
+	assert_eq!(Discriminants::A.scale_variant_index(), 3);
+	assert_eq!(B_INDEX, Discriminants::B.encode()[0]);
+}
@@ -38,3 +38,111 @@ fn index_attr_variant_counted_and_reused_in_default_index() {
 	assert_eq!(T::A.encode(), vec![1]);
 	assert_eq!(T::B.encode(), vec![1]);
 }
+
+mod protocol {
+	pub const PING: u8 = 7;
+	pub const BASE: usize = 40;
+}
+
+#[test]
+fn index_attr_accepts_const_expressions() {
+	use parity_scale_codec::decode_prefix;
+	use parity_scale_codec_derive::Decode as DeriveDecode;
+
+	#[derive(DeriveEncode, DeriveDecode, Debug, PartialEq)]
+	enum T {
+		#[codec(index = protocol::PING)]
+		Ping,
+		#[codec(index = protocol::BASE + 1)]
+		Data(u8),
+		B,
+	}
+
+	assert_eq!(T::Ping.encode(), vec![7]);
+	assert_eq!(T::Data(3).encode(), vec![41, 3]);
+	assert_eq!(T::B.encode(), vec![2]);
+	assert_eq!(decode_prefix::<T>(&[41, 3]), Ok((T::Data(3), &[][..])));
+	assert_eq!(decode_prefix::<T>(&[7]), Ok((T::Ping, &[][..])));
+}
+
+#[test]
+fn cfg_gated_variants_have_reserved_indexes() {
+	use parity_scale_codec::decode_prefix;
+	use parity_scale_codec_derive::Decode as DeriveDecode;
+
+	#[derive(DeriveEncode, DeriveDecode, Debug, PartialEq)]
+	#[codec(reserve_index(1, 9))]
+	enum T {
+		A,
+		#[cfg(test)]
+		#[codec(index = 1)]
+		Enabled(u8),
+		#[cfg(not(test))]
+		#[codec(index = 9)]
+		Disabled,
+		#[codec(index = protocol::PING)]
+		B,
+		#[cfg(test)]
+		#[codec(index = protocol::BASE as u8 - 31)]
+		Expr,
+	}
+
+	assert_eq!(T::Enabled(3).encode(), vec![1, 3]);
+	assert_eq!(T::B.encode(), vec![7]);
+	assert_eq!(T::Expr.encode(), vec![9]);
+	assert_eq!(decode_prefix::<T>(&[1, 3]), Ok((T::Enabled(3), &[][..])));
+}
+
+#[test]
+fn cfg_gated_variants_in_enums_not_reserving_indexes() {
+	#[derive(DeriveEncode)]
+	enum Explicit {
+		A,
+		#[cfg(test)]
+		#[codec(index = 5)]
+		Benchmark,
+		#[codec(index = 1)]
+		B,
+	}
+
+	// A trailing gated variant doesn't shift any index.
+	#[derive(DeriveEncode)]
+	#[codec(reserve_index = 1)]
+	enum Trailing {
+		A,
+		#[cfg(test)]
+		B,
+	}
+
+	assert_eq!(Explicit::A.encode(), vec![0]);
+	assert_eq!(Explicit::Benchmark.encode(), vec![5]);
+	assert_eq!(Explicit::B.encode(), vec![1]);
+	assert_eq!(Trailing::A.encode(), vec![0]);
+	assert_eq!(Trailing::B.encode(), vec![1]);
+}
+
+#[test]
+fn skipped_variant_is_decoded_as_redirect() {
+	use parity_scale_codec::decode_prefix;
+	use parity_scale_codec_derive::Decode as DeriveDecode;
+
+	#[derive(DeriveEncode, DeriveDecode, Debug, PartialEq)]
+	enum T {
+		#[codec(index = 0)]
+		#[codec(skip, decode_as = "B")]
+		A(u8, u16),
+		#[codec(index = protocol::PING)]
+		#[codec(skip, decode_error = "`C` was removed")]
+		C,
+		#[codec(index = 1)]
+		B(u8, u16),
+	}
+
+	assert_eq!(T::A(1, 2).encode(), vec![]);
+	assert_eq!(T::C.encode(), vec![]);
+	assert_eq!(T::B(1, 2).encode(), vec![1, 1, 2, 0]);
+	assert_eq!(decode_prefix::<T>(&[0, 1, 2, 0]), Ok((T::B(1, 2), &[][..])));
+	assert_eq!(decode_prefix::<T>(&[1, 1, 2, 0]), Ok((T::B(1, 2), &[][..])));
+	assert_eq!(decode_prefix::<T>(&[protocol::PING]).unwrap_err().to_string(), "`C` was removed");
+	assert!(decode_prefix::<T>(&[2]).is_err());
+}
@@ -0,0 +1,53 @@
+use parity_scale_codec::{decode_prefix, Compact, DecodeWithMemLimit, Encode, EncodeLike};
+use parity_scale_codec_derive::{WrapperTypeDecode, WrapperTypeEncode};
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, WrapperTypeEncode, WrapperTypeDecode)]
+struct Name(String);
+
+#[derive(Debug, PartialEq, WrapperTypeEncode, WrapperTypeDecode)]
+struct Balance<T> {
+	#[codec(skip)]
+	_marker: PhantomData<T>,
+	amount: Compact<u64>,
+}
+
+#[derive(Debug, PartialEq, WrapperTypeEncode, WrapperTypeDecode)]
+struct List<T>(Vec<T>);
+
+#[derive(WrapperTypeEncode)]
+struct Borrowed<'a>(&'a [u8]);
+
+fn encode_like<T: Encode, R: EncodeLike<T>>(data: &R) -> Vec<u8> {
+	data.encode()
+}
+
+#[test]
+fn wrapper_types_encode_like_their_field() {
+	let name = Name("alice".into());
+	assert_eq!(name.encode(), "alice".encode());
+	assert_eq!(encode_like::<String, _>(&name), "alice".encode());
+	assert_eq!(name.len(), 5);
+
+	let balance = Balance::<u8> { _marker: PhantomData, amount: Compact(1 << 20) };
+	assert_eq!(balance.encode(), Compact(1u64 << 20).encode());
+
+	assert_eq!(List(vec![1u16, 2]).encode(), vec![1u16, 2].encode());
+	assert_eq!(Borrowed(&[1, 2, 3]).encode(), [1u8, 2, 3][..].encode());
+}
+
+#[test]
+fn wrapper_types_decode_like_their_field() {
+	let encoded = "alice".encode();
+	assert_eq!(decode_prefix::<Name>(&encoded).unwrap().0, Name("alice".into()));
+
+	let encoded = Compact(7u64).encode();
+	assert_eq!(
+		decode_prefix::<Balance<u8>>(&encoded).unwrap().0,
+		Balance { _marker: PhantomData, amount: Compact(7) }
+	);
+
+	let encoded = vec![1u32, 2, 3].encode();
+	assert_eq!(List::<u32>::decode_with_mem_limit(&mut &encoded[..], 13), Ok(List(vec![1, 2, 3])));
+	assert!(List::<u32>::decode_with_mem_limit(&mut &encoded[..], 12).is_err());
+}